@@ -22,7 +22,7 @@ pub fn expand_attr_names(
         } else {
             exclude_raw_ident(&ident.to_string()).to_case(Case::Pascal)
         };
-        let name = format_ident!("{}", name);
+        let name = variant_ident(&name, ident);
         quote! {
             #name
         }
@@ -34,7 +34,7 @@ pub fn expand_attr_names(
         let basename = create_renamed(ident.to_string(), renamed, rename_all_type);
         let attr_name = basename.to_string();
         let name = exclude_raw_ident(&basename).to_case(Case::Pascal);
-        let name = format_ident!("{}", name);
+        let name = variant_ident(&name, ident);
         quote! {
             #attr_enum_name::#name => #attr_name.to_owned()
         }
@@ -47,11 +47,16 @@ pub fn expand_attr_names(
         let func_name = basename.to_case(Case::Snake);
         let func_name = if crate::helpers::is_reserved(&func_name) {
             format_ident!("r#{}", func_name)
-        } else {
+        } else if crate::helpers::is_identifier(&func_name) {
             format_ident!("{}", func_name)
+        } else {
+            format_ident!(
+                "{}",
+                exclude_raw_ident(&ident.to_string()).to_case(Case::Snake)
+            )
         };
         let name = exclude_raw_ident(&basename).to_case(Case::Pascal);
-        let name = format_ident!("{}", name);
+        let name = variant_ident(&name, ident);
         quote! {
             pub fn #func_name() -> #attr_enum_name {
                 #attr_enum_name::#name
@@ -87,6 +92,19 @@ pub fn expand_attr_names(
     }
 }
 
+// Attribute names are arbitrary strings such as `1pk` or `PK#x`, so fall back to the field
+// name when the renamed one can't be a Rust identifier.
+fn variant_ident(name: &str, ident: &proc_macro2::Ident) -> proc_macro2::Ident {
+    if crate::helpers::is_identifier(name) {
+        format_ident!("{}", name)
+    } else {
+        format_ident!(
+            "{}",
+            exclude_raw_ident(&ident.to_string()).to_case(Case::Pascal)
+        )
+    }
+}
+
 fn exclude_raw_ident(ident: &str) -> String {
     if &ident[0..2] == "r#" {
         ident[2..].to_owned()
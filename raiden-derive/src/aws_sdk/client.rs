@@ -6,21 +6,7 @@ pub(crate) fn expand_client_constructor(
     client_name: &Ident,
     dynamodb_client_name: &Ident,
     table_name: &str,
-    fields: &FieldsNamed,
-    rename_all_type: &crate::rename::RenameAllType,
 ) -> proc_macro2::TokenStream {
-    let insertion_attribute_name = fields.named.iter().map(|f| {
-        let ident = &f.ident.clone().unwrap();
-        let renamed = crate::finder::find_rename_value(&f.attrs);
-        let result = crate::create_renamed(ident.to_string(), renamed, *rename_all_type);
-        quote! {
-            names.insert(
-                format!("#{}", #result.clone()),
-                #result.to_string(),
-            );
-        }
-    });
-
     quote! {
         impl #client_name {
             pub fn new(region: ::raiden::aws_sdk::config::Region) -> Self {
@@ -29,19 +15,19 @@ pub(crate) fn expand_client_constructor(
                     .region(region)
                     .build();
                 let client = ::raiden::#dynamodb_client_name::from_conf(config);
-                Self::new_with_dynamo_db_client(client)
+                Self::new_with_backend(client)
             }
 
             pub fn new_with_client(client: ::raiden::#dynamodb_client_name) -> Self {
-                Self::new_with_dynamo_db_client(client)
+                Self::new_with_backend(client)
             }
 
-            fn new_with_dynamo_db_client(client: ::raiden::#dynamodb_client_name) -> Self {
-                let names = {
-                    let mut names: ::raiden::AttributeNames = std::collections::HashMap::new();
-                    #(#insertion_attribute_name)*
-                    names
-                };
+            pub fn new_with_backend(backend: impl ::raiden::DynamoBackend + 'static) -> Self {
+                Self::new_with_dynamo_db_client(::std::sync::Arc::new(backend))
+            }
+
+            fn new_with_dynamo_db_client(client: ::raiden::SharedBackend) -> Self {
+                let names = <#struct_name as ::raiden::RaidenItem>::attribute_names().unwrap_or_default();
                 let projection_expression = Some(names.keys().map(|v| v.to_string()).collect::<Vec<String>>().join(", "));
 
                 Self {
@@ -63,6 +49,12 @@ pub(crate) fn expand_client_constructor(
                 self
             }
 
+            /// Reports every request attempt of this client to `observer`.
+            pub fn with_observer(mut self, observer: ::std::sync::Arc<dyn ::raiden::Observer>) -> Self {
+                self.client = ::std::sync::Arc::new(::raiden::ObservedBackend::new(self.client, observer));
+                self
+            }
+
             pub fn table_prefix(mut self, prefix: impl Into<String>) -> Self {
                 self.table_prefix = prefix.into();
                 self
@@ -86,6 +78,10 @@ pub(crate) fn expand_client_constructor(
             pub fn client_with(client: ::raiden::#dynamodb_client_name) -> #client_name {
                 #client_name::new_with_client(client)
             }
+
+            pub fn client_with_backend(backend: impl ::raiden::DynamoBackend + 'static) -> #client_name {
+                #client_name::new_with_backend(backend)
+            }
         }
     }
 }
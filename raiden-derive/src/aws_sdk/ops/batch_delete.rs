@@ -2,17 +2,17 @@ use quote::*;
 use syn::*;
 
 pub(crate) fn expand_batch_delete(
-    partition_key: &(Ident, Type),
-    sort_key: &Option<(Ident, Type)>,
+    partition_key: &(LitStr, Type),
+    sort_key: &Option<(LitStr, Type)>,
     struct_name: &Ident,
 ) -> proc_macro2::TokenStream {
     let trait_name = format_ident!("{}BatchDelete", struct_name);
     let client_name = format_ident!("{}Client", struct_name);
     let builder_name = format_ident!("{}BatchDeleteBuilder", struct_name);
-    let (partition_key_ident, partition_key_type) = partition_key;
+    let (partition_key_name, partition_key_type) = partition_key;
 
     let client_trait = if let Some(sort_key) = sort_key {
-        let (sort_key_ident, sort_key_type) = sort_key;
+        let (sort_key_name, sort_key_type) = sort_key;
         quote! {
             pub trait #trait_name {
                 fn batch_delete(&self, keys: std::vec::Vec<(impl Into<#partition_key_type>, impl Into<#sort_key_type>)>) -> #builder_name;
@@ -30,8 +30,8 @@ pub(crate) fn expand_batch_delete(
                             let write_request = {
                                 let delete_request = ::raiden::aws_sdk::types::DeleteRequest::builder()
                                     .set_key(Some(::std::collections::HashMap::from_iter([
-                                        (stringify!(#partition_key_ident).to_string(), pk_attr_value),
-                                        (stringify!(#sort_key_ident).to_string(), sk_attr_value),
+                                        (#partition_key_name.to_string(), pk_attr_value),
+                                        (#sort_key_name.to_string(), sk_attr_value),
                                     ])))
                                     .build()
                                     .expect("should be built");
@@ -50,6 +50,7 @@ pub(crate) fn expand_batch_delete(
                         client: &self.client,
                         write_requests,
                         table_name: self.table_name(),
+                        return_consumed_capacity: None,
                     }
                 }
             }
@@ -71,7 +72,7 @@ pub(crate) fn expand_batch_delete(
                             let write_request = {
                                 let delete_request = ::raiden::aws_sdk::types::DeleteRequest::builder()
                                     .set_key(Some(::std::collections::HashMap::from_iter([
-                                        (stringify!(#partition_key_ident).to_string(), pk_attr_value),
+                                        (#partition_key_name.to_string(), pk_attr_value),
                                     ])))
                                     .build()
                                     .expect("should be built");
@@ -90,6 +91,7 @@ pub(crate) fn expand_batch_delete(
                         client: &self.client,
                         write_requests,
                         table_name: self.table_name(),
+                        return_consumed_capacity: None,
                     }
                 }
             }
@@ -113,17 +115,25 @@ pub(crate) fn expand_batch_delete(
         #client_trait
 
         pub struct #builder_name<'a> {
-            pub client: &'a ::raiden::Client,
+            pub client: &'a ::raiden::SharedBackend,
             pub write_requests: ::std::vec::Vec<::raiden::aws_sdk::types::WriteRequest>,
             pub table_name: String,
+            pub return_consumed_capacity: Option<::raiden::ReturnConsumedCapacity>,
         }
 
         impl<'a> #builder_name<'a> {
+            /// Requests the consumed capacity, summed over every request as `consumed_capacity`.
+            pub fn return_consumed_capacity(mut self, value: ::raiden::ReturnConsumedCapacity) -> Self {
+                self.return_consumed_capacity = Some(value);
+                self
+            }
+
             pub async fn run(mut self) -> Result<::raiden::batch_delete::BatchDeleteOutput, ::raiden::RaidenError> {
                 // TODO: set the number of retry to 5 for now, which should be made more flexible
                 const RETRY: usize = 5;
                 const MAX_ITEMS_PER_REQUEST: usize = 25;
 
+                let mut consumed_capacity = None;
                 for _ in 0..RETRY {
                     loop {
                         let len = self.write_requests.len();
@@ -141,9 +151,11 @@ pub(crate) fn expand_batch_delete(
                             .into_iter()
                             .collect::<std::collections::HashMap<_, _>>();
                         let builder = ::raiden::aws_sdk::operation::batch_write_item::BatchWriteItemInput::builder()
-                            .set_request_items(Some(request_items));
+                            .set_request_items(Some(request_items))
+                            .set_return_consumed_capacity(self.return_consumed_capacity.map(Into::into));
 
                         let result = #call_inner_run;
+                        consumed_capacity = ::raiden::capacity::merge_consumed_capacities(consumed_capacity, result.consumed_capacity);
 
                         let mut unprocessed_items = match result.unprocessed_items {
                             None => {
@@ -166,7 +178,7 @@ pub(crate) fn expand_batch_delete(
                             .remove(&self.table_name)
                             .expect("request_items hashmap must have a value for the table name");
                         // push unprocessed requests back to the request buffer
-                        self.write_requests.extend(unprocessed_requests);
+                        self.write_requests.extend(unprocessed_requests.into_iter().map(Into::into));
                     }
                 }
 
@@ -177,17 +189,17 @@ pub(crate) fn expand_batch_delete(
                     .filter_map(|write_request| write_request.delete_request)
                     .collect::<std::vec::Vec<_>>();
                 Ok(::raiden::batch_delete::BatchDeleteOutput {
-                    consumed_capacity: None,
+                    consumed_capacity,
                     unprocessed_items,
                 })
             }
 
             async fn inner_run(
                 #inner_run_args
-                client: &::raiden::Client,
+                client: &::raiden::SharedBackend,
                 builder: ::raiden::operation::batch_write_item::builders::BatchWriteItemInputBuilder,
-            ) -> Result<::raiden::operation::batch_write_item::BatchWriteItemOutput, ::raiden::RaidenError> {
-                Ok(#api_call_token?)
+            ) -> Result<::raiden::BatchWriteItemResponse, ::raiden::RaidenError> {
+                #api_call_token
             }
         }
     }
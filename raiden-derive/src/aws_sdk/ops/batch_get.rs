@@ -5,8 +5,8 @@ use syn::*;
 use crate::rename::*;
 
 pub(crate) fn expand_batch_get(
-    partition_key: &(Ident, Type),
-    sort_key: &Option<(Ident, Type)>,
+    partition_key: &(LitStr, Type),
+    sort_key: &Option<(LitStr, Type)>,
     struct_name: &Ident,
     fields: &FieldsNamed,
     rename_all_type: RenameAllType,
@@ -14,8 +14,7 @@ pub(crate) fn expand_batch_get(
     let trait_name = format_ident!("{}BatchGetItem", struct_name);
     let client_name = format_ident!("{}Client", struct_name);
     let builder_name = format_ident!("{}BatchGetItemBuilder", struct_name);
-    let from_item = super::expand_attr_to_item(format_ident!("res_item"), fields, rename_all_type);
-    let (partition_key_ident, partition_key_type) = partition_key;
+    let (partition_key_name, partition_key_type) = partition_key;
 
     let builder_keys_type = if sort_key.is_none() {
         quote! { std::vec::Vec<::raiden::aws_sdk::types::AttributeValue> }
@@ -29,7 +28,7 @@ pub(crate) fn expand_batch_get(
         let result = create_renamed(ident.to_string(), renamed, rename_all_type);
         quote! {
             names.insert(
-                format!("#{}", #result.clone()),
+                ::raiden::name_placeholder(#result),
                 #result.to_string(),
             );
         }
@@ -48,7 +47,8 @@ pub(crate) fn expand_batch_get(
             table_name: self.table_name(),
             keys: key_attrs,
             attribute_names: Some(names),
-            projection_expression
+            projection_expression,
+            return_consumed_capacity: None,
         }
     };
 
@@ -84,12 +84,12 @@ pub(crate) fn expand_batch_get(
     };
 
     let convert_to_external_proc = if let Some(sort_key) = sort_key {
-        let (sort_key_ident, _sort_key_type) = sort_key;
+        let (sort_key_name, _sort_key_type) = sort_key;
         quote! {
             for (pk_attr, sk_attr) in keys.into_iter() {
                 let key_val: std::collections::HashMap<String, ::raiden::aws_sdk::types::AttributeValue> = ::std::collections::HashMap::from_iter([
-                    (stringify!(#partition_key_ident).to_owned(), pk_attr),
-                    (stringify!(#sort_key_ident).to_owned(), sk_attr),
+                    (#partition_key_name.to_owned(), pk_attr),
+                    (#sort_key_name.to_owned(), sk_attr),
                 ]);
 
                 item_builder = item_builder.keys(key_val);
@@ -99,7 +99,7 @@ pub(crate) fn expand_batch_get(
         quote! {
             for key_attr in keys.into_iter() {
                 let key_val: std::collections::HashMap<String, ::raiden::aws_sdk::types::AttributeValue> = ::std::collections::HashMap::from_iter([
-                    (stringify!(#partition_key_ident).to_owned(), key_attr),
+                    (#partition_key_name.to_owned(), key_attr),
                 ]);
 
                 item_builder = item_builder.keys(key_val);
@@ -124,16 +124,23 @@ pub(crate) fn expand_batch_get(
         #client_trait
 
         pub struct #builder_name<'a> {
-            pub client: &'a ::raiden::Client,
+            pub client: &'a ::raiden::SharedBackend,
             pub table_name: String,
             pub keys: #builder_keys_type,
             pub attribute_names: Option<::raiden::AttributeNames>,
-            pub projection_expression: Option<String>
+            pub projection_expression: Option<String>,
+            pub return_consumed_capacity: Option<::raiden::ReturnConsumedCapacity>,
         }
 
         impl<'a> #builder_name<'a> {
 
             #![allow(clippy::field_reassign_with_default)]
+            /// Requests the consumed capacity, summed over every request as `consumed_capacity`.
+            pub fn return_consumed_capacity(mut self, value: ::raiden::ReturnConsumedCapacity) -> Self {
+                self.return_consumed_capacity = Some(value);
+                self
+            }
+
             pub async fn run(mut self) -> Result<::raiden::batch_get::BatchGetOutput<#struct_name>, ::raiden::RaidenError> {
                 use ::std::iter::FromIterator;
 
@@ -145,6 +152,7 @@ pub(crate) fn expand_batch_get(
 
                 // TODO: for now set 5, however we should make it more flexible.
                 let mut unprocessed_retry = 5;
+                let mut consumed_capacity = None;
                 loop {
                     let unprocessed_key_len = unprocessed_keys.keys().len();
                     let mut item_builder = ::raiden::aws_sdk::types::KeysAndAttributes::builder()
@@ -161,9 +169,11 @@ pub(crate) fn expand_batch_get(
                         .request_items(
                             self.table_name.to_string(),
                             item_builder.build().expect("should be built"),
-                        );
+                        )
+                        .set_return_consumed_capacity(self.return_consumed_capacity.map(Into::into));
 
                     let res = #call_inner_run;
+                    consumed_capacity = ::raiden::capacity::merge_consumed_capacities(consumed_capacity, res.consumed_capacity);
 
                     if self.keys.is_empty() {
                         unprocessed_retry -= 1;
@@ -173,10 +183,7 @@ pub(crate) fn expand_batch_get(
                         let mut res_responses = res_responses;
                         if let Some(res_items) = (&mut res_responses).remove(&self.table_name) {
                             for res_item in res_items.into_iter() {
-                                let mut res_item = res_item;
-                                items.push(#struct_name {
-                                    #(#from_item)*
-                                })
+                                items.push(<#struct_name as ::raiden::RaidenItem>::from_item(res_item)?)
                             }
                         } else {
                             return Err(::raiden::RaidenError::ResourceNotFound(format!("'{}' table not found or not active", &self.table_name)));
@@ -198,7 +205,7 @@ pub(crate) fn expand_batch_get(
                     ) || unprocessed_retry == 0
                     {
                             return Ok(::raiden::batch_get::BatchGetOutput {
-                            consumed_capacity: res.consumed_capacity,
+                            consumed_capacity,
                             items,
                             unprocessed_keys: Some(unprocessed_keys),
                         })
@@ -208,10 +215,10 @@ pub(crate) fn expand_batch_get(
 
             async fn inner_run(
                 #inner_run_args
-                client: &::raiden::Client,
+                client: &::raiden::SharedBackend,
                 builder: ::raiden::aws_sdk::operation::batch_get_item::builders::BatchGetItemInputBuilder,
-            ) -> Result<::raiden::aws_sdk::operation::batch_get_item::BatchGetItemOutput, ::raiden::RaidenError> {
-                Ok(#api_call_token?)
+            ) -> Result<::raiden::BatchGetItemResponse, ::raiden::RaidenError> {
+                #api_call_token
             }
         }
     }
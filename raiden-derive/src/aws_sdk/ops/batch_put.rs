@@ -6,12 +6,15 @@ pub(crate) fn expand_batch_put(
     struct_name: &Ident,
     fields: &FieldsNamed,
     rename_all_type: RenameAllType,
+    timestamps: &crate::timestamp::Timestamps,
+    key_templates: &crate::key_template::KeyTemplates,
 ) -> proc_macro2::TokenStream {
     let item_input_name = format_ident!("{}PutItemInput", struct_name);
     let trait_name = format_ident!("{}BatchPut", struct_name);
     let client_name = format_ident!("{}Client", struct_name);
     let builder_name = format_ident!("{}BatchPutBuilder", struct_name);
 
+    let key_insertion = key_templates.expand_insertion(quote! { item });
     let input_items = {
         let insertion = fields.named.iter().map(|f| {
             let ident = &f.ident.clone().unwrap();
@@ -22,6 +25,10 @@ pub(crate) fn expand_batch_put(
                     let id = #struct_name::gen();
                     input_item.insert(#attr_key.to_string(), id.into_attr());
                 }
+            } else if let Some(value) = timestamps.expand_value(f) {
+                quote! {
+                    input_item.insert(#attr_key.to_string(), #value.into_attr());
+                }
             } else {
                 quote! {
                     let value = item.#ident.clone().into_attr();
@@ -34,9 +41,11 @@ pub(crate) fn expand_batch_put(
 
         quote! {
             let mut input_item: std::collections::HashMap<String, ::raiden::aws_sdk::types::AttributeValue> = std::collections::HashMap::new();
+            #key_insertion
             #(#insertion)*
         }
     };
+    let now = timestamps.expand_now(struct_name);
 
     let api_call_token = super::api_call_token!("batch_write_item");
     let (call_inner_run, inner_run_args) = if cfg!(feature = "tracing") {
@@ -58,10 +67,15 @@ pub(crate) fn expand_batch_put(
 
         impl #trait_name for #client_name {
             fn batch_put(&self, items: std::vec::Vec<#item_input_name>) -> #builder_name {
+                #now
+                let mut error = None;
                 let write_requests = {
                     let mut write_requests = vec![];
                     for item in items.into_iter() {
                         #input_items
+                        if error.is_none() {
+                            error = key_error;
+                        }
 
                         let put_request = ::raiden::aws_sdk::types::PutRequest::builder()
                             .set_item(Some(input_item))
@@ -82,21 +96,36 @@ pub(crate) fn expand_batch_put(
                     client: &self.client,
                     write_requests,
                     table_name: self.table_name(),
+                    return_consumed_capacity: None,
+                    key_error: error,
                 }
             }
         }
 
         pub struct #builder_name<'a> {
-            pub client: &'a ::raiden::Client,
+            pub client: &'a ::raiden::SharedBackend,
             pub write_requests: ::std::vec::Vec<::raiden::aws_sdk::types::WriteRequest>,
             pub table_name: String,
+            pub return_consumed_capacity: Option<::raiden::ReturnConsumedCapacity>,
+            key_error: Option<::raiden::RaidenError>,
         }
 
         impl<'a> #builder_name<'a> {
+            /// Requests the consumed capacity, summed over every request as `consumed_capacity`.
+            pub fn return_consumed_capacity(mut self, value: ::raiden::ReturnConsumedCapacity) -> Self {
+                self.return_consumed_capacity = Some(value);
+                self
+            }
+
             pub async fn run(mut self) -> Result<::raiden::batch_put::BatchPutOutput, ::raiden::RaidenError> {
+                if let Some(e) = self.key_error.take() {
+                    return Err(e);
+                }
+
                 const RETRY: usize = 5;
                 const MAX_ITEMS_PER_REQUEST: usize = 25;
 
+                let mut consumed_capacity = None;
                 for _ in 0..RETRY {
                     loop {
                         let len = self.write_requests.len();
@@ -111,9 +140,11 @@ pub(crate) fn expand_batch_put(
                             .into_iter()
                             .collect::<std::collections::HashMap<_, _>>();
                         let builder = ::raiden::aws_sdk::operation::batch_write_item::BatchWriteItemInput::builder()
-                            .set_request_items(Some(request_items));
+                            .set_request_items(Some(request_items))
+                            .set_return_consumed_capacity(self.return_consumed_capacity.map(Into::into));
 
                         let result = #call_inner_run;
+                        consumed_capacity = ::raiden::capacity::merge_consumed_capacities(consumed_capacity, result.consumed_capacity);
 
                         let mut unprocessed_items = match result.unprocessed_items {
                             None => continue,
@@ -124,7 +155,7 @@ pub(crate) fn expand_batch_put(
                         let unprocessed_requests = unprocessed_items
                             .remove(&self.table_name)
                             .expect("request_items hashmap must have a value for the table name");
-                        self.write_requests.extend(unprocessed_requests);
+                        self.write_requests.extend(unprocessed_requests.into_iter().map(Into::into));
                     }
                 }
 
@@ -133,17 +164,17 @@ pub(crate) fn expand_batch_put(
                     .filter_map(|write_request| write_request.put_request)
                     .collect::<std::vec::Vec<_>>();
                 Ok(::raiden::batch_put::BatchPutOutput {
-                    consumed_capacity: None,
+                    consumed_capacity,
                     unprocessed_items,
                 })
             }
 
             async fn inner_run(
                 #inner_run_args
-                client: &::raiden::Client,
+                client: &::raiden::SharedBackend,
                 builder: ::raiden::operation::batch_write_item::builders::BatchWriteItemInputBuilder,
-            ) -> Result<::raiden::operation::batch_write_item::BatchWriteItemOutput, ::raiden::RaidenError> {
-                Ok(#api_call_token?)
+            ) -> Result<::raiden::BatchWriteItemResponse, ::raiden::RaidenError> {
+                #api_call_token
             }
         }
     }
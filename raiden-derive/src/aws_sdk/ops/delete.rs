@@ -3,42 +3,50 @@ use quote::*;
 use syn::*;
 
 pub(crate) fn expand_delete_item(
-    partition_key: &(Ident, Type),
-    sort_key: &Option<(Ident, Type)>,
+    partition_key: &(LitStr, Type),
+    sort_key: &Option<(LitStr, Type)>,
     struct_name: &Ident,
+    version: &Option<crate::version::VersionField>,
 ) -> TokenStream {
     let trait_name = format_ident!("{}DeleteItem", struct_name);
     let client_name = format_ident!("{}Client", struct_name);
     let builder_name = format_ident!("{}DeleteItemBuilder", struct_name);
     let condition_token_name = format_ident!("{}ConditionToken", struct_name);
-    let (partition_key_ident, partition_key_type) = partition_key;
+    let (partition_key_name, partition_key_type) = partition_key;
+    // A versioned model is deleted only if the stored version equals `expected_version`.
+    let expected_version_arg = crate::version::expand_expected_version_arg(version);
+    let expected_version_condition = crate::version::expand_expected_version_condition(version);
 
     let client_trait = if let Some(sort_key) = sort_key {
-        let (sort_key_ident, sort_key_type) = sort_key;
+        let (sort_key_name, sort_key_type) = sort_key;
         quote! {
             pub trait #trait_name {
-                fn delete(&self, pk: impl Into<#partition_key_type>, sk: impl Into<#sort_key_type>) -> #builder_name;
+                fn delete(&self, pk: impl Into<#partition_key_type>, sk: impl Into<#sort_key_type> #expected_version_arg) -> #builder_name;
             }
 
             impl #trait_name for #client_name {
-                fn delete(&self, pk: impl Into<#partition_key_type>, sk: impl Into<#sort_key_type>) -> #builder_name {
+                fn delete(&self, pk: impl Into<#partition_key_type>, sk: impl Into<#sort_key_type> #expected_version_arg) -> #builder_name {
                     use ::std::iter::FromIterator;
 
                     let pk_attr: ::raiden::aws_sdk::types::AttributeValue = pk.into().into_attr();
                     let sk_attr: ::raiden::aws_sdk::types::AttributeValue = sk.into().into_attr();
                     let key_set: std::collections::HashMap<String, ::raiden::aws_sdk::types::AttributeValue> =
                         std::collections::HashMap::from_iter([
-                            (stringify!(#partition_key_ident).to_owned(), pk_attr),
-                            (stringify!(#sort_key_ident).to_owned(), sk_attr),
+                            (#partition_key_name.to_owned(), pk_attr),
+                            (#sort_key_name.to_owned(), sk_attr),
                         ]);
 
                     let mut builder = ::raiden::aws_sdk::operation::delete_item::DeleteItemInput::builder()
                         .set_key(Some(key_set))
                         .table_name(self.table_name());
 
+                    #expected_version_condition
+
                     #builder_name {
                         client: &self.client,
                         builder,
+                        version_condition,
+                        placeholders,
                     }
                 }
             }
@@ -46,26 +54,30 @@ pub(crate) fn expand_delete_item(
     } else {
         quote! {
             pub trait #trait_name {
-                fn delete(&self, key: impl Into<#partition_key_type>) -> #builder_name;
+                fn delete(&self, key: impl Into<#partition_key_type> #expected_version_arg) -> #builder_name;
             }
 
             impl #trait_name for #client_name {
-                fn delete(&self, key: impl Into<#partition_key_type>) -> #builder_name {
+                fn delete(&self, key: impl Into<#partition_key_type> #expected_version_arg) -> #builder_name {
                     use ::std::iter::FromIterator;
 
                     let key_attr: ::raiden::aws_sdk::types::AttributeValue = key.into().into_attr();
                     let key_set: std::collections::HashMap<String, ::raiden::aws_sdk::types::AttributeValue> =
                         std::collections::HashMap::from_iter([
-                            (stringify!(#partition_key_ident).to_owned(), key_attr),
+                            (#partition_key_name.to_owned(), key_attr),
                         ]);
 
                     let mut builder = ::raiden::aws_sdk::operation::delete_item::DeleteItemInput::builder()
                         .set_key(Some(key_set))
                         .table_name(self.table_name());
 
+                    #expected_version_condition
+
                     #builder_name {
                         client: &self.client,
                         builder,
+                        version_condition,
+                        placeholders,
                     }
                 }
             }
@@ -80,15 +92,15 @@ pub(crate) fn expand_delete_item(
                     .builder
                     .get_table_name()
                     .clone()
-                    .expect("table name should be set");
+                    .ok_or_else(|| ::raiden::RaidenError::Validation("the table name of the request is not set".to_owned()))?;
 
-                #builder_name::inner_run(&table_name, &self.client, self.builder).await?
+                #builder_name::inner_run(&table_name, &self.client, self.builder).await
             },
             quote! { table_name: &str, },
         )
     } else {
         (
-            quote! { #builder_name::inner_run(&self.client, self.builder).await? },
+            quote! { #builder_name::inner_run(&self.client, self.builder).await },
             quote! {},
         )
     };
@@ -97,8 +109,10 @@ pub(crate) fn expand_delete_item(
         #client_trait
 
         pub struct #builder_name<'a> {
-            pub client: &'a ::raiden::Client,
+            pub client: &'a ::raiden::SharedBackend,
             pub builder: ::raiden::aws_sdk::operation::delete_item::builders::DeleteItemInputBuilder,
+            pub version_condition: Option<::raiden::version::VersionCondition>,
+            pub placeholders: ::raiden::Placeholders,
         }
 
         impl<'a> #builder_name<'a> {
@@ -108,7 +122,7 @@ pub(crate) fn expand_delete_item(
             }
 
             pub fn condition(mut self, cond: impl ::raiden::condition::ConditionBuilder<#condition_token_name>) -> Self {
-                let (cond_str, attr_names, attr_values) = cond.build();
+                let (cond_str, attr_names, attr_values) = cond.build_with(&mut self.placeholders);
 
                 if !attr_names.is_empty() {
                     self.builder = self.builder
@@ -124,18 +138,75 @@ pub(crate) fn expand_delete_item(
                 self
             }
 
-            pub async fn run(self) -> Result<(), ::raiden::RaidenError> {
-                #call_inner_run;
-                Ok(())
+            /// Returns the deleted item as `old_item`.
+            pub fn return_old(mut self) -> Self {
+                self.builder = self.builder.return_values(::raiden::aws_sdk::types::ReturnValue::AllOld);
+                self
+            }
+
+            /// Returns the current item with `ConditionalCheckFailedWithItem` when the condition fails.
+            pub fn return_old_on_condition_failure(mut self) -> Self {
+                self.builder = self.builder.return_values_on_condition_check_failure(
+                    ::raiden::aws_sdk::types::ReturnValuesOnConditionCheckFailure::AllOld,
+                );
+                self
+            }
+
+            /// Requests the consumed capacity, returned as `consumed_capacity`.
+            pub fn return_consumed_capacity(mut self, value: ::raiden::ReturnConsumedCapacity) -> Self {
+                self.builder = self.builder.return_consumed_capacity(value.into());
+                self
+            }
+
+            /// Requests the size of the modified item collection, returned as `item_collection_metrics`.
+            pub fn return_item_collection_metrics(mut self, value: ::raiden::ReturnItemCollectionMetrics) -> Self {
+                self.builder = self.builder.return_item_collection_metrics(value.into());
+                self
+            }
+
+            pub async fn run(mut self) -> Result<::raiden::delete::DeleteOutput<#struct_name>, ::raiden::RaidenError> {
+                let with_item = self.builder.get_return_values_on_condition_check_failure().is_some();
+                let version_condition = self.version_condition.take();
+                if let Some(version_condition) = &version_condition {
+                    let (expression, names, values) = ::raiden::version::apply_version_condition(
+                        self.builder.get_condition_expression().clone(),
+                        self.builder.get_expression_attribute_names().clone(),
+                        self.builder.get_expression_attribute_values().clone(),
+                        &version_condition,
+                    );
+                    self.builder = self.builder
+                        .condition_expression(expression)
+                        .set_expression_attribute_names(names)
+                        .set_expression_attribute_values(values)
+                        .return_values_on_condition_check_failure(
+                            ::raiden::aws_sdk::types::ReturnValuesOnConditionCheckFailure::AllOld,
+                        );
+                }
+
+                let res = { #call_inner_run };
+                let res = res.map_err(|e| match &version_condition {
+                    Some(version_condition) => ::raiden::version::into_version_conflict(e, version_condition, with_item),
+                    None => e,
+                })?;
+
+                let old_item = match res.attributes {
+                    Some(item) => Some(<#struct_name as ::raiden::RaidenItem>::from_item(item)?),
+                    None => None,
+                };
+
+                Ok(::raiden::delete::DeleteOutput {
+                    consumed_capacity: res.consumed_capacity,
+                    old_item,
+                    item_collection_metrics: res.item_collection_metrics,
+                })
             }
 
             async fn inner_run(
                 #inner_run_args
-                client: &::raiden::Client,
+                client: &::raiden::SharedBackend,
                 builder: ::raiden::aws_sdk::operation::delete_item::builders::DeleteItemInputBuilder,
-            ) -> Result<(), ::raiden::RaidenError> {
-                #api_call_token?;
-                Ok(())
+            ) -> Result<::raiden::DeleteItemResponse, ::raiden::RaidenError> {
+                #api_call_token
             }
         }
     }
@@ -3,20 +3,18 @@ use quote::*;
 use syn::*;
 
 pub(crate) fn expand_get_item(
-    partition_key: &(Ident, Type),
-    sort_key: &Option<(Ident, Type)>,
+    partition_key: &(LitStr, Type),
+    sort_key: &Option<(LitStr, Type)>,
     struct_name: &Ident,
-    fields: &syn::FieldsNamed,
-    rename_all_type: crate::rename::RenameAllType,
+    ttl: &Option<crate::ttl::TtlField>,
 ) -> TokenStream {
     let trait_name = format_ident!("{}GetItem", struct_name);
     let client_name = format_ident!("{}Client", struct_name);
     let builder_name = format_ident!("{}GetItemBuilder", struct_name);
-    let from_item = super::expand_attr_to_item(format_ident!("res_item"), fields, rename_all_type);
-    let (partition_key_ident, partition_key_type) = partition_key;
+    let (partition_key_name, partition_key_type) = partition_key;
 
     let client_trait = if let Some(sort_key) = sort_key {
-        let (sort_key_ident, sort_key_type) = sort_key;
+        let (sort_key_name, sort_key_type) = sort_key;
         quote! {
             pub trait #trait_name {
                 fn get(&self, pk: impl Into<#partition_key_type>, sk: impl Into<#sort_key_type>) -> #builder_name;
@@ -29,8 +27,8 @@ pub(crate) fn expand_get_item(
                     let pk_attr: ::raiden::aws_sdk::types::AttributeValue = pk.into().into_attr();
                     let sk_attr: ::raiden::aws_sdk::types::AttributeValue = sk.into().into_attr();
                     let key_set: std::collections::HashMap<String, ::raiden::aws_sdk::types::AttributeValue> = std::collections::HashMap::from_iter([
-                        (stringify!(#partition_key_ident).to_owned(), pk_attr),
-                        (stringify!(#sort_key_ident).to_owned(), sk_attr),
+                        (#partition_key_name.to_owned(), pk_attr),
+                        (#sort_key_name.to_owned(), sk_attr),
                     ]);
 
                     let mut builder = ::raiden::aws_sdk::operation::get_item::GetItemInput::builder()
@@ -47,6 +45,7 @@ pub(crate) fn expand_get_item(
                         builder,
                         policy: self.retry_condition.strategy.policy(),
                         condition: &self.retry_condition,
+                        filter_expired: false,
                     }
                 }
             }
@@ -63,7 +62,7 @@ pub(crate) fn expand_get_item(
 
                     let key_attr: ::raiden::aws_sdk::types::AttributeValue = key.into().into_attr();
                     let key_set: std::collections::HashMap<String, ::raiden::aws_sdk::types::AttributeValue> = std::collections::HashMap::from_iter([
-                        (stringify!(#partition_key_ident).to_owned(), key_attr),
+                        (#partition_key_name.to_owned(), key_attr),
                     ]);
 
                     let mut builder = ::raiden::aws_sdk::operation::get_item::GetItemInput::builder()
@@ -80,12 +79,35 @@ pub(crate) fn expand_get_item(
                         builder,
                         policy: self.retry_condition.strategy.policy(),
                         condition: &self.retry_condition,
+                        filter_expired: false,
                     }
                 }
             }
         }
     };
 
+    let (filter_expired, expired_check) = match ttl {
+        Some(crate::ttl::TtlField { ident, .. }) => (
+            quote! {
+                /// Reports an item which is already expired but not deleted by DynamoDB yet as not found.
+                pub fn filter_expired(mut self) -> Self {
+                    self.filter_expired = true;
+                    self
+                }
+            },
+            quote! {
+                let res = match res {
+                    Ok(output) if self.filter_expired && ::raiden::ttl::Expiry::is_expired(
+                        &output.item.#ident,
+                        <#struct_name as ::raiden::Clock>::now(),
+                    ) => Err(::raiden::RaidenError::ResourceNotFound("resource not found".to_owned())),
+                    res => res,
+                };
+            },
+        ),
+        None => (quote! {}, quote! {}),
+    };
+
     let api_call_token = super::api_call_token!("get_item");
     let (call_inner_run, inner_run_args) = if cfg!(feature = "tracing") {
         (
@@ -93,7 +115,7 @@ pub(crate) fn expand_get_item(
                 let table_name = builder
                     .get_table_name()
                     .clone()
-                    .expect("table name should be set");
+                    .ok_or_else(|| ::raiden::RaidenError::Validation("the table name of the request is not set".to_owned()))?;
 
                 #builder_name::inner_run(table_name, client, builder).await
             },
@@ -110,10 +132,11 @@ pub(crate) fn expand_get_item(
         #client_trait
 
         pub struct #builder_name<'a> {
-            pub client: &'a ::raiden::Client,
+            pub client: &'a ::raiden::SharedBackend,
             pub builder: ::raiden::aws_sdk::operation::get_item::builders::GetItemInputBuilder,
             pub policy: ::raiden::Policy,
             pub condition: &'a ::raiden::retry::RetryCondition,
+            pub filter_expired: bool,
         }
 
         impl<'a> #builder_name<'a> {
@@ -122,30 +145,37 @@ pub(crate) fn expand_get_item(
                 self
             }
 
+            /// Requests the consumed capacity, returned as `consumed_capacity`.
+            pub fn return_consumed_capacity(mut self, value: ::raiden::ReturnConsumedCapacity) -> Self {
+                self.builder = self.builder.return_consumed_capacity(value.into());
+                self
+            }
+
+            #filter_expired
+
             pub async fn run(self) -> Result<::raiden::get::GetOutput<#struct_name>, ::raiden::RaidenError> {
-                let policy: ::raiden::RetryPolicy = self.policy.into();
                 let client = self.client;
                 let builder = self.builder;
-                policy.retry_if(move || {
-                    let client = client.clone();
+                let res = ::raiden::retry_with_backend(self.policy, &client, self.condition, move |client| {
                     let builder = builder.clone();
                     async { #call_inner_run }
-                }, self.condition).await
+                }).await;
+
+                #expired_check
+                res
             }
 
             async fn inner_run(
                 #inner_run_args
-                client: ::raiden::Client,
+                client: ::raiden::SharedBackend,
                 builder: ::raiden::aws_sdk::operation::get_item::builders::GetItemInputBuilder,
             ) -> Result<::raiden::get::GetOutput<#struct_name>, ::raiden::RaidenError> {
                 let res = #api_call_token?;
                 if res.item.is_none() {
                     return Err(::raiden::RaidenError::ResourceNotFound("resource not found".to_owned()));
                 };
-                let mut res_item = res.item.unwrap();
-                let item = #struct_name {
-                    #(#from_item)*
-                };
+                let res_item = res.item.unwrap();
+                let item = <#struct_name as ::raiden::RaidenItem>::from_item(res_item)?;
                 Ok(::raiden::get::GetOutput {
                     item,
                     consumed_capacity: res.consumed_capacity,
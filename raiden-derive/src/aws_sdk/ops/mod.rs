@@ -7,10 +7,13 @@ mod put;
 mod query;
 mod scan;
 mod shared;
+mod table;
 mod transact_get;
 mod transact_write;
 mod update;
 
+use shared::*;
+
 pub(crate) use batch_delete::*;
 pub(crate) use batch_get::*;
 pub(crate) use batch_put::*;
@@ -19,7 +22,7 @@ pub(crate) use get::*;
 pub(crate) use put::*;
 pub(crate) use query::*;
 pub(crate) use scan::*;
-pub(crate) use shared::*;
+pub(crate) use table::*;
 pub(crate) use transact_get::*;
 pub(crate) use transact_write::*;
 pub(crate) use update::*;
@@ -6,6 +6,9 @@ pub(crate) fn expand_put_item(
     struct_name: &Ident,
     fields: &syn::FieldsNamed,
     rename_all_type: crate::rename::RenameAllType,
+    version: &Option<crate::version::VersionField>,
+    timestamps: &crate::timestamp::Timestamps,
+    key_templates: &crate::key_template::KeyTemplates,
 ) -> TokenStream {
     let item_input_name = format_ident!("{}PutItemInput", struct_name);
     let item_input_builder_name = format_ident!("{}PutItemInputBuilder", struct_name);
@@ -19,6 +22,7 @@ pub(crate) fn expand_put_item(
         .named
         .iter()
         .filter(|f| !crate::finder::include_unary_attr(&f.attrs, "uuid"))
+        .filter(|f| !timestamps.is(f))
         .map(|f| {
             let ident = &f.ident.clone().unwrap();
             let ty = &f.ty;
@@ -43,6 +47,14 @@ pub(crate) fn expand_put_item(
             quote! {
                 #ident: uuid_map.get(#attr_key).cloned().unwrap().into(),
             }
+        } else if version.as_ref().is_some_and(|version| version.is(f)) {
+            quote! {
+                #ident: next_version.unwrap_or(item.#ident),
+            }
+        } else if let Some(value) = timestamps.expand_value(f) {
+            quote! {
+                #ident: #value,
+            }
         } else {
             quote! {
                 #ident: item.#ident,
@@ -50,6 +62,8 @@ pub(crate) fn expand_put_item(
         }
     });
 
+    let key_insertion = key_templates.expand_insertion(quote! { item });
+    let version_overflow = crate::version::expand_version_overflow(version);
     let input_items = {
         let insertion = fields.named.iter().map(|f| {
             let ident = &f.ident.clone().unwrap();
@@ -67,6 +81,22 @@ pub(crate) fn expand_put_item(
                         id,
                     );
                 }
+            } else if version.as_ref().is_some_and(|version| version.is(f)) {
+                quote! {
+                    if let Some(next_version) = next_version {
+                        input_item.insert(
+                            #attr_key.to_string(),
+                            next_version.into_attr(),
+                        );
+                    }
+                }
+            } else if let Some(value) = timestamps.expand_value(f) {
+                quote! {
+                    input_item.insert(
+                        #attr_key.to_string(),
+                        #value.into_attr(),
+                    );
+                }
             } else {
                 quote! {
                     let value = item.#ident.clone().into_attr();
@@ -82,6 +112,8 @@ pub(crate) fn expand_put_item(
 
         quote! {
             let mut input_item: std::collections::HashMap<String, ::raiden::aws_sdk::types::AttributeValue> = std::collections::HashMap::new();
+            #key_insertion
+            #version_overflow
             #(#insertion)*
         }
     };
@@ -91,11 +123,16 @@ pub(crate) fn expand_put_item(
         .named
         .iter()
         .filter(|f| !crate::finder::include_unary_attr(&f.attrs, "uuid"))
+        .filter(|f| !timestamps.is(f))
         .filter(|f| !crate::finder::is_option(&f.ty))
         .map(|f| f.ident.clone().unwrap())
         .collect();
     let default_types = expand_default_type_variables(&required_field_idents);
 
+    let version_condition = crate::version::expand_put_version(version);
+
+    let now = timestamps.expand_now(struct_name);
+
     let api_call_token = super::api_call_token!("put_item");
     let (call_inner_run, inner_run_args) = if cfg!(feature = "tracing") {
         (
@@ -103,7 +140,7 @@ pub(crate) fn expand_put_item(
                 let table_name = builder
                     .get_table_name()
                     .clone()
-                    .expect("table name should be set");
+                    .ok_or_else(|| ::raiden::RaidenError::Validation("the table name of the request is not set".to_owned()))?;
 
                 #builder_name::inner_run(table_name, client, builder).await
             },
@@ -141,6 +178,8 @@ pub(crate) fn expand_put_item(
             fn put(&self, item: #item_input_name) -> #builder_name{
                 let mut uuid_map: std::collections::HashMap<String, String> = std::collections::HashMap::new();
 
+                #version_condition
+                #now
                 #input_items
 
                 let output_item = #item_output_name {
@@ -157,16 +196,22 @@ pub(crate) fn expand_put_item(
                     item: output_item,
                     policy: self.retry_condition.strategy.policy(),
                     condition: &self.retry_condition,
+                    version_condition,
+                    placeholders,
+                    key_error,
                 }
             }
         }
 
         pub struct #builder_name<'a> {
-            pub client: &'a ::raiden::Client,
+            pub client: &'a ::raiden::SharedBackend,
             pub builder: ::raiden::aws_sdk::operation::put_item::builders::PutItemInputBuilder,
             pub item: #item_output_name,
             pub policy: ::raiden::Policy,
             pub condition: &'a ::raiden::retry::RetryCondition,
+            pub version_condition: Option<::raiden::version::VersionCondition>,
+            pub placeholders: ::raiden::Placeholders,
+            key_error: Option<::raiden::RaidenError>,
         }
 
         impl<'a> #builder_name<'a> {
@@ -177,7 +222,7 @@ pub(crate) fn expand_put_item(
             }
 
             pub fn condition(mut self, cond: impl ::raiden::condition::ConditionBuilder<#condition_token_name>) -> Self {
-                let (cond_str, attr_names, attr_values) = cond.build();
+                let (cond_str, attr_names, attr_values) = cond.build_with(&mut self.placeholders);
 
                 if !attr_names.is_empty() {
                     self.builder = self.builder
@@ -193,29 +238,85 @@ pub(crate) fn expand_put_item(
                 self
             }
 
-            pub async fn run(self) -> Result<::raiden::put::PutOutput<#item_output_name>, ::raiden::RaidenError> {
-                let builder = self.builder.clone();
+            /// Returns the replaced item as `old_item`.
+            pub fn return_old(mut self) -> Self {
+                self.builder = self.builder.return_values(::raiden::aws_sdk::types::ReturnValue::AllOld);
+                self
+            }
+
+            /// Returns the current item with `ConditionalCheckFailedWithItem` when the condition fails.
+            pub fn return_old_on_condition_failure(mut self) -> Self {
+                self.builder = self.builder.return_values_on_condition_check_failure(
+                    ::raiden::aws_sdk::types::ReturnValuesOnConditionCheckFailure::AllOld,
+                );
+                self
+            }
+
+            /// Requests the consumed capacity, returned as `consumed_capacity`.
+            pub fn return_consumed_capacity(mut self, value: ::raiden::ReturnConsumedCapacity) -> Self {
+                self.builder = self.builder.return_consumed_capacity(value.into());
+                self
+            }
+
+            /// Requests the size of the modified item collection, returned as `item_collection_metrics`.
+            pub fn return_item_collection_metrics(mut self, value: ::raiden::ReturnItemCollectionMetrics) -> Self {
+                self.builder = self.builder.return_item_collection_metrics(value.into());
+                self
+            }
+
+            pub async fn run(self) -> Result<::raiden::put::PutOutput<#item_output_name, #struct_name>, ::raiden::RaidenError> {
+                if let Some(e) = self.key_error {
+                    return Err(e);
+                }
+
+                let mut builder = self.builder.clone();
+                let with_item = builder.get_return_values_on_condition_check_failure().is_some();
+                let version_condition = self.version_condition;
+                if let Some(version_condition) = &version_condition {
+                    let (expression, names, values) = ::raiden::version::apply_version_condition(
+                        builder.get_condition_expression().clone(),
+                        builder.get_expression_attribute_names().clone(),
+                        builder.get_expression_attribute_values().clone(),
+                        &version_condition,
+                    );
+                    builder = builder
+                        .condition_expression(expression)
+                        .set_expression_attribute_names(names)
+                        .set_expression_attribute_values(values)
+                        .return_values_on_condition_check_failure(
+                            ::raiden::aws_sdk::types::ReturnValuesOnConditionCheckFailure::AllOld,
+                        );
+                }
+
                 let client = self.client.clone();
-                let policy: ::raiden::RetryPolicy = self.policy.into();
 
-                let res = policy.retry_if(move || {
+                let res = ::raiden::retry_with_backend(self.policy, &client, self.condition, move |client| {
                     let builder = builder.clone();
-                    let client = client.clone();
                     async { #call_inner_run }
-                }, self.condition).await?;
+                }).await.map_err(|e| match &version_condition {
+                    Some(version_condition) => ::raiden::version::into_version_conflict(e, version_condition, with_item),
+                    None => e,
+                })?;
+
+                let old_item = match res.attributes {
+                    Some(item) => Some(<#struct_name as ::raiden::RaidenItem>::from_item(item)?),
+                    None => None,
+                };
 
                 Ok(::raiden::put::PutOutput {
                     item: self.item,
                     consumed_capacity: res.consumed_capacity,
+                    old_item,
+                    item_collection_metrics: res.item_collection_metrics,
                 })
             }
 
             async fn inner_run(
                 #inner_run_args
-                client: ::raiden::Client,
+                client: ::raiden::SharedBackend,
                 builder: ::raiden::aws_sdk::operation::put_item::builders::PutItemInputBuilder,
-            ) -> Result<::raiden::aws_sdk::operation::put_item::PutItemOutput, ::raiden::RaidenError> {
-                Ok(#api_call_token?)
+            ) -> Result<::raiden::PutItemResponse, ::raiden::RaidenError> {
+                #api_call_token
             }
         }
     }
@@ -50,7 +50,7 @@ fn resolve_attr_name(
                 .as_ref()
                 .is_some_and(|ident| ident == field_name)
         })
-        .unwrap_or_else(|| panic!("unknown field `{field_name}` for gsi partition_key"));
+        .expect("gsi keys should be validated");
 
     crate::rename::create_renamed(
         field_name.to_owned(),
@@ -65,6 +65,7 @@ pub(crate) fn expand_query(
     rename_all_type: crate::rename::RenameAllType,
     gsi_names: &[String],
     gsi_definitions: &[crate::finder::GsiDefinition],
+    ttl: &Option<crate::ttl::TtlField>,
 ) -> proc_macro2::TokenStream {
     let trait_name = format_ident!("{}Query", struct_name);
     let client_name = format_ident!("{}Client", struct_name);
@@ -74,6 +75,7 @@ pub(crate) fn expand_query(
 
     let filter_expression_token_name = format_ident!("{}FilterExpressionToken", struct_name);
     let key_condition_token_name = format_ident!("{}KeyConditionToken", struct_name);
+    let partition_token_name = format_ident!("{}PartitionKeyConditionToken", struct_name);
     let gsi_tokens = gsi_definitions.iter().flat_map(|gsi| {
         let mut tokens = vec![];
         if gsi.partition_key.is_none() {
@@ -204,40 +206,40 @@ pub(crate) fn expand_query(
             .iter()
             .find(|gsi| gsi.name == *index_name && gsi.partition_key.is_some())
             .map(|gsi| create_gsi_partition_token_name(struct_name, &gsi.name));
+        // An index without typed key definitions accepts any `key_condition`.
+        let token_name = typed_token_name.unwrap_or_else(|| key_condition_token_name.clone());
 
-        if let Some(token_name) = typed_token_name {
-            quote! {
-                pub fn #method_ident(self) -> #builder_name<'a, #token_name> {
-                    let Self {
-                        client,
-                        builder,
-                        next_token,
-                        limit,
-                        policy,
-                        condition,
-                        ..
-                    } = self;
-                    #builder_name {
-                        client,
-                        builder: builder.index_name(#index_name),
-                        next_token,
-                        limit,
-                        policy,
-                        condition,
-                        _token: std::marker::PhantomData::<fn() -> #token_name>,
-                    }
-                }
-            }
-        } else {
-            quote! {
-                pub fn #method_ident(mut self) -> Self {
-                    self.builder = self.builder.index_name(#index_name);
-                    self
+        quote! {
+            pub fn #method_ident(self) -> #builder_name<'a, #token_name> {
+                let Self {
+                    client,
+                    builder,
+                    next_token,
+                    limit,
+                    policy,
+                    condition,
+                    expired_filter,
+                    placeholders,
+                    ..
+                } = self;
+                #builder_name {
+                    client,
+                    builder: builder.index_name(#index_name),
+                    next_token,
+                    limit,
+                    policy,
+                    condition,
+                    expired_filter,
+                    placeholders,
+                    _token: std::marker::PhantomData::<fn() -> #token_name>,
                 }
             }
         }
     });
 
+    let (filter_expired, projected_filter_expired) =
+        super::scan::expand_filter_expired(struct_name, ttl);
+
     let api_call_token = super::api_call_token!("query");
     let (call_inner_run, inner_run_args) = if cfg!(feature = "tracing") {
         (
@@ -245,7 +247,7 @@ pub(crate) fn expand_query(
                 let table_name = builder
                     .get_table_name()
                     .clone()
-                    .expect("table name should be set");
+                    .ok_or_else(|| ::raiden::RaidenError::Validation("the table name of the request is not set".to_owned()))?;
 
                 Self::inner_run(table_name, client, builder).await
             },
@@ -257,16 +259,18 @@ pub(crate) fn expand_query(
 
     quote! {
         pub trait #trait_name {
-            fn query(&self) -> #builder_name<'_, #key_condition_token_name>;
+            fn query(&self) -> #builder_name<'_, #partition_token_name>;
         }
 
-        pub struct #builder_name<'a, T = #key_condition_token_name> {
-            pub client: &'a ::raiden::Client,
+        pub struct #builder_name<'a, T = #partition_token_name> {
+            pub client: &'a ::raiden::SharedBackend,
             pub builder: ::raiden::aws_sdk::operation::query::builders::QueryInputBuilder,
             pub next_token: Option<::raiden::NextToken>,
             pub limit: Option<i64>,
             pub policy: ::raiden::Policy,
             pub condition: &'a ::raiden::retry::RetryCondition,
+            pub expired_filter: Option<::raiden::ttl::ExpiredFilter>,
+            pub placeholders: ::raiden::Placeholders,
             pub _token: std::marker::PhantomData<fn() -> T>,
         }
 
@@ -292,7 +296,7 @@ pub(crate) fn expand_query(
         impl #trait_name for #client_name {
 
             #![allow(clippy::field_reassign_with_default)]
-            fn query(&self) -> #builder_name<'_, #key_condition_token_name> {
+            fn query(&self) -> #builder_name<'_, #partition_token_name> {
                 let builder = ::raiden::aws_sdk::operation::query::QueryInput::builder()
                     .table_name(self.table_name())
                     .set_projection_expression(self.projection_expression.clone())
@@ -305,7 +309,9 @@ pub(crate) fn expand_query(
                     limit: None,
                     policy: self.retry_condition.strategy.policy(),
                     condition: &self.retry_condition,
-                    _token: std::marker::PhantomData::<fn() -> #key_condition_token_name>,
+                    expired_filter: None,
+                    placeholders: ::raiden::Placeholders::new(),
+                    _token: std::marker::PhantomData::<fn() -> #partition_token_name>,
                 }
             }
         }
@@ -316,9 +322,29 @@ pub(crate) fn expand_query(
 
         impl<'a, T> #builder_name<'a, T> {
             #[deprecated(note = "use generated typed index method instead")]
-            pub fn index(mut self, index: impl Into<String>) -> Self {
-                self.builder = self.builder.index_name(index.into());
-                self
+            pub fn index(self, index: impl Into<String>) -> #builder_name<'a, #key_condition_token_name> {
+                let Self {
+                    client,
+                    builder,
+                    next_token,
+                    limit,
+                    policy,
+                    condition,
+                    expired_filter,
+                    placeholders,
+                    ..
+                } = self;
+                #builder_name {
+                    client,
+                    builder: builder.index_name(index.into()),
+                    next_token,
+                    limit,
+                    policy,
+                    condition,
+                    expired_filter,
+                    placeholders,
+                    _token: std::marker::PhantomData::<fn() -> #key_condition_token_name>,
+                }
             }
 
             #(#gsi_methods)*
@@ -346,6 +372,12 @@ pub(crate) fn expand_query(
                 self
             }
 
+            /// Requests the consumed capacity, summed over every page as `consumed_capacity`.
+            pub fn return_consumed_capacity(mut self, value: ::raiden::ReturnConsumedCapacity) -> Self {
+                self.builder = self.builder.return_consumed_capacity(value.into());
+                self
+            }
+
             pub fn next_token(mut self, token: ::raiden::NextToken) -> Self {
                 self.next_token = Some(token);
                 self
@@ -367,7 +399,7 @@ pub(crate) fn expand_query(
             }
 
             pub fn filter(mut self, cond: impl ::raiden::filter_expression::FilterExpressionBuilder<#filter_expression_token_name>) -> Self {
-                let (cond_str, attr_names, attr_values) = cond.build();
+                let (cond_str, attr_names, attr_values) = cond.build_with(&mut self.placeholders);
 
                 if !attr_names.is_empty() {
                     if let Some(v) = self.builder.get_expression_attribute_names().clone() {
@@ -390,11 +422,29 @@ pub(crate) fn expand_query(
                 }
 
                 self.builder = self.builder.filter_expression(cond_str);
+                self.with_expired_filter()
+            }
+
+            #filter_expired
+
+            fn with_expired_filter(mut self) -> Self {
+                if let Some(expired_filter) = self.expired_filter.clone() {
+                    let (expression, names, values) = ::raiden::ttl::apply_expired_filter(
+                        self.builder.get_filter_expression().clone(),
+                        self.builder.get_expression_attribute_names().clone(),
+                        self.builder.get_expression_attribute_values().clone(),
+                        expired_filter,
+                    );
+                    self.builder = self.builder
+                        .filter_expression(expression)
+                        .set_expression_attribute_names(names)
+                        .set_expression_attribute_values(values);
+                }
                 self
             }
 
             pub fn key_condition<U>(mut self, cond: impl ::raiden::key_condition::KeyConditionBuilder<T, U>) -> Self {
-                let (cond_str, attr_names, attr_values) = cond.build();
+                let (cond_str, attr_names, attr_values) = cond.build_with(&mut self.placeholders);
 
                 if !attr_names.is_empty() {
                     if let Some(v) = self.builder.get_expression_attribute_names().clone() {
@@ -439,6 +489,54 @@ pub(crate) fn expand_query(
                 self.project::<I>().run().await
             }
 
+            /// Returns a stream which lazily fetches each page of the query.
+            ///
+            /// The retry policy is applied to every page request.
+            pub fn pages(self) -> impl ::raiden::futures::Stream<Item = Result<::raiden::query::QueryOutput<#struct_name>, ::raiden::RaidenError>> + 'a {
+                self.pages_inner::<#struct_name>()
+            }
+
+            /// Returns a stream of items which follows `LastEvaluatedKey` until the query is exhausted.
+            pub fn into_stream(self) -> impl ::raiden::futures::Stream<Item = Result<#struct_name, ::raiden::RaidenError>> + 'a {
+                ::raiden::pagination::items(self.pages())
+            }
+
+            fn pages_inner<I>(self) -> impl ::raiden::futures::Stream<Item = Result<::raiden::query::QueryOutput<I>, ::raiden::RaidenError>> + 'a
+            where
+                I: ::raiden::RaidenItem + 'a,
+            {
+                let Self { client, builder, next_token, limit, policy, condition, .. } = self;
+
+                ::raiden::pagination::paginate(next_token, limit, move |exclusive_start_key, limit| {
+                    let mut builder = builder.clone().set_exclusive_start_key(exclusive_start_key);
+                    if let Some(limit) = limit {
+                        builder = builder.limit(limit as i32);
+                    }
+
+                    async move {
+                        let res: #query_output_item = ::raiden::retry_with_backend(policy, &client, condition, move |client| {
+                            let builder = builder.clone();
+                            async { #call_inner_run }
+                        }).await?;
+
+                        let mut items: Vec<I> = vec![];
+                        if let Some(res_items) = res.items {
+                            for res_item in res_items.into_iter() {
+                                items.push(I::from_item(res_item)?)
+                            }
+                        };
+
+                        Ok(::raiden::query::QueryOutput {
+                            consumed_capacity: res.consumed_capacity,
+                            count: res.count,
+                            items,
+                            next_token: res.last_evaluated_key.as_ref().map(::raiden::NextToken::from_attr),
+                            scanned_count: res.scanned_count,
+                        })
+                    }
+                })
+            }
+
             async fn run_inner<I>(mut self) -> Result<::raiden::query::QueryOutput<I>, ::raiden::RaidenError>
             where
                 I: ::raiden::RaidenItem,
@@ -449,7 +547,7 @@ pub(crate) fn expand_query(
                     }
 
                 let mut items: Vec<I> = vec![];
-                let policy: ::raiden::RetryPolicy = self.policy.into();
+                let mut consumed_capacity = None;
                 let client = self.client;
 
                 loop {
@@ -460,11 +558,10 @@ pub(crate) fn expand_query(
                     let builder = self.builder.clone();
                     let client = self.client.clone();
 
-                    let res: #query_output_item = policy.retry_if(move || {
+                    let res: #query_output_item = ::raiden::retry_with_backend(self.policy, &client, self.condition, move |client| {
                         let builder = builder.clone();
-                        let client = client.clone();
                         async { #call_inner_run }
-                    }, self.condition).await?;
+                    }).await?;
 
                     if let Some(res_items) = res.items {
                         for res_item in res_items.into_iter() {
@@ -472,6 +569,8 @@ pub(crate) fn expand_query(
                         }
                     };
 
+                    consumed_capacity = ::raiden::capacity::add_consumed_capacity(consumed_capacity, res.consumed_capacity);
+
                     let scanned = res.scanned_count.unwrap_or(0);
 
                     let mut has_next = true;
@@ -487,7 +586,7 @@ pub(crate) fn expand_query(
                             None
                         };
                         return Ok(::raiden::query::QueryOutput {
-                            consumed_capacity: res.consumed_capacity,
+                            consumed_capacity,
                             count: res.count,
                             items,
                             next_token,
@@ -502,16 +601,16 @@ pub(crate) fn expand_query(
 
             async fn inner_run(
                 #inner_run_args
-                client: ::raiden::Client,
+                client: ::raiden::SharedBackend,
                 builder: ::raiden::aws_sdk::operation::query::builders::QueryInputBuilder,
             ) -> Result<#query_output_item, ::raiden::RaidenError> {
                 let res = #api_call_token?;
                 Ok(#query_output_item {
                     consumed_capacity: res.consumed_capacity,
-                    count: Some(res.count as i64),
+                    count: res.count,
                     items: res.items,
                     last_evaluated_key: res.last_evaluated_key,
-                    scanned_count: Some(res.scanned_count as i64),
+                    scanned_count: res.scanned_count,
                 })
             }
         }
@@ -534,6 +633,12 @@ pub(crate) fn expand_query(
                 self
             }
 
+            /// Requests the consumed capacity, summed over every page as `consumed_capacity`.
+            pub fn return_consumed_capacity(mut self, value: ::raiden::ReturnConsumedCapacity) -> Self {
+                self.inner = self.inner.return_consumed_capacity(value);
+                self
+            }
+
             /// Sets the pagination token used to resume the query.
             pub fn next_token(mut self, token: ::raiden::NextToken) -> Self {
                 self.inner = self.inner.next_token(token);
@@ -564,6 +669,8 @@ pub(crate) fn expand_query(
                 self
             }
 
+            #projected_filter_expired
+
             /// Applies a key condition while preserving the projection type.
             pub fn key_condition<U>(mut self, cond: impl ::raiden::key_condition::KeyConditionBuilder<T, U>) -> Self {
                 self.inner = self.inner.key_condition(cond);
@@ -585,6 +692,22 @@ pub(crate) fn expand_query(
             {
                 self.project::<J>().run().await
             }
+
+            /// Returns a stream which lazily fetches each page decoded into the projection item type.
+            pub fn pages(self) -> impl ::raiden::futures::Stream<Item = Result<::raiden::query::QueryOutput<I>, ::raiden::RaidenError>> + 'a
+            where
+                I: 'a,
+            {
+                self.inner.pages_inner::<I>()
+            }
+
+            /// Returns a stream of projection items which follows `LastEvaluatedKey` until the query is exhausted.
+            pub fn into_stream(self) -> impl ::raiden::futures::Stream<Item = Result<I, ::raiden::RaidenError>> + 'a
+            where
+                I: 'a,
+            {
+                ::raiden::pagination::items(self.pages())
+            }
         }
     }
 }
@@ -6,11 +6,13 @@ pub(crate) fn expand_scan(
     _fields: &syn::FieldsNamed,
     _rename_all_type: crate::rename::RenameAllType,
     gsi_names: &[String],
+    ttl: &Option<crate::ttl::TtlField>,
 ) -> proc_macro2::TokenStream {
     let trait_name = format_ident!("{}Scan", struct_name);
     let client_name = format_ident!("{}Client", struct_name);
     let builder_name = format_ident!("{}ScanBuilder", struct_name);
     let projected_builder_name = format_ident!("{}ProjectedScanBuilder", struct_name);
+    let parallel_builder_name = format_ident!("{}ParallelScanBuilder", struct_name);
 
     let filter_expression_token_name = format_ident!("{}FilterExpressionToken", struct_name);
     let gsi_methods = gsi_names.iter().map(|index_name| {
@@ -27,18 +29,20 @@ pub(crate) fn expand_scan(
             }
         }
     });
+    let (filter_expired, projected_filter_expired) = expand_filter_expired(struct_name, ttl);
+
     let api_call_token = super::api_call_token!("scan");
     let (call_inner_run, inner_run_args) = if cfg!(feature = "tracing") {
         (
             quote! {
-                let table_name = self.builder.get_table_name().clone().expect("table name should be set");
-                #builder_name::inner_run(&table_name, &self.client, self.builder.clone()).await?
+                let table_name = builder.get_table_name().clone().ok_or_else(|| ::raiden::RaidenError::Validation("the table name of the request is not set".to_owned()))?;
+                #builder_name::inner_run(&table_name, &client, builder).await
             },
             quote! { table_name: &str, },
         )
     } else {
         (
-            quote! { #builder_name::inner_run(&self.client, self.builder.clone()).await? },
+            quote! { #builder_name::inner_run(&client, builder).await },
             quote! {},
         )
     };
@@ -49,10 +53,14 @@ pub(crate) fn expand_scan(
         }
 
         pub struct #builder_name<'a> {
-            pub client: &'a ::raiden::Client,
+            pub client: &'a ::raiden::SharedBackend,
             pub builder: ::raiden::aws_sdk::operation::scan::builders::ScanInputBuilder,
             pub next_token: Option<::raiden::NextToken>,
-            pub limit: Option<i64>
+            pub limit: Option<i64>,
+            policy: ::raiden::Policy,
+            condition: &'a ::raiden::retry::RetryCondition,
+            pub expired_filter: Option<::raiden::ttl::ExpiredFilter>,
+            pub placeholders: ::raiden::Placeholders,
         }
 
         /// A typed scan builder that decodes results into a projection item.
@@ -64,6 +72,14 @@ pub(crate) fn expand_scan(
             pub _item: std::marker::PhantomData<fn() -> I>,
         }
 
+        /// A scan builder which splits the table into segments and scans them concurrently.
+        pub struct #parallel_builder_name<'a, I = #struct_name> {
+            pub inner: #builder_name<'a>,
+            pub segments: Vec<::raiden::scan::ScanSegment>,
+            pub concurrency: usize,
+            pub _item: std::marker::PhantomData<fn() -> I>,
+        }
+
         impl #trait_name for #client_name {
             #![allow(clippy::field_reassign_with_default)]
             fn scan(&self) -> #builder_name {
@@ -76,7 +92,11 @@ pub(crate) fn expand_scan(
                     client: &self.client,
                     builder,
                     next_token: None,
-                    limit: None
+                    limit: None,
+                    policy: self.retry_condition.strategy.policy(),
+                    condition: &self.retry_condition,
+                    expired_filter: None,
+                    placeholders: ::raiden::Placeholders::new(),
                 }
             }
         }
@@ -113,8 +133,14 @@ pub(crate) fn expand_scan(
                 self
             }
 
+            /// Requests the consumed capacity, summed over every page as `consumed_capacity`.
+            pub fn return_consumed_capacity(mut self, value: ::raiden::ReturnConsumedCapacity) -> Self {
+                self.builder = self.builder.return_consumed_capacity(value.into());
+                self
+            }
+
             pub fn filter(mut self, cond: impl ::raiden::filter_expression::FilterExpressionBuilder<#filter_expression_token_name>) -> Self {
-                let (cond_str, attr_names, attr_values) = cond.build();
+                let (cond_str, attr_names, attr_values) = cond.build_with(&mut self.placeholders);
 
                 if !attr_names.is_empty() {
                     if let Some(v) = self.builder.get_expression_attribute_names().clone() {
@@ -137,6 +163,24 @@ pub(crate) fn expand_scan(
                 }
 
                 self.builder = self.builder.filter_expression(cond_str);
+                self.with_expired_filter()
+            }
+
+            #filter_expired
+
+            fn with_expired_filter(mut self) -> Self {
+                if let Some(expired_filter) = self.expired_filter.clone() {
+                    let (expression, names, values) = ::raiden::ttl::apply_expired_filter(
+                        self.builder.get_filter_expression().clone(),
+                        self.builder.get_expression_attribute_names().clone(),
+                        self.builder.get_expression_attribute_values().clone(),
+                        expired_filter,
+                    );
+                    self.builder = self.builder
+                        .filter_expression(expression)
+                        .set_expression_attribute_names(names)
+                        .set_expression_attribute_values(values);
+                }
                 self
             }
 
@@ -150,6 +194,54 @@ pub(crate) fn expand_scan(
                 self
             }
 
+            /// Scans only the given segment of a parallel scan.
+            pub fn segment(mut self, segment: usize, total_segments: usize) -> Self {
+                self.builder = self.builder
+                    .segment(segment as i32)
+                    .total_segments(total_segments as i32);
+                self
+            }
+
+            /// Splits the scan into `total_segments` segments which are scanned concurrently.
+            ///
+            /// Running it fails with `RaidenError::Validation` if `total_segments` is zero.
+            pub fn parallel(self, total_segments: usize) -> #parallel_builder_name<'a, #struct_name> {
+                self.parallel_inner::<#struct_name>(total_segments)
+            }
+
+            fn parallel_inner<I>(self, total_segments: usize) -> #parallel_builder_name<'a, I> {
+                let segments = (0..total_segments as i64)
+                    .map(|segment| ::raiden::scan::ScanSegment {
+                        segment,
+                        total_segments: total_segments as i64,
+                        state: ::raiden::scan::ScanSegmentState::Pending,
+                    })
+                    .collect();
+
+                #parallel_builder_name {
+                    inner: self,
+                    segments,
+                    concurrency: total_segments,
+                    _item: std::marker::PhantomData,
+                }
+            }
+
+            fn for_segment(&self, segment: &::raiden::scan::ScanSegment) -> Self {
+                Self {
+                    client: self.client,
+                    builder: self.builder
+                        .clone()
+                        .segment(segment.segment as i32)
+                        .total_segments(segment.total_segments as i32),
+                    next_token: segment.next_token().cloned(),
+                    limit: self.limit,
+                    policy: self.policy,
+                    condition: self.condition,
+                    expired_filter: self.expired_filter.clone(),
+                    placeholders: self.placeholders.clone(),
+                }
+            }
+
             /// Runs the scan and decodes items into the builder output type.
             pub async fn run(self) -> Result<::raiden::scan::ScanOutput<#struct_name>, ::raiden::RaidenError> {
                 self.run_inner::<#struct_name>().await
@@ -165,6 +257,54 @@ pub(crate) fn expand_scan(
                 self.project::<I>().run().await
             }
 
+            /// Returns a stream which lazily fetches each page of the scan.
+            ///
+            /// The retry policy is applied to every page request.
+            pub fn pages(self) -> impl ::raiden::futures::Stream<Item = Result<::raiden::scan::ScanOutput<#struct_name>, ::raiden::RaidenError>> + 'a {
+                self.pages_inner::<#struct_name>()
+            }
+
+            /// Returns a stream of items which follows `LastEvaluatedKey` until the scan is exhausted.
+            pub fn into_stream(self) -> impl ::raiden::futures::Stream<Item = Result<#struct_name, ::raiden::RaidenError>> + 'a {
+                ::raiden::pagination::items(self.pages())
+            }
+
+            fn pages_inner<I>(self) -> impl ::raiden::futures::Stream<Item = Result<::raiden::scan::ScanOutput<I>, ::raiden::RaidenError>> + 'a
+            where
+                I: ::raiden::RaidenItem + 'a,
+            {
+                let Self { client, builder, next_token, limit, policy, condition, .. } = self;
+
+                ::raiden::pagination::paginate(next_token, limit, move |exclusive_start_key, limit| {
+                    let mut builder = builder.clone().set_exclusive_start_key(exclusive_start_key);
+                    if let Some(limit) = limit {
+                        builder = builder.limit(limit as i32);
+                    }
+
+                    async move {
+                        let res = ::raiden::retry_with_backend(policy, &client, condition, move |client| {
+                            let builder = builder.clone();
+                            async move { #call_inner_run }
+                        }).await?;
+
+                        let mut items: Vec<I> = vec![];
+                        if let Some(res_items) = res.items {
+                            for res_item in res_items.into_iter() {
+                                items.push(I::from_item(res_item)?)
+                            }
+                        };
+
+                        Ok(::raiden::scan::ScanOutput {
+                            consumed_capacity: res.consumed_capacity,
+                            count: res.count,
+                            items,
+                            last_evaluated_key: res.last_evaluated_key,
+                            scanned_count: res.scanned_count,
+                        })
+                    }
+                })
+            }
+
             async fn run_inner<I>(mut self) -> Result<::raiden::scan::ScanOutput<I>, ::raiden::RaidenError>
             where
                 I: ::raiden::RaidenItem,
@@ -175,13 +315,18 @@ pub(crate) fn expand_scan(
                 }
 
                 let mut items: Vec<I> = vec![];
+                let mut consumed_capacity = None;
 
                 loop {
                     if let Some(limit) = self.limit {
                         self.builder = self.builder.limit(limit as i32);
                     }
 
-                    let res = { #call_inner_run };
+                    let res = {
+                        let client = self.client;
+                        let builder = self.builder.clone();
+                        #call_inner_run?
+                    };
 
                     if let Some(res_items) = res.items {
                         for res_item in res_items.into_iter() {
@@ -189,7 +334,9 @@ pub(crate) fn expand_scan(
                         }
                     };
 
-                    let scanned = res.scanned_count as i64;
+                    consumed_capacity = ::raiden::capacity::add_consumed_capacity(consumed_capacity, res.consumed_capacity);
+
+                    let scanned = res.scanned_count.unwrap_or(0);
 
                     let mut has_next = true;
                     if let Some(limit) = self.limit {
@@ -198,11 +345,11 @@ pub(crate) fn expand_scan(
                     }
                     if res.last_evaluated_key.is_none() || !has_next {
                         return Ok(::raiden::scan::ScanOutput {
-                            consumed_capacity: res.consumed_capacity,
-                            count: Some(res.count as i64),
+                            consumed_capacity,
+                            count: res.count,
                             items,
                             last_evaluated_key: res.last_evaluated_key,
-                            scanned_count: Some(res.scanned_count as i64),
+                            scanned_count: res.scanned_count,
                         })
                     }
 
@@ -213,10 +360,10 @@ pub(crate) fn expand_scan(
 
             async fn inner_run(
                 #inner_run_args
-                client: &::raiden::Client,
+                client: &::raiden::SharedBackend,
                 builder: ::raiden::aws_sdk::operation::scan::builders::ScanInputBuilder,
-            ) -> Result<::raiden::aws_sdk::operation::scan::ScanOutput, ::raiden::RaidenError> {
-                Ok(#api_call_token?)
+            ) -> Result<::raiden::ScanResponse, ::raiden::RaidenError> {
+                #api_call_token
             }
         }
 
@@ -238,12 +385,20 @@ pub(crate) fn expand_scan(
                 self
             }
 
+            /// Requests the consumed capacity, summed over every page as `consumed_capacity`.
+            pub fn return_consumed_capacity(mut self, value: ::raiden::ReturnConsumedCapacity) -> Self {
+                self.inner = self.inner.return_consumed_capacity(value);
+                self
+            }
+
             /// Applies a filter expression while preserving the projection type.
             pub fn filter(mut self, cond: impl ::raiden::filter_expression::FilterExpressionBuilder<#filter_expression_token_name>) -> Self {
                 self.inner = self.inner.filter(cond);
                 self
             }
 
+            #projected_filter_expired
+
             /// Sets the pagination token used to resume the scan.
             pub fn next_token(mut self, token: ::raiden::NextToken) -> Self {
                 self.inner = self.inner.next_token(token);
@@ -256,6 +411,11 @@ pub(crate) fn expand_scan(
                 self
             }
 
+            /// Splits the scan into `total_segments` segments decoded into the projection item type.
+            pub fn parallel(self, total_segments: usize) -> #parallel_builder_name<'a, I> {
+                self.inner.parallel_inner::<I>(total_segments)
+            }
+
             /// Runs the scan and decodes items into the projection item type.
             pub async fn run(self) -> Result<::raiden::scan::ScanOutput<I>, ::raiden::RaidenError> {
                 self.inner.run_inner::<I>().await
@@ -268,6 +428,104 @@ pub(crate) fn expand_scan(
             {
                 self.project::<J>().run().await
             }
+
+            /// Returns a stream which lazily fetches each page decoded into the projection item type.
+            pub fn pages(self) -> impl ::raiden::futures::Stream<Item = Result<::raiden::scan::ScanOutput<I>, ::raiden::RaidenError>> + 'a
+            where
+                I: 'a,
+            {
+                self.inner.pages_inner::<I>()
+            }
+
+            /// Returns a stream of projection items which follows `LastEvaluatedKey` until the scan is exhausted.
+            pub fn into_stream(self) -> impl ::raiden::futures::Stream<Item = Result<I, ::raiden::RaidenError>> + 'a
+            where
+                I: 'a,
+            {
+                ::raiden::pagination::items(self.pages())
+            }
+        }
+
+        impl<'a, I> #parallel_builder_name<'a, I>
+        where
+            I: ::raiden::RaidenItem + 'a,
+        {
+            /// Limits the number of segments scanned at the same time. Defaults to `total_segments`.
+            pub fn concurrency(mut self, concurrency: usize) -> Self {
+                self.concurrency = concurrency;
+                self
+            }
+
+            /// Limits the number of items evaluated in each segment.
+            pub fn limit(mut self, limit: usize) -> Self {
+                self.inner = self.inner.limit(limit);
+                self
+            }
+
+            /// Continues an interrupted scan from the segments of a previous run.
+            ///
+            /// Segments which have already been scanned to the end are skipped,
+            /// but are still returned by `run`.
+            pub fn resume(mut self, segments: impl IntoIterator<Item = ::raiden::scan::ScanSegment>) -> Self {
+                self.segments = segments.into_iter().collect();
+                self
+            }
+
+            /// Runs all segments and merges their items in segment order.
+            ///
+            /// If a segment fails, the error holds the items scanned so far and the
+            /// position of every segment, which can be passed to `resume`.
+            pub async fn run(self) -> Result<::raiden::scan::ParallelScanOutput<I>, ::raiden::scan::ParallelScanError<I>> {
+                let Self { inner, segments, concurrency, .. } = self;
+                ::raiden::pagination::run_segments(segments, concurrency, |segment| {
+                    inner.for_segment(&segment).run_inner::<I>()
+                }).await
+            }
+
+            /// Returns a stream of pages from all segments in the order they are fetched.
+            pub fn pages(self) -> impl ::raiden::futures::Stream<Item = Result<::raiden::scan::ParallelScanPage<I>, ::raiden::RaidenError>> + 'a {
+                let Self { inner, segments, concurrency, .. } = self;
+                ::raiden::pagination::segment_pages(segments, concurrency, move |segment| {
+                    inner.for_segment(&segment).pages_inner::<I>()
+                })
+            }
+
+            /// Returns a stream of items from all segments in the order they are fetched.
+            pub fn into_stream(self) -> impl ::raiden::futures::Stream<Item = Result<I, ::raiden::RaidenError>> + 'a {
+                ::raiden::pagination::segment_items(self.pages())
+            }
         }
     }
 }
+
+// `filter_expired` is generated only for models with a `#[raiden(ttl)]` field.
+pub(crate) fn expand_filter_expired(
+    struct_name: &proc_macro2::Ident,
+    ttl: &Option<crate::ttl::TtlField>,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let Some(crate::ttl::TtlField { attr_name, .. }) = ttl else {
+        return (quote! {}, quote! {});
+    };
+
+    (
+        quote! {
+            /// Drops items which are already expired but not deleted by DynamoDB yet.
+            pub fn filter_expired(mut self) -> Self {
+                if self.expired_filter.is_some() {
+                    return self;
+                }
+
+                let now = <#struct_name as ::raiden::Clock>::now();
+                self.expired_filter = Some(::raiden::ttl::not_expired_filter(#attr_name, now, &mut self.placeholders));
+                self.with_expired_filter()
+            }
+        },
+        quote! {
+            /// Drops items which are already expired but not deleted by DynamoDB yet.
+            pub fn filter_expired(mut self) -> Self {
+                self.inner = self.inner.filter_expired();
+                self
+            }
+        },
+    )
+}
@@ -0,0 +1,318 @@
+use proc_macro2::*;
+use quote::*;
+
+use crate::table::{KeyType, ProjectionType, TableDefinition};
+
+fn expand_key_schema(key_schema: &[(String, KeyType)]) -> Vec<TokenStream> {
+    key_schema
+        .iter()
+        .map(|(name, key_type)| {
+            let key_type = match key_type {
+                KeyType::Hash => quote! { ::raiden::aws_sdk::types::KeyType::Hash },
+                KeyType::Range => quote! { ::raiden::aws_sdk::types::KeyType::Range },
+            };
+            quote! {
+                ::raiden::aws_sdk::types::KeySchemaElement::builder()
+                    .attribute_name(#name)
+                    .key_type(#key_type)
+                    .build()
+                    .expect("should be built")
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn expand_table(struct_name: &Ident, definition: &TableDefinition) -> TokenStream {
+    let client_name = format_ident!("{}Client", struct_name);
+    let create_trait_name = format_ident!("{}CreateTable", struct_name);
+    let delete_trait_name = format_ident!("{}DeleteTable", struct_name);
+    let describe_trait_name = format_ident!("{}DescribeTable", struct_name);
+    let create_builder_name = format_ident!("{}CreateTableBuilder", struct_name);
+    let delete_builder_name = format_ident!("{}DeleteTableBuilder", struct_name);
+    let describe_builder_name = format_ident!("{}DescribeTableBuilder", struct_name);
+
+    let key_schema = expand_key_schema(&definition.key_schema);
+    let attribute_definitions = definition.attribute_definitions.iter().map(|(name, ty)| {
+        quote! {
+            ::raiden::aws_sdk::types::AttributeDefinition::builder()
+                .attribute_name(#name)
+                .attribute_type(::raiden::aws_sdk::types::ScalarAttributeType::from(#ty.to_string().as_str()))
+                .build()
+                .expect("should be built")
+        }
+    });
+    let global_secondary_indexes = definition.global_secondary_indexes.iter().map(|index| {
+        let index_name = &index.name;
+        let key_schema = expand_key_schema(&index.key_schema);
+        let projection = match &index.projection {
+            ProjectionType::All => quote! {
+                ::raiden::aws_sdk::types::Projection::builder()
+                    .projection_type(::raiden::aws_sdk::types::ProjectionType::All)
+                    .build()
+            },
+            ProjectionType::Include(non_key_attributes) => quote! {
+                ::raiden::aws_sdk::types::Projection::builder()
+                    .projection_type(::raiden::aws_sdk::types::ProjectionType::Include)
+                    .set_non_key_attributes(Some(vec![#(#non_key_attributes.to_owned()),*]))
+                    .build()
+            },
+        };
+        quote! {
+            ::raiden::aws_sdk::types::GlobalSecondaryIndex::builder()
+                .index_name(#index_name)
+                .set_key_schema(Some(vec![#(#key_schema),*]))
+                .projection(#projection)
+                .build()
+                .expect("should be built")
+        }
+    });
+    let set_global_secondary_indexes = if definition.global_secondary_indexes.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            .set_global_secondary_indexes(Some(vec![#(#global_secondary_indexes),*]))
+        }
+    };
+
+    let time_to_live = match &definition.time_to_live {
+        Some(attribute_name) => quote! { Some(#attribute_name.to_owned()) },
+        None => quote! { None },
+    };
+
+    let create_api_call_token = super::api_call_token!("create_table");
+    let delete_api_call_token = super::api_call_token!("delete_table");
+    let describe_api_call_token = super::api_call_token!("describe_table");
+    let ttl_api_call_token =
+        super::api_call_token!("table_name", "client", "update_time_to_live", "ttl_builder");
+    let (table_name_arg, table_name_param) = if cfg!(feature = "tracing") {
+        (quote! { table_name, }, quote! { table_name: String, })
+    } else {
+        (quote! {}, quote! {})
+    };
+    let (table_name_clone, table_name_clone_from_builder) = if cfg!(feature = "tracing") {
+        (
+            quote! { let table_name = table_name.clone(); },
+            quote! {
+                let table_name = builder
+                    .get_table_name()
+                    .clone()
+                    .ok_or_else(|| ::raiden::RaidenError::Validation("the table name of the request is not set".to_owned()))?;
+            },
+        )
+    } else {
+        (quote! {}, quote! {})
+    };
+
+    quote! {
+        pub trait #create_trait_name {
+            fn create_table(&self) -> #create_builder_name;
+        }
+
+        pub trait #delete_trait_name {
+            fn delete_table(&self) -> #delete_builder_name;
+        }
+
+        pub trait #describe_trait_name {
+            fn describe_table(&self) -> #describe_builder_name;
+        }
+
+        impl #create_trait_name for #client_name {
+            fn create_table(&self) -> #create_builder_name {
+                let builder = ::raiden::aws_sdk::operation::create_table::CreateTableInput::builder()
+                    .table_name(self.table_name())
+                    .set_key_schema(Some(vec![#(#key_schema),*]))
+                    .set_attribute_definitions(Some(vec![#(#attribute_definitions),*]))
+                    #set_global_secondary_indexes
+                    .billing_mode(::raiden::aws_sdk::types::BillingMode::PayPerRequest);
+
+                #create_builder_name {
+                    client: &self.client,
+                    builder,
+                    provisioned_throughput: None,
+                    time_to_live: #time_to_live,
+                }
+            }
+        }
+
+        impl #delete_trait_name for #client_name {
+            fn delete_table(&self) -> #delete_builder_name {
+                let builder = ::raiden::aws_sdk::operation::delete_table::DeleteTableInput::builder()
+                    .table_name(self.table_name());
+
+                #delete_builder_name {
+                    client: &self.client,
+                    builder,
+                }
+            }
+        }
+
+        impl #describe_trait_name for #client_name {
+            fn describe_table(&self) -> #describe_builder_name {
+                let builder = ::raiden::aws_sdk::operation::describe_table::DescribeTableInput::builder()
+                    .table_name(self.table_name());
+
+                #describe_builder_name {
+                    client: &self.client,
+                    builder,
+                }
+            }
+        }
+
+        pub struct #create_builder_name<'a> {
+            pub client: &'a ::raiden::SharedBackend,
+            pub builder: ::raiden::aws_sdk::operation::create_table::builders::CreateTableInputBuilder,
+            pub provisioned_throughput: Option<(i64, i64)>,
+            pub time_to_live: Option<String>,
+        }
+
+        impl<'a> #create_builder_name<'a> {
+            pub fn raw_input(mut self, builder: ::raiden::aws_sdk::operation::create_table::builders::CreateTableInputBuilder) -> Self {
+                self.builder = builder;
+                self
+            }
+
+            /// Creates the table in on-demand capacity mode. This is the default.
+            pub fn pay_per_request(mut self) -> Self {
+                self.provisioned_throughput = None;
+                self
+            }
+
+            /// Creates the table and its global secondary indexes in provisioned capacity mode.
+            pub fn provisioned_throughput(mut self, read_capacity_units: i64, write_capacity_units: i64) -> Self {
+                self.provisioned_throughput = Some((read_capacity_units, write_capacity_units));
+                self
+            }
+
+            /// Enables TTL on the given attribute once the table has been created.
+            ///
+            /// The field marked with `#[raiden(ttl)]` is used by default.
+            pub fn time_to_live(mut self, attribute_name: impl Into<String>) -> Self {
+                self.time_to_live = Some(attribute_name.into());
+                self
+            }
+
+            pub async fn run(self) -> Result<::raiden::table::TableOutput, ::raiden::RaidenError> {
+                let Self { client, mut builder, provisioned_throughput, time_to_live } = self;
+                let table_name = builder
+                    .get_table_name()
+                    .clone()
+                    .ok_or_else(|| ::raiden::RaidenError::Validation("the table name of the request is not set".to_owned()))?;
+
+                if let Some((read_capacity_units, write_capacity_units)) = provisioned_throughput {
+                    let throughput = ::raiden::aws_sdk::types::ProvisionedThroughput::builder()
+                        .read_capacity_units(read_capacity_units)
+                        .write_capacity_units(write_capacity_units)
+                        .build()
+                        .expect("should be built");
+                    let indexes = builder.get_global_secondary_indexes().clone().map(|indexes| {
+                        indexes
+                            .into_iter()
+                            .map(|mut index| {
+                                index.provisioned_throughput = Some(throughput.clone());
+                                index
+                            })
+                            .collect()
+                    });
+                    builder = builder
+                        .billing_mode(::raiden::aws_sdk::types::BillingMode::Provisioned)
+                        .provisioned_throughput(throughput)
+                        .set_global_secondary_indexes(indexes);
+                }
+
+                let output = {
+                    #table_name_clone
+                    #create_builder_name::inner_run(#table_name_arg client, builder).await?
+                };
+
+                if let Some(attribute_name) = time_to_live {
+                    let specification = ::raiden::aws_sdk::types::TimeToLiveSpecification::builder()
+                        .attribute_name(attribute_name)
+                        .enabled(true)
+                        .build()
+                        .expect("should be built");
+                    let ttl_builder = ::raiden::aws_sdk::operation::update_time_to_live::UpdateTimeToLiveInput::builder()
+                        .table_name(table_name.clone())
+                        .time_to_live_specification(specification);
+
+                    // TTL can not be updated until the table becomes active.
+                    let policy = ::raiden::Policy::Exponential(10, std::time::Duration::from_millis(100));
+                    ::raiden::retry_with_backend(policy, &client, |e: &::raiden::RaidenError| matches!(e, ::raiden::RaidenError::ResourceInUse(_)), move |client| {
+                        let ttl_builder = ttl_builder.clone();
+                        #table_name_clone
+                        async { #create_builder_name::update_time_to_live(#table_name_arg client, ttl_builder).await }
+                    }).await?;
+                }
+
+                Ok(output)
+            }
+
+            async fn inner_run(
+                #table_name_param
+                client: &::raiden::SharedBackend,
+                builder: ::raiden::aws_sdk::operation::create_table::builders::CreateTableInputBuilder,
+            ) -> Result<::raiden::table::TableOutput, ::raiden::RaidenError> {
+                let res = #create_api_call_token?;
+                Ok(::raiden::table::TableOutput {
+                    table_description: res.table_description,
+                })
+            }
+
+            async fn update_time_to_live(
+                #table_name_param
+                client: ::raiden::SharedBackend,
+                ttl_builder: ::raiden::aws_sdk::operation::update_time_to_live::builders::UpdateTimeToLiveInputBuilder,
+            ) -> Result<(), ::raiden::RaidenError> {
+                #ttl_api_call_token?;
+                Ok(())
+            }
+        }
+
+        pub struct #delete_builder_name<'a> {
+            pub client: &'a ::raiden::SharedBackend,
+            pub builder: ::raiden::aws_sdk::operation::delete_table::builders::DeleteTableInputBuilder,
+        }
+
+        impl<'a> #delete_builder_name<'a> {
+            pub async fn run(self) -> Result<::raiden::table::TableOutput, ::raiden::RaidenError> {
+                let Self { client, builder } = self;
+                #table_name_clone_from_builder
+                #delete_builder_name::inner_run(#table_name_arg client, builder).await
+            }
+
+            async fn inner_run(
+                #table_name_param
+                client: &::raiden::SharedBackend,
+                builder: ::raiden::aws_sdk::operation::delete_table::builders::DeleteTableInputBuilder,
+            ) -> Result<::raiden::table::TableOutput, ::raiden::RaidenError> {
+                let res = #delete_api_call_token?;
+                Ok(::raiden::table::TableOutput {
+                    table_description: res.table_description,
+                })
+            }
+        }
+
+        pub struct #describe_builder_name<'a> {
+            pub client: &'a ::raiden::SharedBackend,
+            pub builder: ::raiden::aws_sdk::operation::describe_table::builders::DescribeTableInputBuilder,
+        }
+
+        impl<'a> #describe_builder_name<'a> {
+            pub async fn run(self) -> Result<::raiden::table::TableOutput, ::raiden::RaidenError> {
+                let Self { client, builder } = self;
+                #table_name_clone_from_builder
+                #describe_builder_name::inner_run(#table_name_arg client, builder).await
+            }
+
+            async fn inner_run(
+                #table_name_param
+                client: &::raiden::SharedBackend,
+                builder: ::raiden::aws_sdk::operation::describe_table::builders::DescribeTableInputBuilder,
+            ) -> Result<::raiden::table::TableOutput, ::raiden::RaidenError> {
+                let res = #describe_api_call_token?;
+                Ok(::raiden::table::TableOutput {
+                    table_description: res.table,
+                })
+            }
+        }
+    }
+}
@@ -4,14 +4,17 @@ use syn::*;
 
 use crate::rename::*;
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn expand_transact_write(
     struct_name: &Ident,
-    partition_key: &(Ident, Type),
-    _sort_key: &Option<(Ident, Type)>, // TODO: Support sort key
+    partition_key: &(LitStr, Type),
+    sort_key: &Option<(LitStr, Type)>,
     fields: &FieldsNamed,
-    attr_enum_name: &Ident,
     rename_all_type: RenameAllType,
     table_name: &str,
+    version: &Option<crate::version::VersionField>,
+    timestamps: &crate::timestamp::Timestamps,
+    key_templates: &crate::key_template::KeyTemplates,
 ) -> TokenStream {
     let item_input_name = format_ident!("{}PutItemInput", struct_name);
     let put_builder = format_ident!("{}TransactPutItemBuilder", struct_name);
@@ -19,8 +22,49 @@ pub(crate) fn expand_transact_write(
     let delete_builder = format_ident!("{}TransactDeleteItemBuilder", struct_name);
     let condition_check_builder = format_ident!("{}TransactConditionCheckBuilder", struct_name);
     let condition_token_name = format_ident!("{}ConditionToken", struct_name);
-    let (partition_key_ident, partition_key_type) = partition_key;
+    let (partition_key_name, partition_key_type) = partition_key;
+
+    let now = timestamps.expand_now(struct_name);
+    let timestamp_items = timestamps.expand_update_items(struct_name);
+
+    let version_condition = crate::version::expand_put_version(version);
+    let version_overflow = crate::version::expand_version_overflow(version);
+    let increment_version = match version {
+        Some(crate::version::VersionField { attr_name, .. }) => quote! {
+            self.add_items.insert(0, ::raiden::version::increment_version(#attr_name, &mut self.placeholders));
+        },
+        None => quote! {},
+    };
+    // A versioned model is updated or deleted only if the stored version equals `expected_version`.
+    let expected_version_arg = crate::version::expand_expected_version_arg(version);
+    let expected_version_condition = crate::version::expand_expected_version_condition(version);
+
+    let (key_args, key_set) = if let Some(sort_key) = sort_key {
+        let (sort_key_name, sort_key_type) = sort_key;
+        (
+            quote! { pk: impl Into<#partition_key_type>, sk: impl Into<#sort_key_type> },
+            quote! {
+                let pk_attr: ::raiden::aws_sdk::types::AttributeValue = pk.into().into_attr();
+                let sk_attr: ::raiden::aws_sdk::types::AttributeValue = sk.into().into_attr();
+                let key_set: std::collections::HashMap<String, ::raiden::aws_sdk::types::AttributeValue> = std::collections::HashMap::from_iter([
+                    (#partition_key_name.to_owned(), pk_attr),
+                    (#sort_key_name.to_owned(), sk_attr),
+                ]);
+            },
+        )
+    } else {
+        (
+            quote! { key: impl Into<#partition_key_type> },
+            quote! {
+                let key_attr: ::raiden::aws_sdk::types::AttributeValue = key.into().into_attr();
+                let key_set: std::collections::HashMap<String, ::raiden::aws_sdk::types::AttributeValue> = std::collections::HashMap::from_iter([
+                    (#partition_key_name.to_owned(), key_attr),
+                ]);
+            },
+        )
+    };
 
+    let key_insertion = key_templates.expand_insertion(quote! { item });
     let input_items = {
         let insertion = fields.named.iter().map(|f| {
             let ident = &f.ident.clone().unwrap();
@@ -38,6 +82,22 @@ pub(crate) fn expand_transact_write(
                         id,
                     );
                 }
+            } else if version.as_ref().is_some_and(|version| version.is(f)) {
+                quote! {
+                    if let Some(next_version) = next_version {
+                        input_item.insert(
+                            #attr_key.to_string(),
+                            next_version.into_attr(),
+                        );
+                    }
+                }
+            } else if let Some(value) = timestamps.expand_value(f) {
+                quote! {
+                    input_item.insert(
+                        #attr_key.to_string(),
+                        #value.into_attr(),
+                    );
+                }
             } else {
                 quote! {
                     let value = item.#ident.into_attr();
@@ -53,10 +113,22 @@ pub(crate) fn expand_transact_write(
 
         quote! {
             let mut input_item: std::collections::HashMap<String, ::raiden::aws_sdk::types::AttributeValue> = std::collections::HashMap::new();
+            #key_insertion
+            #version_overflow
             #(#insertion)*
         }
     };
 
+    let return_old_on_condition_failure = quote! {
+        /// Returns the current item in the cancellation reason when the condition fails.
+        pub fn return_old_on_condition_failure(mut self) -> Self {
+            self.builder = self.builder.return_values_on_condition_check_failure(
+                ::raiden::aws_sdk::types::ReturnValuesOnConditionCheckFailure::AllOld,
+            );
+            self
+        }
+    };
+
     quote! {
         impl #struct_name {
             pub fn put(item: #item_input_name) -> #put_builder {
@@ -64,6 +136,8 @@ pub(crate) fn expand_transact_write(
                 let mut attribute_values: std::collections::HashMap<String, ::raiden::aws_sdk::types::AttributeValue> = std::collections::HashMap::new();
                 let mut uuid_map: std::collections::HashMap<String, String> = std::collections::HashMap::new();
 
+                #version_condition
+                #now
                 #input_items
 
                 let builder = ::raiden::aws_sdk::types::Put::builder().set_item(Some(input_item));
@@ -73,17 +147,16 @@ pub(crate) fn expand_transact_write(
                     table_name: #table_name.to_owned(),
                     table_prefix: "".to_owned(),
                     table_suffix: "".to_owned(),
+                    version_condition,
+                    placeholders,
+                    key_error,
                 }
             }
 
-            // TODO: Support sort key
-            pub fn condition_check(key: impl Into<#partition_key_type>) -> #condition_check_builder {
+            pub fn condition_check(#key_args) -> #condition_check_builder {
                 use std::iter::FromIterator;
 
-                let key_attr: ::raiden::aws_sdk::types::AttributeValue = key.into().into_attr();
-                let key_set: std::collections::HashMap<String, ::raiden::aws_sdk::types::AttributeValue> = std::collections::HashMap::from_iter([
-                    (stringify!(#partition_key_ident).to_owned(), key_attr),
-                ]);
+                #key_set
                 let builder = ::raiden::aws_sdk::types::ConditionCheck::builder()
                     .set_key(Some(key_set));
 
@@ -92,17 +165,16 @@ pub(crate) fn expand_transact_write(
                     table_name: #table_name.to_owned(),
                     table_prefix: "".to_owned(),
                     table_suffix: "".to_owned(),
+                    placeholders: ::raiden::Placeholders::new(),
                 }
             }
 
-            // TODO: Support sort key
-            pub fn delete(key: impl Into<#partition_key_type>) -> #delete_builder {
+            pub fn delete(#key_args #expected_version_arg) -> #delete_builder {
                 use std::iter::FromIterator;
 
-                let key_attr: ::raiden::aws_sdk::types::AttributeValue = key.into().into_attr();
-                let key_set: std::collections::HashMap<String, ::raiden::aws_sdk::types::AttributeValue> = std::collections::HashMap::from_iter([
-                    (stringify!(#partition_key_ident).to_owned(), key_attr),
-                ]);
+                #expected_version_condition
+
+                #key_set
                 let builder = ::raiden::aws_sdk::types::Delete::builder().set_key(Some(key_set));
 
                 #delete_builder {
@@ -110,17 +182,17 @@ pub(crate) fn expand_transact_write(
                     table_name: #table_name.to_owned(),
                     table_prefix: "".to_owned(),
                     table_suffix: "".to_owned(),
+                    version_condition,
+                    placeholders,
                 }
             }
 
-            // TODO: Support sort key
-            pub fn update(key: impl Into<#partition_key_type>) -> #update_builder {
+            pub fn update(#key_args #expected_version_arg) -> #update_builder {
                 use std::iter::FromIterator;
 
-                let key_attr: ::raiden::aws_sdk::types::AttributeValue = key.into().into_attr();
-                let key_set: std::collections::HashMap<String, ::raiden::aws_sdk::types::AttributeValue> = std::collections::HashMap::from_iter([
-                    (stringify!(#partition_key_ident).to_owned(), key_attr),
-                ]);
+                #expected_version_condition
+
+                #key_set
                 let builder = ::raiden::aws_sdk::types::Update::builder().set_key(Some(key_set));
 
                 #update_builder {
@@ -133,6 +205,8 @@ pub(crate) fn expand_transact_write(
                     set_items: vec![],
                     remove_items: vec![],
                     delete_items: vec![],
+                    version_condition,
+                    placeholders,
                 }
             }
         }
@@ -142,11 +216,32 @@ pub(crate) fn expand_transact_write(
             pub table_prefix: String,
             pub table_suffix: String,
             pub builder: ::raiden::aws_sdk::types::builders::PutBuilder,
+            pub version_condition: Option<::raiden::version::VersionCondition>,
+            pub placeholders: ::raiden::Placeholders,
+            key_error: Option<::raiden::RaidenError>,
         }
 
         impl ::raiden::TransactWritePutBuilder for #put_builder {
+            fn take_error(&mut self) -> Option<::raiden::RaidenError> {
+                self.key_error.take()
+            }
+
             fn build(self) -> ::raiden::aws_sdk::types::Put {
-                self.builder
+                let mut builder = self.builder;
+                if let Some(version_condition) = self.version_condition {
+                    let (expression, names, values) = ::raiden::version::apply_version_condition(
+                        builder.get_condition_expression().clone(),
+                        builder.get_expression_attribute_names().clone(),
+                        builder.get_expression_attribute_values().clone(),
+                        &version_condition,
+                    );
+                    builder = builder
+                        .condition_expression(expression)
+                        .set_expression_attribute_names(names)
+                        .set_expression_attribute_values(values);
+                }
+
+                builder
                     .table_name(format!("{}{}{}", self.table_prefix, self.table_name, self.table_suffix))
                     .build()
                     .expect("should be built")
@@ -166,7 +261,7 @@ pub(crate) fn expand_transact_write(
             }
 
             pub fn condition(mut self, cond: impl ::raiden::condition::ConditionBuilder<#condition_token_name>) -> Self {
-                let (cond_str, attr_names, attr_values) = cond.build();
+                let (cond_str, attr_names, attr_values) = cond.build_with(&mut self.placeholders);
 
                 if !attr_names.is_empty() {
                     self.builder = self.builder
@@ -181,6 +276,8 @@ pub(crate) fn expand_transact_write(
                 self.builder = self.builder.condition_expression(cond_str);
                 self
             }
+
+            #return_old_on_condition_failure
         }
 
         pub struct #update_builder {
@@ -191,27 +288,42 @@ pub(crate) fn expand_transact_write(
 
             pub add_items: Vec<(String, ::raiden::AttributeNames, ::raiden::AttributeValues)>,
             pub set_items: Vec<::raiden::update_expression::SetOrRemove>,
-            pub remove_items: Vec<#attr_enum_name>,
-            pub delete_items: Vec<(#attr_enum_name, ::raiden::AttributeValue)>,
+            pub remove_items: Vec<::raiden::AttrPath>,
+            pub delete_items: Vec<(::raiden::AttrPath, ::raiden::AttributeValue)>,
+            pub version_condition: Option<::raiden::version::VersionCondition>,
+            pub placeholders: ::raiden::Placeholders,
         }
 
         impl ::raiden::TransactWriteUpdateBuilder for #update_builder {
             fn build(mut self) -> ::raiden::aws_sdk::types::Update {
+                if let Some(version_condition) = self.version_condition.take() {
+                    let (expression, names, values) = ::raiden::version::apply_version_condition(
+                        self.builder.get_condition_expression().clone(),
+                        self.builder.get_expression_attribute_names().clone(),
+                        self.builder.get_expression_attribute_values().clone(),
+                        &version_condition,
+                    );
+                    self.builder = self.builder
+                        .condition_expression(expression)
+                        .set_expression_attribute_names(names)
+                        .set_expression_attribute_values(values);
+                }
+
                 // TODO: Refactor later
                 let mut attr_names: ::raiden::AttributeNames = std::collections::HashMap::new();
                 let mut attr_values: ::raiden::AttributeValues = std::collections::HashMap::new();
 
+                #increment_version
+                #timestamp_items
+
                 let add_items = std::mem::replace(&mut self.add_items, vec![]);
                 let set_items = std::mem::replace(&mut self.set_items, vec![]);
                 let remove_items = std::mem::replace(&mut self.remove_items, vec![]);
                 let delete_items = std::mem::replace(&mut self.delete_items, vec![]);
 
-                let mut remove_expressions = remove_items.into_iter().map(|name| {
-                    let placeholder = format!(":value{}", ::raiden::generate_value_id());
-                    let attr_name = format!("#{}", name.into_attr_name());
-                    let val = format!("{}", attr_name);
-                    attr_names.insert(attr_name, name.into_attr_name());
-                    val
+                let mut remove_expressions = remove_items.into_iter().map(|path| {
+                    attr_names.extend(path.attribute_names());
+                    path.expression()
                 }).collect::<Vec<String>>();
 
                 let mut set_expressions = vec![];
@@ -243,13 +355,10 @@ pub(crate) fn expand_transact_write(
                 }
                 let add_expression = add_expressions.join(", ");
 
-                let delete_expression = delete_items.into_iter().map(|(name, value)| {
-                    let placeholder = format!(":value{}", ::raiden::generate_value_id());
-                    let attr_name = format!("#{}", name.into_attr_name());
-                    let val = format!("{} {}", attr_name, placeholder);
-                    attr_names.insert(attr_name, name.into_attr_name());
-                    attr_values.insert(placeholder, value);
-                    val
+                let delete_expression = delete_items.into_iter().map(|(path, value)| {
+                    let placeholder = self.placeholders.bind(value, &mut attr_values);
+                    attr_names.extend(path.attribute_names());
+                    format!("{} {}", path.expression(), placeholder)
                 }).collect::<Vec<_>>().join(", ");
 
                 let mut update_expressions: Vec<String> = vec![];
@@ -306,27 +415,27 @@ pub(crate) fn expand_transact_write(
             }
 
             pub fn add(mut self, add: impl ::raiden::update_expression::UpdateAddExpressionBuilder) -> Self {
-                self.add_items.push(add.build());
+                self.add_items.push(add.build_with(&mut self.placeholders));
                 self
             }
 
             pub fn set(mut self, set: impl ::raiden::update_expression::UpdateSetExpressionBuilder) -> Self {
-                self.set_items.push(set.build());
+                self.set_items.push(set.build_with(&mut self.placeholders));
                 self
             }
 
-            pub fn remove(mut self, attr: #attr_enum_name) -> Self {
-                self.remove_items.push(attr);
+            pub fn remove(mut self, attr: impl ::raiden::IntoAttrPath) -> Self {
+                self.remove_items.push(attr.into_attr_path());
                 self
             }
 
-            pub fn delete(mut self, attr: #attr_enum_name, value: impl ::raiden::IntoAttribute) -> Self {
-                self.delete_items.push((attr, value.into_attr()));
+            pub fn delete(mut self, attr: impl ::raiden::IntoAttrPath, value: impl ::raiden::IntoAttribute) -> Self {
+                self.delete_items.push((attr.into_attr_path(), value.into_attr()));
                 self
             }
 
             pub fn condition(mut self, cond: impl ::raiden::condition::ConditionBuilder<#condition_token_name>) -> Self {
-                let (cond_str, attr_names, attr_values) = cond.build();
+                let (cond_str, attr_names, attr_values) = cond.build_with(&mut self.placeholders);
 
                 if !attr_names.is_empty() {
                     self.builder = self.builder
@@ -341,6 +450,8 @@ pub(crate) fn expand_transact_write(
                 self.builder = self.builder.condition_expression(cond_str);
                 self
             }
+
+            #return_old_on_condition_failure
         }
 
         pub struct #delete_builder {
@@ -348,11 +459,27 @@ pub(crate) fn expand_transact_write(
             pub table_prefix: String,
             pub table_suffix: String,
             pub builder: ::raiden::aws_sdk::types::builders::DeleteBuilder,
+            pub version_condition: Option<::raiden::version::VersionCondition>,
+            pub placeholders: ::raiden::Placeholders,
         }
 
         impl ::raiden::TransactWriteDeleteBuilder for #delete_builder {
             fn build(self) -> ::raiden::aws_sdk::types::Delete {
-                self.builder
+                let mut builder = self.builder;
+                if let Some(version_condition) = self.version_condition {
+                    let (expression, names, values) = ::raiden::version::apply_version_condition(
+                        builder.get_condition_expression().clone(),
+                        builder.get_expression_attribute_names().clone(),
+                        builder.get_expression_attribute_values().clone(),
+                        &version_condition,
+                    );
+                    builder = builder
+                        .condition_expression(expression)
+                        .set_expression_attribute_names(names)
+                        .set_expression_attribute_values(values);
+                }
+
+                builder
                     .table_name(format!("{}{}{}", self.table_prefix, self.table_name, self.table_suffix))
                     .build()
                     .expect("should be built")
@@ -371,7 +498,7 @@ pub(crate) fn expand_transact_write(
             }
 
             pub fn condition(mut self, cond: impl ::raiden::condition::ConditionBuilder<#condition_token_name>) -> Self {
-                let (cond_str, attr_names, attr_values) = cond.build();
+                let (cond_str, attr_names, attr_values) = cond.build_with(&mut self.placeholders);
 
                 if !attr_names.is_empty() {
                     self.builder = self.builder
@@ -386,6 +513,8 @@ pub(crate) fn expand_transact_write(
                 self.builder = self.builder.condition_expression(cond_str);
                 self
             }
+
+            #return_old_on_condition_failure
         }
 
         pub struct #condition_check_builder {
@@ -393,6 +522,7 @@ pub(crate) fn expand_transact_write(
             pub table_prefix: String,
             pub table_suffix: String,
             pub builder: ::raiden::aws_sdk::types::builders::ConditionCheckBuilder,
+            pub placeholders: ::raiden::Placeholders,
         }
 
         impl ::raiden::TransactWriteConditionCheckBuilder for #condition_check_builder {
@@ -416,7 +546,7 @@ pub(crate) fn expand_transact_write(
             }
 
             pub fn condition(mut self, cond: impl ::raiden::condition::ConditionBuilder<#condition_token_name>) -> Self {
-                let (cond_str, attr_names, attr_values) = cond.build();
+                let (cond_str, attr_names, attr_values) = cond.build_with(&mut self.placeholders);
 
                 if !attr_names.is_empty() {
                     self.builder = self.builder
@@ -431,6 +561,8 @@ pub(crate) fn expand_transact_write(
                 self.builder = self.builder.condition_expression(cond_str);
                 self
             }
+
+            #return_old_on_condition_failure
         }
 
     }
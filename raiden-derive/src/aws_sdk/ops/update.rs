@@ -2,45 +2,57 @@ use proc_macro2::*;
 use quote::*;
 use syn::*;
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn expand_update_item(
-    partition_key: &(Ident, Type),
-    sort_key: &Option<(Ident, Type)>,
-    fields: &FieldsNamed,
+    partition_key: &(LitStr, Type),
+    sort_key: &Option<(LitStr, Type)>,
     attr_enum_name: &Ident,
     struct_name: &Ident,
-    rename_all_type: crate::rename::RenameAllType,
+    version: &Option<crate::version::VersionField>,
+    timestamps: &crate::timestamp::Timestamps,
 ) -> TokenStream {
     let item_output_name = format_ident!("{}UpdateItemOutput", struct_name);
     let trait_name = format_ident!("{}UpdateItem", struct_name);
     let update_expression_name = format_ident!("{}UpdateExpression", struct_name);
     let client_name = format_ident!("{}Client", struct_name);
     let builder_name = format_ident!("{}UpdateItemBuilder", struct_name);
-    let from_item = super::expand_attr_to_item(format_ident!("res_item"), fields, rename_all_type);
+    let projected_builder_name = format_ident!("{}ProjectedUpdateItemBuilder", struct_name);
     let condition_token_name = format_ident!("{}ConditionToken", struct_name);
-    let (partition_key_ident, partition_key_type) = partition_key;
+    let (partition_key_name, partition_key_type) = partition_key;
+    let increment_version = match version {
+        Some(crate::version::VersionField { attr_name, .. }) => quote! {
+            self.add_items.insert(0, ::raiden::version::increment_version(#attr_name, &mut self.placeholders));
+        },
+        None => quote! {},
+    };
+    // A versioned model is updated only if the stored version equals `expected_version`.
+    let expected_version_arg = crate::version::expand_expected_version_arg(version);
+    let expected_version_condition = crate::version::expand_expected_version_condition(version);
 
     let client_trait = if let Some(sort_key) = sort_key {
-        let (sort_key_ident, sort_key_type) = sort_key;
+        let (sort_key_name, sort_key_type) = sort_key;
         quote! {
             pub trait #trait_name {
-                fn update(&self, pk: impl Into<#partition_key_type>, sk: impl Into<#sort_key_type>) -> #builder_name;
+                fn update(&self, pk: impl Into<#partition_key_type>, sk: impl Into<#sort_key_type> #expected_version_arg) -> #builder_name;
             }
 
             impl #trait_name for #client_name {
-                fn update(&self, pk: impl Into<#partition_key_type>, sk: impl Into<#sort_key_type>) -> #builder_name {
+                fn update(&self, pk: impl Into<#partition_key_type>, sk: impl Into<#sort_key_type> #expected_version_arg) -> #builder_name {
                     use std::iter::FromIterator;
 
                     let pk_attr: ::raiden::aws_sdk::types::AttributeValue = pk.into().into_attr();
                     let sk_attr: ::raiden::aws_sdk::types::AttributeValue = sk.into().into_attr();
                     let key_set: std::collections::HashMap<String, ::raiden::aws_sdk::types::AttributeValue> = std::collections::HashMap::from_iter([
-                        (stringify!(#partition_key_ident).to_owned(), pk_attr),
-                        (stringify!(#sort_key_ident).to_owned(), sk_attr),
+                        (#partition_key_name.to_owned(), pk_attr),
+                        (#sort_key_name.to_owned(), sk_attr),
                     ]);
 
                     let builder = ::raiden::aws_sdk::operation::update_item::UpdateItemInput::builder()
                         .set_key(Some(key_set))
                         .table_name(self.table_name());
 
+                    #expected_version_condition
+
                     #builder_name {
                         client: &self.client,
                         builder,
@@ -50,6 +62,8 @@ pub(crate) fn expand_update_item(
                         delete_items: vec![],
                         policy: self.retry_condition.strategy.policy(),
                         condition: &self.retry_condition,
+                        version_condition,
+                        placeholders,
                     }
                 }
             }
@@ -57,22 +71,24 @@ pub(crate) fn expand_update_item(
     } else {
         quote! {
             pub trait #trait_name {
-                fn update(&self, key: impl Into<#partition_key_type>) -> #builder_name;
+                fn update(&self, key: impl Into<#partition_key_type> #expected_version_arg) -> #builder_name;
             }
 
             impl #trait_name for #client_name {
-                fn update(&self, key: impl Into<#partition_key_type>) -> #builder_name {
+                fn update(&self, key: impl Into<#partition_key_type> #expected_version_arg) -> #builder_name {
                     use std::iter::FromIterator;
 
                     let key_attr: ::raiden::aws_sdk::types::AttributeValue = key.into().into_attr();
                     let key_set: std::collections::HashMap<String, ::raiden::aws_sdk::types::AttributeValue> = std::collections::HashMap::from_iter([
-                        (stringify!(#partition_key_ident).to_owned(), key_attr),
+                        (#partition_key_name.to_owned(), key_attr),
                     ]);
 
                     let builder = ::raiden::aws_sdk::operation::update_item::UpdateItemInput::builder()
                         .set_key(Some(key_set))
                         .table_name(self.table_name());
 
+                    #expected_version_condition
+
                     #builder_name {
                         client: &self.client,
                         builder,
@@ -82,17 +98,21 @@ pub(crate) fn expand_update_item(
                         delete_items: vec![],
                         policy: self.retry_condition.strategy.policy(),
                         condition: &self.retry_condition,
+                        version_condition,
+                        placeholders,
                     }
                 }
             }
         }
     };
 
+    let timestamp_items = timestamps.expand_update_items(struct_name);
+
     let api_call_token = super::api_call_token!("update_item");
     let (call_inner_run, inner_run_args) = if cfg!(feature = "tracing") {
         (
             quote! {
-                let table_name = builder.get_table_name().clone().expect("table name should be set");
+                let table_name = builder.get_table_name().clone().ok_or_else(|| ::raiden::RaidenError::Validation("the table name of the request is not set".to_owned()))?;
                 #builder_name::inner_run(table_name, client, builder).await
             },
             quote! { table_name: String, },
@@ -119,30 +139,56 @@ pub(crate) fn expand_update_item(
         }
 
         impl #update_expression_name {
-            pub fn set(&self, attr: #attr_enum_name) -> ::raiden::update_expression::Set<#attr_enum_name> {
-                ::raiden::update_expression::Set::new(attr)
+            pub fn set(&self, attr: impl ::raiden::IntoAttrPath) -> ::raiden::update_expression::Set<#attr_enum_name> {
+                ::raiden::update_expression::Set::path(attr)
             }
 
-            pub fn add(&self, attr: #attr_enum_name) -> ::raiden::update_expression::Add<#attr_enum_name> {
-                ::raiden::update_expression::Add::new(attr)
+            pub fn add(&self, attr: impl ::raiden::IntoAttrPath) -> ::raiden::update_expression::Add<#attr_enum_name> {
+                ::raiden::update_expression::Add::path(attr)
             }
 
-            pub fn delete(&self, attr: #attr_enum_name) -> ::raiden::update_expression::Delete<#attr_enum_name> {
-                ::raiden::update_expression::Delete::new(attr)
+            pub fn delete(&self, attr: impl ::raiden::IntoAttrPath) -> ::raiden::update_expression::Delete<#attr_enum_name> {
+                ::raiden::update_expression::Delete::path(attr)
             }
         }
 
         #client_trait
 
+        /// An update builder that decodes the updated attributes into a projection item.
+        ///
+        /// Created by `return_updated_old` or `return_updated_new`.
+        pub struct #projected_builder_name<'a, P> {
+            inner: #builder_name<'a>,
+            marker: std::marker::PhantomData<P>,
+        }
+
+        impl<'a, P: ::raiden::RaidenItem> #projected_builder_name<'a, P> {
+            pub async fn run(self) -> Result<::raiden::update::UpdateOutput<P>, ::raiden::RaidenError> {
+                let res = self.inner.run_raw().await?;
+                let item = match res.attributes {
+                    Some(item) => Some(P::from_item(item)?),
+                    None => None,
+                };
+
+                Ok(::raiden::update::UpdateOutput {
+                    item,
+                    consumed_capacity: res.consumed_capacity,
+                    item_collection_metrics: res.item_collection_metrics,
+                })
+            }
+        }
+
         pub struct #builder_name<'a> {
-            pub client: &'a ::raiden::Client,
+            pub client: &'a ::raiden::SharedBackend,
             pub builder: ::raiden::aws_sdk::operation::update_item::builders::UpdateItemInputBuilder,
             pub add_items: Vec<(String, ::raiden::AttributeNames, ::raiden::AttributeValues)>,
             pub set_items: Vec<::raiden::update_expression::SetOrRemove>,
-            pub remove_items: Vec<#attr_enum_name>,
+            pub remove_items: Vec<::raiden::AttrPath>,
             pub delete_items: Vec<(String, ::raiden::AttributeNames, ::raiden::AttributeValues)>,
             pub policy: ::raiden::Policy,
             pub condition: &'a ::raiden::retry::RetryCondition,
+            pub version_condition: Option<::raiden::version::VersionCondition>,
+            pub placeholders: ::raiden::Placeholders,
         }
 
         impl<'a> #builder_name<'a> {
@@ -152,22 +198,22 @@ pub(crate) fn expand_update_item(
             }
 
             pub fn add(mut self, add: impl ::raiden::update_expression::UpdateAddExpressionBuilder) -> Self {
-                self.add_items.push(add.build());
+                self.add_items.push(add.build_with(&mut self.placeholders));
                 self
             }
 
             pub fn set(mut self, set: impl ::raiden::update_expression::UpdateSetExpressionBuilder) -> Self {
-                self.set_items.push(set.build());
+                self.set_items.push(set.build_with(&mut self.placeholders));
                 self
             }
 
-            pub fn remove(mut self, attr: #attr_enum_name) -> Self {
-                self.remove_items.push(attr);
+            pub fn remove(mut self, attr: impl ::raiden::IntoAttrPath) -> Self {
+                self.remove_items.push(attr.into_attr_path());
                 self
             }
 
             pub fn delete(mut self, set: impl ::raiden::update_expression::UpdateDeleteExpressionBuilder) -> Self {
-                self.delete_items.push(set.build());
+                self.delete_items.push(set.build_with(&mut self.placeholders));
                 self
             }
 
@@ -183,8 +229,46 @@ pub(crate) fn expand_update_item(
                 self
             }
 
+            /// Returns the updated attributes as they were before the update, decoded into `P`.
+            pub fn return_updated_old<P: ::raiden::RaidenItem>(mut self) -> #projected_builder_name<'a, P> {
+                self.builder = self.builder.return_values(::raiden::aws_sdk::types::ReturnValue::UpdatedOld);
+                #projected_builder_name {
+                    inner: self,
+                    marker: std::marker::PhantomData,
+                }
+            }
+
+            /// Returns the updated attributes as they are after the update, decoded into `P`.
+            pub fn return_updated_new<P: ::raiden::RaidenItem>(mut self) -> #projected_builder_name<'a, P> {
+                self.builder = self.builder.return_values(::raiden::aws_sdk::types::ReturnValue::UpdatedNew);
+                #projected_builder_name {
+                    inner: self,
+                    marker: std::marker::PhantomData,
+                }
+            }
+
+            /// Returns the current item with `ConditionalCheckFailedWithItem` when the condition fails.
+            pub fn return_old_on_condition_failure(mut self) -> Self {
+                self.builder = self.builder.return_values_on_condition_check_failure(
+                    ::raiden::aws_sdk::types::ReturnValuesOnConditionCheckFailure::AllOld,
+                );
+                self
+            }
+
+            /// Requests the consumed capacity, returned as `consumed_capacity`.
+            pub fn return_consumed_capacity(mut self, value: ::raiden::ReturnConsumedCapacity) -> Self {
+                self.builder = self.builder.return_consumed_capacity(value.into());
+                self
+            }
+
+            /// Requests the size of the modified item collection, returned as `item_collection_metrics`.
+            pub fn return_item_collection_metrics(mut self, value: ::raiden::ReturnItemCollectionMetrics) -> Self {
+                self.builder = self.builder.return_item_collection_metrics(value.into());
+                self
+            }
+
             pub fn condition(mut self, cond: impl ::raiden::condition::ConditionBuilder<#condition_token_name>) -> Self {
-                let (cond_str, attr_names, attr_values) = cond.build();
+                let (cond_str, attr_names, attr_values) = cond.build_with(&mut self.placeholders);
 
                 if !attr_names.is_empty() {
                     self.builder = self.builder
@@ -204,17 +288,17 @@ pub(crate) fn expand_update_item(
                 let mut attr_names: ::raiden::AttributeNames = std::collections::HashMap::new();
                 let mut attr_values: ::raiden::AttributeValues = std::collections::HashMap::new();
 
+                #increment_version
+                #timestamp_items
+
                 let add_items = std::mem::replace(&mut self.add_items, vec![]);
                 let set_items = std::mem::replace(&mut self.set_items, vec![]);
                 let remove_items = std::mem::replace(&mut self.remove_items, vec![]);
                 let delete_items = std::mem::replace(&mut self.delete_items, vec![]);
 
-                let mut remove_expressions = remove_items.into_iter().map(|name| {
-                    let placeholder = format!(":value{}", ::raiden::generate_value_id());
-                    let attr_name = format!("#{}", name.into_attr_name());
-                    let val = format!("{}", attr_name);
-                    attr_names.insert(attr_name, name.into_attr_name());
-                    val
+                let mut remove_expressions = remove_items.into_iter().map(|path| {
+                    attr_names.extend(path.attribute_names());
+                    path.expression()
                 }).collect::<Vec<String>>();
 
                 let mut set_expressions = vec![];
@@ -276,7 +360,43 @@ pub(crate) fn expand_update_item(
                 (update_expression, attr_names, attr_values)
             }
 
-            pub async fn run(mut self) -> Result<::raiden::update::UpdateOutput<#struct_name>, ::raiden::RaidenError> {
+            pub async fn run(self) -> Result<::raiden::update::UpdateOutput<#struct_name>, ::raiden::RaidenError> {
+                let has_return_values = self.builder.get_return_values().is_some();
+                let res = self.run_raw().await?;
+
+                let item = if has_return_values {
+                    let res_item = res.attributes.unwrap();
+                    Some(<#struct_name as ::raiden::RaidenItem>::from_item(res_item)?)
+                } else {
+                    None
+                };
+
+                Ok(::raiden::update::UpdateOutput {
+                    item,
+                    consumed_capacity: res.consumed_capacity,
+                    item_collection_metrics: res.item_collection_metrics,
+                })
+            }
+
+            async fn run_raw(mut self) -> Result<::raiden::UpdateItemResponse, ::raiden::RaidenError> {
+                let with_item = self.builder.get_return_values_on_condition_check_failure().is_some();
+                let version_condition = self.version_condition.take();
+                if let Some(version_condition) = &version_condition {
+                    let (expression, names, values) = ::raiden::version::apply_version_condition(
+                        self.builder.get_condition_expression().clone(),
+                        self.builder.get_expression_attribute_names().clone(),
+                        self.builder.get_expression_attribute_values().clone(),
+                        &version_condition,
+                    );
+                    self.builder = self.builder
+                        .condition_expression(expression)
+                        .set_expression_attribute_names(names)
+                        .set_expression_attribute_values(values)
+                        .return_values_on_condition_check_failure(
+                            ::raiden::aws_sdk::types::ReturnValuesOnConditionCheckFailure::AllOld,
+                        );
+                }
+
                 let (expression, names, values) = self.build_expression();
 
                 if self.builder.get_expression_attribute_names().is_none() {
@@ -319,40 +439,24 @@ pub(crate) fn expand_update_item(
                     self.builder = self.builder.update_expression(expression);
                 }
 
-                let has_return_values = self.builder.get_return_values().is_some();
                 let builder = self.builder.clone();
                 let client = self.client.clone();
-                let policy: ::raiden::RetryPolicy = self.policy.into();
 
-                let res = policy.retry_if(move || {
+                ::raiden::retry_with_backend(self.policy, &client, self.condition, move |client| {
                     let builder = builder.clone();
-                    let client = client.clone();
                     async { #call_inner_run }
-                }, self.condition).await?;
-
-
-                let item = if has_return_values {
-                    let mut res_item = res.attributes.unwrap();
-                    Some(#struct_name {
-                        #(#from_item)*
-                    })
-                } else {
-                    None
-                };
-
-                Ok(::raiden::update::UpdateOutput {
-                    item,
-                    consumed_capacity: res.consumed_capacity,
-                    item_collection_metrics: res.item_collection_metrics,
+                }).await.map_err(|e| match &version_condition {
+                    Some(version_condition) => ::raiden::version::into_version_conflict(e, version_condition, with_item),
+                    None => e,
                 })
             }
 
             async fn inner_run(
                 #inner_run_args
-                client: ::raiden::Client,
+                client: ::raiden::SharedBackend,
                 builder: ::raiden::aws_sdk::operation::update_item::builders::UpdateItemInputBuilder,
-            ) -> Result<::raiden::aws_sdk::operation::update_item::UpdateItemOutput, ::raiden::RaidenError> {
-                Ok(#api_call_token?)
+            ) -> Result<::raiden::UpdateItemResponse, ::raiden::RaidenError> {
+                #api_call_token
             }
         }
 
@@ -8,13 +8,6 @@ pub fn expand_condition_builder(
     let condition_name = format_ident!("{}Condition", struct_name);
     let condition_token_name = format_ident!("{}ConditionToken", struct_name);
     let wait_attr_op_name = format_ident!("{}LeftAttrAndWaitOp", struct_name);
-    let attribute_value_path = if cfg!(feature = "rusoto") {
-        quote! { ::raiden::AttributeValue }
-    } else if cfg!(feature = "aws-sdk") {
-        quote! { ::raiden::aws_sdk::types::AttributeValue }
-    } else {
-        unreachable!();
-    };
 
     quote! {
 
@@ -85,31 +78,27 @@ pub fn expand_condition_builder(
             pub fn attr(self, field: impl ::raiden::IntoAttrPath) -> #wait_attr_op_name {
                 #wait_attr_op_name {
                     not: self.not,
-                    attr_or_placeholder: ::raiden::AttrOrPlaceholder::Attr(field.into_attr_path()),
-                    attr_value: None,
+                    attr_or_value: ::raiden::AttrOrValue::Attr(field.into_attr_path()),
                 }
             }
 
             pub fn value(self, value: impl ::raiden::IntoAttribute) -> #wait_attr_op_name {
-                let placeholder = format!("value{}", ::raiden::generate_value_id());
                 #wait_attr_op_name {
                     not: self.not,
-                    attr_or_placeholder: ::raiden::AttrOrPlaceholder::Placeholder(placeholder),
-                    attr_value: Some(value.into_attr()),
+                    attr_or_value: ::raiden::AttrOrValue::Value(value.into_attr()),
                 }
             }
         }
 
         pub struct #wait_attr_op_name {
             not: bool,
-            attr_or_placeholder: ::raiden::AttrOrPlaceholder,
-            attr_value: Option<#attribute_value_path>,
+            attr_or_value: ::raiden::AttrOrValue,
         }
 
         impl #wait_attr_op_name {
             pub fn eq_attr(self, attr: impl ::raiden::IntoAttrPath) -> ::raiden::ConditionFilledOrWaitOperator<#condition_token_name>  {
-                let attr = ::raiden::AttrOrPlaceholder::Attr(attr.into_attr_path());
-                let cond = ::raiden::condition::Cond::Cmp(::raiden::condition::ConditionComparisonExpression::Eq(self.attr_or_placeholder, self.attr_value, attr, None));
+                let attr = ::raiden::AttrOrValue::Attr(attr.into_attr_path());
+                let cond = ::raiden::condition::Cond::Cmp(::raiden::condition::ConditionComparisonExpression::Eq(self.attr_or_value, attr));
                 ::raiden::ConditionFilledOrWaitOperator {
                     not: self.not,
                     cond,
@@ -119,8 +108,8 @@ pub fn expand_condition_builder(
             }
 
             pub fn eq_value(self, value: impl ::raiden::IntoAttribute) -> ::raiden::ConditionFilledOrWaitOperator<#condition_token_name>  {
-                let placeholder = ::raiden::AttrOrPlaceholder::Placeholder(format!("value{}", ::raiden::generate_value_id()));
-                let cond = ::raiden::condition::Cond::Cmp(::raiden::condition::ConditionComparisonExpression::Eq(self.attr_or_placeholder, self.attr_value, placeholder, Some(value.into_attr())));
+                let value = ::raiden::AttrOrValue::Value(value.into_attr());
+                let cond = ::raiden::condition::Cond::Cmp(::raiden::condition::ConditionComparisonExpression::Eq(self.attr_or_value, value));
                 ::raiden::ConditionFilledOrWaitOperator {
                     not: self.not,
                     cond,
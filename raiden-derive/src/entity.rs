@@ -0,0 +1,255 @@
+use quote::*;
+
+use crate::{client, ops};
+
+/// Expands `#[derive(Raiden)]` on an enum whose variants are entity models sharing one table.
+///
+/// The table keys are declared on the enum with `#[raiden(partition_key = "...")]` and
+/// `#[raiden(sort_key = "...")]`, and every item is dispatched to a variant by the attribute
+/// named with `#[raiden(tag = "...")]`.
+pub(crate) fn expand_entity(
+    enum_name: &proc_macro2::Ident,
+    attrs: &[syn::Attribute],
+    data: &syn::DataEnum,
+    dynamodb_client_name: &proc_macro2::Ident,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let mut errors = crate::validate::Errors::default();
+    crate::validate::validate_attrs(attrs, crate::validate::ENTITY_KEYS, &mut errors);
+    for variant in data.variants.iter() {
+        crate::validate::validate_attrs(&variant.attrs, crate::validate::VARIANT_KEYS, &mut errors);
+    }
+    errors.finish()?;
+
+    let client_name = format_ident!("{}Client", enum_name);
+    let attr_enum_name = format_ident!("{}AttrNames", enum_name);
+
+    let table_name = crate::finder::find_table_name(attrs).unwrap_or_else(|| enum_name.to_string());
+    let tag = crate::finder::find_string_values(attrs, "tag")
+        .into_iter()
+        .next()
+        .ok_or_else(|| {
+            syn::Error::new_spanned(
+                enum_name,
+                "Please specify the tag attribute with #[raiden(tag = \"...\")]",
+            )
+        })?;
+    let rename_all_type = crate::finder::find_rename_all_type(attrs)?;
+
+    let fields = expand_key_fields(enum_name, attrs)?;
+    let partition_key =
+        crate::key::fetch_partition_key(enum_name, &fields, crate::rename::RenameAllType::None)?;
+    let sort_key = crate::key::fetch_sort_key(&fields, crate::rename::RenameAllType::None)?;
+
+    let mut errors = crate::validate::Errors::default();
+    let variants: Vec<(String, &syn::Ident, &syn::Type)> = data
+        .variants
+        .iter()
+        .filter_map(|variant| {
+            let ty = match &variant.fields {
+                syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0].ty,
+                _ => {
+                    errors.push(syn::Error::new_spanned(
+                        variant,
+                        format!(
+                            "variant `{}` should hold a single entity model, e.g. `{}({})`",
+                            variant.ident, variant.ident, variant.ident
+                        ),
+                    ));
+                    return None;
+                }
+            };
+            let tag_value = crate::rename::create_renamed_variant(
+                variant.ident.to_string(),
+                crate::finder::find_rename_value(&variant.attrs),
+                rename_all_type,
+            );
+            Some((tag_value, &variant.ident, ty))
+        })
+        .collect();
+    errors.finish()?;
+
+    let insertion_attribute_names = variants.iter().map(|(_, _, ty)| {
+        quote! {
+            if let Some(variant_names) = <#ty as ::raiden::RaidenItem>::attribute_names() {
+                names.extend(variant_names);
+            }
+        }
+    });
+    let key_attribute_names = std::iter::once(&partition_key)
+        .chain(sort_key.as_ref())
+        .map(|(name, _)| name.value())
+        .collect::<Vec<_>>();
+    let from_item_arms = variants.iter().map(|(tag_value, ident, ty)| {
+        quote! {
+            #tag_value => Ok(#enum_name::#ident(<#ty as ::raiden::RaidenItem>::from_item(item)?)),
+        }
+    });
+    let tag_arms = variants.iter().map(|(tag_value, ident, _)| {
+        quote! {
+            #enum_name::#ident(_) => #tag_value,
+        }
+    });
+
+    let client_struct = crate::expand_client_struct(&client_name);
+    let attr_names = crate::attribute::expand_attr_names(
+        &attr_enum_name,
+        &fields,
+        crate::rename::RenameAllType::None,
+        enum_name,
+    );
+    let condition_builder =
+        crate::condition::expand_condition_builder(&attr_enum_name, enum_name, &fields);
+    let key_condition_builder = crate::key_condition::expand_key_condition_builder(
+        &attr_enum_name,
+        enum_name,
+        &partition_key,
+        &sort_key,
+    );
+    let filter_expression_builder =
+        crate::filter_expression::expand_filter_expression_builder(&attr_enum_name, enum_name);
+    let get_item = ops::expand_get_item(&partition_key, &sort_key, enum_name, &None);
+    let query = ops::expand_query(
+        enum_name,
+        &fields,
+        crate::rename::RenameAllType::None,
+        &[],
+        &[],
+        &None,
+    );
+    let scan = ops::expand_scan(
+        enum_name,
+        &fields,
+        crate::rename::RenameAllType::None,
+        &[],
+        &None,
+    );
+    let delete_item = ops::expand_delete_item(&partition_key, &sort_key, enum_name, &None);
+    let table_definition = crate::table::build_table_definition(
+        &fields,
+        crate::rename::RenameAllType::None,
+        &partition_key,
+        &sort_key,
+        &[],
+        &None,
+    );
+    let table = ops::expand_table(enum_name, &table_definition);
+    let client_constructor = client::expand_client_constructor(
+        enum_name,
+        &client_name,
+        dynamodb_client_name,
+        &table_name,
+    );
+
+    Ok(quote! {
+        use ::raiden::IntoAttribute as _;
+        use ::raiden::IntoAttrName as _;
+
+        #client_struct
+
+        #attr_names
+
+        #condition_builder
+
+        #key_condition_builder
+
+        #filter_expression_builder
+
+        #get_item
+
+        #query
+
+        #scan
+
+        #delete_item
+
+        #table
+
+        #client_constructor
+
+        impl #enum_name {
+            /// The name of the attribute which tells the entity type of an item.
+            pub const TAG: &'static str = #tag;
+
+            /// Returns the value stored in the tag attribute for this entity.
+            pub fn tag(&self) -> &'static str {
+                match self {
+                    #(#tag_arms)*
+                }
+            }
+        }
+
+        impl ::raiden::RaidenItem for #enum_name {
+            fn attribute_names() -> Option<::raiden::AttributeNames> {
+                let mut names: ::raiden::AttributeNames = std::collections::HashMap::new();
+                #(#insertion_attribute_names)*
+                for name in [#tag, #(#key_attribute_names),*] {
+                    names.insert(::raiden::name_placeholder(name), name.to_string());
+                }
+                Some(names)
+            }
+
+            fn projection_expression() -> Option<String> {
+                Self::attribute_names()
+                    .map(|names| names.keys().cloned().collect::<Vec<String>>().join(", "))
+            }
+
+            fn from_item(item: ::raiden::AttributeValues) -> Result<Self, ::raiden::RaidenError> {
+                let tag: String = match ::raiden::FromAttribute::from_attr(item.get(#tag).cloned()) {
+                    Ok(tag) => tag,
+                    Err(source) => return Err(::raiden::RaidenError::AttributeConvertError {
+                        attr_name: #tag.to_string(),
+                        source,
+                    }),
+                };
+                match tag.as_str() {
+                    #(#from_item_arms)*
+                    _ => Err(::raiden::RaidenError::AttributeConvertError {
+                        attr_name: #tag.to_string(),
+                        source: ::raiden::ConversionError::message(format!(
+                            "unknown {} `{}`",
+                            stringify!(#enum_name),
+                            tag
+                        )),
+                    }),
+                }
+            }
+        }
+    })
+}
+
+// Declares the table keys as `String` fields, so that the operations of a model can be reused.
+// Key names are arbitrary attribute names, so the fields get fixed idents and keep the name in
+// `rename`.
+fn expand_key_fields(
+    enum_name: &proc_macro2::Ident,
+    attrs: &[syn::Attribute],
+) -> syn::Result<syn::FieldsNamed> {
+    let partition_key = crate::finder::find_string_values(attrs, "partition_key")
+        .into_iter()
+        .next()
+        .ok_or_else(|| {
+            syn::Error::new_spanned(
+                enum_name,
+                "Please specify partition key with #[raiden(partition_key = \"...\")]",
+            )
+        })?;
+    let sort_key = crate::finder::find_string_values(attrs, "sort_key")
+        .into_iter()
+        .next()
+        .map(|sort_key| {
+            quote! {
+                #[raiden(sort_key)]
+                #[raiden(rename = #sort_key)]
+                __raiden_sort_key: String,
+            }
+        });
+
+    Ok(syn::parse_quote! {
+        {
+            #[raiden(partition_key)]
+            #[raiden(rename = #partition_key)]
+            __raiden_partition_key: String,
+            #sort_key
+        }
+    })
+}
@@ -1,4 +1,6 @@
-use syn::{punctuated::Punctuated, Expr, ExprLit, Lit, Meta, MetaNameValue, Token};
+use syn::{punctuated::Punctuated, Expr, ExprLit, Lit, LitStr, Meta, MetaNameValue, Token};
+
+use crate::rename::RenameAllType;
 
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) struct GsiDefinition {
@@ -11,17 +13,10 @@ pub(crate) fn find_unary_attr(attr: &syn::Attribute, name: &str) -> Option<proc_
     match attr.meta {
         Meta::List(ref list) => {
             match list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated) {
-                Ok(parsed) if parsed.is_empty() => None,
-                Ok(parsed) if parsed.len() > 1 => panic!("TODO: should unary"),
-                Ok(parsed) => {
-                    let meta = parsed.first().expect("should get meta");
-
-                    if meta.path().segments[0].ident == name {
-                        Some(meta.path().segments[0].ident.clone())
-                    } else {
-                        None
-                    }
-                }
+                Ok(parsed) => parsed.iter().find_map(|meta| match meta {
+                    Meta::Path(path) if path.is_ident(name) => path.get_ident().cloned(),
+                    _ => None,
+                }),
                 _ => None,
             }
         }
@@ -29,7 +24,7 @@ pub(crate) fn find_unary_attr(attr: &syn::Attribute, name: &str) -> Option<proc_
     }
 }
 
-pub(crate) fn find_eq_string_from(attr: &syn::Attribute, name: &str) -> Option<String> {
+pub(crate) fn find_eq_lit_from(attr: &syn::Attribute, name: &str) -> Option<LitStr> {
     match attr.meta {
         Meta::List(ref list) => {
             match list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated) {
@@ -43,7 +38,7 @@ pub(crate) fn find_eq_string_from(attr: &syn::Attribute, name: &str) -> Option<S
                                     }),
                                 ..
                             }) if meta.path().segments[0].ident == name => {
-                                return Some(lit.value());
+                                return Some(lit.clone());
                             }
                             _ => continue,
                         }
@@ -58,6 +53,10 @@ pub(crate) fn find_eq_string_from(attr: &syn::Attribute, name: &str) -> Option<S
     }
 }
 
+pub(crate) fn find_eq_string_from(attr: &syn::Attribute, name: &str) -> Option<String> {
+    find_eq_lit_from(attr, name).map(|lit| lit.value())
+}
+
 pub(crate) fn find_table_name(attrs: &[syn::Attribute]) -> Option<String> {
     for attr in attrs {
         if attr.path().segments[0].ident != "raiden" {
@@ -72,13 +71,13 @@ pub(crate) fn find_table_name(attrs: &[syn::Attribute]) -> Option<String> {
     None
 }
 
-pub(crate) fn find_rename_all(attrs: &[syn::Attribute]) -> Option<String> {
+pub(crate) fn find_rename_all(attrs: &[syn::Attribute]) -> Option<LitStr> {
     for attr in attrs {
         if attr.path().segments[0].ident != "raiden" {
             continue;
         }
 
-        if let Some(lit) = find_eq_string_from(attr, "rename_all") {
+        if let Some(lit) = find_eq_lit_from(attr, "rename_all") {
             return Some(lit);
         }
     }
@@ -86,6 +85,22 @@ pub(crate) fn find_rename_all(attrs: &[syn::Attribute]) -> Option<String> {
     None
 }
 
+pub(crate) fn find_rename_all_type(attrs: &[syn::Attribute]) -> syn::Result<RenameAllType> {
+    let Some(lit) = find_rename_all(attrs) else {
+        return Ok(RenameAllType::None);
+    };
+    lit.value().parse().map_err(|_| {
+        syn::Error::new_spanned(
+            &lit,
+            format!(
+                "{} is not support type, expected one of {}",
+                lit.value(),
+                crate::rename::RENAME_ALL_TYPES.join(", ")
+            ),
+        )
+    })
+}
+
 pub(crate) fn find_gsi_names(attrs: &[syn::Attribute]) -> Vec<String> {
     let mut names = vec![];
 
@@ -108,8 +123,9 @@ pub(crate) fn find_gsi_names(attrs: &[syn::Attribute]) -> Vec<String> {
     names
 }
 
-pub(crate) fn find_gsi_definitions(attrs: &[syn::Attribute]) -> Vec<GsiDefinition> {
-    let mut defs = vec![];
+// Returns the arguments of every `#[raiden(gsi(...))]`.
+fn find_gsi_args(attrs: &[syn::Attribute]) -> Vec<Punctuated<Meta, Token![,]>> {
+    let mut args = vec![];
 
     for attr in attrs {
         if attr.path().segments[0].ident != "raiden" {
@@ -134,59 +150,86 @@ pub(crate) fn find_gsi_definitions(attrs: &[syn::Attribute]) -> Vec<GsiDefinitio
                 continue;
             }
 
-            let Ok(gsi_args) =
+            if let Ok(gsi_args) =
                 gsi_list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
-            else {
-                continue;
-            };
+            {
+                args.push(gsi_args);
+            }
+        }
+    }
 
-            let mut name = None;
-            let mut partition_key = None;
-            let mut sort_keys = vec![];
-
-            for gsi_arg in gsi_args.iter() {
-                match gsi_arg {
-                    Meta::NameValue(MetaNameValue {
-                        path,
-                        value:
-                            Expr::Lit(ExprLit {
-                                lit: Lit::Str(lit), ..
-                            }),
-                        ..
-                    }) if path.segments[0].ident == "name" => {
-                        name = Some(lit.value());
-                    }
-                    Meta::NameValue(MetaNameValue {
-                        path,
-                        value:
-                            Expr::Lit(ExprLit {
-                                lit: Lit::Str(lit), ..
-                            }),
-                        ..
-                    }) if path.segments[0].ident == "partition_key" => {
-                        partition_key = Some(lit.value());
-                    }
-                    Meta::NameValue(MetaNameValue {
-                        path,
-                        value:
-                            Expr::Lit(ExprLit {
-                                lit: Lit::Str(lit), ..
-                            }),
-                        ..
-                    }) if path.segments[0].ident == "sort_key" => {
-                        sort_keys.push(lit.value());
-                    }
-                    _ => {}
+    args
+}
+
+/// Returns the `partition_key` and `sort_key` literals of the gsi definitions.
+pub(crate) fn find_gsi_key_lits(attrs: &[syn::Attribute]) -> Vec<LitStr> {
+    find_gsi_args(attrs)
+        .iter()
+        .flatten()
+        .filter_map(|gsi_arg| match gsi_arg {
+            Meta::NameValue(MetaNameValue {
+                path,
+                value:
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(lit), ..
+                    }),
+                ..
+            }) if path.is_ident("partition_key") || path.is_ident("sort_key") => Some(lit.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+pub(crate) fn find_gsi_definitions(attrs: &[syn::Attribute]) -> Vec<GsiDefinition> {
+    let mut defs = vec![];
+
+    for gsi_args in find_gsi_args(attrs) {
+        let mut name = None;
+        let mut partition_key = None;
+        let mut sort_keys = vec![];
+
+        for gsi_arg in gsi_args.iter() {
+            match gsi_arg {
+                Meta::NameValue(MetaNameValue {
+                    path,
+                    value:
+                        Expr::Lit(ExprLit {
+                            lit: Lit::Str(lit), ..
+                        }),
+                    ..
+                }) if path.segments[0].ident == "name" => {
+                    name = Some(lit.value());
+                }
+                Meta::NameValue(MetaNameValue {
+                    path,
+                    value:
+                        Expr::Lit(ExprLit {
+                            lit: Lit::Str(lit), ..
+                        }),
+                    ..
+                }) if path.segments[0].ident == "partition_key" => {
+                    partition_key = Some(lit.value());
                 }
+                Meta::NameValue(MetaNameValue {
+                    path,
+                    value:
+                        Expr::Lit(ExprLit {
+                            lit: Lit::Str(lit), ..
+                        }),
+                    ..
+                }) if path.segments[0].ident == "sort_key" => {
+                    sort_keys.push(lit.value());
+                }
+                _ => {}
             }
+        }
 
-            if let Some(name) = name {
-                defs.push(GsiDefinition {
-                    name,
-                    partition_key,
-                    sort_keys,
-                });
-            }
+        if let Some(name) = name {
+            defs.push(GsiDefinition {
+                name,
+                partition_key,
+                sort_keys,
+            });
         }
     }
 
@@ -207,7 +250,7 @@ pub(crate) fn find_rename_value(attrs: &[syn::Attribute]) -> Option<String> {
     None
 }
 
-pub(crate) fn find_string_values(attrs: &[syn::Attribute], name: &str) -> Vec<String> {
+pub(crate) fn find_lit_strs(attrs: &[syn::Attribute], name: &str) -> Vec<LitStr> {
     let mut values = vec![];
 
     for attr in attrs {
@@ -215,7 +258,7 @@ pub(crate) fn find_string_values(attrs: &[syn::Attribute], name: &str) -> Vec<St
             continue;
         }
 
-        if let Some(lit) = find_eq_string_from(attr, name) {
+        if let Some(lit) = find_eq_lit_from(attr, name) {
             values.push(lit);
         }
     }
@@ -223,6 +266,13 @@ pub(crate) fn find_string_values(attrs: &[syn::Attribute], name: &str) -> Vec<St
     values
 }
 
+pub(crate) fn find_string_values(attrs: &[syn::Attribute], name: &str) -> Vec<String> {
+    find_lit_strs(attrs, name)
+        .into_iter()
+        .map(|lit| lit.value())
+        .collect()
+}
+
 pub(crate) fn find_omit_gsi_names(attrs: &[syn::Attribute]) -> Vec<String> {
     find_string_values(attrs, "omit_gsi")
 }
@@ -234,50 +284,104 @@ pub(crate) fn include_unary_attr(attrs: &[syn::Attribute], name: &str) -> bool {
         })
 }
 
-pub(crate) fn validate_omit_gsi_fields(fields: &syn::FieldsNamed, gsi_names: &[String]) {
-    for field in fields.named.iter() {
-        let ident = field
-            .ident
-            .as_ref()
-            .expect("raiden only supports named fields");
-        let omit_gsi_names = find_omit_gsi_names(&field.attrs);
-
-        for gsi_name in omit_gsi_names.iter() {
-            if !gsi_names.iter().any(|known_name| known_name == gsi_name) {
-                panic!("unknown gsi `{gsi_name}` specified in omit_gsi for field `{ident}`");
-            }
-        }
-    }
+// Returns the `name` key in `#[raiden(...)]`, to point errors at it.
+pub(crate) fn find_unary_path(attrs: &[syn::Attribute], name: &str) -> Option<syn::Path> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("raiden"))
+        .find_map(|attr| {
+            let ident = find_unary_attr(attr, name)?;
+            Some(syn::Path::from(ident))
+        })
 }
 
-// TODO: Add validation
-pub(crate) fn find_partition_key_field(fields: &syn::FieldsNamed) -> Option<syn::Field> {
-    let fields: Vec<syn::Field> = fields
+// Returns the only field marked with `#[raiden(name)]`.
+fn find_marked_field(
+    fields: &syn::FieldsNamed,
+    name: &str,
+    description: &str,
+) -> syn::Result<Option<syn::Field>> {
+    let mut marked = fields
         .named
         .iter()
-        .filter(|f| include_unary_attr(&f.attrs, "partition_key"))
-        .cloned()
-        .collect();
-
-    if fields.len() > 1 {
-        panic!("partition key should be only one.")
+        .filter(|f| include_unary_attr(&f.attrs, name));
+    let field = marked.next().cloned();
+
+    let mut errors = crate::validate::Errors::default();
+    for duplicate in marked {
+        let path = find_unary_path(&duplicate.attrs, name).expect("should be marked");
+        errors.push(syn::Error::new_spanned(
+            path,
+            format!("{description} should be only one."),
+        ));
     }
-    fields.first().cloned()
+    errors.finish()?;
+
+    Ok(field)
 }
 
-pub(crate) fn find_sort_key_field(fields: &syn::FieldsNamed) -> Option<syn::Field> {
-    let fields: Vec<syn::Field> = fields
-        .named
-        .iter()
-        .filter(|f| include_unary_attr(&f.attrs, "sort_key"))
-        .cloned()
-        .collect();
+pub(crate) fn find_partition_key_field(
+    fields: &syn::FieldsNamed,
+) -> syn::Result<Option<syn::Field>> {
+    find_marked_field(fields, "partition_key", "partition key")
+}
+
+pub(crate) fn find_sort_key_field(fields: &syn::FieldsNamed) -> syn::Result<Option<syn::Field>> {
+    find_marked_field(fields, "sort_key", "sort key")
+}
 
-    if fields.len() > 1 {
-        panic!("sort key should be only one.")
+pub(crate) fn find_version_field(fields: &syn::FieldsNamed) -> syn::Result<Option<syn::Field>> {
+    let Some(field) = find_marked_field(fields, "version", "version field")? else {
+        return Ok(None);
+    };
+    if is_option(&field.ty) {
+        return Err(syn::Error::new_spanned(
+            &field.ty,
+            "version field should be an integer, not an Option.",
+        ));
     }
+    if !is_integer(&field.ty) {
+        return Err(syn::Error::new_spanned(
+            &field.ty,
+            "version field should be an integer type such as `u64`.",
+        ));
+    }
+
+    Ok(Some(field))
+}
+
+pub(crate) fn find_timestamp_field(
+    fields: &syn::FieldsNamed,
+    name: &str,
+) -> syn::Result<Option<syn::Field>> {
+    find_marked_field(fields, name, &format!("{name} field"))
+}
+
+pub(crate) fn find_ttl_field(fields: &syn::FieldsNamed) -> syn::Result<Option<syn::Field>> {
+    find_marked_field(fields, "ttl", "ttl field")
+}
 
-    fields.first().cloned()
+// The version is incremented with `checked_add`, which only the integer primitives have.
+fn is_integer(ty: &syn::Type) -> bool {
+    let syn::Type::Path(syn::TypePath { qself: None, path }) = ty else {
+        return false;
+    };
+    path.get_ident().is_some_and(|ident| {
+        matches!(
+            ident.to_string().as_str(),
+            "u8" | "u16"
+                | "u32"
+                | "u64"
+                | "u128"
+                | "usize"
+                | "i8"
+                | "i16"
+                | "i32"
+                | "i64"
+                | "i128"
+                | "isize"
+        )
+    })
 }
 
 pub(crate) fn is_option(ty: &syn::Type) -> bool {
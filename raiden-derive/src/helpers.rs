@@ -53,3 +53,14 @@ pub fn is_reserved(v: &str) -> bool {
             | "try"
     )
 }
+
+// Checks the name by hand, since parsing an invalid string in a proc macro reports lexer errors.
+pub fn is_identifier(v: &str) -> bool {
+    let mut chars = v.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && v != "_"
+        && !is_reserved(v)
+}
@@ -4,14 +4,21 @@ fn expand_raiden_item_impl_from_fields(
     struct_name: &proc_macro2::Ident,
     fields: &[syn::Field],
     rename_all_type: crate::rename::RenameAllType,
+    key_templates: &crate::key_template::KeyTemplates,
 ) -> proc_macro2::TokenStream {
+    let insertion_key_attribute_name = key_templates.attr_names().into_iter().map(|name| {
+        quote! {
+            names.insert(::raiden::name_placeholder(#name), #name.to_string());
+        }
+    });
+    let key_resolution = key_templates.expand_resolution();
     let insertion_attribute_name = fields.iter().map(|f| {
         let ident = f.ident.as_ref().expect("raiden only supports named fields");
         let renamed = crate::finder::find_rename_value(&f.attrs);
         let result = crate::rename::create_renamed(ident.to_string(), renamed, rename_all_type);
         quote! {
             names.insert(
-                format!("#{}", #result.clone()),
+                ::raiden::name_placeholder(#result),
                 #result.to_string(),
             );
         }
@@ -41,11 +48,13 @@ fn expand_raiden_item_impl_from_fields(
                     if item.is_none() {
                         None
                     } else {
-                        let converted = ::raiden::FromAttribute::from_attr(item);
-                        if converted.is_err() {
-                            return Err(::raiden::RaidenError::AttributeConvertError{ attr_name: #attr_key.to_string() });
+                        match ::raiden::FromAttribute::from_attr(item) {
+                            Ok(converted) => converted,
+                            Err(source) => return Err(::raiden::RaidenError::AttributeConvertError {
+                                attr_name: #attr_key.to_string(),
+                                source,
+                            }),
                         }
-                        converted.unwrap()
                     }
                 },
             }
@@ -65,11 +74,13 @@ fn expand_raiden_item_impl_from_fields(
                         if is_null {
                             Default::default()
                         } else {
-                            let converted = ::raiden::FromAttribute::from_attr(Some(item));
-                            if converted.is_err() {
-                                return Err(::raiden::RaidenError::AttributeConvertError{ attr_name: #attr_key.to_string() });
+                            match ::raiden::FromAttribute::from_attr(Some(item)) {
+                                Ok(converted) => converted,
+                                Err(source) => return Err(::raiden::RaidenError::AttributeConvertError {
+                                    attr_name: #attr_key.to_string(),
+                                    source,
+                                }),
                             }
-                            converted.unwrap()
                         }
                     }
                 },
@@ -78,11 +89,13 @@ fn expand_raiden_item_impl_from_fields(
             quote! {
                 #ident: {
                     #item
-                    let converted = ::raiden::FromAttribute::from_attr(item);
-                    if converted.is_err() {
-                        return Err(::raiden::RaidenError::AttributeConvertError{ attr_name: #attr_key.to_string() });
+                    match ::raiden::FromAttribute::from_attr(item) {
+                        Ok(converted) => converted,
+                        Err(source) => return Err(::raiden::RaidenError::AttributeConvertError {
+                            attr_name: #attr_key.to_string(),
+                            source,
+                        }),
                     }
-                    converted.unwrap()
                 },
             }
         }
@@ -93,6 +106,7 @@ fn expand_raiden_item_impl_from_fields(
             fn attribute_names() -> Option<::raiden::AttributeNames> {
                 let mut names: ::raiden::AttributeNames = std::collections::HashMap::new();
                 #(#insertion_attribute_name)*
+                #(#insertion_key_attribute_name)*
 
                 if names.is_empty() {
                     None
@@ -107,6 +121,7 @@ fn expand_raiden_item_impl_from_fields(
             }
 
             fn from_item(mut item: ::raiden::AttributeValues) -> Result<Self, ::raiden::RaidenError> {
+                #key_resolution
                 Ok(Self {
                     #(#from_item)*
                 })
@@ -119,9 +134,10 @@ pub(crate) fn expand_raiden_item_impl(
     struct_name: &proc_macro2::Ident,
     fields: &syn::FieldsNamed,
     rename_all_type: crate::rename::RenameAllType,
+    key_templates: &crate::key_template::KeyTemplates,
 ) -> proc_macro2::TokenStream {
     let fields: Vec<syn::Field> = fields.named.iter().cloned().collect();
-    expand_raiden_item_impl_from_fields(struct_name, &fields, rename_all_type)
+    expand_raiden_item_impl_from_fields(struct_name, &fields, rename_all_type, key_templates)
 }
 
 pub(crate) fn expand_raiden_item_impl_for_fields(
@@ -129,5 +145,10 @@ pub(crate) fn expand_raiden_item_impl_for_fields(
     fields: &[syn::Field],
     rename_all_type: crate::rename::RenameAllType,
 ) -> proc_macro2::TokenStream {
-    expand_raiden_item_impl_from_fields(struct_name, fields, rename_all_type)
+    expand_raiden_item_impl_from_fields(
+        struct_name,
+        fields,
+        rename_all_type,
+        &crate::key_template::KeyTemplates::default(),
+    )
 }
@@ -1,54 +1,40 @@
 use proc_macro2::*;
-use quote::*;
 use syn::*;
 
 use crate::finder::*;
 use crate::rename::{rename, RenameAllType};
 
 pub fn fetch_partition_key(
+    struct_name: &Ident,
     fields: &syn::FieldsNamed,
     rename_all_type: crate::rename::RenameAllType,
-) -> (Ident, Type) {
-    match find_partition_key_field(fields) {
-        Some(key) => {
-            // Rename partition key if renamed.
-            let renamed = find_rename_value(&key.attrs);
-            if let Some(renamed) = renamed {
-                (format_ident!("{}", renamed), key.ty)
-            } else if rename_all_type != RenameAllType::None {
-                let ident = format_ident!(
-                    "{}",
-                    rename(rename_all_type, key.ident.unwrap().to_string())
-                );
-                (ident, key.ty)
-            } else {
-                (key.ident.unwrap(), key.ty)
-            }
-        }
-        None => panic!("Please specify partition key"),
+) -> syn::Result<(LitStr, Type)> {
+    match find_partition_key_field(fields)? {
+        Some(key) => Ok(renamed_key(key, rename_all_type)),
+        None => Err(syn::Error::new_spanned(
+            struct_name,
+            "Please specify partition key with #[raiden(partition_key)] on a field, or #[raiden(partition_key = \"...\")] on the struct",
+        )),
     }
 }
 
 pub fn fetch_sort_key(
     fields: &syn::FieldsNamed,
     rename_all_type: crate::rename::RenameAllType,
-) -> Option<(Ident, Type)> {
-    match find_sort_key_field(fields) {
-        Some(key) => {
-            // Rename partition key if renamed.
-            let renamed = find_rename_value(&key.attrs);
-            if let Some(renamed) = renamed {
-                Some((format_ident!("{}", renamed), key.ty))
-            } else if rename_all_type != RenameAllType::None {
-                let ident = format_ident!(
-                    "{}",
-                    rename(rename_all_type, key.ident.unwrap().to_string())
-                );
-                Some((ident, key.ty))
-            } else {
-                Some((key.ident.unwrap(), key.ty))
-            }
+) -> syn::Result<Option<(LitStr, Type)>> {
+    Ok(find_sort_key_field(fields)?.map(|key| renamed_key(key, rename_all_type)))
+}
+
+// Rename key if renamed.
+// The attribute name is kept as a string, since it does not have to be a valid identifier.
+fn renamed_key(key: Field, rename_all_type: RenameAllType) -> (LitStr, Type) {
+    let ident = key.ident.expect("named field");
+    let name = match find_rename_value(&key.attrs) {
+        Some(renamed) => renamed,
+        None if rename_all_type != RenameAllType::None => {
+            rename(rename_all_type, ident.to_string())
         }
-        None => None,
-    }
+        None => ident.to_string(),
+    };
+    (LitStr::new(&name, ident.span()), key.ty)
 }
@@ -3,8 +3,44 @@ use quote::*;
 pub fn expand_key_condition_builder(
     attr_enum_name: &proc_macro2::Ident,
     struct_name: &proc_macro2::Ident,
+    partition_key: &(syn::LitStr, syn::Type),
+    sort_key: &Option<(syn::LitStr, syn::Type)>,
 ) -> proc_macro2::TokenStream {
     let key_condition_token_name = format_ident!("{}KeyConditionToken", struct_name);
+    let partition_token_name = format_ident!("{}PartitionKeyConditionToken", struct_name);
+    let sort_token_name = format_ident!("{}SortKeyConditionToken", struct_name);
+    let terminal_token_name = format_ident!("{}TerminalKeyConditionToken", struct_name);
+
+    let partition_key_name = partition_key.0.value();
+    let (partition_next_token_name, sort_key_condition) = match sort_key {
+        Some((sort_key, _)) => {
+            let sort_key_name = sort_key.value();
+            (
+                sort_token_name.clone(),
+                quote! {
+                    pub struct #sort_token_name;
+                    impl ::raiden::key_condition::SupportsEqCondition for #sort_token_name {}
+                    impl ::raiden::key_condition::SupportsRangeCondition for #sort_token_name {}
+
+                    impl #struct_name {
+                        /// Starts a typed key condition with the sort key of the table.
+                        ///
+                        /// This helper can only be chained after the partition key
+                        /// condition has already been specified.
+                        pub fn sort_key_condition() -> ::raiden::KeyCondition<#sort_token_name, #terminal_token_name> {
+                            ::raiden::KeyCondition {
+                                attr: #sort_key_name.to_owned(),
+                                _token: std::marker::PhantomData,
+                                _next_token: std::marker::PhantomData,
+                            }
+                        }
+                    }
+                },
+            )
+        }
+        None => (terminal_token_name.clone(), quote! {}),
+    };
+
     quote! {
 
         pub struct #key_condition_token_name;
@@ -12,7 +48,18 @@ pub fn expand_key_condition_builder(
         impl ::raiden::key_condition::SupportsEqCondition for #key_condition_token_name {}
         impl ::raiden::key_condition::SupportsRangeCondition for #key_condition_token_name {}
 
+        pub struct #partition_token_name;
+        impl ::raiden::key_condition::SupportsEqCondition for #partition_token_name {}
+
+        pub struct #terminal_token_name;
+
+        #sort_key_condition
+
         impl #struct_name {
+            /// Starts an untyped key condition with any attribute.
+            ///
+            /// This is accepted only by queries on an index without typed key
+            /// definitions. Use `partition_key_condition` for the table itself.
             pub fn key_condition(attr: #attr_enum_name) -> ::raiden::KeyCondition<#key_condition_token_name, #key_condition_token_name> {
                 let attr = attr.into_attr_name();
                 ::raiden::KeyCondition {
@@ -21,6 +68,17 @@ pub fn expand_key_condition_builder(
                     _next_token: std::marker::PhantomData,
                 }
             }
+
+            /// Starts a typed key condition with the partition key of the table.
+            ///
+            /// This is the required first condition when querying the table.
+            pub fn partition_key_condition() -> ::raiden::KeyCondition<#partition_token_name, #partition_next_token_name> {
+                ::raiden::KeyCondition {
+                    attr: #partition_key_name.to_owned(),
+                    _token: std::marker::PhantomData,
+                    _next_token: std::marker::PhantomData,
+                }
+            }
         }
     }
 }
@@ -0,0 +1,347 @@
+use proc_macro2::*;
+use quote::*;
+use syn::*;
+
+use crate::finder::*;
+use crate::rename::RenameAllType;
+
+/// A field named by a placeholder of a key template.
+#[derive(Clone)]
+pub(crate) struct TemplateField {
+    pub ident: Ident,
+    pub ty: Type,
+    pub attr_name: String,
+}
+
+/// A key attribute synthesized from other fields, declared on the model with
+/// `#[raiden(partition_key = "USER#{id}")]` or `#[raiden(sort_key = "...")]`.
+#[derive(Clone)]
+pub(crate) struct KeyTemplate {
+    pub attr_name: String,
+    pub template: String,
+    pub fields: Vec<TemplateField>,
+}
+
+impl KeyTemplate {
+    /// The key as declared by a key field, i.e. the attribute name and its type.
+    pub(crate) fn key(&self) -> (LitStr, Type) {
+        (
+            LitStr::new(&self.attr_name, Span::call_site()),
+            parse_quote!(String),
+        )
+    }
+
+    // The values of the placeholders, converted from the fields of `item` or from the variables
+    // named after them.
+    fn expand_values(&self, item: Option<&TokenStream>) -> TokenStream {
+        let values = self.fields.iter().map(|f| {
+            let ident = &f.ident;
+            match item {
+                Some(item) => quote! { #item.#ident.to_string() },
+                None => quote! { #ident.to_string() },
+            }
+        });
+        quote! { &[#(#values),*] }
+    }
+}
+
+/// The keys declared with a template instead of a key field.
+#[derive(Clone, Default)]
+pub(crate) struct KeyTemplates {
+    pub partition_key: Option<KeyTemplate>,
+    pub sort_key: Option<KeyTemplate>,
+}
+
+impl KeyTemplates {
+    fn iter(&self) -> impl Iterator<Item = &KeyTemplate> {
+        self.partition_key.iter().chain(self.sort_key.iter())
+    }
+
+    /// The names of the synthesized key attributes.
+    pub(crate) fn attr_names(&self) -> Vec<String> {
+        self.iter().map(|t| t.attr_name.clone()).collect()
+    }
+
+    /// Inserts the synthesized key attributes of `item` into `input_item`, and binds the first
+    /// error formatting them to `key_error`.
+    pub(crate) fn expand_insertion(&self, item: TokenStream) -> TokenStream {
+        let insertion = self.iter().map(|t| {
+            let attr_name = &t.attr_name;
+            let template = &t.template;
+            let values = t.expand_values(Some(&item));
+            quote! {
+                ::raiden::key_template::format_attr(#template, #attr_name, #values).map(|key| {
+                    input_item.insert(#attr_name.to_string(), ::raiden::IntoAttribute::into_attr(key));
+                })
+            }
+        });
+        if self.iter().next().is_none() {
+            return quote! {
+                let key_error: Option<::raiden::RaidenError> = None;
+            };
+        }
+        quote! {
+            let key_error = [#(#insertion),*].into_iter().find_map(Result::err);
+        }
+    }
+
+    /// Fills the fields missing from `item` with the values parsed from its key attributes.
+    pub(crate) fn expand_resolution(&self) -> TokenStream {
+        let resolution = self.iter().map(|t| {
+            let attr_name = &t.attr_name;
+            let template = &t.template;
+            let fields = t.fields.iter().enumerate().map(|(i, f)| {
+                let field_attr_name = &f.attr_name;
+                let ty = &f.ty;
+                quote! {
+                    if !item.contains_key(#field_attr_name) {
+                        let value: #ty = ::raiden::key_template::parse_value(#attr_name, &values[#i])?;
+                        item.insert(#field_attr_name.to_string(), ::raiden::IntoAttribute::into_attr(value));
+                    }
+                }
+            });
+            quote! {
+                if let Some(values) = ::raiden::key_template::parse_attr(#template, #attr_name, &item)? {
+                    #(#fields)*
+                }
+            }
+        });
+        quote! { #(#resolution)* }
+    }
+
+    /// Expands the helpers formatting the keys, and ties the templates and their typed values to
+    /// the key condition tokens.
+    pub(crate) fn expand_helpers(&self, struct_name: &Ident) -> TokenStream {
+        let helpers = [
+            (&self.partition_key, "partition", "Partition"),
+            (&self.sort_key, "sort", "Sort"),
+        ]
+        .into_iter()
+        .filter_map(|(t, name, token)| Some((t.as_ref()?, name, token)))
+        .map(|(t, name, token)| {
+            let func_name = format_ident!("format_{}_key", name);
+            let token_name = format_ident!("{}{}KeyConditionToken", struct_name, token);
+            let template = &t.template;
+            let doc = format!("Formats the {name} key `{template}`.");
+            let args = t.fields.iter().map(|f| {
+                let ident = &f.ident;
+                let ty = &f.ty;
+                quote! { #ident: #ty }
+            });
+            let values = t.expand_values(None);
+            let tys = t.fields.iter().map(|f| &f.ty);
+            // Only a sort key is matched by the values of its leading placeholders.
+            let lens = if name == "sort" {
+                0..=t.fields.len()
+            } else {
+                t.fields.len()..=t.fields.len()
+            };
+            let values_impls = lens.map(|len| {
+                let fields = &t.fields[..len];
+                let tys = fields.iter().map(|f| &f.ty);
+                let body = if fields.is_empty() {
+                    quote! { vec![] }
+                } else {
+                    let idents: Vec<_> = fields.iter().map(|f| &f.ident).collect();
+                    quote! {
+                        let (#(#idents,)*) = self;
+                        vec![#(#idents.to_string()),*]
+                    }
+                };
+                quote! {
+                    impl ::raiden::KeyTemplateValues<#token_name> for (#(#tys,)*) {
+                        fn into_values(self) -> Vec<String> {
+                            #body
+                        }
+                    }
+                }
+            });
+            quote! {
+                impl #struct_name {
+                    #[doc = #doc]
+                    pub fn #func_name(#(#args),*) -> String {
+                        ::raiden::key_template::format(#template, #values)
+                    }
+                }
+
+                impl ::raiden::KeyTemplate for #token_name {
+                    const TEMPLATE: &'static str = #template;
+                    type Values = (#(#tys,)*);
+                }
+
+                #(#values_impls)*
+            }
+        });
+        quote! { #(#helpers)* }
+    }
+}
+
+pub(crate) fn fetch_key_templates(
+    attrs: &[Attribute],
+    fields: &FieldsNamed,
+    rename_all_type: RenameAllType,
+) -> syn::Result<KeyTemplates> {
+    let mut errors = crate::validate::Errors::default();
+    let partition_key = errors
+        .check(fetch_key_template(
+            attrs,
+            fields,
+            rename_all_type,
+            "partition_key",
+            "PK",
+        ))
+        .flatten();
+    let sort_key = errors
+        .check(fetch_key_template(
+            attrs,
+            fields,
+            rename_all_type,
+            "sort_key",
+            "SK",
+        ))
+        .flatten();
+    for (template, name, description) in [
+        (&partition_key, "partition_key", "partition key"),
+        (&sort_key, "sort_key", "sort key"),
+    ] {
+        let attr_key = format!("{name}_attr");
+        if find_lit_strs(attrs, name).is_empty() {
+            if let Some(lit) = find_lit_strs(attrs, &attr_key).into_iter().next() {
+                errors.push(syn::Error::new_spanned(
+                    lit,
+                    format!("{attr_key} should be used with a {description} template, e.g. #[raiden({name} = \"USER#{{id}}\")]."),
+                ));
+            }
+        }
+        if template.is_none() {
+            continue;
+        }
+        for field in fields.named.iter() {
+            if let Some(path) = find_unary_path(&field.attrs, name) {
+                errors.push(syn::Error::new_spanned(
+                    path,
+                    format!("{description} should be declared either with a template or a field, not both."),
+                ));
+            }
+        }
+    }
+    errors.finish()?;
+
+    Ok(KeyTemplates {
+        partition_key,
+        sort_key,
+    })
+}
+
+// The key attribute is named with `#[raiden(partition_key_attr = "...")]` or
+// `#[raiden(sort_key_attr = "...")]`, or `default_attr_name` otherwise.
+fn fetch_key_template(
+    attrs: &[Attribute],
+    fields: &FieldsNamed,
+    rename_all_type: RenameAllType,
+    name: &str,
+    default_attr_name: &str,
+) -> syn::Result<Option<KeyTemplate>> {
+    let Some(lit) = find_lit_strs(attrs, name).into_iter().next() else {
+        return Ok(None);
+    };
+    let template = lit.value();
+    let mut errors = crate::validate::Errors::default();
+    let attr_lit = find_lit_strs(attrs, &format!("{name}_attr"))
+        .into_iter()
+        .next();
+    let attr_name = attr_lit
+        .as_ref()
+        .map(LitStr::value)
+        .unwrap_or_else(|| default_attr_name.to_owned());
+    // The key attribute is written over the item, so it can't be the attribute of a field.
+    if let Some(field) = fields.named.iter().find(|f| {
+        crate::rename::create_renamed(
+            f.ident.as_ref().unwrap().to_string(),
+            find_rename_value(&f.attrs),
+            rename_all_type,
+        ) == attr_name
+    }) {
+        let message = format!(
+            "the key attribute `{attr_name}` of the key template `{template}` is also the attribute of the field `{}`.",
+            field.ident.as_ref().unwrap()
+        );
+        match &attr_lit {
+            Some(attr_lit) => errors.push(syn::Error::new_spanned(attr_lit, message)),
+            None => errors.push(syn::Error::new_spanned(&lit, message)),
+        }
+    }
+    let fields = parse_placeholders(&lit)?
+        .into_iter()
+        .filter_map(|placeholder| {
+            let Some(field) = fields
+                .named
+                .iter()
+                .find(|f| f.ident.as_ref().is_some_and(|ident| ident == &placeholder))
+            else {
+                errors.push(syn::Error::new_spanned(
+                    &lit,
+                    format!("the key template `{template}` refers to the unknown field `{placeholder}`."),
+                ));
+                return None;
+            };
+            if is_option(&field.ty) {
+                errors.push(syn::Error::new_spanned(
+                    &field.ty,
+                    format!("the field `{placeholder}` in the key template `{template}` should not be an Option."),
+                ));
+            }
+            for attr in ["uuid", "created_at", "updated_at", "version"] {
+                if let Some(path) = find_unary_path(&field.attrs, attr) {
+                    errors.push(syn::Error::new_spanned(
+                        path,
+                        format!("the field `{placeholder}` in the key template `{template}` should be set by the caller."),
+                    ));
+                }
+            }
+            let ident = field.ident.clone().unwrap();
+            Some(TemplateField {
+                attr_name: crate::rename::create_renamed(
+                    ident.to_string(),
+                    find_rename_value(&field.attrs),
+                    rename_all_type,
+                ),
+                ident,
+                ty: field.ty.clone(),
+            })
+        })
+        .collect();
+    errors.finish()?;
+
+    Ok(Some(KeyTemplate {
+        attr_name,
+        template,
+        fields,
+    }))
+}
+
+// Returns the field names in `{...}`, which must be separated by a literal to be parsed back.
+fn parse_placeholders(lit: &LitStr) -> syn::Result<Vec<String>> {
+    let template = lit.value();
+    let mut placeholders = vec![];
+    let mut rest = template.as_str();
+    let mut adjacent = false;
+    while let Some(start) = rest.find('{') {
+        if adjacent && start == 0 {
+            return Err(syn::Error::new_spanned(
+                lit,
+                format!("the placeholders in the key template `{template}` should be separated by a literal."),
+            ));
+        }
+        let Some(end) = rest[start..].find('}') else {
+            return Err(syn::Error::new_spanned(
+                lit,
+                format!("the key template `{template}` has an unclosed `{{`."),
+            ));
+        };
+        placeholders.push(rest[start + 1..start + end].trim().to_string());
+        rest = &rest[start + end + 1..];
+        adjacent = true;
+    }
+    Ok(placeholders)
+}
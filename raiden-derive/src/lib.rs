@@ -9,13 +9,21 @@ use syn::*;
 
 mod attribute;
 mod condition;
+mod entity;
 mod filter_expression;
 mod finder;
 mod helpers;
 mod item;
 mod key;
 mod key_condition;
+mod key_template;
 mod rename;
+mod scalar;
+mod table;
+mod timestamp;
+mod ttl;
+mod validate;
+mod version;
 
 #[cfg(feature = "rusoto")]
 mod rusoto;
@@ -27,9 +35,6 @@ mod aws_sdk;
 #[cfg(feature = "aws-sdk")]
 use aws_sdk::*;
 
-use crate::rename::*;
-use std::str::FromStr;
-
 fn create_gsi_partition_token_name(
     struct_name: &proc_macro2::Ident,
     index_name: &str,
@@ -92,7 +97,7 @@ fn resolve_attr_name_for_fields(
                 .as_ref()
                 .is_some_and(|ident| ident == field_name)
         })
-        .unwrap_or_else(|| panic!("unknown field `{field_name}` for gsi key definition"));
+        .expect("gsi keys should be validated");
 
     crate::rename::create_renamed(
         field_name.to_owned(),
@@ -229,7 +234,6 @@ fn expand_projection_item_support(
     let source_query_trait_ident = format_ident!("{}Query", source_struct_ident);
     let source_scan_trait_ident = format_ident!("{}Scan", source_struct_ident);
     let source_query_builder_ident = format_ident!("{}QueryBuilder", source_struct_ident);
-    let source_scan_builder_ident = format_ident!("{}ScanBuilder", source_struct_ident);
     let source_projected_query_builder_ident =
         format_ident!("{}ProjectedQueryBuilder", source_struct_ident);
     let source_projected_scan_builder_ident =
@@ -276,6 +280,8 @@ fn expand_projection_item_support(
                         limit,
                         policy,
                         condition,
+                        expired_filter,
+                        placeholders,
                         ..
                     } = builder;
                     #source_query_builder_ident {
@@ -285,6 +291,8 @@ fn expand_projection_item_support(
                         limit,
                         policy,
                         condition,
+                        expired_filter,
+                        placeholders,
                         _token: std::marker::PhantomData::<fn() -> #query_token_ident>,
                     }
                     .project::<Self>()
@@ -300,20 +308,7 @@ fn expand_projection_item_support(
                 pub fn scan<'a>(
                     client: &'a #source_client_ident,
                 ) -> #source_projected_scan_builder_ident<'a, Self> {
-                    let builder = <#source_client_ident as #source_scan_trait_ident>::scan(client);
-                    let #source_scan_builder_ident {
-                        client,
-                        builder,
-                        next_token,
-                        limit,
-                    } = builder;
-                    #source_scan_builder_ident {
-                        client,
-                        builder: builder.index_name(#gsi_name),
-                        next_token,
-                        limit,
-                    }
-                    .project::<Self>()
+                    <#source_client_ident as #source_scan_trait_ident>::scan(client).project::<Self>()
                 }
             }
         }
@@ -338,6 +333,8 @@ fn expand_projection_item_support(
                         limit,
                         policy,
                         condition,
+                        expired_filter,
+                        placeholders,
                         ..
                     } = builder;
                     input.index_name = Some(#gsi_name.to_owned());
@@ -348,6 +345,8 @@ fn expand_projection_item_support(
                         limit,
                         policy,
                         condition,
+                        expired_filter,
+                        placeholders,
                         _token: std::marker::PhantomData::<fn() -> #query_token_ident>,
                     }
                     .project::<Self>()
@@ -363,25 +362,7 @@ fn expand_projection_item_support(
                 pub fn scan<'a>(
                     client: &'a #source_client_ident,
                 ) -> #source_projected_scan_builder_ident<'a, Self> {
-                    let builder = <#source_client_ident as #source_scan_trait_ident>::scan(client);
-                    let #source_scan_builder_ident {
-                        client,
-                        mut input,
-                        policy,
-                        condition,
-                        next_token,
-                        limit,
-                    } = builder;
-                    input.index_name = Some(#gsi_name.to_owned());
-                    #source_scan_builder_ident {
-                        client,
-                        input,
-                        policy,
-                        condition,
-                        next_token,
-                        limit,
-                    }
-                    .project::<Self>()
+                    <#source_client_ident as #source_scan_trait_ident>::scan(client).project::<Self>()
                 }
             }
         }
@@ -477,18 +458,35 @@ fn expand_auto_gsi_projection_items(
     }
 }
 
+// The client struct is shared by models and entity enums.
+fn expand_client_struct(client_name: &proc_macro2::Ident) -> proc_macro2::TokenStream {
+    quote! {
+        pub struct #client_name {
+            table_name: &'static str,
+            client: ::raiden::SharedBackend,
+            table_prefix: String,
+            table_suffix: String,
+            retry_condition: ::raiden::RetryCondition,
+            attribute_names: Option<::raiden::AttributeNames>,
+            projection_expression: Option<String>
+        }
+    }
+}
+
 /// Derives the main table model, typed builders, and query helpers.
 #[proc_macro_derive(Raiden, attributes(raiden))]
 pub fn derive_raiden(input: TokenStream) -> TokenStream {
     let input = syn::parse_macro_input!(input as DeriveInput);
+    expand_raiden(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
 
-    let (dynamodb_client_name, use_dynamodb_trait) = if cfg!(feature = "rusoto") {
-        (
-            format_ident!("DynamoDbClient"),
-            Some(quote! { use ::raiden::DynamoDb as _; }),
-        )
+fn expand_raiden(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let dynamodb_client_name = if cfg!(feature = "rusoto") {
+        format_ident!("DynamoDbClient")
     } else if cfg!(feature = "aws-sdk") {
-        (format_ident!("Client"), None)
+        format_ident!("Client")
     } else {
         unreachable!();
     };
@@ -502,40 +500,80 @@ pub fn derive_raiden(input: TokenStream) -> TokenStream {
 
     let attrs = input.attrs;
 
-    let table_name = if let Some(name) = finder::find_table_name(&attrs) {
-        name
-    } else {
-        struct_name.to_string()
-    };
-
-    let rename_all = finder::find_rename_all(&attrs);
-    let rename_all_type = if let Some(rename_all) = rename_all {
-        rename::RenameAllType::from_str(&rename_all).unwrap()
-    } else {
-        rename::RenameAllType::None
-    };
-    let gsi_names = finder::find_gsi_names(&attrs);
-    let gsi_definitions = finder::find_gsi_definitions(&attrs);
+    if let Data::Enum(data) = &input.data {
+        return entity::expand_entity(&struct_name, &attrs, data, &dynamodb_client_name);
+    }
 
     let fields = match input.data {
         Data::Struct(DataStruct {
             fields: Fields::Named(n),
             ..
         }) => n,
-        _ => unimplemented!(),
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &struct_name,
+                "Raiden supports structs with named fields and enums of entity models",
+            ))
+        }
     };
 
-    finder::validate_omit_gsi_fields(&fields, &gsi_names);
+    let mut errors = validate::Errors::default();
+    validate::validate_attrs(&attrs, validate::MODEL_KEYS, &mut errors);
+    for field in fields.named.iter() {
+        validate::validate_attrs(&field.attrs, validate::MODEL_FIELD_KEYS, &mut errors);
+    }
+    errors.finish()?;
 
-    let partition_key = key::fetch_partition_key(&fields, rename_all_type);
-    let sort_key = key::fetch_sort_key(&fields, rename_all_type);
+    let table_name = if let Some(name) = finder::find_table_name(&attrs) {
+        name
+    } else {
+        struct_name.to_string()
+    };
 
-    let table_name_field = format_ident!("table_name");
-    let client_field = format_ident!("client");
-    let n = vec![
-        quote! { #table_name_field: &'static str },
-        quote! { #client_field: ::raiden::#dynamodb_client_name },
-    ];
+    let mut errors = validate::Errors::default();
+    let rename_all_type = errors
+        .check(finder::find_rename_all_type(&attrs))
+        .unwrap_or(rename::RenameAllType::None);
+    let gsi_names = finder::find_gsi_names(&attrs);
+    let gsi_definitions = finder::find_gsi_definitions(&attrs);
+
+    validate::validate_gsi_keys(&attrs, &fields, &mut errors);
+    validate::validate_omit_gsi(&fields, &gsi_names, &mut errors);
+
+    // The keys are only looked up on the fields once the templates are known to be valid.
+    let key_templates = errors.check(key_template::fetch_key_templates(
+        &attrs,
+        &fields,
+        rename_all_type,
+    ));
+    let partition_key = match key_templates.as_ref().map(|t| &t.partition_key) {
+        Some(Some(template)) => Some(template.key()),
+        Some(None) => errors.check(key::fetch_partition_key(
+            &struct_name,
+            &fields,
+            rename_all_type,
+        )),
+        None => None,
+    };
+    let sort_key = match key_templates.as_ref().map(|t| &t.sort_key) {
+        Some(Some(template)) => Some(template.key()),
+        Some(None) => errors
+            .check(key::fetch_sort_key(&fields, rename_all_type))
+            .flatten(),
+        None => None,
+    };
+    let version = errors
+        .check(version::fetch_version(&fields, rename_all_type))
+        .flatten();
+    let timestamps = errors
+        .check(timestamp::fetch_timestamps(&fields, rename_all_type))
+        .unwrap_or_default();
+    let ttl = errors
+        .check(ttl::fetch_ttl(&fields, rename_all_type))
+        .flatten();
+    errors.finish()?;
+    let key_templates = key_templates.expect("key templates should be checked");
+    let partition_key = partition_key.expect("partition key should be checked");
 
     // let struct_fields = fields.named.iter().map(|f| {
     //     let ident = &f.ident.clone().unwrap();
@@ -546,13 +584,7 @@ pub fn derive_raiden(input: TokenStream) -> TokenStream {
     //     }
     // });
 
-    let get_item = ops::expand_get_item(
-        &partition_key,
-        &sort_key,
-        &struct_name,
-        &fields,
-        rename_all_type,
-    );
+    let get_item = ops::expand_get_item(&partition_key, &sort_key, &struct_name, &ttl);
 
     let query = ops::expand_query(
         &struct_name,
@@ -560,9 +592,10 @@ pub fn derive_raiden(input: TokenStream) -> TokenStream {
         rename_all_type,
         &gsi_names,
         &gsi_definitions,
+        &ttl,
     );
 
-    let scan = ops::expand_scan(&struct_name, &fields, rename_all_type, &gsi_names);
+    let scan = ops::expand_scan(&struct_name, &fields, rename_all_type, &gsi_names, &ttl);
 
     let batch_get = ops::expand_batch_get(
         &partition_key,
@@ -572,20 +605,47 @@ pub fn derive_raiden(input: TokenStream) -> TokenStream {
         rename_all_type,
     );
 
-    let put_item = ops::expand_put_item(&struct_name, &fields, rename_all_type);
+    // rusoto can't return the stored item with a failed version check, so it reads the item back
+    // by its key.
+    #[cfg(feature = "rusoto")]
+    let put_item = ops::expand_put_item(
+        &partition_key,
+        &sort_key,
+        &struct_name,
+        &fields,
+        rename_all_type,
+        &version,
+        &timestamps,
+        &key_templates,
+    );
+    #[cfg(feature = "aws-sdk")]
+    let put_item = ops::expand_put_item(
+        &struct_name,
+        &fields,
+        rename_all_type,
+        &version,
+        &timestamps,
+        &key_templates,
+    );
 
-    let batch_put = ops::expand_batch_put(&struct_name, &fields, rename_all_type);
+    let batch_put = ops::expand_batch_put(
+        &struct_name,
+        &fields,
+        rename_all_type,
+        &timestamps,
+        &key_templates,
+    );
 
     let update_item = ops::expand_update_item(
         &partition_key,
         &sort_key,
-        &fields,
         &attr_enum_name,
         &struct_name,
-        rename_all_type,
+        &version,
+        &timestamps,
     );
 
-    let delete_item = ops::expand_delete_item(&partition_key, &sort_key, &struct_name);
+    let delete_item = ops::expand_delete_item(&partition_key, &sort_key, &struct_name, &version);
 
     let batch_delete = ops::expand_batch_delete(&partition_key, &sort_key, &struct_name);
 
@@ -595,8 +655,12 @@ pub fn derive_raiden(input: TokenStream) -> TokenStream {
     let condition_builder =
         condition::expand_condition_builder(&attr_enum_name, &struct_name, &fields);
 
-    let key_condition_builder =
-        key_condition::expand_key_condition_builder(&attr_enum_name, &struct_name);
+    let key_condition_builder = key_condition::expand_key_condition_builder(
+        &attr_enum_name,
+        &struct_name,
+        &partition_key,
+        &sort_key,
+    );
 
     let filter_expression_builder =
         filter_expression::expand_filter_expression_builder(&attr_enum_name, &struct_name);
@@ -606,28 +670,36 @@ pub fn derive_raiden(input: TokenStream) -> TokenStream {
         &partition_key,
         &sort_key,
         &fields,
-        &attr_enum_name,
         rename_all_type,
         &table_name,
+        &version,
+        &timestamps,
+        &key_templates,
     );
 
-    let transact_get = ops::expand_transact_get(
-        &partition_key,
-        &sort_key,
-        &struct_name,
+    let transact_get =
+        ops::expand_transact_get(&partition_key, &sort_key, &struct_name, &table_name);
+
+    let table_definition = table::build_table_definition(
         &fields,
         rename_all_type,
+        &partition_key,
+        &sort_key,
+        &gsi_definitions,
+        &ttl,
     );
+    let table = ops::expand_table(&struct_name, &table_definition);
 
     let client_constructor = client::expand_client_constructor(
         &struct_name,
         &client_name,
         &dynamodb_client_name,
         &table_name,
-        &fields,
-        &rename_all_type,
     );
-    let raiden_item = item::expand_raiden_item_impl(&struct_name, &fields, rename_all_type);
+    let client_struct = expand_client_struct(&client_name);
+    let raiden_item =
+        item::expand_raiden_item_impl(&struct_name, &fields, rename_all_type, &key_templates);
+    let key_template_helpers = key_templates.expand_helpers(&struct_name);
     let source_fields: Vec<syn::Field> = fields.named.iter().cloned().collect();
     let auto_gsi_projection_items = expand_auto_gsi_projection_items(
         &struct_name,
@@ -638,21 +710,11 @@ pub fn derive_raiden(input: TokenStream) -> TokenStream {
         &gsi_definitions,
     );
 
-    let expanded = quote! {
+    Ok(quote! {
         use ::raiden::IntoAttribute as _;
         use ::raiden::IntoAttrName as _;
-        #use_dynamodb_trait
 
-        pub struct #client_name {
-            #(
-                #n,
-            )*
-            table_prefix: String,
-            table_suffix: String,
-            retry_condition: ::raiden::RetryCondition,
-            attribute_names: Option<::raiden::AttributeNames>,
-            projection_expression: Option<String>
-        }
+        #client_struct
 
         #attr_names
 
@@ -684,74 +746,107 @@ pub fn derive_raiden(input: TokenStream) -> TokenStream {
 
         #transact_write
 
+        #table
+
         #client_constructor
 
         #raiden_item
 
+        #key_template_helpers
+
         #auto_gsi_projection_items
 
         impl ::raiden::IdGenerator for #struct_name {}
-    };
-    // Hand the output tokens back to the compiler.
-    proc_macro::TokenStream::from(expanded)
+
+        impl ::raiden::Clock for #struct_name {}
+    })
 }
 
 /// Derives a typed projection model for a secondary index.
 #[proc_macro_derive(RaidenIndex, attributes(raiden))]
 pub fn derive_raiden_index(input: TokenStream) -> TokenStream {
     let input = syn::parse_macro_input!(input as DeriveInput);
+    expand_raiden_index(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
 
+fn expand_raiden_index(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     let struct_name = input.ident;
     let attrs = input.attrs;
 
-    let source = attrs
-        .iter()
-        .find_map(|attr| {
-            if attr.path().segments[0].ident != "raiden" {
-                return None;
-            }
-            finder::find_eq_string_from(attr, "source")
-        })
-        .unwrap_or_else(|| panic!("RaidenIndex requires #[raiden(source = \"...\")]"));
-    let gsi_names = finder::find_gsi_names(&attrs);
-    if gsi_names.is_empty() {
-        panic!("RaidenIndex requires #[raiden(gsi = \"...\")]");
-    }
-    if gsi_names.len() > 1 {
-        panic!("RaidenIndex currently supports exactly one gsi");
-    }
-    let gsi_name = gsi_names[0].clone();
-    let gsi_definitions = finder::find_gsi_definitions(&attrs);
-
-    let rename_all = finder::find_rename_all(&attrs);
-    let rename_all_type = if let Some(rename_all) = rename_all {
-        rename::RenameAllType::from_str(&rename_all).unwrap()
-    } else {
-        rename::RenameAllType::None
-    };
-
     let fields = match input.data {
         Data::Struct(DataStruct {
             fields: Fields::Named(n),
             ..
         }) => n,
-        _ => unimplemented!(),
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &struct_name,
+                "RaidenIndex supports structs with named fields",
+            ))
+        }
     };
 
-    let source_ty: Type = syn::parse_str(&source)
-        .unwrap_or_else(|_| panic!("invalid source type `{source}` for RaidenIndex"));
-    let source_struct_ident = match &source_ty {
-        Type::Path(type_path) => type_path
-            .path
-            .segments
-            .last()
-            .expect("source path should not be empty")
-            .ident
-            .clone(),
-        _ => panic!("RaidenIndex source must be a path type"),
-    };
+    let mut errors = validate::Errors::default();
+    validate::validate_attrs(&attrs, validate::INDEX_KEYS, &mut errors);
+    for field in fields.named.iter() {
+        validate::validate_attrs(&field.attrs, validate::INDEX_FIELD_KEYS, &mut errors);
+    }
+    errors.finish()?;
+
+    let mut errors = validate::Errors::default();
+    let source = finder::find_lit_strs(&attrs, "source").into_iter().next();
+    if source.is_none() {
+        errors.push(syn::Error::new_spanned(
+            &struct_name,
+            "RaidenIndex requires #[raiden(source = \"...\")]",
+        ));
+    }
+    let gsi_names = finder::find_gsi_names(&attrs);
+    if gsi_names.is_empty() {
+        errors.push(syn::Error::new_spanned(
+            &struct_name,
+            "RaidenIndex requires #[raiden(gsi = \"...\")]",
+        ));
+    }
+    if gsi_names.len() > 1 {
+        errors.push(syn::Error::new_spanned(
+            &struct_name,
+            "RaidenIndex currently supports exactly one gsi",
+        ));
+    }
+    let rename_all_type = errors
+        .check(finder::find_rename_all_type(&attrs))
+        .unwrap_or(rename::RenameAllType::None);
+    let source_ty = source.and_then(|source| {
+        let source_ty = errors.check(source.parse::<Type>().map_err(|_| {
+            syn::Error::new_spanned(
+                &source,
+                format!("invalid source type `{}` for RaidenIndex", source.value()),
+            )
+        }))?;
+        match &source_ty {
+            Type::Path(type_path) if !type_path.path.segments.is_empty() => {
+                let ident = type_path.path.segments.last().unwrap().ident.clone();
+                Some((source_ty, ident))
+            }
+            _ => {
+                errors.push(syn::Error::new_spanned(
+                    &source,
+                    "RaidenIndex source must be a path type",
+                ));
+                None
+            }
+        }
+    });
+    errors.finish()?;
+
+    let (source_ty, source_struct_ident) = source_ty.expect("source should be checked");
+    let gsi_name = gsi_names[0].clone();
+    let gsi_definitions = finder::find_gsi_definitions(&attrs);
     let projection_fields: Vec<syn::Field> = fields.named.iter().cloned().collect();
-    let expanded = expand_projection_item_support(
+    Ok(expand_projection_item_support(
         &struct_name,
         &source_ty,
         &source_struct_ident,
@@ -760,9 +855,7 @@ pub fn derive_raiden_index(input: TokenStream) -> TokenStream {
         rename_all_type,
         &gsi_name,
         &gsi_definitions,
-    );
-
-    proc_macro::TokenStream::from(expanded)
+    ))
 }
 
 /// Derives DynamoDB document conversion support for a nested type.
@@ -822,16 +915,20 @@ pub fn derive_raiden_document(input: TokenStream) -> TokenStream {
                         item: ::raiden::AttributeValues,
                     ) -> Result<Self, ::raiden::RaidenError> {
                         ::raiden::deserialize_document_item(item)
-                            .map_err(|err| ::raiden::RaidenError::AttributeConvertError {
-                                attr_name: err.to_string(),
-                            })
                     }
                 }
             };
 
             return proc_macro::TokenStream::from(expanded);
         }
-        _ => unimplemented!(),
+        _ => {
+            return syn::Error::new_spanned(
+                &struct_name,
+                "RaidenDocument supports structs with named fields and enums",
+            )
+            .into_compile_error()
+            .into()
+        }
     };
     let attr_enum_name = format_ident!("{}DocumentAttrNames", struct_name);
 
@@ -894,6 +991,19 @@ pub fn derive_raiden_document(input: TokenStream) -> TokenStream {
     proc_macro::TokenStream::from(expanded)
 }
 
+/// Derives `IntoAttribute` / `FromAttribute` and the string set item conversions for a custom scalar type.
+///
+/// A newtype such as `struct UserId(String)` is stored as its inner value. A unit enum is stored as
+/// the variant name, which can be changed with `rename` and `rename_all`, or as its discriminant
+/// with `#[raiden(as_number)]`.
+#[proc_macro_derive(RaidenAttribute, attributes(raiden))]
+pub fn derive_raiden_attribute(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    scalar::expand_raiden_attribute(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
 fn find_serde_rename(attrs: &[syn::Attribute]) -> Option<String> {
     let mut renamed = None;
 
@@ -1,6 +1,14 @@
 use std::str::FromStr;
 
-// "lowercase", "UPPERCASE", "PascalCase", "camelCase", "snake_case", "SCREAMING_SNAKE_CASE", "kebab-case", "SCREAMING-KEBAB-CASE".
+/// The values accepted by `#[raiden(rename_all = "...")]`.
+pub const RENAME_ALL_TYPES: &[&str] = &[
+    "lowercase",
+    "camelCase",
+    "PascalCase",
+    "snake_case",
+    "SCREAMING_SNAKE_CASE",
+    "kebab-case",
+];
 
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum RenameAllType {
@@ -23,7 +31,7 @@ impl FromStr for RenameAllType {
             "snake_case" => Ok(RenameAllType::SnakeCase),
             "SCREAMING_SNAKE_CASE" => Ok(RenameAllType::ScreamingSnakeCase),
             "kebab-case" => Ok(RenameAllType::KebabCase),
-            _ => panic!("{s} is not support type."),
+            _ => Err(()),
         }
     }
 }
@@ -48,7 +56,7 @@ pub fn rename(t: RenameAllType, base: String) -> String {
         crate::rename::RenameAllType::KebabCase => {
             ident_case::RenameRule::KebabCase.apply_to_field(base)
         }
-        _ => panic!("{base} is not supported rename type"),
+        crate::rename::RenameAllType::None => base,
     }
 }
 
@@ -65,3 +73,25 @@ pub fn create_renamed(
         basename
     }
 }
+
+// Variants are written in PascalCase, unlike fields.
+fn rename_variant(t: RenameAllType, base: String) -> String {
+    let rule = match t {
+        RenameAllType::LowerCase => ident_case::RenameRule::LowerCase,
+        RenameAllType::CamelCase => ident_case::RenameRule::CamelCase,
+        RenameAllType::PascalCase => ident_case::RenameRule::PascalCase,
+        RenameAllType::SnakeCase => ident_case::RenameRule::SnakeCase,
+        RenameAllType::ScreamingSnakeCase => ident_case::RenameRule::ScreamingSnakeCase,
+        RenameAllType::KebabCase => ident_case::RenameRule::KebabCase,
+        RenameAllType::None => return base,
+    };
+    rule.apply_to_variant(base)
+}
+
+pub fn create_renamed_variant(
+    basename: String,
+    renamed: Option<String>,
+    rename_all_type: RenameAllType,
+) -> String {
+    renamed.unwrap_or_else(|| rename_variant(rename_all_type, basename))
+}
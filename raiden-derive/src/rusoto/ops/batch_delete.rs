@@ -2,17 +2,17 @@ use quote::*;
 use syn::*;
 
 pub(crate) fn expand_batch_delete(
-    partition_key: &(Ident, Type),
-    sort_key: &Option<(Ident, Type)>,
+    partition_key: &(LitStr, Type),
+    sort_key: &Option<(LitStr, Type)>,
     struct_name: &Ident,
 ) -> proc_macro2::TokenStream {
     let trait_name = format_ident!("{}BatchDelete", struct_name);
     let client_name = format_ident!("{}Client", struct_name);
     let builder_name = format_ident!("{}BatchDeleteBuilder", struct_name);
-    let (partition_key_ident, partition_key_type) = partition_key;
+    let (partition_key_name, partition_key_type) = partition_key;
 
     let client_trait = if let Some(sort_key) = sort_key {
-        let (sort_key_ident, sort_key_type) = sort_key;
+        let (sort_key_name, sort_key_type) = sort_key;
         quote! {
             pub trait #trait_name {
                 fn batch_delete(&self, keys: std::vec::Vec<(impl Into<#partition_key_type>, impl Into<#sort_key_type>)>) -> #builder_name;
@@ -30,8 +30,8 @@ pub(crate) fn expand_batch_delete(
                                 let mut write_request = ::raiden::WriteRequest::default();
                                 let delete_request = ::raiden::DeleteRequest {
                                     key: vec![
-                                        (stringify!(#partition_key_ident).to_string(), pk_attr_value),
-                                        (stringify!(#sort_key_ident).to_string(), sk_attr_value)
+                                        (#partition_key_name.to_string(), pk_attr_value),
+                                        (#sort_key_name.to_string(), sk_attr_value)
                                     ].into_iter().collect(),
                                 };
                                 write_request.delete_request = Some(delete_request);
@@ -48,6 +48,7 @@ pub(crate) fn expand_batch_delete(
                         client: &self.client,
                         write_requests,
                         table_name: self.table_name(),
+                        return_consumed_capacity: None,
                         policy: self.retry_condition.strategy.policy(),
                         condition: &self.retry_condition,
                     }
@@ -71,7 +72,7 @@ pub(crate) fn expand_batch_delete(
                                 let mut write_request = ::raiden::WriteRequest::default();
                                 let delete_request = ::raiden::DeleteRequest {
                                     key: vec![
-                                        (stringify!(#partition_key_ident).to_string(), pk_attr_value),
+                                        (#partition_key_name.to_string(), pk_attr_value),
                                     ].into_iter().collect(),
                                 };
                                 write_request.delete_request = Some(delete_request);
@@ -88,6 +89,7 @@ pub(crate) fn expand_batch_delete(
                         client: &self.client,
                         write_requests,
                         table_name: self.table_name(),
+                        return_consumed_capacity: None,
                         policy: self.retry_condition.strategy.policy(),
                         condition: &self.retry_condition,
                     }
@@ -113,22 +115,29 @@ pub(crate) fn expand_batch_delete(
         #client_trait
 
         pub struct #builder_name<'a> {
-            pub client: &'a ::raiden::DynamoDbClient,
+            pub client: &'a ::raiden::SharedBackend,
             pub write_requests: std::vec::Vec<::raiden::WriteRequest>,
             pub table_name: String,
+            pub return_consumed_capacity: Option<::raiden::ReturnConsumedCapacity>,
             pub policy: ::raiden::Policy,
             pub condition: &'a ::raiden::retry::RetryCondition,
         }
 
         impl<'a> #builder_name<'a> {
+            /// Requests the consumed capacity, summed over every request as `consumed_capacity`.
+            pub fn return_consumed_capacity(mut self, value: ::raiden::ReturnConsumedCapacity) -> Self {
+                self.return_consumed_capacity = Some(value);
+                self
+            }
+
             pub async fn run(self) -> Result<::raiden::batch_delete::BatchDeleteOutput, ::raiden::RaidenError> {
-                let Self { client, mut write_requests, table_name, policy, condition } = self;
-                let policy: ::raiden::RetryPolicy = policy.into();
+                let Self { client, mut write_requests, table_name, return_consumed_capacity, policy, condition } = self;
 
                 // TODO: set the number of retry to 5 for now, which should be made more flexible
                 const RETRY: usize = 5;
                 const MAX_ITEMS_PER_REQUEST: usize = 25;
 
+                let mut consumed_capacity = None;
                 for _ in 0..RETRY {
                     loop {
                         let len = write_requests.len();
@@ -147,18 +156,18 @@ pub(crate) fn expand_batch_delete(
                             .collect::<std::collections::HashMap<_, _>>();
                         let result = {
                             let t = table_name.clone();
-                            let c = client.clone();
                             let i = ::raiden::BatchWriteItemInput {
                                 request_items,
+                                return_consumed_capacity: return_consumed_capacity.map(|v| v.as_str().to_owned()),
                                 ..std::default::Default::default()
                             };
 
-                            policy.retry_if(move || {
-                                let (table_name, client, input)
-                                    = (t.clone(), c.clone(), i.clone());
+                            ::raiden::retry_with_backend(policy, &client, condition, move |client| {
+                                let (table_name, input) = (t.clone(), i.clone());
                                 async move { #call_inner_run }
-                            }, condition).await?
+                            }).await?
                         };
+                        consumed_capacity = ::raiden::capacity::merge_consumed_capacities(consumed_capacity, result.consumed_capacity);
 
                         let mut unprocessed_items = match result.unprocessed_items {
                             None => {
@@ -181,7 +190,7 @@ pub(crate) fn expand_batch_delete(
                             .remove(&table_name)
                             .expect("request_items hashmap must have a value for the table name");
                         // push unprocessed requests back to the request buffer
-                        write_requests.extend(unprocessed_requests);
+                        write_requests.extend(unprocessed_requests.into_iter().map(Into::into));
                     }
                 }
 
@@ -192,17 +201,17 @@ pub(crate) fn expand_batch_delete(
                     .filter_map(|write_request| write_request.delete_request)
                     .collect::<std::vec::Vec<_>>();
                 Ok(::raiden::batch_delete::BatchDeleteOutput {
-                    consumed_capacity: None,
+                    consumed_capacity,
                     unprocessed_items,
                 })
             }
 
             async fn inner_run(
                 #inner_run_args
-                client: ::raiden::DynamoDbClient,
+                client: ::raiden::SharedBackend,
                 input: ::raiden::BatchWriteItemInput,
-            ) -> Result<::raiden::BatchWriteItemOutput, ::raiden::RaidenError> {
-                Ok(#api_call_token?)
+            ) -> Result<::raiden::BatchWriteItemResponse, ::raiden::RaidenError> {
+                #api_call_token
             }
         }
     }
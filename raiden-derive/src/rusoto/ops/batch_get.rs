@@ -5,8 +5,8 @@ use syn::*;
 use crate::rename::*;
 
 pub(crate) fn expand_batch_get(
-    partition_key: &(Ident, Type),
-    sort_key: &Option<(Ident, Type)>,
+    partition_key: &(LitStr, Type),
+    sort_key: &Option<(LitStr, Type)>,
     struct_name: &Ident,
     fields: &FieldsNamed,
     rename_all_type: RenameAllType,
@@ -14,8 +14,7 @@ pub(crate) fn expand_batch_get(
     let trait_name = format_ident!("{}BatchGetItem", struct_name);
     let client_name = format_ident!("{}Client", struct_name);
     let builder_name = format_ident!("{}BatchGetItemBuilder", struct_name);
-    let from_item = super::expand_attr_to_item(format_ident!("res_item"), fields, rename_all_type);
-    let (partition_key_ident, partition_key_type) = partition_key;
+    let (partition_key_name, partition_key_type) = partition_key;
 
     let builder_keys_type = if sort_key.is_none() {
         quote! { std::vec::Vec<::raiden::AttributeValue> }
@@ -29,7 +28,7 @@ pub(crate) fn expand_batch_get(
         let result = create_renamed(ident.to_string(), renamed, rename_all_type);
         quote! {
             names.insert(
-                format!("#{}", #result.clone()),
+                ::raiden::name_placeholder(#result),
                 #result.to_string(),
             );
         }
@@ -49,6 +48,7 @@ pub(crate) fn expand_batch_get(
             keys: key_attrs,
             attribute_names: Some(names),
             projection_expression,
+            return_consumed_capacity: None,
             policy: self.retry_condition.strategy.policy(),
             condition: &self.retry_condition,
         }
@@ -92,12 +92,12 @@ pub(crate) fn expand_batch_get(
     };
 
     let convert_to_external_proc = if let Some(sort_key) = sort_key {
-        let (sort_key_ident, _sort_key_type) = sort_key;
+        let (sort_key_name, _sort_key_type) = sort_key;
         quote! {
             for (pk_attr, sk_attr) in keys.into_iter() {
                 let mut key_val: std::collections::HashMap<String, ::raiden::AttributeValue> = Default::default();
-                key_val.insert(stringify!(#partition_key_ident).to_owned(), pk_attr);
-                key_val.insert(stringify!(#sort_key_ident).to_owned(), sk_attr);
+                key_val.insert(#partition_key_name.to_owned(), pk_attr);
+                key_val.insert(#sort_key_name.to_owned(), sk_attr);
                 item.keys.push(key_val);
             }
         }
@@ -105,7 +105,7 @@ pub(crate) fn expand_batch_get(
         quote! {
             for key_attr in keys.into_iter() {
                 let mut key_val: std::collections::HashMap<String, ::raiden::AttributeValue> = Default::default();
-                key_val.insert(stringify!(#partition_key_ident).to_owned(), key_attr);
+                key_val.insert(#partition_key_name.to_owned(), key_attr);
                 item.keys.push(key_val);
             }
         }
@@ -128,11 +128,12 @@ pub(crate) fn expand_batch_get(
         #client_trait
 
         pub struct #builder_name<'a> {
-            pub client: &'a ::raiden::DynamoDbClient,
+            pub client: &'a ::raiden::SharedBackend,
             pub table_name: String,
             pub keys: #builder_keys_type,
             pub attribute_names: Option<::raiden::AttributeNames>,
             pub projection_expression: Option<String>,
+            pub return_consumed_capacity: Option<::raiden::ReturnConsumedCapacity>,
             pub policy: ::raiden::Policy,
             pub condition: &'a ::raiden::retry::RetryCondition,
         }
@@ -140,14 +141,20 @@ pub(crate) fn expand_batch_get(
         impl<'a> #builder_name<'a> {
 
             #![allow(clippy::field_reassign_with_default)]
+            /// Requests the consumed capacity, summed over every request as `consumed_capacity`.
+            pub fn return_consumed_capacity(mut self, value: ::raiden::ReturnConsumedCapacity) -> Self {
+                self.return_consumed_capacity = Some(value);
+                self
+            }
+
             pub async fn run(self) -> Result<::raiden::batch_get::BatchGetOutput<#struct_name>, ::raiden::RaidenError> {
-                let Self { client, table_name, mut keys, attribute_names, projection_expression, policy, condition } = self;
-                let policy: ::raiden::RetryPolicy = policy.into();
+                let Self { client, table_name, mut keys, attribute_names, projection_expression, return_consumed_capacity, policy, condition } = self;
                 let mut items: std::vec::Vec<#struct_name> = vec![];
                 let mut unprocessed_keys = ::raiden::KeysAndAttributes::default();
 
                 // TODO: for now set 5, however we should make it more flexible.
                 let mut unprocessed_retry = 5;
+                let mut consumed_capacity = None;
                 loop {
                     let mut input = ::raiden::BatchGetItemInput::default();
                     let mut item = ::raiden::KeysAndAttributes::default();
@@ -165,20 +172,20 @@ pub(crate) fn expand_batch_get(
                     }
 
                     input.request_items = Default::default();
+                    input.return_consumed_capacity = return_consumed_capacity.map(|v| v.as_str().to_owned());
                     input
                         .request_items
                         .insert(table_name.to_string(), item);
 
                     let res = {
                         let t = table_name.clone();
-                        let c = client.clone();
                         let i = input.clone();
-                        policy.retry_if(move || {
-                            let (table_name, client, input)
-                                = (t.clone(), c.clone(), i.clone());
+                        ::raiden::retry_with_backend(policy, &client, condition, move |client| {
+                            let (table_name, input) = (t.clone(), i.clone());
                             async move { #call_inner_run }
-                        }, condition).await?
+                        }).await?
                     };
+                    consumed_capacity = ::raiden::capacity::merge_consumed_capacities(consumed_capacity, res.consumed_capacity);
 
                     if keys.is_empty() {
                         unprocessed_retry -= 1;
@@ -188,10 +195,7 @@ pub(crate) fn expand_batch_get(
                         let mut res_responses = res_responses;
                         if let Some(res_items) = (&mut res_responses).remove(&table_name) {
                             for res_item in res_items.into_iter() {
-                                let mut res_item = res_item;
-                                items.push(#struct_name {
-                                    #(#from_item)*
-                                })
+                                items.push(<#struct_name as ::raiden::RaidenItem>::from_item(res_item)?)
                             }
                         } else {
                             return Err(::raiden::RaidenError::ResourceNotFound(format!("'{}' table not found or not active", &table_name)));
@@ -211,7 +215,7 @@ pub(crate) fn expand_batch_get(
 
                     if (keys.is_empty() && unprocessed_keys.keys.is_empty()) || unprocessed_retry == 0 {
                         return Ok(::raiden::batch_get::BatchGetOutput {
-                            consumed_capacity: res.consumed_capacity,
+                            consumed_capacity,
                             items,
                             unprocessed_keys: Some(unprocessed_keys),
                         })
@@ -221,10 +225,10 @@ pub(crate) fn expand_batch_get(
 
             async fn inner_run(
                 #inner_run_args
-                client: ::raiden::DynamoDbClient,
+                client: ::raiden::SharedBackend,
                 input: ::raiden::BatchGetItemInput,
-            ) -> Result<::raiden::BatchGetItemOutput, ::raiden::RaidenError> {
-                Ok(#api_call_token?)
+            ) -> Result<::raiden::BatchGetItemResponse, ::raiden::RaidenError> {
+                #api_call_token
             }
         }
     }
@@ -6,12 +6,15 @@ pub(crate) fn expand_batch_put(
     struct_name: &Ident,
     fields: &FieldsNamed,
     rename_all_type: RenameAllType,
+    timestamps: &crate::timestamp::Timestamps,
+    key_templates: &crate::key_template::KeyTemplates,
 ) -> proc_macro2::TokenStream {
     let item_input_name = format_ident!("{}PutItemInput", struct_name);
     let trait_name = format_ident!("{}BatchPut", struct_name);
     let client_name = format_ident!("{}Client", struct_name);
     let builder_name = format_ident!("{}BatchPutBuilder", struct_name);
 
+    let key_insertion = key_templates.expand_insertion(quote! { item });
     let input_items = {
         let insertion = fields.named.iter().map(|f| {
             let ident = &f.ident.clone().unwrap();
@@ -22,6 +25,10 @@ pub(crate) fn expand_batch_put(
                     let id = #struct_name::gen();
                     input_item.insert(#attr_key.to_string(), id.into_attr());
                 }
+            } else if let Some(value) = timestamps.expand_value(f) {
+                quote! {
+                    input_item.insert(#attr_key.to_string(), #value.into_attr());
+                }
             } else {
                 quote! {
                     let value = item.#ident.clone().into_attr();
@@ -34,9 +41,11 @@ pub(crate) fn expand_batch_put(
 
         quote! {
             let mut input_item: std::collections::HashMap<String, ::raiden::AttributeValue> = std::collections::HashMap::new();
+            #key_insertion
             #(#insertion)*
         }
     };
+    let now = timestamps.expand_now(struct_name);
 
     let api_call_token = super::api_call_token!("batch_write_item");
     let (call_inner_run, inner_run_args) = if cfg!(feature = "tracing") {
@@ -58,10 +67,15 @@ pub(crate) fn expand_batch_put(
 
         impl #trait_name for #client_name {
             fn batch_put(&self, items: std::vec::Vec<#item_input_name>) -> #builder_name {
+                #now
+                let mut error = None;
                 let write_requests = {
                     let mut write_requests = vec![];
                     for item in items.into_iter() {
                         #input_items
+                        if error.is_none() {
+                            error = key_error;
+                        }
 
                         let mut write_request = ::raiden::WriteRequest::default();
                         write_request.put_request = Some(::raiden::PutRequest {
@@ -77,6 +91,8 @@ pub(crate) fn expand_batch_put(
                     client: &self.client,
                     write_requests,
                     table_name: self.table_name(),
+                    return_consumed_capacity: None,
+                    key_error: error,
                     policy: self.retry_condition.strategy.policy(),
                     condition: &self.retry_condition,
                 }
@@ -84,21 +100,32 @@ pub(crate) fn expand_batch_put(
         }
 
         pub struct #builder_name<'a> {
-            pub client: &'a ::raiden::DynamoDbClient,
+            pub client: &'a ::raiden::SharedBackend,
             pub write_requests: std::vec::Vec<::raiden::WriteRequest>,
             pub table_name: String,
+            pub return_consumed_capacity: Option<::raiden::ReturnConsumedCapacity>,
             pub policy: ::raiden::Policy,
             pub condition: &'a ::raiden::retry::RetryCondition,
+            key_error: Option<::raiden::RaidenError>,
         }
 
         impl<'a> #builder_name<'a> {
+            /// Requests the consumed capacity, summed over every request as `consumed_capacity`.
+            pub fn return_consumed_capacity(mut self, value: ::raiden::ReturnConsumedCapacity) -> Self {
+                self.return_consumed_capacity = Some(value);
+                self
+            }
+
             pub async fn run(self) -> Result<::raiden::batch_put::BatchPutOutput, ::raiden::RaidenError> {
-                let Self { client, mut write_requests, table_name, policy, condition } = self;
-                let policy: ::raiden::RetryPolicy = policy.into();
+                let Self { client, mut write_requests, table_name, return_consumed_capacity, policy, condition, key_error } = self;
+                if let Some(e) = key_error {
+                    return Err(e);
+                }
 
                 const RETRY: usize = 5;
                 const MAX_ITEMS_PER_REQUEST: usize = 25;
 
+                let mut consumed_capacity = None;
                 for _ in 0..RETRY {
                     loop {
                         let len = write_requests.len();
@@ -114,17 +141,18 @@ pub(crate) fn expand_batch_put(
                             .collect::<std::collections::HashMap<_, _>>();
                         let result = {
                             let t = table_name.clone();
-                            let c = client.clone();
                             let i = ::raiden::BatchWriteItemInput {
                                 request_items,
+                                return_consumed_capacity: return_consumed_capacity.map(|v| v.as_str().to_owned()),
                                 ..std::default::Default::default()
                             };
 
-                            policy.retry_if(move || {
-                                let (table_name, client, input) = (t.clone(), c.clone(), i.clone());
+                            ::raiden::retry_with_backend(policy, &client, condition, move |client| {
+                                let (table_name, input) = (t.clone(), i.clone());
                                 async move { #call_inner_run }
-                            }, condition).await?
+                            }).await?
                         };
+                        consumed_capacity = ::raiden::capacity::merge_consumed_capacities(consumed_capacity, result.consumed_capacity);
 
                         let mut unprocessed_items = match result.unprocessed_items {
                             None => continue,
@@ -135,7 +163,7 @@ pub(crate) fn expand_batch_put(
                         let unprocessed_requests = unprocessed_items
                             .remove(&table_name)
                             .expect("request_items hashmap must have a value for the table name");
-                        write_requests.extend(unprocessed_requests);
+                        write_requests.extend(unprocessed_requests.into_iter().map(Into::into));
                     }
                 }
 
@@ -144,17 +172,17 @@ pub(crate) fn expand_batch_put(
                     .filter_map(|write_request| write_request.put_request)
                     .collect::<std::vec::Vec<_>>();
                 Ok(::raiden::batch_put::BatchPutOutput {
-                    consumed_capacity: None,
+                    consumed_capacity,
                     unprocessed_items,
                 })
             }
 
             async fn inner_run(
                 #inner_run_args
-                client: ::raiden::DynamoDbClient,
+                client: ::raiden::SharedBackend,
                 input: ::raiden::BatchWriteItemInput,
-            ) -> Result<::raiden::BatchWriteItemOutput, ::raiden::RaidenError> {
-                Ok(#api_call_token?)
+            ) -> Result<::raiden::BatchWriteItemResponse, ::raiden::RaidenError> {
+                #api_call_token
             }
         }
     }
@@ -3,38 +3,46 @@ use quote::*;
 use syn::*;
 
 pub(crate) fn expand_delete_item(
-    partition_key: &(Ident, Type),
-    sort_key: &Option<(Ident, Type)>,
+    partition_key: &(LitStr, Type),
+    sort_key: &Option<(LitStr, Type)>,
     struct_name: &Ident,
+    version: &Option<crate::version::VersionField>,
 ) -> TokenStream {
     let trait_name = format_ident!("{}DeleteItem", struct_name);
     let client_name = format_ident!("{}Client", struct_name);
     let builder_name = format_ident!("{}DeleteItemBuilder", struct_name);
     let condition_token_name = format_ident!("{}ConditionToken", struct_name);
-    let (partition_key_ident, partition_key_type) = partition_key;
+    let (partition_key_name, partition_key_type) = partition_key;
+    // A versioned model is deleted only if the stored version equals `expected_version`.
+    let expected_version_arg = crate::version::expand_expected_version_arg(version);
+    let expected_version_condition = crate::version::expand_expected_version_condition(version);
 
     let client_trait = if let Some(sort_key) = sort_key {
-        let (sort_key_ident, sort_key_type) = sort_key;
+        let (sort_key_name, sort_key_type) = sort_key;
         quote! {
             pub trait #trait_name {
-                fn delete(&self, pk: impl Into<#partition_key_type>, sk: impl Into<#sort_key_type>) -> #builder_name;
+                fn delete(&self, pk: impl Into<#partition_key_type>, sk: impl Into<#sort_key_type> #expected_version_arg) -> #builder_name;
             }
 
             impl #trait_name for #client_name {
-                fn delete(&self, pk: impl Into<#partition_key_type>, sk: impl Into<#sort_key_type>) -> #builder_name {
+                fn delete(&self, pk: impl Into<#partition_key_type>, sk: impl Into<#sort_key_type> #expected_version_arg) -> #builder_name {
                     let mut input = ::raiden::DeleteItemInput::default();
                     let pk_attr: ::raiden::AttributeValue = pk.into().into_attr();
                     let sk_attr: ::raiden::AttributeValue = sk.into().into_attr();
                     let mut key_set: std::collections::HashMap<String, ::raiden::AttributeValue> = std::collections::HashMap::new();
-                    key_set.insert(stringify!(#partition_key_ident).to_owned(), pk_attr);
-                    key_set.insert(stringify!(#sort_key_ident).to_owned(), sk_attr);
+                    key_set.insert(#partition_key_name.to_owned(), pk_attr);
+                    key_set.insert(#sort_key_name.to_owned(), sk_attr);
                     input.key = key_set;
                     input.table_name = self.table_name();
+                    #expected_version_condition
+
                     #builder_name {
                         client: &self.client,
                         input,
                         policy: self.retry_condition.strategy.policy(),
                         condition: &self.retry_condition,
+                        version_condition,
+                        placeholders,
                     }
                 }
             }
@@ -42,22 +50,26 @@ pub(crate) fn expand_delete_item(
     } else {
         quote! {
             pub trait #trait_name {
-                fn delete(&self, key: impl Into<#partition_key_type>) -> #builder_name;
+                fn delete(&self, key: impl Into<#partition_key_type> #expected_version_arg) -> #builder_name;
             }
 
             impl #trait_name for #client_name {
-                fn delete(&self, key: impl Into<#partition_key_type>) -> #builder_name {
+                fn delete(&self, key: impl Into<#partition_key_type> #expected_version_arg) -> #builder_name {
                     let mut input = ::raiden::DeleteItemInput::default();
                     let key_attr: ::raiden::AttributeValue = key.into().into_attr();
                     let mut key_set: std::collections::HashMap<String, ::raiden::AttributeValue> = std::collections::HashMap::new();
-                    key_set.insert(stringify!(#partition_key_ident).to_owned(), key_attr);
+                    key_set.insert(#partition_key_name.to_owned(), key_attr);
                     input.key = key_set;
                     input.table_name = self.table_name();
+                    #expected_version_condition
+
                     #builder_name {
                         client: &self.client,
                         input,
                         policy: self.retry_condition.strategy.policy(),
                         condition: &self.retry_condition,
+                        version_condition,
+                        placeholders,
                     }
                 }
             }
@@ -81,10 +93,12 @@ pub(crate) fn expand_delete_item(
         #client_trait
 
         pub struct #builder_name<'a> {
-            pub client: &'a ::raiden::DynamoDbClient,
+            pub client: &'a ::raiden::SharedBackend,
             pub input: ::raiden::DeleteItemInput,
             pub policy: ::raiden::Policy,
             pub condition: &'a ::raiden::retry::RetryCondition,
+            pub version_condition: Option<::raiden::version::VersionCondition>,
+            pub placeholders: ::raiden::Placeholders,
         }
 
         impl<'a> #builder_name<'a> {
@@ -94,7 +108,7 @@ pub(crate) fn expand_delete_item(
             }
 
             pub fn condition(mut self, cond: impl ::raiden::condition::ConditionBuilder<#condition_token_name>) -> Self {
-                let (cond_str, attr_names, attr_values) = cond.build();
+                let (cond_str, attr_names, attr_values) = cond.build_with(&mut self.placeholders);
                 if !attr_names.is_empty() {
                     self.input.expression_attribute_names = Some(attr_names);
                 }
@@ -105,25 +119,68 @@ pub(crate) fn expand_delete_item(
                 self
             }
 
-            pub async fn run(self) -> Result<(), ::raiden::RaidenError> {
-                let Self { client, input, policy, condition } = self;
-                let policy: ::raiden::RetryPolicy = policy.into();
-                policy.retry_if(move || {
-                    let client = client.clone();
+            /// Returns the deleted item as `old_item`.
+            pub fn return_old(mut self) -> Self {
+                self.input.return_values = Some("ALL_OLD".to_owned());
+                self
+            }
+
+            /// Requests the consumed capacity, returned as `consumed_capacity`.
+            pub fn return_consumed_capacity(mut self, value: ::raiden::ReturnConsumedCapacity) -> Self {
+                self.input.return_consumed_capacity = Some(value.as_str().to_owned());
+                self
+            }
+
+            /// Requests the size of the modified item collection, returned as `item_collection_metrics`.
+            pub fn return_item_collection_metrics(mut self, value: ::raiden::ReturnItemCollectionMetrics) -> Self {
+                self.input.return_item_collection_metrics = Some(value.as_str().to_owned());
+                self
+            }
+
+            pub async fn run(self) -> Result<::raiden::delete::DeleteOutput<#struct_name>, ::raiden::RaidenError> {
+                let Self { client, mut input, policy, condition, version_condition, .. } = self;
+                if let Some(version_condition) = &version_condition {
+                    let (expression, names, values) = ::raiden::version::apply_version_condition(
+                        input.condition_expression.take(),
+                        input.expression_attribute_names.take(),
+                        input.expression_attribute_values.take(),
+                        &version_condition,
+                    );
+                    input.condition_expression = Some(expression);
+                    input.expression_attribute_names = names;
+                    input.expression_attribute_values = values;
+                }
+
+                let (table_name, key) = (input.table_name.clone(), input.key.clone());
+                let res = ::raiden::retry_with_backend(policy, &client, condition, move |client| {
                     let input = input.clone();
                     async { #call_inner_run }
-                }, condition).await?;
+                }).await;
+                let res = match (res, &version_condition) {
+                    (Err(e), Some(version_condition)) => {
+                        return Err(::raiden::version::into_version_conflict(e, version_condition, &client, table_name, key).await);
+                    }
+                    (res, _) => res?,
+                };
+
+                let old_item = match res.attributes {
+                    Some(item) => Some(<#struct_name as ::raiden::RaidenItem>::from_item(item)?),
+                    None => None,
+                };
 
-                Ok(())
+                Ok(::raiden::delete::DeleteOutput {
+                    consumed_capacity: res.consumed_capacity,
+                    old_item,
+                    item_collection_metrics: res.item_collection_metrics,
+                })
             }
 
             async fn inner_run(
                 #inner_run_args
-                client: ::raiden::DynamoDbClient,
+                client: ::raiden::SharedBackend,
                 input: ::raiden::DeleteItemInput,
-            ) -> Result<(), ::raiden::RaidenError> {
-                #api_call_token?;
-                Ok(())
+            ) -> Result<::raiden::DeleteItemResponse, ::raiden::RaidenError> {
+                #api_call_token
             }
         }
     }
@@ -2,12 +2,19 @@ use crate::rename::*;
 use proc_macro2::*;
 use quote::*;
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn expand_put_item(
+    partition_key: &(syn::LitStr, syn::Type),
+    sort_key: &Option<(syn::LitStr, syn::Type)>,
     struct_name: &Ident,
     fields: &syn::FieldsNamed,
     rename_all_type: crate::rename::RenameAllType,
+    version: &Option<crate::version::VersionField>,
+    timestamps: &crate::timestamp::Timestamps,
+    key_templates: &crate::key_template::KeyTemplates,
 ) -> TokenStream {
     let item_input_name = format_ident!("{}PutItemInput", struct_name);
+    let key_names = std::iter::once(&partition_key.0).chain(sort_key.iter().map(|(name, _)| name));
     let item_input_builder_name = format_ident!("{}PutItemInputBuilder", struct_name);
     let item_output_name = format_ident!("{}PutItemOutput", struct_name);
     let trait_name = format_ident!("{}PutItem", struct_name);
@@ -19,6 +26,7 @@ pub(crate) fn expand_put_item(
         .named
         .iter()
         .filter(|f| !crate::finder::include_unary_attr(&f.attrs, "uuid"))
+        .filter(|f| !timestamps.is(f))
         .map(|f| {
             let ident = &f.ident.clone().unwrap();
             let ty = &f.ty;
@@ -43,6 +51,14 @@ pub(crate) fn expand_put_item(
             quote! {
                 #ident: uuid_map.get(#attr_key).cloned().unwrap().into(),
             }
+        } else if version.as_ref().is_some_and(|version| version.is(f)) {
+            quote! {
+                #ident: next_version.unwrap_or(item.#ident),
+            }
+        } else if let Some(value) = timestamps.expand_value(f) {
+            quote! {
+                #ident: #value,
+            }
         } else {
             quote! {
                 #ident: item.#ident,
@@ -50,6 +66,8 @@ pub(crate) fn expand_put_item(
         }
     });
 
+    let key_insertion = key_templates.expand_insertion(quote! { item });
+    let version_overflow = crate::version::expand_version_overflow(version);
     let input_items = {
         let insertion = fields.named.iter().map(|f| {
             let ident = &f.ident.clone().unwrap();
@@ -67,6 +85,22 @@ pub(crate) fn expand_put_item(
                         id,
                     );
                 }
+            } else if version.as_ref().is_some_and(|version| version.is(f)) {
+                quote! {
+                    if let Some(next_version) = next_version {
+                        input_item.insert(
+                            #attr_key.to_string(),
+                            next_version.into_attr(),
+                        );
+                    }
+                }
+            } else if let Some(value) = timestamps.expand_value(f) {
+                quote! {
+                    input_item.insert(
+                        #attr_key.to_string(),
+                        #value.into_attr(),
+                    );
+                }
             } else {
                 quote! {
                     let value = item.#ident.clone().into_attr();
@@ -82,6 +116,8 @@ pub(crate) fn expand_put_item(
 
         quote! {
             let mut input_item: std::collections::HashMap<String, raiden::AttributeValue> = std::collections::HashMap::new();
+            #key_insertion
+            #version_overflow
             #(#insertion)*
         }
     };
@@ -91,11 +127,16 @@ pub(crate) fn expand_put_item(
         .named
         .iter()
         .filter(|f| !crate::finder::include_unary_attr(&f.attrs, "uuid"))
+        .filter(|f| !timestamps.is(f))
         .filter(|f| !crate::finder::is_option(&f.ty))
         .map(|f| f.ident.clone().unwrap())
         .collect();
     let default_types = expand_default_type_variables(&required_field_idents);
 
+    let version_condition = crate::version::expand_put_version(version);
+
+    let now = timestamps.expand_now(struct_name);
+
     let api_call_token = super::api_call_token!("put_item");
     let (call_inner_run, inner_run_args) = if cfg!(feature = "tracing") {
         (
@@ -138,6 +179,8 @@ pub(crate) fn expand_put_item(
                 // let mut attribute_values: std::collections::HashMap<String, raiden::AttributeValue> = std::collections::HashMap::new();
                 let mut uuid_map: std::collections::HashMap<String, String> = std::collections::HashMap::new();
 
+                #version_condition
+                #now
                 #input_items
 
                 let output_item = #item_output_name {
@@ -169,16 +212,22 @@ pub(crate) fn expand_put_item(
                     item: output_item,
                     policy: self.retry_condition.strategy.policy(),
                     condition: &self.retry_condition,
+                    version_condition,
+                    placeholders,
+                    key_error,
                 }
             }
         }
 
         pub struct #builder_name<'a> {
-            pub client: &'a ::raiden::DynamoDbClient,
+            pub client: &'a ::raiden::SharedBackend,
             pub input: ::raiden::PutItemInput,
             pub item: #item_output_name,
             pub policy: ::raiden::Policy,
             pub condition: &'a ::raiden::retry::RetryCondition,
+            pub version_condition: Option<::raiden::version::VersionCondition>,
+            pub placeholders: ::raiden::Placeholders,
+            key_error: Option<::raiden::RaidenError>,
         }
 
         impl<'a> #builder_name<'a> {
@@ -189,7 +238,7 @@ pub(crate) fn expand_put_item(
             }
 
             pub fn condition(mut self, cond: impl ::raiden::condition::ConditionBuilder<#condition_token_name>) -> Self {
-                let (cond_str, attr_names, attr_values) = cond.build();
+                let (cond_str, attr_names, attr_values) = cond.build_with(&mut self.placeholders);
                 if !attr_names.is_empty() {
                     self.input.expression_attribute_names = Some(attr_names);
                 }
@@ -200,29 +249,80 @@ pub(crate) fn expand_put_item(
                 self
             }
 
-            pub async fn run(self) -> Result<::raiden::put::PutOutput<#item_output_name>, ::raiden::RaidenError> {
-                let input = self.input.clone();
+            /// Returns the replaced item as `old_item`.
+            pub fn return_old(mut self) -> Self {
+                self.input.return_values = Some("ALL_OLD".to_owned());
+                self
+            }
+
+            /// Requests the consumed capacity, returned as `consumed_capacity`.
+            pub fn return_consumed_capacity(mut self, value: ::raiden::ReturnConsumedCapacity) -> Self {
+                self.input.return_consumed_capacity = Some(value.as_str().to_owned());
+                self
+            }
+
+            /// Requests the size of the modified item collection, returned as `item_collection_metrics`.
+            pub fn return_item_collection_metrics(mut self, value: ::raiden::ReturnItemCollectionMetrics) -> Self {
+                self.input.return_item_collection_metrics = Some(value.as_str().to_owned());
+                self
+            }
+
+            pub async fn run(self) -> Result<::raiden::put::PutOutput<#item_output_name, #struct_name>, ::raiden::RaidenError> {
+                if let Some(e) = self.key_error {
+                    return Err(e);
+                }
+
+                let mut input = self.input.clone();
+                let version_condition = self.version_condition;
+                if let Some(version_condition) = &version_condition {
+                    let (expression, names, values) = ::raiden::version::apply_version_condition(
+                        input.condition_expression.take(),
+                        input.expression_attribute_names.take(),
+                        input.expression_attribute_values.take(),
+                        &version_condition,
+                    );
+                    input.condition_expression = Some(expression);
+                    input.expression_attribute_names = names;
+                    input.expression_attribute_values = values;
+                }
+
                 let client = self.client.clone();
-                let policy: ::raiden::RetryPolicy = self.policy.into();
 
-                let res = policy.retry_if(move || {
+                let res = ::raiden::retry_with_backend(self.policy, &client, self.condition, move |client| {
                     let input = input.clone();
-                    let client = client.clone();
                     async { #call_inner_run }
-                }, self.condition).await?;
+                }).await;
+                let res = match (res, &version_condition) {
+                    (Err(e), Some(version_condition)) => {
+                        let key: ::raiden::AttributeValues = [#(#key_names),*]
+                            .into_iter()
+                            .filter_map(|name| Some((name.to_owned(), self.input.item.get(name)?.clone())))
+                            .collect();
+                        let table_name = self.input.table_name.clone();
+                        return Err(::raiden::version::into_version_conflict(e, version_condition, &client, table_name, key).await);
+                    }
+                    (res, _) => res?,
+                };
+
+                let old_item = match res.attributes {
+                    Some(item) => Some(<#struct_name as ::raiden::RaidenItem>::from_item(item)?),
+                    None => None,
+                };
 
                 Ok(::raiden::put::PutOutput {
                     item: self.item,
                     consumed_capacity: res.consumed_capacity,
+                    old_item,
+                    item_collection_metrics: res.item_collection_metrics,
                 })
             }
 
             async fn inner_run(
                 #inner_run_args
-                client: ::raiden::DynamoDbClient,
+                client: ::raiden::SharedBackend,
                 input: ::raiden::PutItemInput,
-            ) -> Result<::raiden::PutItemOutput, ::raiden::RaidenError> {
-                Ok(#api_call_token?)
+            ) -> Result<::raiden::PutItemResponse, ::raiden::RaidenError> {
+                #api_call_token
             }
         }
     }
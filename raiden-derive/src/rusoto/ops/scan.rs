@@ -6,11 +6,13 @@ pub(crate) fn expand_scan(
     _fields: &syn::FieldsNamed,
     _rename_all_type: crate::rename::RenameAllType,
     gsi_names: &[String],
+    ttl: &Option<crate::ttl::TtlField>,
 ) -> proc_macro2::TokenStream {
     let trait_name = format_ident!("{}Scan", struct_name);
     let client_name = format_ident!("{}Client", struct_name);
     let builder_name = format_ident!("{}ScanBuilder", struct_name);
     let projected_builder_name = format_ident!("{}ProjectedScanBuilder", struct_name);
+    let parallel_builder_name = format_ident!("{}ParallelScanBuilder", struct_name);
 
     let filter_expression_token_name = format_ident!("{}FilterExpressionToken", struct_name);
     let gsi_methods = gsi_names.iter().map(|index_name| {
@@ -27,6 +29,8 @@ pub(crate) fn expand_scan(
             }
         }
     });
+    let (filter_expired, projected_filter_expired) = expand_filter_expired(struct_name, ttl);
+
     let api_call_token = super::api_call_token!("scan");
     let (call_inner_run, inner_run_args) = if cfg!(feature = "tracing") {
         (
@@ -46,10 +50,12 @@ pub(crate) fn expand_scan(
         }
 
         pub struct #builder_name<'a> {
-            pub client: &'a ::raiden::DynamoDbClient,
+            pub client: &'a ::raiden::SharedBackend,
             pub input: ::raiden::ScanInput,
             pub policy: ::raiden::Policy,
             pub condition: &'a ::raiden::retry::RetryCondition,
+            pub expired_filter: Option<::raiden::ttl::ExpiredFilter>,
+            pub placeholders: ::raiden::Placeholders,
             pub next_token: Option<::raiden::NextToken>,
             pub limit: Option<i64>,
         }
@@ -63,6 +69,14 @@ pub(crate) fn expand_scan(
             pub _item: std::marker::PhantomData<fn() -> I>,
         }
 
+        /// A scan builder which splits the table into segments and scans them concurrently.
+        pub struct #parallel_builder_name<'a, I = #struct_name> {
+            pub inner: #builder_name<'a>,
+            pub segments: Vec<::raiden::scan::ScanSegment>,
+            pub concurrency: usize,
+            pub _item: std::marker::PhantomData<fn() -> I>,
+        }
+
         impl #trait_name for #client_name {
             #![allow(clippy::field_reassign_with_default)]
             fn scan(&self) -> #builder_name {
@@ -75,6 +89,8 @@ pub(crate) fn expand_scan(
                     input,
                     policy: self.retry_condition.strategy.policy(),
                     condition: &self.retry_condition,
+                    expired_filter: None,
+                    placeholders: ::raiden::Placeholders::new(),
                     next_token: None,
                     limit: None,
                 }
@@ -112,8 +128,14 @@ pub(crate) fn expand_scan(
                 self
             }
 
+            /// Requests the consumed capacity, summed over every page as `consumed_capacity`.
+            pub fn return_consumed_capacity(mut self, value: ::raiden::ReturnConsumedCapacity) -> Self {
+                self.input.return_consumed_capacity = Some(value.as_str().to_owned());
+                self
+            }
+
             pub fn filter(mut self, cond: impl ::raiden::filter_expression::FilterExpressionBuilder<#filter_expression_token_name>) -> Self {
-                let (cond_str, attr_names, attr_values) = cond.build();
+                let (cond_str, attr_names, attr_values) = cond.build_with(&mut self.placeholders);
                 if !attr_names.is_empty() {
                     if let Some(v) = self.input.expression_attribute_names {
                         self.input.expression_attribute_names = Some(::raiden::merge_map(attr_names, v));
@@ -129,6 +151,23 @@ pub(crate) fn expand_scan(
                     }
                 }
                 self.input.filter_expression = Some(cond_str);
+                self.with_expired_filter()
+            }
+
+            #filter_expired
+
+            fn with_expired_filter(mut self) -> Self {
+                if let Some(expired_filter) = self.expired_filter.clone() {
+                    let (expression, names, values) = ::raiden::ttl::apply_expired_filter(
+                        self.input.filter_expression.take(),
+                        self.input.expression_attribute_names.take(),
+                        self.input.expression_attribute_values.take(),
+                        expired_filter,
+                    );
+                    self.input.filter_expression = Some(expression);
+                    self.input.expression_attribute_names = names;
+                    self.input.expression_attribute_values = values;
+                }
                 self
             }
 
@@ -142,6 +181,54 @@ pub(crate) fn expand_scan(
                 self
             }
 
+            /// Scans only the given segment of a parallel scan.
+            pub fn segment(mut self, segment: usize, total_segments: usize) -> Self {
+                self.input.segment = Some(segment as i64);
+                self.input.total_segments = Some(total_segments as i64);
+                self
+            }
+
+            /// Splits the scan into `total_segments` segments which are scanned concurrently.
+            ///
+            /// Running it fails with `RaidenError::Validation` if `total_segments` is zero.
+            pub fn parallel(self, total_segments: usize) -> #parallel_builder_name<'a, #struct_name> {
+                self.parallel_inner::<#struct_name>(total_segments)
+            }
+
+            fn parallel_inner<I>(self, total_segments: usize) -> #parallel_builder_name<'a, I> {
+                let segments = (0..total_segments as i64)
+                    .map(|segment| ::raiden::scan::ScanSegment {
+                        segment,
+                        total_segments: total_segments as i64,
+                        state: ::raiden::scan::ScanSegmentState::Pending,
+                    })
+                    .collect();
+
+                #parallel_builder_name {
+                    inner: self,
+                    segments,
+                    concurrency: total_segments,
+                    _item: std::marker::PhantomData,
+                }
+            }
+
+            fn for_segment(&self, segment: &::raiden::scan::ScanSegment) -> Self {
+                let mut input = self.input.clone();
+                input.segment = Some(segment.segment);
+                input.total_segments = Some(segment.total_segments);
+
+                Self {
+                    client: self.client,
+                    input,
+                    policy: self.policy,
+                    condition: self.condition,
+                    expired_filter: self.expired_filter.clone(),
+                    placeholders: self.placeholders.clone(),
+                    next_token: segment.next_token().cloned(),
+                    limit: self.limit,
+                }
+            }
+
             /// Runs the scan and decodes items into the builder output type.
             pub async fn run(self) -> Result<::raiden::scan::ScanOutput<#struct_name>, ::raiden::RaidenError> {
                 self.run_inner::<#struct_name>().await
@@ -157,18 +244,65 @@ pub(crate) fn expand_scan(
                 self.project::<I>().run().await
             }
 
+            /// Returns a stream which lazily fetches each page of the scan.
+            ///
+            /// The retry policy is applied to every page request.
+            pub fn pages(self) -> impl ::raiden::futures::Stream<Item = Result<::raiden::scan::ScanOutput<#struct_name>, ::raiden::RaidenError>> + 'a {
+                self.pages_inner::<#struct_name>()
+            }
+
+            /// Returns a stream of items which follows `LastEvaluatedKey` until the scan is exhausted.
+            pub fn into_stream(self) -> impl ::raiden::futures::Stream<Item = Result<#struct_name, ::raiden::RaidenError>> + 'a {
+                ::raiden::pagination::items(self.pages())
+            }
+
+            fn pages_inner<I>(self) -> impl ::raiden::futures::Stream<Item = Result<::raiden::scan::ScanOutput<I>, ::raiden::RaidenError>> + 'a
+            where
+                I: ::raiden::RaidenItem + 'a,
+            {
+                let Self { client, input, next_token, limit, policy, condition, .. } = self;
+
+                ::raiden::pagination::paginate(next_token, limit, move |exclusive_start_key, limit| {
+                    let mut input = input.clone();
+                    input.exclusive_start_key = exclusive_start_key;
+                    input.limit = limit;
+
+                    async move {
+                        let res = ::raiden::retry_with_backend(policy, &client, condition, move |client| {
+                            let input = input.clone();
+                            async { #call_inner_run }
+                        }).await?;
+
+                        let mut items: Vec<I> = vec![];
+                        if let Some(res_items) = res.items {
+                            for res_item in res_items.into_iter() {
+                                items.push(I::from_item(res_item)?)
+                            }
+                        };
+
+                        Ok(::raiden::scan::ScanOutput {
+                            consumed_capacity: res.consumed_capacity,
+                            count: res.count,
+                            items,
+                            last_evaluated_key: res.last_evaluated_key,
+                            scanned_count: res.scanned_count,
+                        })
+                    }
+                })
+            }
+
             async fn run_inner<I>(self) -> Result<::raiden::scan::ScanOutput<I>, ::raiden::RaidenError>
             where
                 I: ::raiden::RaidenItem,
             {
                 let Self { client, mut input, next_token, mut limit, policy, condition, .. } = self;
-                let policy: ::raiden::RetryPolicy = policy.into();
 
                 if let Some(token) = next_token {
                     input.exclusive_start_key = Some(token.into_attr_values()?);
                 }
 
                 let mut items: Vec<I> = vec![];
+                let mut consumed_capacity = None;
 
                 loop {
                     if let Some(limit) = limit {
@@ -176,12 +310,11 @@ pub(crate) fn expand_scan(
                     }
 
                     let res = {
-                        let c = client.clone();
                         let i = input.clone();
-                        policy.retry_if(move || {
-                            let (client, input) = (c.clone(), i.clone());
+                        ::raiden::retry_with_backend(policy, &client, condition, move |client| {
+                            let input = i.clone();
                             async move { #call_inner_run }
-                        }, condition).await?
+                        }).await?
                     };
 
                     if let Some(res_items) = res.items {
@@ -190,6 +323,8 @@ pub(crate) fn expand_scan(
                         }
                     };
 
+                    consumed_capacity = ::raiden::capacity::add_consumed_capacity(consumed_capacity, res.consumed_capacity);
+
                     let scanned = &res.scanned_count.unwrap_or(0);
 
                     let mut has_next = true;
@@ -199,7 +334,7 @@ pub(crate) fn expand_scan(
                     }
                     if res.last_evaluated_key.is_none() || !has_next {
                         return Ok(::raiden::scan::ScanOutput {
-                            consumed_capacity: res.consumed_capacity,
+                            consumed_capacity,
                             count: res.count,
                             items,
                             last_evaluated_key: res.last_evaluated_key,
@@ -212,10 +347,10 @@ pub(crate) fn expand_scan(
 
             async fn inner_run(
                 #inner_run_args
-                client: ::raiden::DynamoDbClient,
+                client: ::raiden::SharedBackend,
                 input: ::raiden::ScanInput,
-            ) -> Result<::raiden::ScanOutput, ::raiden::RaidenError> {
-                Ok(#api_call_token?)
+            ) -> Result<::raiden::ScanResponse, ::raiden::RaidenError> {
+                #api_call_token
             }
         }
 
@@ -237,12 +372,20 @@ pub(crate) fn expand_scan(
                 self
             }
 
+            /// Requests the consumed capacity, summed over every page as `consumed_capacity`.
+            pub fn return_consumed_capacity(mut self, value: ::raiden::ReturnConsumedCapacity) -> Self {
+                self.inner = self.inner.return_consumed_capacity(value);
+                self
+            }
+
             /// Applies a filter expression while preserving the projection type.
             pub fn filter(mut self, cond: impl ::raiden::filter_expression::FilterExpressionBuilder<#filter_expression_token_name>) -> Self {
                 self.inner = self.inner.filter(cond);
                 self
             }
 
+            #projected_filter_expired
+
             /// Sets the pagination token used to resume the scan.
             pub fn next_token(mut self, token: ::raiden::NextToken) -> Self {
                 self.inner = self.inner.next_token(token);
@@ -255,6 +398,11 @@ pub(crate) fn expand_scan(
                 self
             }
 
+            /// Splits the scan into `total_segments` segments decoded into the projection item type.
+            pub fn parallel(self, total_segments: usize) -> #parallel_builder_name<'a, I> {
+                self.inner.parallel_inner::<I>(total_segments)
+            }
+
             /// Runs the scan and decodes items into the projection item type.
             pub async fn run(self) -> Result<::raiden::scan::ScanOutput<I>, ::raiden::RaidenError> {
                 self.inner.run_inner::<I>().await
@@ -267,6 +415,72 @@ pub(crate) fn expand_scan(
             {
                 self.project::<J>().run().await
             }
+
+            /// Returns a stream which lazily fetches each page decoded into the projection item type.
+            pub fn pages(self) -> impl ::raiden::futures::Stream<Item = Result<::raiden::scan::ScanOutput<I>, ::raiden::RaidenError>> + 'a
+            where
+                I: 'a,
+            {
+                self.inner.pages_inner::<I>()
+            }
+
+            /// Returns a stream of projection items which follows `LastEvaluatedKey` until the scan is exhausted.
+            pub fn into_stream(self) -> impl ::raiden::futures::Stream<Item = Result<I, ::raiden::RaidenError>> + 'a
+            where
+                I: 'a,
+            {
+                ::raiden::pagination::items(self.pages())
+            }
+        }
+
+        impl<'a, I> #parallel_builder_name<'a, I>
+        where
+            I: ::raiden::RaidenItem + 'a,
+        {
+            /// Limits the number of segments scanned at the same time. Defaults to `total_segments`.
+            pub fn concurrency(mut self, concurrency: usize) -> Self {
+                self.concurrency = concurrency;
+                self
+            }
+
+            /// Limits the number of items evaluated in each segment.
+            pub fn limit(mut self, limit: usize) -> Self {
+                self.inner = self.inner.limit(limit);
+                self
+            }
+
+            /// Continues an interrupted scan from the segments of a previous run.
+            ///
+            /// Segments which have already been scanned to the end are skipped,
+            /// but are still returned by `run`.
+            pub fn resume(mut self, segments: impl IntoIterator<Item = ::raiden::scan::ScanSegment>) -> Self {
+                self.segments = segments.into_iter().collect();
+                self
+            }
+
+            /// Runs all segments and merges their items in segment order.
+            ///
+            /// If a segment fails, the error holds the items scanned so far and the
+            /// position of every segment, which can be passed to `resume`.
+            pub async fn run(self) -> Result<::raiden::scan::ParallelScanOutput<I>, ::raiden::scan::ParallelScanError<I>> {
+                let Self { inner, segments, concurrency, .. } = self;
+                ::raiden::pagination::run_segments(segments, concurrency, |segment| {
+                    inner.for_segment(&segment).run_inner::<I>()
+                }).await
+            }
+
+            /// Returns a stream of pages from all segments in the order they are fetched.
+            pub fn pages(self) -> impl ::raiden::futures::Stream<Item = Result<::raiden::scan::ParallelScanPage<I>, ::raiden::RaidenError>> + 'a {
+                let Self { inner, segments, concurrency, .. } = self;
+                ::raiden::pagination::segment_pages(segments, concurrency, move |segment| {
+                    inner.for_segment(&segment).pages_inner::<I>()
+                })
+            }
+
+            /// Returns a stream of items from all segments in the order they are fetched.
+            pub fn into_stream(self) -> impl ::raiden::futures::Stream<Item = Result<I, ::raiden::RaidenError>> + 'a {
+                ::raiden::pagination::segment_items(self.pages())
+            }
         }
     }
 }
@@ -340,3 +554,35 @@ pub struct ScanInput {
     pub total_segments: Option<i64>,
 }
 */
+
+// `filter_expired` is generated only for models with a `#[raiden(ttl)]` field.
+pub(crate) fn expand_filter_expired(
+    struct_name: &proc_macro2::Ident,
+    ttl: &Option<crate::ttl::TtlField>,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let Some(crate::ttl::TtlField { attr_name, .. }) = ttl else {
+        return (quote! {}, quote! {});
+    };
+
+    (
+        quote! {
+            /// Drops items which are already expired but not deleted by DynamoDB yet.
+            pub fn filter_expired(mut self) -> Self {
+                if self.expired_filter.is_some() {
+                    return self;
+                }
+
+                let now = <#struct_name as ::raiden::Clock>::now();
+                self.expired_filter = Some(::raiden::ttl::not_expired_filter(#attr_name, now, &mut self.placeholders));
+                self.with_expired_filter()
+            }
+        },
+        quote! {
+            /// Drops items which are already expired but not deleted by DynamoDB yet.
+            pub fn filter_expired(mut self) -> Self {
+                self.inner = self.inner.filter_expired();
+                self
+            }
+        },
+    )
+}
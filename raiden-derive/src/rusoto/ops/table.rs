@@ -0,0 +1,312 @@
+use proc_macro2::*;
+use quote::*;
+
+use crate::table::{KeyType, ProjectionType, TableDefinition};
+
+fn expand_key_schema(key_schema: &[(String, KeyType)]) -> Vec<TokenStream> {
+    key_schema
+        .iter()
+        .map(|(name, key_type)| {
+            let key_type = match key_type {
+                KeyType::Hash => "HASH",
+                KeyType::Range => "RANGE",
+            };
+            quote! {
+                ::raiden::KeySchemaElement {
+                    attribute_name: #name.to_owned(),
+                    key_type: #key_type.to_owned(),
+                }
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn expand_table(struct_name: &Ident, definition: &TableDefinition) -> TokenStream {
+    let client_name = format_ident!("{}Client", struct_name);
+    let create_trait_name = format_ident!("{}CreateTable", struct_name);
+    let delete_trait_name = format_ident!("{}DeleteTable", struct_name);
+    let describe_trait_name = format_ident!("{}DescribeTable", struct_name);
+    let create_builder_name = format_ident!("{}CreateTableBuilder", struct_name);
+    let delete_builder_name = format_ident!("{}DeleteTableBuilder", struct_name);
+    let describe_builder_name = format_ident!("{}DescribeTableBuilder", struct_name);
+
+    let key_schema = expand_key_schema(&definition.key_schema);
+    let attribute_definitions = definition.attribute_definitions.iter().map(|(name, ty)| {
+        quote! {
+            ::raiden::AttributeDefinition {
+                attribute_name: #name.to_owned(),
+                attribute_type: #ty.to_string(),
+            }
+        }
+    });
+    let global_secondary_indexes = definition.global_secondary_indexes.iter().map(|index| {
+        let index_name = &index.name;
+        let key_schema = expand_key_schema(&index.key_schema);
+        let projection = match &index.projection {
+            ProjectionType::All => quote! {
+                ::raiden::Projection {
+                    projection_type: Some("ALL".to_owned()),
+                    ..::raiden::Projection::default()
+                }
+            },
+            ProjectionType::Include(non_key_attributes) => quote! {
+                ::raiden::Projection {
+                    projection_type: Some("INCLUDE".to_owned()),
+                    non_key_attributes: Some(vec![#(#non_key_attributes.to_owned()),*]),
+                }
+            },
+        };
+        quote! {
+            ::raiden::GlobalSecondaryIndex {
+                index_name: #index_name.to_owned(),
+                key_schema: vec![#(#key_schema),*],
+                projection: #projection,
+                ..::raiden::GlobalSecondaryIndex::default()
+            }
+        }
+    });
+    let global_secondary_indexes = if definition.global_secondary_indexes.is_empty() {
+        quote! { None }
+    } else {
+        quote! { Some(vec![#(#global_secondary_indexes),*]) }
+    };
+
+    let time_to_live = match &definition.time_to_live {
+        Some(attribute_name) => quote! { Some(#attribute_name.to_owned()) },
+        None => quote! { None },
+    };
+
+    let create_api_call_token = super::api_call_token!("create_table");
+    let delete_api_call_token = super::api_call_token!("delete_table");
+    let describe_api_call_token = super::api_call_token!("describe_table");
+    let ttl_api_call_token =
+        super::api_call_token!("table_name", "client", "update_time_to_live", "ttl_input");
+    let (table_name_arg, ttl_table_name_arg, table_name_param) = if cfg!(feature = "tracing") {
+        (
+            quote! { input.table_name.clone(), },
+            quote! { ttl_input.table_name.clone(), },
+            quote! { table_name: String, },
+        )
+    } else {
+        (quote! {}, quote! {}, quote! {})
+    };
+
+    quote! {
+        pub trait #create_trait_name {
+            fn create_table(&self) -> #create_builder_name;
+        }
+
+        pub trait #delete_trait_name {
+            fn delete_table(&self) -> #delete_builder_name;
+        }
+
+        pub trait #describe_trait_name {
+            fn describe_table(&self) -> #describe_builder_name;
+        }
+
+        impl #create_trait_name for #client_name {
+            fn create_table(&self) -> #create_builder_name {
+                let input = ::raiden::CreateTableInput {
+                    table_name: self.table_name(),
+                    key_schema: vec![#(#key_schema),*],
+                    attribute_definitions: vec![#(#attribute_definitions),*],
+                    global_secondary_indexes: #global_secondary_indexes,
+                    billing_mode: Some("PAY_PER_REQUEST".to_owned()),
+                    ..::raiden::CreateTableInput::default()
+                };
+
+                #create_builder_name {
+                    client: &self.client,
+                    input,
+                    provisioned_throughput: None,
+                    time_to_live: #time_to_live,
+                    policy: self.retry_condition.strategy.policy(),
+                    condition: &self.retry_condition,
+                }
+            }
+        }
+
+        impl #delete_trait_name for #client_name {
+            fn delete_table(&self) -> #delete_builder_name {
+                let mut input = ::raiden::DeleteTableInput::default();
+                input.table_name = self.table_name();
+
+                #delete_builder_name {
+                    client: &self.client,
+                    input,
+                    policy: self.retry_condition.strategy.policy(),
+                    condition: &self.retry_condition,
+                }
+            }
+        }
+
+        impl #describe_trait_name for #client_name {
+            fn describe_table(&self) -> #describe_builder_name {
+                let mut input = ::raiden::DescribeTableInput::default();
+                input.table_name = self.table_name();
+
+                #describe_builder_name {
+                    client: &self.client,
+                    input,
+                    policy: self.retry_condition.strategy.policy(),
+                    condition: &self.retry_condition,
+                }
+            }
+        }
+
+        pub struct #create_builder_name<'a> {
+            pub client: &'a ::raiden::SharedBackend,
+            pub input: ::raiden::CreateTableInput,
+            pub provisioned_throughput: Option<(i64, i64)>,
+            pub time_to_live: Option<String>,
+            pub policy: ::raiden::Policy,
+            pub condition: &'a ::raiden::retry::RetryCondition,
+        }
+
+        impl<'a> #create_builder_name<'a> {
+            pub fn raw_input(mut self, input: ::raiden::CreateTableInput) -> Self {
+                self.input = input;
+                self
+            }
+
+            /// Creates the table in on-demand capacity mode. This is the default.
+            pub fn pay_per_request(mut self) -> Self {
+                self.provisioned_throughput = None;
+                self
+            }
+
+            /// Creates the table and its global secondary indexes in provisioned capacity mode.
+            pub fn provisioned_throughput(mut self, read_capacity_units: i64, write_capacity_units: i64) -> Self {
+                self.provisioned_throughput = Some((read_capacity_units, write_capacity_units));
+                self
+            }
+
+            /// Enables TTL on the given attribute once the table has been created.
+            ///
+            /// The field marked with `#[raiden(ttl)]` is used by default.
+            pub fn time_to_live(mut self, attribute_name: impl Into<String>) -> Self {
+                self.time_to_live = Some(attribute_name.into());
+                self
+            }
+
+            pub async fn run(self) -> Result<::raiden::table::TableOutput, ::raiden::RaidenError> {
+                let Self { client, mut input, provisioned_throughput, time_to_live, policy, condition } = self;
+
+                if let Some((read_capacity_units, write_capacity_units)) = provisioned_throughput {
+                    let throughput = ::raiden::ProvisionedThroughput {
+                        read_capacity_units,
+                        write_capacity_units,
+                    };
+                    if let Some(indexes) = input.global_secondary_indexes.as_mut() {
+                        for index in indexes.iter_mut() {
+                            index.provisioned_throughput = Some(throughput.clone());
+                        }
+                    }
+                    input.billing_mode = Some("PROVISIONED".to_owned());
+                    input.provisioned_throughput = Some(throughput);
+                }
+
+                let table_name = input.table_name.clone();
+                let output = ::raiden::retry_with_backend(policy, &client, condition, move |client| {
+                    let input = input.clone();
+                    async { #create_builder_name::inner_run(#table_name_arg client, input).await }
+                }).await?;
+
+                if let Some(attribute_name) = time_to_live {
+                    let ttl_input = ::raiden::UpdateTimeToLiveInput {
+                        table_name,
+                        time_to_live_specification: ::raiden::TimeToLiveSpecification {
+                            attribute_name,
+                            enabled: true,
+                        },
+                    };
+
+                    // TTL can not be updated until the table becomes active.
+                    let policy = ::raiden::Policy::Exponential(10, std::time::Duration::from_millis(100));
+                    ::raiden::retry_with_backend(policy, &client, |e: &::raiden::RaidenError| matches!(e, ::raiden::RaidenError::ResourceInUse(_)), move |client| {
+                        let ttl_input = ttl_input.clone();
+                        async { #create_builder_name::update_time_to_live(#ttl_table_name_arg client, ttl_input).await }
+                    }).await?;
+                }
+
+                Ok(output)
+            }
+
+            async fn inner_run(
+                #table_name_param
+                client: ::raiden::SharedBackend,
+                input: ::raiden::CreateTableInput,
+            ) -> Result<::raiden::table::TableOutput, ::raiden::RaidenError> {
+                let res = #create_api_call_token?;
+                Ok(::raiden::table::TableOutput {
+                    table_description: res.table_description,
+                })
+            }
+
+            async fn update_time_to_live(
+                #table_name_param
+                client: ::raiden::SharedBackend,
+                ttl_input: ::raiden::UpdateTimeToLiveInput,
+            ) -> Result<(), ::raiden::RaidenError> {
+                #ttl_api_call_token?;
+                Ok(())
+            }
+        }
+
+        pub struct #delete_builder_name<'a> {
+            pub client: &'a ::raiden::SharedBackend,
+            pub input: ::raiden::DeleteTableInput,
+            pub policy: ::raiden::Policy,
+            pub condition: &'a ::raiden::retry::RetryCondition,
+        }
+
+        impl<'a> #delete_builder_name<'a> {
+            pub async fn run(self) -> Result<::raiden::table::TableOutput, ::raiden::RaidenError> {
+                let Self { client, input, policy, condition } = self;
+                ::raiden::retry_with_backend(policy, &client, condition, move |client| {
+                    let input = input.clone();
+                    async { #delete_builder_name::inner_run(#table_name_arg client, input).await }
+                }).await
+            }
+
+            async fn inner_run(
+                #table_name_param
+                client: ::raiden::SharedBackend,
+                input: ::raiden::DeleteTableInput,
+            ) -> Result<::raiden::table::TableOutput, ::raiden::RaidenError> {
+                let res = #delete_api_call_token?;
+                Ok(::raiden::table::TableOutput {
+                    table_description: res.table_description,
+                })
+            }
+        }
+
+        pub struct #describe_builder_name<'a> {
+            pub client: &'a ::raiden::SharedBackend,
+            pub input: ::raiden::DescribeTableInput,
+            pub policy: ::raiden::Policy,
+            pub condition: &'a ::raiden::retry::RetryCondition,
+        }
+
+        impl<'a> #describe_builder_name<'a> {
+            pub async fn run(self) -> Result<::raiden::table::TableOutput, ::raiden::RaidenError> {
+                let Self { client, input, policy, condition } = self;
+                ::raiden::retry_with_backend(policy, &client, condition, move |client| {
+                    let input = input.clone();
+                    async { #describe_builder_name::inner_run(#table_name_arg client, input).await }
+                }).await
+            }
+
+            async fn inner_run(
+                #table_name_param
+                client: ::raiden::SharedBackend,
+                input: ::raiden::DescribeTableInput,
+            ) -> Result<::raiden::table::TableOutput, ::raiden::RaidenError> {
+                let res = #describe_api_call_token?;
+                Ok(::raiden::table::TableOutput {
+                    table_description: res.table,
+                })
+            }
+        }
+    }
+}
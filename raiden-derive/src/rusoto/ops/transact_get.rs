@@ -3,17 +3,16 @@ use quote::*;
 use syn::*;
 
 pub(crate) fn expand_transact_get(
-    partition_key: &(Ident, Type),
-    sort_key: &Option<(Ident, Type)>,
+    partition_key: &(LitStr, Type),
+    sort_key: &Option<(LitStr, Type)>,
     struct_name: &Ident,
-    fields: &FieldsNamed,
-    rename_all_type: crate::rename::RenameAllType,
+    table_name: &str,
 ) -> TokenStream {
     let trait_name = format_ident!("{}TransactGetItems", struct_name);
     let client_name = format_ident!("{}Client", struct_name);
     let builder_name = format_ident!("{}TransactGetItemsBuilder", struct_name);
-    let from_item = super::expand_attr_to_item(format_ident!("res_item"), fields, rename_all_type);
-    let (partition_key_ident, partition_key_type) = partition_key;
+    let get_builder = format_ident!("{}TransactGetItemBuilder", struct_name);
+    let (partition_key_name, partition_key_type) = partition_key;
 
     let builder_keys_type = if sort_key.is_none() {
         quote! { std::vec::Vec<::raiden::AttributeValue> }
@@ -22,7 +21,7 @@ pub(crate) fn expand_transact_get(
     };
 
     let client_trait = if let Some(sort_key) = sort_key {
-        let (_sort_key_ident, sort_key_type) = sort_key;
+        let (_sort_key_name, sort_key_type) = sort_key;
         quote! {
             pub trait #trait_name {
                 fn transact_get(&self, keys: std::vec::Vec<(impl Into<#partition_key_type>, impl Into<#sort_key_type>)>) -> #builder_name;
@@ -38,6 +37,7 @@ pub(crate) fn expand_transact_get(
                         keys,
                         attribute_names: self.attribute_names.clone(),
                         projection_expression: self.projection_expression.clone(),
+                        return_consumed_capacity: None,
                         policy: self.retry_condition.strategy.policy(),
                         condition: &self.retry_condition,
                     }
@@ -60,6 +60,7 @@ pub(crate) fn expand_transact_get(
                         keys,
                         attribute_names: self.attribute_names.clone(),
                         projection_expression: self.projection_expression.clone(),
+                        return_consumed_capacity: None,
                         policy: self.retry_condition.strategy.policy(),
                         condition: &self.retry_condition,
                     }
@@ -69,12 +70,12 @@ pub(crate) fn expand_transact_get(
     };
 
     let push_gets = if let Some(sort_key) = sort_key {
-        let (sort_key_ident, _) = sort_key;
+        let (sort_key_name, _) = sort_key;
         quote! {
             for (pk_attr, sk_attr) in self.keys.into_iter() {
                 let key = vec![
-                    (stringify!(#partition_key_ident).to_owned(), pk_attr),
-                    (stringify!(#sort_key_ident).to_owned(), sk_attr),
+                    (#partition_key_name.to_owned(), pk_attr),
+                    (#sort_key_name.to_owned(), sk_attr),
                 ].into_iter().collect();
                 transact_items.push(::raiden::TransactGetItem {
                     get: ::raiden::Get {
@@ -90,7 +91,7 @@ pub(crate) fn expand_transact_get(
         quote! {
             for key_attr in self.keys.into_iter() {
                 let key = vec![
-                    (stringify!(#partition_key_ident).to_owned(), key_attr),
+                    (#partition_key_name.to_owned(), key_attr),
                 ].into_iter().collect();
                 transact_items.push(::raiden::TransactGetItem {
                     get: ::raiden::Get {
@@ -104,6 +105,77 @@ pub(crate) fn expand_transact_get(
         }
     };
 
+    let (key_args, key_set) = if let Some(sort_key) = sort_key {
+        let (sort_key_name, sort_key_type) = sort_key;
+        (
+            quote! { pk: impl Into<#partition_key_type>, sk: impl Into<#sort_key_type> },
+            quote! {
+                let mut key_set: std::collections::HashMap<String, ::raiden::AttributeValue> = std::collections::HashMap::new();
+                key_set.insert(#partition_key_name.to_owned(), pk.into().into_attr());
+                key_set.insert(#sort_key_name.to_owned(), sk.into().into_attr());
+            },
+        )
+    } else {
+        (
+            quote! { key: impl Into<#partition_key_type> },
+            quote! {
+                let mut key_set: std::collections::HashMap<String, ::raiden::AttributeValue> = std::collections::HashMap::new();
+                key_set.insert(#partition_key_name.to_owned(), key.into().into_attr());
+            },
+        )
+    };
+
+    let get_item = quote! {
+        impl #struct_name {
+            /// Starts a get for a `ReadTx`.
+            pub fn get(#key_args) -> #get_builder {
+                #key_set
+                let input = ::raiden::Get {
+                    expression_attribute_names: <Self as ::raiden::RaidenItem>::attribute_names(),
+                    key: key_set,
+                    projection_expression: <Self as ::raiden::RaidenItem>::projection_expression(),
+                    ..std::default::Default::default()
+                };
+
+                #get_builder {
+                    input,
+                    table_name: #table_name.to_owned(),
+                    table_prefix: "".to_owned(),
+                    table_suffix: "".to_owned(),
+                }
+            }
+        }
+
+        pub struct #get_builder {
+            pub table_name: String,
+            pub table_prefix: String,
+            pub table_suffix: String,
+            pub input: ::raiden::Get,
+        }
+
+        impl ::raiden::TransactGetItemBuilder for #get_builder {
+            type Item = #struct_name;
+
+            fn build(self) -> ::raiden::Get {
+                let mut input = self.input;
+                input.table_name = format!("{}{}{}", self.table_prefix, self.table_name, self.table_suffix);
+                input
+            }
+        }
+
+        impl #get_builder {
+            pub fn table_prefix(mut self, s: impl Into<String>) -> Self {
+                self.table_prefix = s.into();
+                self
+            }
+
+            pub fn table_suffix(mut self, s: impl Into<String>) -> Self {
+                self.table_suffix = s.into();
+                self
+            }
+        }
+    };
+
     let api_call_token = super::api_call_token!("transact_get_items");
     let (call_inner_run, inner_run_args) = if cfg!(feature = "tracing") {
         (
@@ -120,44 +192,49 @@ pub(crate) fn expand_transact_get(
     quote! {
         #client_trait
 
+        #get_item
+
         pub struct #builder_name<'a> {
-            pub client: &'a ::raiden::DynamoDbClient,
+            pub client: &'a ::raiden::SharedBackend,
             pub table_name: String,
             pub keys: #builder_keys_type,
             pub attribute_names: Option<::raiden::AttributeNames>,
             pub projection_expression: Option<String>,
+            pub return_consumed_capacity: Option<::raiden::ReturnConsumedCapacity>,
             pub policy: ::raiden::Policy,
             pub condition: &'a ::raiden::retry::RetryCondition,
         }
 
         impl<'a> #builder_name<'a> {
+            /// Requests the consumed capacity, returned as `consumed_capacity`.
+            pub fn return_consumed_capacity(mut self, value: ::raiden::ReturnConsumedCapacity) -> Self {
+                self.return_consumed_capacity = Some(value);
+                self
+            }
+
             pub async fn run(self) -> Result<::raiden::transact_get::TransactGetOutput<#struct_name>, ::raiden::RaidenError> {
                 let mut transact_items = vec![];
                 #push_gets
 
                 let input = ::raiden::TransactGetItemsInput {
                     transact_items,
-                    ..std::default::Default::default()
+                    return_consumed_capacity: self.return_consumed_capacity.map(|v| v.as_str().to_owned()),
                 };
 
-                let policy: ::raiden::RetryPolicy = self.policy.into();
                 let client = self.client;
                 let table_name = self.table_name.clone();
-                let res = policy.retry_if(move || {
-                    let client = client.clone();
+                let res = ::raiden::retry_with_backend(self.policy, &client, self.condition, move |client| {
                     let input = input.clone();
                     let table_name = table_name.clone();
                     async { #call_inner_run }
-                }, self.condition).await?;
+                }).await?;
 
                 let items = res.responses
                     .unwrap_or_default()
                     .into_iter()
-                    .map(|response| {
-                        match response.item {
-                            Some(mut res_item) => Ok(Some(#struct_name {
-                                #(#from_item)*
-                            })),
+                    .map(|item| {
+                        match item {
+                            Some(res_item) => Ok(Some(<#struct_name as ::raiden::RaidenItem>::from_item(res_item)?)),
                             None => Ok(None),
                         }
                     })
@@ -171,10 +248,10 @@ pub(crate) fn expand_transact_get(
 
             async fn inner_run(
                 #inner_run_args
-                client: ::raiden::DynamoDbClient,
+                client: ::raiden::SharedBackend,
                 input: ::raiden::TransactGetItemsInput,
-            ) -> Result<::raiden::TransactGetItemsOutput, ::raiden::RaidenError> {
-                Ok(#api_call_token?)
+            ) -> Result<::raiden::TransactGetItemsResponse, ::raiden::RaidenError> {
+                #api_call_token
             }
         }
     }
@@ -4,14 +4,17 @@ use syn::*;
 
 use crate::rename::*;
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn expand_transact_write(
     struct_name: &Ident,
-    partition_key: &(Ident, Type),
-    _sort_key: &Option<(Ident, Type)>, // TODO: Support sort key
+    partition_key: &(LitStr, Type),
+    sort_key: &Option<(LitStr, Type)>,
     fields: &FieldsNamed,
-    attr_enum_name: &Ident,
     rename_all_type: RenameAllType,
     table_name: &str,
+    version: &Option<crate::version::VersionField>,
+    timestamps: &crate::timestamp::Timestamps,
+    key_templates: &crate::key_template::KeyTemplates,
 ) -> TokenStream {
     let item_input_name = format_ident!("{}PutItemInput", struct_name);
     let put_builder = format_ident!("{}TransactPutItemBuilder", struct_name);
@@ -19,9 +22,46 @@ pub(crate) fn expand_transact_write(
     let delete_builder = format_ident!("{}TransactDeleteItemBuilder", struct_name);
     let condition_check_builder = format_ident!("{}TransactConditionCheckBuilder", struct_name);
     let condition_token_name = format_ident!("{}ConditionToken", struct_name);
-    let (partition_key_ident, partition_key_type) = partition_key;
+    let (partition_key_name, partition_key_type) = partition_key;
+
+    let now = timestamps.expand_now(struct_name);
+    let timestamp_items = timestamps.expand_update_items(struct_name);
+
+    let version_condition = crate::version::expand_put_version(version);
+    let version_overflow = crate::version::expand_version_overflow(version);
+    let increment_version = match version {
+        Some(crate::version::VersionField { attr_name, .. }) => quote! {
+            self.add_items.insert(0, ::raiden::version::increment_version(#attr_name, &mut self.placeholders));
+        },
+        None => quote! {},
+    };
+    // A versioned model is updated or deleted only if the stored version equals `expected_version`.
+    let expected_version_arg = crate::version::expand_expected_version_arg(version);
+    let expected_version_condition = crate::version::expand_expected_version_condition(version);
+
+    let (key_args, key_set) = if let Some(sort_key) = sort_key {
+        let (sort_key_name, sort_key_type) = sort_key;
+        (
+            quote! { pk: impl Into<#partition_key_type>, sk: impl Into<#sort_key_type> },
+            quote! {
+                let pk_attr: ::raiden::AttributeValue = pk.into().into_attr();
+                let sk_attr: ::raiden::AttributeValue = sk.into().into_attr();
+                let mut key_set: std::collections::HashMap<String, ::raiden::AttributeValue> = std::collections::HashMap::new();
+                key_set.insert(#partition_key_name.to_owned(), pk_attr);
+                key_set.insert(#sort_key_name.to_owned(), sk_attr);
+            },
+        )
+    } else {
+        (
+            quote! { key: impl Into<#partition_key_type> },
+            quote! {
+                let key_attr: ::raiden::AttributeValue = key.into().into_attr();
+                let mut key_set: std::collections::HashMap<String, ::raiden::AttributeValue> = std::collections::HashMap::new();
+                key_set.insert(#partition_key_name.to_owned(), key_attr);
+            },
+        )
+    };
 
-    // let (sort_key_ident, sort_key_type) = sort_key;
     // let output_values = fields.named.iter().map(|f| {
     //     let ident = &f.ident.clone().unwrap();
     //     let renamed = crate::finder::find_rename_value(&f.attrs);
@@ -37,6 +77,7 @@ pub(crate) fn expand_transact_write(
     //     }
     // });
 
+    let key_insertion = key_templates.expand_insertion(quote! { item });
     let input_items = {
         let insertion = fields.named.iter().map(|f| {
             let ident = &f.ident.clone().unwrap();
@@ -54,6 +95,22 @@ pub(crate) fn expand_transact_write(
                         id,
                     );
                 }
+            } else if version.as_ref().is_some_and(|version| version.is(f)) {
+                quote! {
+                    if let Some(next_version) = next_version {
+                        input_item.insert(
+                            #attr_key.to_string(),
+                            next_version.into_attr(),
+                        );
+                    }
+                }
+            } else if let Some(value) = timestamps.expand_value(f) {
+                quote! {
+                    input_item.insert(
+                        #attr_key.to_string(),
+                        #value.into_attr(),
+                    );
+                }
             } else {
                 quote! {
                     let value = item.#ident.into_attr();
@@ -69,6 +126,8 @@ pub(crate) fn expand_transact_write(
 
         quote! {
             let mut input_item: std::collections::HashMap<String, raiden::AttributeValue> = std::collections::HashMap::new();
+            #key_insertion
+            #version_overflow
             #(#insertion)*
         }
     };
@@ -81,6 +140,8 @@ pub(crate) fn expand_transact_write(
                 let mut attribute_values: std::collections::HashMap<String, raiden::AttributeValue> = std::collections::HashMap::new();
                 let mut uuid_map: std::collections::HashMap<String, String> = std::collections::HashMap::new();
 
+                #version_condition
+                #now
                 #input_items
 
                 // let output_item = #item_output_name {
@@ -92,16 +153,15 @@ pub(crate) fn expand_transact_write(
                     table_name: #table_name.to_owned(),
                     table_prefix: "".to_owned(),
                     table_suffix: "".to_owned(),
-                    // item: output_item,
+                    version_condition,
+                    placeholders,
+                    key_error,
                 }
             }
 
-            // TODO: Support sort key
-            pub fn condition_check(key: impl Into<#partition_key_type>) -> #condition_check_builder {
+            pub fn condition_check(#key_args) -> #condition_check_builder {
                 let mut input = ::raiden::ConditionCheck::default();
-                let key_attr: ::raiden::AttributeValue = key.into().into_attr();
-                let mut key_set: std::collections::HashMap<String, ::raiden::AttributeValue> = std::collections::HashMap::new();
-                key_set.insert(stringify!(#partition_key_ident).to_owned(), key_attr);
+                #key_set
                 input.key = key_set;
                 #condition_check_builder {
                     input,
@@ -109,32 +169,30 @@ pub(crate) fn expand_transact_write(
                     table_prefix: "".to_owned(),
                     table_suffix: "".to_owned(),
                     // item: output_item,
+                    placeholders: ::raiden::Placeholders::new(),
                 }
             }
 
-            // TODO: Support sort key
-            pub fn delete(key: impl Into<#partition_key_type>) -> #delete_builder {
+            pub fn delete(#key_args #expected_version_arg) -> #delete_builder {
+                #expected_version_condition
                 let mut input = ::raiden::Delete::default();
-                let key_attr: ::raiden::AttributeValue = key.into().into_attr();
-                let mut key_set: std::collections::HashMap<String, ::raiden::AttributeValue> = std::collections::HashMap::new();
-                key_set.insert(stringify!(#partition_key_ident).to_owned(), key_attr);
+                #key_set
                 input.key = key_set;
                 #delete_builder {
                     input,
                     table_name: #table_name.to_owned(),
                     table_prefix: "".to_owned(),
                     table_suffix: "".to_owned(),
-                    // item: output_item,
+                    version_condition,
+                    placeholders,
                 }
             }
 
-            // TODO: Support sort key
-            pub fn update(key: impl Into<#partition_key_type>) -> #update_builder {
+            pub fn update(#key_args #expected_version_arg) -> #update_builder {
+                #expected_version_condition
                 let mut input = ::raiden::Update::default();
 
-                let key_attr: ::raiden::AttributeValue = key.into().into_attr();
-                let mut key_set: std::collections::HashMap<String, ::raiden::AttributeValue> = std::collections::HashMap::new();
-                key_set.insert(stringify!(#partition_key_ident).to_owned(), key_attr);
+                #key_set
                 input.key = key_set;
 
                 #update_builder {
@@ -147,6 +205,8 @@ pub(crate) fn expand_transact_write(
                     set_items: vec![],
                     remove_items: vec![],
                     delete_items: vec![],
+                    version_condition,
+                    placeholders,
                 }
             }
         }
@@ -156,11 +216,29 @@ pub(crate) fn expand_transact_write(
             pub table_prefix: String,
             pub table_suffix: String,
             pub input: ::raiden::Put,
+            pub version_condition: Option<::raiden::version::VersionCondition>,
+            pub placeholders: ::raiden::Placeholders,
+            key_error: Option<::raiden::RaidenError>,
         }
 
         impl ::raiden::TransactWritePutBuilder for #put_builder {
+            fn take_error(&mut self) -> Option<::raiden::RaidenError> {
+                self.key_error.take()
+            }
+
             fn build(self) -> ::raiden::Put {
                 let mut input = self.input;
+                if let Some(version_condition) = self.version_condition {
+                    let (expression, names, values) = ::raiden::version::apply_version_condition(
+                        input.condition_expression.take(),
+                        input.expression_attribute_names.take(),
+                        input.expression_attribute_values.take(),
+                        &version_condition,
+                    );
+                    input.condition_expression = Some(expression);
+                    input.expression_attribute_names = names;
+                    input.expression_attribute_values = values;
+                }
                 input.table_name = format!("{}{}{}", self.table_prefix, self.table_name, self.table_suffix);
                 input
             }
@@ -179,7 +257,7 @@ pub(crate) fn expand_transact_write(
             }
 
             pub fn condition(mut self, cond: impl ::raiden::condition::ConditionBuilder<#condition_token_name>) -> Self {
-                let (cond_str, attr_names, attr_values) = cond.build();
+                let (cond_str, attr_names, attr_values) = cond.build_with(&mut self.placeholders);
                 if !attr_names.is_empty() {
                     self.input.expression_attribute_names = Some(attr_names);
                 }
@@ -199,29 +277,43 @@ pub(crate) fn expand_transact_write(
 
             pub add_items: Vec<(String, ::raiden::AttributeNames, ::raiden::AttributeValues)>,
             pub set_items: Vec<::raiden::update_expression::SetOrRemove>,
-            pub remove_items: Vec<#attr_enum_name>,
-            pub delete_items: Vec<(#attr_enum_name, ::raiden::AttributeValue)>,
+            pub remove_items: Vec<::raiden::AttrPath>,
+            pub delete_items: Vec<(::raiden::AttrPath, ::raiden::AttributeValue)>,
+            pub version_condition: Option<::raiden::version::VersionCondition>,
+            pub placeholders: ::raiden::Placeholders,
         }
 
         impl ::raiden::TransactWriteUpdateBuilder for #update_builder {
             fn build(mut self) -> ::raiden::Update {
+                if let Some(version_condition) = self.version_condition.take() {
+                    let (expression, names, values) = ::raiden::version::apply_version_condition(
+                        self.input.condition_expression.take(),
+                        self.input.expression_attribute_names.take(),
+                        self.input.expression_attribute_values.take(),
+                        &version_condition,
+                    );
+                    self.input.condition_expression = Some(expression);
+                    self.input.expression_attribute_names = names;
+                    self.input.expression_attribute_values = values;
+                }
+
                 // let mut input = self.input;
 
                 // TODO: Refactor later
                 let mut attr_names: ::raiden::AttributeNames = std::collections::HashMap::new();
                 let mut attr_values: ::raiden::AttributeValues = std::collections::HashMap::new();
 
+                #increment_version
+                #timestamp_items
+
                 let add_items = std::mem::replace(&mut self.add_items, vec![]);
                 let set_items = std::mem::replace(&mut self.set_items, vec![]);
                 let remove_items = std::mem::replace(&mut self.remove_items, vec![]);
                 let delete_items = std::mem::replace(&mut self.delete_items, vec![]);
 
-                let mut remove_expressions = remove_items.into_iter().map(|name| {
-                    let placeholder = format!(":value{}", ::raiden::generate_value_id());
-                    let attr_name = format!("#{}", name.into_attr_name());
-                    let val = format!("{}", attr_name);
-                    attr_names.insert(attr_name, name.into_attr_name());
-                    val
+                let mut remove_expressions = remove_items.into_iter().map(|path| {
+                    attr_names.extend(path.attribute_names());
+                    path.expression()
                 }).collect::<Vec<String>>();
 
                 let mut set_expressions = vec![];
@@ -256,13 +348,10 @@ pub(crate) fn expand_transact_write(
 
 
 
-                let delete_expression = delete_items.into_iter().map(|(name, value)| {
-                    let placeholder = format!(":value{}", ::raiden::generate_value_id());
-                    let attr_name = format!("#{}", name.into_attr_name());
-                    let val = format!("{} {}", attr_name, placeholder);
-                    attr_names.insert(attr_name, name.into_attr_name());
-                    attr_values.insert(placeholder, value);
-                    val
+                let delete_expression = delete_items.into_iter().map(|(path, value)| {
+                    let placeholder = self.placeholders.bind(value, &mut attr_values);
+                    attr_names.extend(path.attribute_names());
+                    format!("{} {}", path.expression(), placeholder)
                 }).collect::<Vec<_>>().join(", ");
 
                 let mut update_expressions: Vec<String> = vec![];
@@ -311,27 +400,27 @@ pub(crate) fn expand_transact_write(
             }
 
             pub fn add(mut self, add: impl ::raiden::update_expression::UpdateAddExpressionBuilder) -> Self {
-                self.add_items.push(add.build());
+                self.add_items.push(add.build_with(&mut self.placeholders));
                 self
             }
 
             pub fn set(mut self, set: impl ::raiden::update_expression::UpdateSetExpressionBuilder) -> Self {
-                self.set_items.push(set.build());
+                self.set_items.push(set.build_with(&mut self.placeholders));
                 self
             }
 
-            pub fn remove(mut self, attr: #attr_enum_name) -> Self {
-                self.remove_items.push(attr);
+            pub fn remove(mut self, attr: impl ::raiden::IntoAttrPath) -> Self {
+                self.remove_items.push(attr.into_attr_path());
                 self
             }
 
-            pub fn delete(mut self, attr: #attr_enum_name, value: impl ::raiden::IntoAttribute) -> Self {
-                self.delete_items.push((attr, value.into_attr()));
+            pub fn delete(mut self, attr: impl ::raiden::IntoAttrPath, value: impl ::raiden::IntoAttribute) -> Self {
+                self.delete_items.push((attr.into_attr_path(), value.into_attr()));
                 self
             }
 
             pub fn condition(mut self, cond: impl ::raiden::condition::ConditionBuilder<#condition_token_name>) -> Self {
-                let (cond_str, attr_names, attr_values) = cond.build();
+                let (cond_str, attr_names, attr_values) = cond.build_with(&mut self.placeholders);
                 if !attr_names.is_empty() {
                     self.input.expression_attribute_names = Some(attr_names);
                 }
@@ -348,11 +437,24 @@ pub(crate) fn expand_transact_write(
             pub table_prefix: String,
             pub table_suffix: String,
             pub input: ::raiden::Delete,
+            pub version_condition: Option<::raiden::version::VersionCondition>,
+            pub placeholders: ::raiden::Placeholders,
         }
 
         impl ::raiden::TransactWriteDeleteBuilder for #delete_builder {
             fn build(self) -> ::raiden::Delete {
                 let mut input = self.input;
+                if let Some(version_condition) = self.version_condition {
+                    let (expression, names, values) = ::raiden::version::apply_version_condition(
+                        input.condition_expression.take(),
+                        input.expression_attribute_names.take(),
+                        input.expression_attribute_values.take(),
+                        &version_condition,
+                    );
+                    input.condition_expression = Some(expression);
+                    input.expression_attribute_names = names;
+                    input.expression_attribute_values = values;
+                }
                 input.table_name = format!("{}{}{}", self.table_prefix, self.table_name, self.table_suffix);
                 input
             }
@@ -370,7 +472,7 @@ pub(crate) fn expand_transact_write(
             }
 
             pub fn condition(mut self, cond: impl ::raiden::condition::ConditionBuilder<#condition_token_name>) -> Self {
-                let (cond_str, attr_names, attr_values) = cond.build();
+                let (cond_str, attr_names, attr_values) = cond.build_with(&mut self.placeholders);
                 if !attr_names.is_empty() {
                     self.input.expression_attribute_names = Some(attr_names);
                 }
@@ -387,6 +489,7 @@ pub(crate) fn expand_transact_write(
             pub table_prefix: String,
             pub table_suffix: String,
             pub input: ::raiden::ConditionCheck,
+            pub placeholders: ::raiden::Placeholders,
         }
 
         impl ::raiden::TransactWriteConditionCheckBuilder for #condition_check_builder {
@@ -409,7 +512,7 @@ pub(crate) fn expand_transact_write(
             }
 
             pub fn condition(mut self, cond: impl ::raiden::condition::ConditionBuilder<#condition_token_name>) -> Self {
-                let (cond_str, attr_names, attr_values) = cond.build();
+                let (cond_str, attr_names, attr_values) = cond.build_with(&mut self.placeholders);
                 if !attr_names.is_empty() {
                     self.input.expression_attribute_names = Some(attr_names);
                 }
@@ -2,40 +2,52 @@ use proc_macro2::*;
 use quote::*;
 use syn::*;
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn expand_update_item(
-    partition_key: &(Ident, Type),
-    sort_key: &Option<(Ident, Type)>,
-    fields: &FieldsNamed,
+    partition_key: &(LitStr, Type),
+    sort_key: &Option<(LitStr, Type)>,
     attr_enum_name: &Ident,
     struct_name: &Ident,
-    rename_all_type: crate::rename::RenameAllType,
+    version: &Option<crate::version::VersionField>,
+    timestamps: &crate::timestamp::Timestamps,
 ) -> TokenStream {
     let item_output_name = format_ident!("{}UpdateItemOutput", struct_name);
     let trait_name = format_ident!("{}UpdateItem", struct_name);
     let update_expression_name = format_ident!("{}UpdateExpression", struct_name);
     let client_name = format_ident!("{}Client", struct_name);
     let builder_name = format_ident!("{}UpdateItemBuilder", struct_name);
-    let from_item = super::expand_attr_to_item(format_ident!("res_item"), fields, rename_all_type);
+    let projected_builder_name = format_ident!("{}ProjectedUpdateItemBuilder", struct_name);
     let condition_token_name = format_ident!("{}ConditionToken", struct_name);
-    let (partition_key_ident, partition_key_type) = partition_key;
+    let (partition_key_name, partition_key_type) = partition_key;
+    let increment_version = match version {
+        Some(crate::version::VersionField { attr_name, .. }) => quote! {
+            self.add_items.insert(0, ::raiden::version::increment_version(#attr_name, &mut self.placeholders));
+        },
+        None => quote! {},
+    };
+    // A versioned model is updated only if the stored version equals `expected_version`.
+    let expected_version_arg = crate::version::expand_expected_version_arg(version);
+    let expected_version_condition = crate::version::expand_expected_version_condition(version);
 
     let client_trait = if let Some(sort_key) = sort_key {
-        let (sort_key_ident, sort_key_type) = sort_key;
+        let (sort_key_name, sort_key_type) = sort_key;
         quote! {
             pub trait #trait_name {
-                fn update(&self, pk: impl Into<#partition_key_type>, sk: impl Into<#sort_key_type>) -> #builder_name;
+                fn update(&self, pk: impl Into<#partition_key_type>, sk: impl Into<#sort_key_type> #expected_version_arg) -> #builder_name;
             }
 
             impl #trait_name for #client_name {
-                fn update(&self, pk: impl Into<#partition_key_type>, sk: impl Into<#sort_key_type>) -> #builder_name {
+                fn update(&self, pk: impl Into<#partition_key_type>, sk: impl Into<#sort_key_type> #expected_version_arg) -> #builder_name {
                     let mut input = ::raiden::UpdateItemInput::default();
                     let pk_attr: ::raiden::AttributeValue = pk.into().into_attr();
                     let sk_attr: ::raiden::AttributeValue = sk.into().into_attr();
                     let mut key_set: std::collections::HashMap<String, ::raiden::AttributeValue> = std::collections::HashMap::new();
-                    key_set.insert(stringify!(#partition_key_ident).to_owned(), pk_attr);
-                    key_set.insert(stringify!(#sort_key_ident).to_owned(), sk_attr);
+                    key_set.insert(#partition_key_name.to_owned(), pk_attr);
+                    key_set.insert(#sort_key_name.to_owned(), sk_attr);
                     input.key = key_set;
                     input.table_name = self.table_name();
+                    #expected_version_condition
+
                     #builder_name {
                         client: &self.client,
                         input,
@@ -45,6 +57,8 @@ pub(crate) fn expand_update_item(
                         delete_items: vec![],
                         policy: self.retry_condition.strategy.policy(),
                         condition: &self.retry_condition,
+                        version_condition,
+                        placeholders,
                     }
                 }
             }
@@ -52,17 +66,19 @@ pub(crate) fn expand_update_item(
     } else {
         quote! {
             pub trait #trait_name {
-                fn update(&self, key: impl Into<#partition_key_type>) -> #builder_name;
+                fn update(&self, key: impl Into<#partition_key_type> #expected_version_arg) -> #builder_name;
             }
 
             impl #trait_name for #client_name {
-                fn update(&self, key: impl Into<#partition_key_type>) -> #builder_name {
+                fn update(&self, key: impl Into<#partition_key_type> #expected_version_arg) -> #builder_name {
                     let mut input = ::raiden::UpdateItemInput::default();
                     let key_attr: ::raiden::AttributeValue = key.into().into_attr();
                     let mut key_set: std::collections::HashMap<String, ::raiden::AttributeValue> = std::collections::HashMap::new();
-                    key_set.insert(stringify!(#partition_key_ident).to_owned(), key_attr);
+                    key_set.insert(#partition_key_name.to_owned(), key_attr);
                     input.key = key_set;
                     input.table_name = self.table_name();
+                    #expected_version_condition
+
                     #builder_name {
                         client: &self.client,
                         input,
@@ -72,12 +88,16 @@ pub(crate) fn expand_update_item(
                         delete_items: vec![],
                         policy: self.retry_condition.strategy.policy(),
                         condition: &self.retry_condition,
+                        version_condition,
+                        placeholders,
                     }
                 }
             }
         }
     };
 
+    let timestamp_items = timestamps.expand_update_items(struct_name);
+
     let api_call_token = super::api_call_token!("update_item");
     let (call_inner_run, inner_run_args) = if cfg!(feature = "tracing") {
         (
@@ -106,30 +126,56 @@ pub(crate) fn expand_update_item(
         }
 
         impl #update_expression_name {
-            pub fn set(&self, attr: #attr_enum_name) -> ::raiden::update_expression::Set<#attr_enum_name> {
-                ::raiden::update_expression::Set::new(attr)
+            pub fn set(&self, attr: impl ::raiden::IntoAttrPath) -> ::raiden::update_expression::Set<#attr_enum_name> {
+                ::raiden::update_expression::Set::path(attr)
             }
 
-            pub fn add(&self, attr: #attr_enum_name) -> ::raiden::update_expression::Add<#attr_enum_name> {
-                ::raiden::update_expression::Add::new(attr)
+            pub fn add(&self, attr: impl ::raiden::IntoAttrPath) -> ::raiden::update_expression::Add<#attr_enum_name> {
+                ::raiden::update_expression::Add::path(attr)
             }
 
-            pub fn delete(&self, attr: #attr_enum_name) -> ::raiden::update_expression::Delete<#attr_enum_name> {
-                ::raiden::update_expression::Delete::new(attr)
+            pub fn delete(&self, attr: impl ::raiden::IntoAttrPath) -> ::raiden::update_expression::Delete<#attr_enum_name> {
+                ::raiden::update_expression::Delete::path(attr)
             }
         }
 
         #client_trait
 
+        /// An update builder that decodes the updated attributes into a projection item.
+        ///
+        /// Created by `return_updated_old` or `return_updated_new`.
+        pub struct #projected_builder_name<'a, P> {
+            inner: #builder_name<'a>,
+            marker: std::marker::PhantomData<P>,
+        }
+
+        impl<'a, P: ::raiden::RaidenItem> #projected_builder_name<'a, P> {
+            pub async fn run(self) -> Result<::raiden::update::UpdateOutput<P>, ::raiden::RaidenError> {
+                let res = self.inner.run_raw().await?;
+                let item = match res.attributes {
+                    Some(item) => Some(P::from_item(item)?),
+                    None => None,
+                };
+
+                Ok(::raiden::update::UpdateOutput {
+                    item,
+                    consumed_capacity: res.consumed_capacity,
+                    item_collection_metrics: res.item_collection_metrics,
+                })
+            }
+        }
+
         pub struct #builder_name<'a> {
-            pub client: &'a ::raiden::DynamoDbClient,
+            pub client: &'a ::raiden::SharedBackend,
             pub input: ::raiden::UpdateItemInput,
             pub add_items: Vec<(String, ::raiden::AttributeNames, ::raiden::AttributeValues)>,
             pub set_items: Vec<::raiden::update_expression::SetOrRemove>,
-            pub remove_items: Vec<#attr_enum_name>,
+            pub remove_items: Vec<::raiden::AttrPath>,
             pub delete_items: Vec<(String, ::raiden::AttributeNames, ::raiden::AttributeValues)>,
             pub policy: ::raiden::Policy,
             pub condition: &'a ::raiden::retry::RetryCondition,
+            pub version_condition: Option<::raiden::version::VersionCondition>,
+            pub placeholders: ::raiden::Placeholders,
         }
 
         impl<'a> #builder_name<'a> {
@@ -139,22 +185,22 @@ pub(crate) fn expand_update_item(
             }
 
             pub fn add(mut self, add: impl ::raiden::update_expression::UpdateAddExpressionBuilder) -> Self {
-                self.add_items.push(add.build());
+                self.add_items.push(add.build_with(&mut self.placeholders));
                 self
             }
 
             pub fn set(mut self, set: impl ::raiden::update_expression::UpdateSetExpressionBuilder) -> Self {
-                self.set_items.push(set.build());
+                self.set_items.push(set.build_with(&mut self.placeholders));
                 self
             }
 
-            pub fn remove(mut self, attr: #attr_enum_name) -> Self {
-                self.remove_items.push(attr);
+            pub fn remove(mut self, attr: impl ::raiden::IntoAttrPath) -> Self {
+                self.remove_items.push(attr.into_attr_path());
                 self
             }
 
             pub fn delete(mut self, set: impl ::raiden::update_expression::UpdateDeleteExpressionBuilder) -> Self {
-                self.delete_items.push(set.build());
+                self.delete_items.push(set.build_with(&mut self.placeholders));
                 self
             }
 
@@ -170,8 +216,38 @@ pub(crate) fn expand_update_item(
                 self
             }
 
+            /// Returns the updated attributes as they were before the update, decoded into `P`.
+            pub fn return_updated_old<P: ::raiden::RaidenItem>(mut self) -> #projected_builder_name<'a, P> {
+                self.input.return_values = Some("UPDATED_OLD".to_owned());
+                #projected_builder_name {
+                    inner: self,
+                    marker: std::marker::PhantomData,
+                }
+            }
+
+            /// Returns the updated attributes as they are after the update, decoded into `P`.
+            pub fn return_updated_new<P: ::raiden::RaidenItem>(mut self) -> #projected_builder_name<'a, P> {
+                self.input.return_values = Some("UPDATED_NEW".to_owned());
+                #projected_builder_name {
+                    inner: self,
+                    marker: std::marker::PhantomData,
+                }
+            }
+
+            /// Requests the consumed capacity, returned as `consumed_capacity`.
+            pub fn return_consumed_capacity(mut self, value: ::raiden::ReturnConsumedCapacity) -> Self {
+                self.input.return_consumed_capacity = Some(value.as_str().to_owned());
+                self
+            }
+
+            /// Requests the size of the modified item collection, returned as `item_collection_metrics`.
+            pub fn return_item_collection_metrics(mut self, value: ::raiden::ReturnItemCollectionMetrics) -> Self {
+                self.input.return_item_collection_metrics = Some(value.as_str().to_owned());
+                self
+            }
+
             pub fn condition(mut self, cond: impl ::raiden::condition::ConditionBuilder<#condition_token_name>) -> Self {
-                let (cond_str, attr_names, attr_values) = cond.build();
+                let (cond_str, attr_names, attr_values) = cond.build_with(&mut self.placeholders);
                 if !attr_names.is_empty() {
                     self.input.expression_attribute_names = Some(attr_names);
                 }
@@ -186,17 +262,17 @@ pub(crate) fn expand_update_item(
                 let mut attr_names: ::raiden::AttributeNames = std::collections::HashMap::new();
                 let mut attr_values: ::raiden::AttributeValues = std::collections::HashMap::new();
 
+                #increment_version
+                #timestamp_items
+
                 let add_items = std::mem::replace(&mut self.add_items, vec![]);
                 let set_items = std::mem::replace(&mut self.set_items, vec![]);
                 let remove_items = std::mem::replace(&mut self.remove_items, vec![]);
                 let delete_items = std::mem::replace(&mut self.delete_items, vec![]);
 
-                let mut remove_expressions = remove_items.into_iter().map(|name| {
-                    let placeholder = format!(":value{}", ::raiden::generate_value_id());
-                    let attr_name = format!("#{}", name.into_attr_name());
-                    let val = format!("{}", attr_name);
-                    attr_names.insert(attr_name, name.into_attr_name());
-                    val
+                let mut remove_expressions = remove_items.into_iter().map(|path| {
+                    attr_names.extend(path.attribute_names());
+                    path.expression()
                 }).collect::<Vec<String>>();
 
                 let mut set_expressions = vec![];
@@ -259,7 +335,38 @@ pub(crate) fn expand_update_item(
             }
 
 
-            pub async fn run(mut self) -> Result<::raiden::update::UpdateOutput<#struct_name>, ::raiden::RaidenError> {
+            pub async fn run(self) -> Result<::raiden::update::UpdateOutput<#struct_name>, ::raiden::RaidenError> {
+                let has_return_values = self.input.return_values.is_some();
+                let res = self.run_raw().await?;
+
+                let item = if has_return_values {
+                    let res_item = res.attributes.unwrap();
+                    Some(<#struct_name as ::raiden::RaidenItem>::from_item(res_item)?)
+                } else {
+                    None
+                };
+
+                Ok(::raiden::update::UpdateOutput {
+                    item,
+                    consumed_capacity: res.consumed_capacity,
+                    item_collection_metrics: res.item_collection_metrics,
+                })
+            }
+
+            async fn run_raw(mut self) -> Result<::raiden::UpdateItemResponse, ::raiden::RaidenError> {
+                let version_condition = self.version_condition.take();
+                if let Some(version_condition) = &version_condition {
+                    let (expression, names, values) = ::raiden::version::apply_version_condition(
+                        self.input.condition_expression.take(),
+                        self.input.expression_attribute_names.take(),
+                        self.input.expression_attribute_values.take(),
+                        &version_condition,
+                    );
+                    self.input.condition_expression = Some(expression);
+                    self.input.expression_attribute_names = names;
+                    self.input.expression_attribute_values = values;
+                }
+
                 let (expression, names, values) = self.build_expression();
                 if self.input.expression_attribute_names.is_none() {
                     if names.is_empty() {
@@ -285,40 +392,28 @@ pub(crate) fn expand_update_item(
                     self.input.update_expression = Some(expression);
                 }
 
-                let has_return_values = self.input.return_values.is_some();
                 let input = self.input.clone();
                 let client = self.client.clone();
-                let policy: ::raiden::RetryPolicy = self.policy.into();
 
-                let res = policy.retry_if(move || {
+                let res = ::raiden::retry_with_backend(self.policy, &client, self.condition, move |client| {
                     let input = input.clone();
-                    let client = client.clone();
                     async { #call_inner_run }
-                }, self.condition).await?;
-
-
-                let item = if has_return_values {
-                    let mut res_item = res.attributes.unwrap();
-                    Some(#struct_name {
-                        #(#from_item)*
-                    })
-                } else {
-                    None
-                };
-
-                Ok(::raiden::update::UpdateOutput {
-                    item,
-                    consumed_capacity: res.consumed_capacity,
-                    item_collection_metrics: res.item_collection_metrics,
-                })
+                }).await;
+                match (res, &version_condition) {
+                    (Err(e), Some(version_condition)) => {
+                        let (table_name, key) = (self.input.table_name.clone(), self.input.key.clone());
+                        Err(::raiden::version::into_version_conflict(e, version_condition, &client, table_name, key).await)
+                    }
+                    (res, _) => res,
+                }
             }
 
             async fn inner_run(
                 #inner_run_args
-                client: ::raiden::DynamoDbClient,
+                client: ::raiden::SharedBackend,
                 input: ::raiden::UpdateItemInput,
-            ) -> Result<::raiden::UpdateItemOutput, ::raiden::RaidenError> {
-                Ok(#api_call_token?)
+            ) -> Result<::raiden::UpdateItemResponse, ::raiden::RaidenError> {
+                #api_call_token
             }
         }
 
@@ -0,0 +1,271 @@
+use quote::*;
+
+/// Expands `#[derive(RaidenAttribute)]` for a newtype, which is stored as its inner value, or for
+/// a unit enum, which is stored as the variant name or with `#[raiden(as_number)]` as its discriminant.
+pub(crate) fn expand_raiden_attribute(
+    input: &syn::DeriveInput,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let mut errors = crate::validate::Errors::default();
+    crate::validate::validate_attrs(&input.attrs, crate::validate::ATTRIBUTE_KEYS, &mut errors);
+    if let syn::Data::Enum(data) = &input.data {
+        for variant in data.variants.iter() {
+            crate::validate::validate_attrs(
+                &variant.attrs,
+                crate::validate::VARIANT_KEYS,
+                &mut errors,
+            );
+        }
+    }
+    errors.finish()?;
+
+    let as_number = crate::finder::find_unary_path(&input.attrs, "as_number");
+    match &input.data {
+        syn::Data::Struct(data) => {
+            if let Some(as_number) = as_number {
+                return Err(syn::Error::new_spanned(
+                    as_number,
+                    "#[raiden(as_number)] is only supported on enums",
+                ));
+            }
+            expand_newtype(input, &data.fields)
+        }
+        syn::Data::Enum(data) if as_number.is_some() => expand_number_enum(&input.ident, data),
+        syn::Data::Enum(data) => expand_string_enum(&input.ident, &input.attrs, data),
+        syn::Data::Union(data) => Err(syn::Error::new_spanned(
+            data.union_token,
+            "RaidenAttribute does not support unions",
+        )),
+    }
+}
+
+fn expand_newtype(
+    input: &syn::DeriveInput,
+    fields: &syn::Fields,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let inner = match fields {
+        syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0].ty,
+        syn::Fields::Unit => {
+            return Err(syn::Error::new_spanned(
+                name,
+                format!("RaidenAttribute supports a struct with a single unnamed field, e.g. `{name}(String)`"),
+            ))
+        }
+        fields => {
+            return Err(syn::Error::new_spanned(
+                fields,
+                format!("RaidenAttribute supports a struct with a single unnamed field, e.g. `{name}(String)`"),
+            ))
+        }
+    };
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let predicates = where_clause.map(|where_clause| &where_clause.predicates);
+
+    // The set item impls only apply when the inner type is an item of a set, which is
+    // checked where they are used thanks to the higher-ranked bound. The set is a number
+    // set when the inner type is a number.
+    Ok(quote! {
+        impl #impl_generics ::raiden::IntoAttribute for #name #ty_generics
+        where
+            #inner: ::raiden::IntoAttribute,
+            #predicates
+        {
+            fn into_attr(self) -> ::raiden::AttributeValue {
+                ::raiden::IntoAttribute::into_attr(self.0)
+            }
+        }
+
+        impl #impl_generics ::raiden::FromAttribute for #name #ty_generics
+        where
+            #inner: ::raiden::FromAttribute,
+            #predicates
+        {
+            fn from_attr(
+                value: Option<::raiden::AttributeValue>,
+            ) -> Result<Self, ::raiden::ConversionError> {
+                Ok(Self(::raiden::FromAttribute::from_attr(value)?))
+            }
+        }
+
+        impl #impl_generics ::raiden::KeyAttribute for #name #ty_generics
+        where
+            #inner: ::raiden::KeyAttribute,
+            #predicates
+        {
+            const KEY_TYPE: ::raiden::AttributeType = <#inner as ::raiden::KeyAttribute>::KEY_TYPE;
+        }
+
+        impl #impl_generics ::raiden::IntoStringSetItem for #name #ty_generics
+        where
+            for<'__raiden> #inner: ::raiden::IntoStringSetItem,
+            #predicates
+        {
+            const IS_NUMBER: bool = <#inner as ::raiden::IntoStringSetItem>::IS_NUMBER;
+
+            fn into_ss_item(self) -> String {
+                ::raiden::IntoStringSetItem::into_ss_item(self.0)
+            }
+        }
+
+        impl #impl_generics ::raiden::FromStringSetItem for #name #ty_generics
+        where
+            for<'__raiden> #inner: ::raiden::FromStringSetItem,
+            #predicates
+        {
+            const IS_NUMBER: bool = <#inner as ::raiden::FromStringSetItem>::IS_NUMBER;
+
+            fn from_ss_item(value: String) -> Result<Self, ::raiden::ConversionError> {
+                Ok(Self(::raiden::FromStringSetItem::from_ss_item(value)?))
+            }
+        }
+    })
+}
+
+fn unit_variants(name: &syn::Ident, data: &syn::DataEnum) -> syn::Result<Vec<syn::Variant>> {
+    let mut errors = crate::validate::Errors::default();
+    for variant in data.variants.iter() {
+        if !matches!(variant.fields, syn::Fields::Unit) {
+            errors.push(syn::Error::new_spanned(
+                &variant.fields,
+                format!(
+                    "RaidenAttribute supports enums of unit variants, but `{}::{}` holds fields",
+                    name, variant.ident
+                ),
+            ));
+        }
+    }
+    errors.finish()?;
+
+    Ok(data.variants.iter().cloned().collect())
+}
+
+fn expand_string_enum(
+    name: &syn::Ident,
+    attrs: &[syn::Attribute],
+    data: &syn::DataEnum,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let rename_all_type = crate::finder::find_rename_all_type(attrs)?;
+    let variants: Vec<(syn::Ident, String)> = unit_variants(name, data)?
+        .into_iter()
+        .map(|variant| {
+            let value = crate::rename::create_renamed_variant(
+                variant.ident.to_string(),
+                crate::finder::find_rename_value(&variant.attrs),
+                rename_all_type,
+            );
+            (variant.ident, value)
+        })
+        .collect();
+    let into_arms = variants.iter().map(|(ident, value)| {
+        quote! { #name::#ident => #value, }
+    });
+    let from_arms = variants.iter().map(|(ident, value)| {
+        quote! { #value => Ok(#name::#ident), }
+    });
+
+    Ok(quote! {
+        impl ::raiden::KeyAttribute for #name {
+            const KEY_TYPE: ::raiden::AttributeType = ::raiden::AttributeType::S;
+        }
+
+        impl ::raiden::IntoStringSetItem for #name {
+            fn into_ss_item(self) -> String {
+                match self {
+                    #(#into_arms)*
+                }
+                .to_owned()
+            }
+        }
+
+        impl ::raiden::FromStringSetItem for #name {
+            fn from_ss_item(value: String) -> Result<Self, ::raiden::ConversionError> {
+                match value.as_str() {
+                    #(#from_arms)*
+                    _ => Err(::raiden::ConversionError::message(format!(
+                        "unknown {} `{}`",
+                        stringify!(#name),
+                        value
+                    ))),
+                }
+            }
+        }
+
+        impl ::raiden::IntoAttribute for #name {
+            fn into_attr(self) -> ::raiden::AttributeValue {
+                ::raiden::IntoAttribute::into_attr(::raiden::IntoStringSetItem::into_ss_item(self))
+            }
+        }
+
+        impl ::raiden::FromAttribute for #name {
+            fn from_attr(
+                value: Option<::raiden::AttributeValue>,
+            ) -> Result<Self, ::raiden::ConversionError> {
+                let value: String = ::raiden::FromAttribute::from_attr(value)?;
+                ::raiden::FromStringSetItem::from_ss_item(value)
+            }
+        }
+    })
+}
+
+fn expand_number_enum(
+    name: &syn::Ident,
+    data: &syn::DataEnum,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let idents: Vec<syn::Ident> = unit_variants(name, data)?
+        .into_iter()
+        .map(|variant| variant.ident)
+        .collect();
+
+    Ok(quote! {
+        impl #name {
+            fn __raiden_from_number(value: i64) -> Result<Self, ::raiden::ConversionError> {
+                #(
+                    if value == #name::#idents as i64 {
+                        return Ok(#name::#idents);
+                    }
+                )*
+                Err(::raiden::ConversionError::message(format!(
+                    "unknown {} `{}`",
+                    stringify!(#name),
+                    value
+                )))
+            }
+        }
+
+        impl ::raiden::KeyAttribute for #name {
+            const KEY_TYPE: ::raiden::AttributeType = ::raiden::AttributeType::N;
+        }
+
+        impl ::raiden::IntoStringSetItem for #name {
+            const IS_NUMBER: bool = true;
+
+            fn into_ss_item(self) -> String {
+                (self as i64).to_string()
+            }
+        }
+
+        impl ::raiden::FromStringSetItem for #name {
+            const IS_NUMBER: bool = true;
+
+            fn from_ss_item(value: String) -> Result<Self, ::raiden::ConversionError> {
+                let value: i64 = value.parse().map_err(|_| ::raiden::ConversionError::ParseInt)?;
+                Self::__raiden_from_number(value)
+            }
+        }
+
+        impl ::raiden::IntoAttribute for #name {
+            fn into_attr(self) -> ::raiden::AttributeValue {
+                ::raiden::IntoAttribute::into_attr(self as i64)
+            }
+        }
+
+        impl ::raiden::FromAttribute for #name {
+            fn from_attr(
+                value: Option<::raiden::AttributeValue>,
+            ) -> Result<Self, ::raiden::ConversionError> {
+                let value: i64 = ::raiden::FromAttribute::from_attr(value)?;
+                Self::__raiden_from_number(value)
+            }
+        }
+    })
+}
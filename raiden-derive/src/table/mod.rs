@@ -0,0 +1,147 @@
+use syn::spanned::Spanned;
+use syn::*;
+
+use crate::finder::*;
+use crate::rename::RenameAllType;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum KeyType {
+    Hash,
+    Range,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ProjectionType {
+    All,
+    Include(Vec<String>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct GlobalSecondaryIndexDefinition {
+    pub name: String,
+    pub key_schema: Vec<(String, KeyType)>,
+    pub projection: ProjectionType,
+}
+
+/// Schema of the table derived from the model definition.
+///
+/// Attribute names are already renamed and attribute types are expressions of
+/// the scalar key type (`S` or `N`) of each key.
+#[derive(Debug, Clone)]
+pub(crate) struct TableDefinition {
+    pub key_schema: Vec<(String, KeyType)>,
+    pub attribute_definitions: Vec<(String, proc_macro2::TokenStream)>,
+    pub global_secondary_indexes: Vec<GlobalSecondaryIndexDefinition>,
+    pub time_to_live: Option<String>,
+}
+
+// The scalar attribute type of a key comes from the `KeyAttribute` impl of its field type,
+// so that newtypes and enums are stored as what they convert to. A type without one is
+// reported at the field.
+fn key_attribute_type(ty: &Type) -> proc_macro2::TokenStream {
+    quote::quote_spanned! { ty.span() => <#ty as ::raiden::KeyAttribute>::KEY_TYPE }
+}
+
+fn find_field<'a>(fields: &'a FieldsNamed, field_name: &str) -> &'a Field {
+    fields
+        .named
+        .iter()
+        .find(|f| f.ident.as_ref().is_some_and(|ident| ident == field_name))
+        .expect("gsi keys should be validated")
+}
+
+fn attr_name_of(field: &Field, rename_all_type: RenameAllType) -> String {
+    crate::rename::create_renamed(
+        field.ident.as_ref().unwrap().to_string(),
+        find_rename_value(&field.attrs),
+        rename_all_type,
+    )
+}
+
+pub(crate) fn build_table_definition(
+    fields: &FieldsNamed,
+    rename_all_type: RenameAllType,
+    partition_key: &(LitStr, Type),
+    sort_key: &Option<(LitStr, Type)>,
+    gsi_definitions: &[GsiDefinition],
+    ttl: &Option<crate::ttl::TtlField>,
+) -> TableDefinition {
+    let mut attribute_definitions: Vec<(String, proc_macro2::TokenStream)> = vec![];
+    let mut define = |name: &str, ty: &Type| {
+        if !attribute_definitions.iter().any(|(n, _)| n == name) {
+            attribute_definitions.push((name.to_owned(), key_attribute_type(ty)));
+        }
+    };
+
+    let (partition_key_name, partition_key_type) = partition_key;
+    let mut key_schema = vec![(partition_key_name.value(), KeyType::Hash)];
+    define(&partition_key_name.value(), partition_key_type);
+
+    if let Some((sort_key_name, sort_key_type)) = sort_key {
+        key_schema.push((sort_key_name.value(), KeyType::Range));
+        define(&sort_key_name.value(), sort_key_type);
+    }
+
+    let mut global_secondary_indexes = vec![];
+
+    // Indexes declared only by name (`#[raiden(gsi = "...")]`) have no key schema,
+    // so they can not be created from the model.
+    for gsi in gsi_definitions {
+        let Some(gsi_partition_key) = gsi.partition_key.as_ref() else {
+            continue;
+        };
+        if global_secondary_indexes
+            .iter()
+            .any(|index: &GlobalSecondaryIndexDefinition| index.name == gsi.name)
+        {
+            continue;
+        }
+
+        let mut gsi_key_schema = vec![];
+        let field = find_field(fields, gsi_partition_key);
+        let name = attr_name_of(field, rename_all_type);
+        define(&name, &field.ty);
+        gsi_key_schema.push((name, KeyType::Hash));
+
+        for sort_key in gsi.sort_keys.iter() {
+            let field = find_field(fields, sort_key);
+            let name = attr_name_of(field, rename_all_type);
+            define(&name, &field.ty);
+            gsi_key_schema.push((name, KeyType::Range));
+        }
+
+        let omitted = fields
+            .named
+            .iter()
+            .any(|f| find_omit_gsi_names(&f.attrs).contains(&gsi.name));
+        let projection = if omitted {
+            // Table and index keys are always projected, so only list the rest.
+            let non_key_attributes = fields
+                .named
+                .iter()
+                .filter(|f| !find_omit_gsi_names(&f.attrs).contains(&gsi.name))
+                .map(|f| attr_name_of(f, rename_all_type))
+                .filter(|name| {
+                    !key_schema.iter().any(|(key, _)| key == name)
+                        && !gsi_key_schema.iter().any(|(key, _)| key == name)
+                })
+                .collect();
+            ProjectionType::Include(non_key_attributes)
+        } else {
+            ProjectionType::All
+        };
+
+        global_secondary_indexes.push(GlobalSecondaryIndexDefinition {
+            name: gsi.name.clone(),
+            key_schema: gsi_key_schema,
+            projection,
+        });
+    }
+
+    TableDefinition {
+        key_schema,
+        attribute_definitions,
+        global_secondary_indexes,
+        time_to_live: ttl.as_ref().map(|ttl| ttl.attr_name.clone()),
+    }
+}
@@ -0,0 +1,110 @@
+use proc_macro2::*;
+use quote::*;
+use syn::*;
+
+use crate::finder::*;
+use crate::rename::RenameAllType;
+
+/// A field marked with `#[raiden(created_at)]` or `#[raiden(updated_at)]`.
+#[derive(Clone)]
+pub(crate) struct TimestampField {
+    pub ident: Ident,
+    pub ty: Type,
+    pub attr_name: String,
+}
+
+/// The timestamp fields which are filled by raiden instead of the caller.
+#[derive(Clone, Default)]
+pub(crate) struct Timestamps {
+    pub created_at: Option<TimestampField>,
+    pub updated_at: Option<TimestampField>,
+}
+
+impl Timestamps {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.created_at.is_none() && self.updated_at.is_none()
+    }
+
+    fn find(&self, field: &Field) -> Option<&TimestampField> {
+        [&self.created_at, &self.updated_at]
+            .into_iter()
+            .flatten()
+            .find(|timestamp| field.ident.as_ref() == Some(&timestamp.ident))
+    }
+
+    pub(crate) fn is(&self, field: &Field) -> bool {
+        self.find(field).is_some()
+    }
+
+    /// Reads the current time once, so that every timestamp of an item agrees.
+    pub(crate) fn expand_now(&self, struct_name: &Ident) -> TokenStream {
+        if self.is_empty() {
+            return quote! {};
+        }
+
+        quote! {
+            let now = <#struct_name as ::raiden::Clock>::now();
+        }
+    }
+
+    /// The value of the timestamp `field` at `now`.
+    pub(crate) fn expand_value(&self, field: &Field) -> Option<TokenStream> {
+        let TimestampField { ty, .. } = self.find(field)?;
+
+        Some(quote! {
+            <#ty as ::raiden::Timestamp>::from_system_time(now)
+        })
+    }
+
+    /// Pushes the `SET` actions which keep `created_at` and refresh `updated_at` into `self.set_items`.
+    pub(crate) fn expand_update_items(&self, struct_name: &Ident) -> TokenStream {
+        let now = self.expand_now(struct_name);
+        let items = [(&self.created_at, true), (&self.updated_at, false)]
+            .into_iter()
+            .filter_map(|(field, if_not_exists)| {
+                let TimestampField { ty, attr_name, .. } = field.as_ref()?;
+                Some(quote! {
+                    ::raiden::clock::push_timestamp(
+                        &mut self.set_items,
+                        #attr_name,
+                        <#ty as ::raiden::Timestamp>::from_system_time(now).into_attr(),
+                        #if_not_exists,
+                        &mut self.placeholders,
+                    );
+                })
+            });
+
+        quote! {
+            #now
+            #(#items)*
+        }
+    }
+}
+
+pub(crate) fn fetch_timestamps(
+    fields: &FieldsNamed,
+    rename_all_type: RenameAllType,
+) -> syn::Result<Timestamps> {
+    let fetch = |name: &str| -> syn::Result<Option<TimestampField>> {
+        let Some(field) = find_timestamp_field(fields, name)? else {
+            return Ok(None);
+        };
+        let ident = field.ident.clone().unwrap();
+        let attr_name = crate::rename::create_renamed(
+            ident.to_string(),
+            find_rename_value(&field.attrs),
+            rename_all_type,
+        );
+
+        Ok(Some(TimestampField {
+            ident,
+            ty: field.ty,
+            attr_name,
+        }))
+    };
+
+    Ok(Timestamps {
+        created_at: fetch("created_at")?,
+        updated_at: fetch("updated_at")?,
+    })
+}
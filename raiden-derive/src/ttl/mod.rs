@@ -0,0 +1,29 @@
+use proc_macro2::*;
+use syn::*;
+
+use crate::finder::*;
+use crate::rename::RenameAllType;
+
+/// The field marked with `#[raiden(ttl)]`.
+#[derive(Clone)]
+pub(crate) struct TtlField {
+    pub ident: Ident,
+    pub attr_name: String,
+}
+
+pub(crate) fn fetch_ttl(
+    fields: &FieldsNamed,
+    rename_all_type: RenameAllType,
+) -> syn::Result<Option<TtlField>> {
+    let Some(field) = find_ttl_field(fields)? else {
+        return Ok(None);
+    };
+    let ident = field.ident.clone().unwrap();
+    let attr_name = crate::rename::create_renamed(
+        ident.to_string(),
+        find_rename_value(&field.attrs),
+        rename_all_type,
+    );
+
+    Ok(Some(TtlField { ident, attr_name }))
+}
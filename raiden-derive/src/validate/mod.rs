@@ -0,0 +1,200 @@
+use syn::{punctuated::Punctuated, Expr, ExprLit, Lit, Meta, Token};
+
+/// Collects the errors of a derive input, so that all of them are reported at once.
+#[derive(Default)]
+pub(crate) struct Errors(Option<syn::Error>);
+
+impl Errors {
+    pub(crate) fn push(&mut self, error: syn::Error) {
+        match &mut self.0 {
+            Some(errors) => errors.combine(error),
+            None => self.0 = Some(error),
+        }
+    }
+
+    /// Records the error of `result`, if any.
+    pub(crate) fn check<T>(&mut self, result: syn::Result<T>) -> Option<T> {
+        result.map_err(|error| self.push(error)).ok()
+    }
+
+    pub(crate) fn finish(self) -> syn::Result<()> {
+        self.0.map_or(Ok(()), Err)
+    }
+}
+
+/// How a key is written in `#[raiden(...)]`.
+#[derive(Clone, Copy)]
+pub(crate) enum Kind {
+    /// `#[raiden(key)]`
+    Flag,
+    /// `#[raiden(key = "...")]`
+    Str,
+    /// `#[raiden(gsi = "...")]` or `#[raiden(gsi(name = "...", ...))]`
+    Gsi,
+}
+
+use Kind::*;
+
+pub(crate) const MODEL_KEYS: &[(&str, Kind)] = &[
+    ("table_name", Str),
+    ("rename_all", Str),
+    ("gsi", Gsi),
+    ("partition_key", Str),
+    ("sort_key", Str),
+    ("partition_key_attr", Str),
+    ("sort_key_attr", Str),
+];
+
+pub(crate) const MODEL_FIELD_KEYS: &[(&str, Kind)] = &[
+    ("partition_key", Flag),
+    ("sort_key", Flag),
+    ("rename", Str),
+    ("uuid", Flag),
+    ("use_default", Flag),
+    ("version", Flag),
+    ("created_at", Flag),
+    ("updated_at", Flag),
+    ("ttl", Flag),
+    ("omit_gsi", Str),
+];
+
+pub(crate) const ENTITY_KEYS: &[(&str, Kind)] = &[
+    ("table_name", Str),
+    ("tag", Str),
+    ("partition_key", Str),
+    ("sort_key", Str),
+    ("rename_all", Str),
+];
+
+pub(crate) const INDEX_KEYS: &[(&str, Kind)] =
+    &[("source", Str), ("gsi", Gsi), ("rename_all", Str)];
+
+pub(crate) const INDEX_FIELD_KEYS: &[(&str, Kind)] = &[("rename", Str), ("use_default", Flag)];
+
+pub(crate) const ATTRIBUTE_KEYS: &[(&str, Kind)] = &[("rename_all", Str), ("as_number", Flag)];
+
+pub(crate) const VARIANT_KEYS: &[(&str, Kind)] = &[("rename", Str)];
+
+const GSI_KEYS: &[(&str, Kind)] = &[("name", Str), ("partition_key", Str), ("sort_key", Str)];
+
+/// Checks that every key in the `#[raiden(...)]` attributes is one of `keys`, in its form.
+pub(crate) fn validate_attrs(attrs: &[syn::Attribute], keys: &[(&str, Kind)], errors: &mut Errors) {
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("raiden")) {
+        let list = match &attr.meta {
+            Meta::List(list) => list,
+            meta => {
+                errors.push(syn::Error::new_spanned(
+                    meta,
+                    "expected an attribute list, e.g. `#[raiden(...)]`",
+                ));
+                continue;
+            }
+        };
+        match list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated) {
+            Ok(metas) => validate_metas(&metas, keys, errors),
+            Err(error) => errors.push(error),
+        }
+    }
+}
+
+fn validate_metas(metas: &Punctuated<Meta, Token![,]>, keys: &[(&str, Kind)], errors: &mut Errors) {
+    for meta in metas {
+        let path = meta.path();
+        let Some((name, kind)) = keys.iter().find(|(name, _)| path.is_ident(name)) else {
+            let expected = keys
+                .iter()
+                .map(|(name, _)| format!("`{name}`"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            errors.push(syn::Error::new_spanned(
+                path,
+                format!(
+                    "unknown raiden attribute `{}`, expected one of {expected}",
+                    quote::quote!(#path)
+                ),
+            ));
+            continue;
+        };
+        match (kind, meta) {
+            (Flag, Meta::Path(_)) => {}
+            (Flag, _) => errors.push(syn::Error::new_spanned(
+                meta,
+                format!("`{name}` does not take a value, e.g. `#[raiden({name})]`"),
+            )),
+            (Str | Gsi, Meta::NameValue(name_value)) if is_str(&name_value.value) => {}
+            (Gsi, Meta::List(list)) => {
+                match list.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated) {
+                    Ok(metas) => {
+                        if !metas.iter().any(|meta| meta.path().is_ident("name")) {
+                            errors.push(syn::Error::new_spanned(
+                                list,
+                                "gsi definition requires a name, e.g. `gsi(name = \"...\", partition_key = \"...\")`",
+                            ));
+                        }
+                        validate_metas(&metas, GSI_KEYS, errors);
+                    }
+                    Err(error) => errors.push(error),
+                }
+            }
+            (Str, _) => errors.push(syn::Error::new_spanned(
+                meta,
+                format!("`{name}` expects a string, e.g. `#[raiden({name} = \"...\")]`"),
+            )),
+            (Gsi, _) => errors.push(syn::Error::new_spanned(
+                meta,
+                format!("`{name}` expects an index name or a definition, e.g. `gsi = \"...\"` or `gsi(name = \"...\", partition_key = \"...\")`"),
+            )),
+        }
+    }
+}
+
+fn is_str(expr: &Expr) -> bool {
+    matches!(
+        expr,
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(_),
+            ..
+        })
+    )
+}
+
+/// Checks that the keys of the gsi definitions name fields of the model.
+pub(crate) fn validate_gsi_keys(
+    attrs: &[syn::Attribute],
+    fields: &syn::FieldsNamed,
+    errors: &mut Errors,
+) {
+    for lit in crate::finder::find_gsi_key_lits(attrs) {
+        let name = lit.value();
+        let known = fields
+            .named
+            .iter()
+            .any(|f| f.ident.as_ref().is_some_and(|ident| ident == &name));
+        if !known {
+            errors.push(syn::Error::new_spanned(
+                &lit,
+                format!("unknown field `{name}` for gsi key definition"),
+            ));
+        }
+    }
+}
+
+/// Checks that `omit_gsi` names an index declared on the model.
+pub(crate) fn validate_omit_gsi(
+    fields: &syn::FieldsNamed,
+    gsi_names: &[String],
+    errors: &mut Errors,
+) {
+    for field in fields.named.iter() {
+        let ident = field.ident.as_ref().expect("named field");
+        for lit in crate::finder::find_lit_strs(&field.attrs, "omit_gsi") {
+            let gsi_name = lit.value();
+            if !gsi_names.iter().any(|known_name| known_name == &gsi_name) {
+                errors.push(syn::Error::new_spanned(
+                    &lit,
+                    format!("unknown gsi `{gsi_name}` specified in omit_gsi for field `{ident}`"),
+                ));
+            }
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use proc_macro2::*;
+use quote::*;
+use syn::*;
+
+use crate::finder::*;
+use crate::rename::RenameAllType;
+
+/// The field marked with `#[raiden(version)]` for optimistic locking.
+#[derive(Clone)]
+pub(crate) struct VersionField {
+    pub ident: Ident,
+    pub ty: Type,
+    pub attr_name: String,
+}
+
+impl VersionField {
+    pub(crate) fn is(&self, field: &Field) -> bool {
+        field.ident.as_ref() == Some(&self.ident)
+    }
+}
+
+/// The argument of `update` and `delete` of a versioned model, taking the version the caller
+/// last read.
+pub(crate) fn expand_expected_version_arg(version: &Option<VersionField>) -> TokenStream {
+    match version {
+        Some(VersionField { ty, .. }) => quote! { , expected_version: #ty },
+        None => quote! {},
+    }
+}
+
+/// Binds `placeholders`, and the `version_condition` of `update` and `delete` which checks
+/// their `expected_version` argument.
+pub(crate) fn expand_expected_version_condition(version: &Option<VersionField>) -> TokenStream {
+    match version {
+        Some(VersionField { attr_name, .. }) => quote! {
+            let mut placeholders = ::raiden::Placeholders::new();
+            let version_condition = Some(::raiden::version::version_condition(
+                #attr_name,
+                Some(::raiden::IntoAttribute::into_attr(expected_version)),
+                &mut placeholders,
+            ));
+        },
+        None => quote! {
+            let placeholders = ::raiden::Placeholders::new();
+            let version_condition = None;
+        },
+    }
+}
+
+/// Binds `placeholders`, and the `version_condition` and `next_version` of a put of `item`.
+///
+/// A new item is expected when the version is zero.
+pub(crate) fn expand_put_version(version: &Option<VersionField>) -> TokenStream {
+    match version {
+        Some(VersionField {
+            ident, attr_name, ..
+        }) => quote! {
+            let mut placeholders = ::raiden::Placeholders::new();
+            let version_condition = Some(::raiden::version::version_condition(
+                #attr_name,
+                if item.#ident == 0 { None } else { Some(::raiden::IntoAttribute::into_attr(item.#ident)) },
+                &mut placeholders,
+            ));
+            let next_version = item.#ident.checked_add(1);
+        },
+        None => quote! {
+            let placeholders = ::raiden::Placeholders::new();
+            let version_condition = None;
+        },
+    }
+}
+
+/// Records a `next_version` which overflowed in `key_error`, so that the put fails before its
+/// request is sent.
+pub(crate) fn expand_version_overflow(version: &Option<VersionField>) -> TokenStream {
+    match version {
+        Some(VersionField { attr_name, .. }) => quote! {
+            let key_error = key_error.or_else(|| {
+                next_version
+                    .is_none()
+                    .then(|| ::raiden::version::version_overflow(#attr_name))
+            });
+        },
+        None => quote! {},
+    }
+}
+
+pub(crate) fn fetch_version(
+    fields: &FieldsNamed,
+    rename_all_type: RenameAllType,
+) -> syn::Result<Option<VersionField>> {
+    let Some(field) = find_version_field(fields)? else {
+        return Ok(None);
+    };
+    let ident = field.ident.clone().unwrap();
+    let attr_name = crate::rename::create_renamed(
+        ident.to_string(),
+        find_rename_value(&field.attrs),
+        rename_all_type,
+    );
+
+    Ok(Some(VersionField {
+        ident,
+        ty: field.ty,
+        attr_name,
+    }))
+}
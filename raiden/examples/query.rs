@@ -11,6 +11,7 @@ pub struct QueryTestData0 {
     #[allow(dead_code)]
     id: String,
     name: String,
+    #[raiden(sort_key)]
     year: usize,
     num: usize,
 }
@@ -21,23 +22,23 @@ async fn example() {
         endpoint: "http://localhost:8000".into(),
         name: "ap-northeast-1".into(),
     });
-    let cond = QueryTestData0::key_condition(QueryTestData0::id())
+    let cond = QueryTestData0::partition_key_condition()
         .eq("id0")
-        .and(QueryTestData0::key_condition(QueryTestData0::year()).eq(1999));
+        .and(QueryTestData0::sort_key_condition().eq(1999));
     let res = client.query().key_condition(cond).run().await;
 
     dbg!(&res);
     assert!(res.is_ok());
 
-    let cond = QueryTestData0::key_condition(QueryTestData0::id())
+    let cond = QueryTestData0::partition_key_condition()
         .eq("id0")
-        .and(QueryTestData0::key_condition(QueryTestData0::year()).eq(1999));
+        .and(QueryTestData0::sort_key_condition().eq(1999));
     let res = client.query().key_condition(cond).run().await;
 
     dbg!(&res);
     assert!(res.is_ok());
 
-    let cond = QueryTestData0::key_condition(QueryTestData0::id()).eq("id0");
+    let cond = QueryTestData0::partition_key_condition().eq("id0");
     let filter = QueryTestData0::filter_expression(QueryTestData0::num()).eq(1000);
     let res = client
         .query()
@@ -61,23 +62,23 @@ async fn example() {
     .await;
     let sdk_client = ::raiden::Client::new(&sdk_config);
     let client = QueryTestData0::client_with(sdk_client);
-    let cond = QueryTestData0::key_condition(QueryTestData0::id())
+    let cond = QueryTestData0::partition_key_condition()
         .eq("id0")
-        .and(QueryTestData0::key_condition(QueryTestData0::year()).eq(1999));
+        .and(QueryTestData0::sort_key_condition().eq(1999));
     let res = client.query().key_condition(cond).run().await;
 
     dbg!(&res);
     assert!(res.is_ok());
 
-    let cond = QueryTestData0::key_condition(QueryTestData0::id())
+    let cond = QueryTestData0::partition_key_condition()
         .eq("id0")
-        .and(QueryTestData0::key_condition(QueryTestData0::year()).eq(1999));
+        .and(QueryTestData0::sort_key_condition().eq(1999));
     let res = client.query().key_condition(cond).run().await;
 
     dbg!(&res);
     assert!(res.is_ok());
 
-    let cond = QueryTestData0::key_condition(QueryTestData0::id()).eq("id0");
+    let cond = QueryTestData0::partition_key_condition().eq("id0");
     let filter = QueryTestData0::filter_expression(QueryTestData0::num()).eq(1000);
     let res = client
         .query()
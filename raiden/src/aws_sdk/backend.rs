@@ -0,0 +1,1109 @@
+use aws_smithy_types::error::operation::BuildError;
+
+use crate::{
+    aws_sdk::{
+        operation::{
+            batch_get_item::{builders::BatchGetItemInputBuilder, BatchGetItemOutput},
+            batch_write_item::{builders::BatchWriteItemInputBuilder, BatchWriteItemOutput},
+            create_table::{builders::CreateTableInputBuilder, CreateTableOutput},
+            delete_item::{builders::DeleteItemInputBuilder, DeleteItemOutput},
+            delete_table::{builders::DeleteTableInputBuilder, DeleteTableOutput},
+            describe_table::{builders::DescribeTableInputBuilder, DescribeTableOutput},
+            get_item::{builders::GetItemInputBuilder, GetItemOutput},
+            put_item::{builders::PutItemInputBuilder, PutItemOutput},
+            query::{builders::QueryInputBuilder, QueryOutput},
+            scan::{builders::ScanInputBuilder, ScanOutput},
+            transact_get_items::{builders::TransactGetItemsInputBuilder, TransactGetItemsOutput},
+            transact_write_items::{
+                builders::TransactWriteItemsInputBuilder, TransactWriteItemsOutput,
+            },
+            update_item::{builders::UpdateItemInputBuilder, UpdateItemOutput},
+            update_time_to_live::{builders::UpdateTimeToLiveInputBuilder, UpdateTimeToLiveOutput},
+        },
+        types,
+    },
+    backend::*,
+    AttributeType, Client, RaidenError, ReturnConsumedCapacity, ReturnItemCollectionMetrics,
+};
+
+macro_rules! backend {
+    ($($operation: ident => $request: ident($input: ty), $response: ident;)*) => {
+        impl DynamoBackend for Client {
+            $(
+                fn $operation(&self, request: $request) -> BackendFuture<'_, $response> {
+                    Box::pin(async move { Ok(<$input>::from(request).send_with(self).await?.into()) })
+                }
+            )*
+        }
+    };
+}
+
+backend! {
+    get_item => GetItemRequest(GetItemInputBuilder), GetItemResponse;
+    put_item => PutItemRequest(PutItemInputBuilder), PutItemResponse;
+    update_item => UpdateItemRequest(UpdateItemInputBuilder), UpdateItemResponse;
+    delete_item => DeleteItemRequest(DeleteItemInputBuilder), DeleteItemResponse;
+    query => QueryRequest(QueryInputBuilder), QueryResponse;
+    scan => ScanRequest(ScanInputBuilder), ScanResponse;
+    batch_get_item => BatchGetItemRequest(BatchGetItemInputBuilder), BatchGetItemResponse;
+    batch_write_item => BatchWriteItemRequest(BatchWriteItemInputBuilder), BatchWriteItemResponse;
+    transact_get_items => TransactGetItemsRequest(TransactGetItemsInputBuilder), TransactGetItemsResponse;
+    transact_write_items => TransactWriteItemsRequest(TransactWriteItemsInputBuilder), TransactWriteItemsResponse;
+    create_table => CreateTableRequest(CreateTableInputBuilder), CreateTableResponse;
+    delete_table => DeleteTableRequest(DeleteTableInputBuilder), DeleteTableResponse;
+    describe_table => DescribeTableRequest(DescribeTableInputBuilder), DescribeTableResponse;
+    update_time_to_live => UpdateTimeToLiveRequest(UpdateTimeToLiveInputBuilder), UpdateTimeToLiveResponse;
+}
+
+// The generated builders hold the input builders of the SDK, which are converted into requests
+// when they are sent.
+
+fn build_error(error: BuildError) -> RaidenError {
+    RaidenError::Validation(error.to_string())
+}
+
+#[allow(clippy::result_large_err)]
+fn table_name(table_name: Option<String>) -> Result<String, RaidenError> {
+    table_name.ok_or_else(|| {
+        RaidenError::Validation("the table name of the request is not set".to_owned())
+    })
+}
+
+impl From<types::ReturnConsumedCapacity> for ReturnConsumedCapacity {
+    fn from(value: types::ReturnConsumedCapacity) -> Self {
+        match value {
+            types::ReturnConsumedCapacity::Indexes => Self::Indexes,
+            types::ReturnConsumedCapacity::Total => Self::Total,
+            _ => Self::None,
+        }
+    }
+}
+
+impl From<types::ReturnItemCollectionMetrics> for ReturnItemCollectionMetrics {
+    fn from(value: types::ReturnItemCollectionMetrics) -> Self {
+        match value {
+            types::ReturnItemCollectionMetrics::Size => Self::Size,
+            _ => Self::None,
+        }
+    }
+}
+
+impl From<types::ReturnValue> for ReturnValues {
+    fn from(value: types::ReturnValue) -> Self {
+        match value {
+            types::ReturnValue::AllOld => Self::AllOld,
+            types::ReturnValue::UpdatedOld => Self::UpdatedOld,
+            types::ReturnValue::AllNew => Self::AllNew,
+            types::ReturnValue::UpdatedNew => Self::UpdatedNew,
+            _ => Self::None,
+        }
+    }
+}
+
+impl From<ReturnValues> for types::ReturnValue {
+    fn from(value: ReturnValues) -> Self {
+        Self::from(value.as_str())
+    }
+}
+
+fn return_old_on_condition_failure(
+    value: Option<types::ReturnValuesOnConditionCheckFailure>,
+) -> bool {
+    value == Some(types::ReturnValuesOnConditionCheckFailure::AllOld)
+}
+
+fn return_values_on_condition_check_failure(
+    return_old: bool,
+) -> Option<types::ReturnValuesOnConditionCheckFailure> {
+    return_old.then_some(types::ReturnValuesOnConditionCheckFailure::AllOld)
+}
+
+impl TryFrom<GetItemInputBuilder> for GetItemRequest {
+    type Error = RaidenError;
+
+    fn try_from(builder: GetItemInputBuilder) -> Result<Self, Self::Error> {
+        let input = builder.build().map_err(build_error)?;
+        Ok(Self {
+            table_name: table_name(input.table_name)?,
+            key: input.key.unwrap_or_default(),
+            projection_expression: input.projection_expression,
+            expression_attribute_names: input.expression_attribute_names,
+            consistent_read: input.consistent_read,
+            return_consumed_capacity: input.return_consumed_capacity.map(Into::into),
+        })
+    }
+}
+
+impl From<GetItemRequest> for GetItemInputBuilder {
+    fn from(request: GetItemRequest) -> Self {
+        Self::default()
+            .table_name(request.table_name)
+            .set_key(Some(request.key))
+            .set_projection_expression(request.projection_expression)
+            .set_expression_attribute_names(request.expression_attribute_names)
+            .set_consistent_read(request.consistent_read)
+            .set_return_consumed_capacity(request.return_consumed_capacity.map(Into::into))
+    }
+}
+
+impl From<GetItemOutput> for GetItemResponse {
+    fn from(output: GetItemOutput) -> Self {
+        Self {
+            item: output.item,
+            consumed_capacity: output.consumed_capacity,
+        }
+    }
+}
+
+impl TryFrom<PutItemInputBuilder> for PutItemRequest {
+    type Error = RaidenError;
+
+    fn try_from(builder: PutItemInputBuilder) -> Result<Self, Self::Error> {
+        let input = builder.build().map_err(build_error)?;
+        Ok(Self {
+            table_name: table_name(input.table_name)?,
+            item: input.item.unwrap_or_default(),
+            condition_expression: input.condition_expression,
+            expression_attribute_names: input.expression_attribute_names,
+            expression_attribute_values: input.expression_attribute_values,
+            return_values: input.return_values.map(Into::into),
+            return_consumed_capacity: input.return_consumed_capacity.map(Into::into),
+            return_item_collection_metrics: input.return_item_collection_metrics.map(Into::into),
+            return_old_on_condition_failure: return_old_on_condition_failure(
+                input.return_values_on_condition_check_failure,
+            ),
+        })
+    }
+}
+
+impl From<PutItemRequest> for PutItemInputBuilder {
+    fn from(request: PutItemRequest) -> Self {
+        Self::default()
+            .table_name(request.table_name)
+            .set_item(Some(request.item))
+            .set_condition_expression(request.condition_expression)
+            .set_expression_attribute_names(request.expression_attribute_names)
+            .set_expression_attribute_values(request.expression_attribute_values)
+            .set_return_values(request.return_values.map(Into::into))
+            .set_return_consumed_capacity(request.return_consumed_capacity.map(Into::into))
+            .set_return_item_collection_metrics(
+                request.return_item_collection_metrics.map(Into::into),
+            )
+            .set_return_values_on_condition_check_failure(return_values_on_condition_check_failure(
+                request.return_old_on_condition_failure,
+            ))
+    }
+}
+
+impl From<PutItemOutput> for PutItemResponse {
+    fn from(output: PutItemOutput) -> Self {
+        Self {
+            attributes: output.attributes,
+            consumed_capacity: output.consumed_capacity,
+            item_collection_metrics: output.item_collection_metrics,
+        }
+    }
+}
+
+impl TryFrom<UpdateItemInputBuilder> for UpdateItemRequest {
+    type Error = RaidenError;
+
+    fn try_from(builder: UpdateItemInputBuilder) -> Result<Self, Self::Error> {
+        let input = builder.build().map_err(build_error)?;
+        Ok(Self {
+            table_name: table_name(input.table_name)?,
+            key: input.key.unwrap_or_default(),
+            update_expression: input.update_expression,
+            condition_expression: input.condition_expression,
+            expression_attribute_names: input.expression_attribute_names,
+            expression_attribute_values: input.expression_attribute_values,
+            return_values: input.return_values.map(Into::into),
+            return_consumed_capacity: input.return_consumed_capacity.map(Into::into),
+            return_item_collection_metrics: input.return_item_collection_metrics.map(Into::into),
+            return_old_on_condition_failure: return_old_on_condition_failure(
+                input.return_values_on_condition_check_failure,
+            ),
+        })
+    }
+}
+
+impl From<UpdateItemRequest> for UpdateItemInputBuilder {
+    fn from(request: UpdateItemRequest) -> Self {
+        Self::default()
+            .table_name(request.table_name)
+            .set_key(Some(request.key))
+            .set_update_expression(request.update_expression)
+            .set_condition_expression(request.condition_expression)
+            .set_expression_attribute_names(request.expression_attribute_names)
+            .set_expression_attribute_values(request.expression_attribute_values)
+            .set_return_values(request.return_values.map(Into::into))
+            .set_return_consumed_capacity(request.return_consumed_capacity.map(Into::into))
+            .set_return_item_collection_metrics(
+                request.return_item_collection_metrics.map(Into::into),
+            )
+            .set_return_values_on_condition_check_failure(return_values_on_condition_check_failure(
+                request.return_old_on_condition_failure,
+            ))
+    }
+}
+
+impl From<UpdateItemOutput> for UpdateItemResponse {
+    fn from(output: UpdateItemOutput) -> Self {
+        Self {
+            attributes: output.attributes,
+            consumed_capacity: output.consumed_capacity,
+            item_collection_metrics: output.item_collection_metrics,
+        }
+    }
+}
+
+impl TryFrom<DeleteItemInputBuilder> for DeleteItemRequest {
+    type Error = RaidenError;
+
+    fn try_from(builder: DeleteItemInputBuilder) -> Result<Self, Self::Error> {
+        let input = builder.build().map_err(build_error)?;
+        Ok(Self {
+            table_name: table_name(input.table_name)?,
+            key: input.key.unwrap_or_default(),
+            condition_expression: input.condition_expression,
+            expression_attribute_names: input.expression_attribute_names,
+            expression_attribute_values: input.expression_attribute_values,
+            return_values: input.return_values.map(Into::into),
+            return_consumed_capacity: input.return_consumed_capacity.map(Into::into),
+            return_item_collection_metrics: input.return_item_collection_metrics.map(Into::into),
+            return_old_on_condition_failure: return_old_on_condition_failure(
+                input.return_values_on_condition_check_failure,
+            ),
+        })
+    }
+}
+
+impl From<DeleteItemRequest> for DeleteItemInputBuilder {
+    fn from(request: DeleteItemRequest) -> Self {
+        Self::default()
+            .table_name(request.table_name)
+            .set_key(Some(request.key))
+            .set_condition_expression(request.condition_expression)
+            .set_expression_attribute_names(request.expression_attribute_names)
+            .set_expression_attribute_values(request.expression_attribute_values)
+            .set_return_values(request.return_values.map(Into::into))
+            .set_return_consumed_capacity(request.return_consumed_capacity.map(Into::into))
+            .set_return_item_collection_metrics(
+                request.return_item_collection_metrics.map(Into::into),
+            )
+            .set_return_values_on_condition_check_failure(return_values_on_condition_check_failure(
+                request.return_old_on_condition_failure,
+            ))
+    }
+}
+
+impl From<DeleteItemOutput> for DeleteItemResponse {
+    fn from(output: DeleteItemOutput) -> Self {
+        Self {
+            attributes: output.attributes,
+            consumed_capacity: output.consumed_capacity,
+            item_collection_metrics: output.item_collection_metrics,
+        }
+    }
+}
+
+impl TryFrom<QueryInputBuilder> for QueryRequest {
+    type Error = RaidenError;
+
+    fn try_from(builder: QueryInputBuilder) -> Result<Self, Self::Error> {
+        let input = builder.build().map_err(build_error)?;
+        Ok(Self {
+            table_name: table_name(input.table_name)?,
+            index_name: input.index_name,
+            key_condition_expression: input.key_condition_expression,
+            filter_expression: input.filter_expression,
+            projection_expression: input.projection_expression,
+            expression_attribute_names: input.expression_attribute_names,
+            expression_attribute_values: input.expression_attribute_values,
+            exclusive_start_key: input.exclusive_start_key,
+            limit: input.limit.map(i64::from),
+            scan_index_forward: input.scan_index_forward,
+            consistent_read: input.consistent_read,
+            return_consumed_capacity: input.return_consumed_capacity.map(Into::into),
+        })
+    }
+}
+
+impl From<QueryRequest> for QueryInputBuilder {
+    fn from(request: QueryRequest) -> Self {
+        Self::default()
+            .table_name(request.table_name)
+            .set_index_name(request.index_name)
+            .set_key_condition_expression(request.key_condition_expression)
+            .set_filter_expression(request.filter_expression)
+            .set_projection_expression(request.projection_expression)
+            .set_expression_attribute_names(request.expression_attribute_names)
+            .set_expression_attribute_values(request.expression_attribute_values)
+            .set_exclusive_start_key(request.exclusive_start_key)
+            .set_limit(request.limit.map(limit))
+            .set_scan_index_forward(request.scan_index_forward)
+            .set_consistent_read(request.consistent_read)
+            .set_return_consumed_capacity(request.return_consumed_capacity.map(Into::into))
+    }
+}
+
+impl From<QueryOutput> for QueryResponse {
+    fn from(output: QueryOutput) -> Self {
+        Self {
+            items: output.items,
+            count: Some(output.count.into()),
+            scanned_count: Some(output.scanned_count.into()),
+            last_evaluated_key: output.last_evaluated_key,
+            consumed_capacity: output.consumed_capacity,
+        }
+    }
+}
+
+// DynamoDB caps the page size anyway, so a larger limit is only clamped.
+fn limit(limit: i64) -> i32 {
+    i32::try_from(limit).unwrap_or(i32::MAX)
+}
+
+impl TryFrom<ScanInputBuilder> for ScanRequest {
+    type Error = RaidenError;
+
+    fn try_from(builder: ScanInputBuilder) -> Result<Self, Self::Error> {
+        let input = builder.build().map_err(build_error)?;
+        Ok(Self {
+            table_name: table_name(input.table_name)?,
+            index_name: input.index_name,
+            filter_expression: input.filter_expression,
+            projection_expression: input.projection_expression,
+            expression_attribute_names: input.expression_attribute_names,
+            expression_attribute_values: input.expression_attribute_values,
+            exclusive_start_key: input.exclusive_start_key,
+            limit: input.limit.map(i64::from),
+            segment: input.segment.map(i64::from),
+            total_segments: input.total_segments.map(i64::from),
+            consistent_read: input.consistent_read,
+            return_consumed_capacity: input.return_consumed_capacity.map(Into::into),
+        })
+    }
+}
+
+impl From<ScanRequest> for ScanInputBuilder {
+    fn from(request: ScanRequest) -> Self {
+        Self::default()
+            .table_name(request.table_name)
+            .set_index_name(request.index_name)
+            .set_filter_expression(request.filter_expression)
+            .set_projection_expression(request.projection_expression)
+            .set_expression_attribute_names(request.expression_attribute_names)
+            .set_expression_attribute_values(request.expression_attribute_values)
+            .set_exclusive_start_key(request.exclusive_start_key)
+            .set_limit(request.limit.map(limit))
+            .set_segment(request.segment.map(limit))
+            .set_total_segments(request.total_segments.map(limit))
+            .set_consistent_read(request.consistent_read)
+            .set_return_consumed_capacity(request.return_consumed_capacity.map(Into::into))
+    }
+}
+
+impl From<ScanOutput> for ScanResponse {
+    fn from(output: ScanOutput) -> Self {
+        Self {
+            items: output.items,
+            count: Some(output.count.into()),
+            scanned_count: Some(output.scanned_count.into()),
+            last_evaluated_key: output.last_evaluated_key,
+            consumed_capacity: output.consumed_capacity,
+        }
+    }
+}
+
+impl From<types::KeysAndAttributes> for KeysAndAttributes {
+    fn from(keys: types::KeysAndAttributes) -> Self {
+        Self {
+            keys: keys.keys,
+            projection_expression: keys.projection_expression,
+            expression_attribute_names: keys.expression_attribute_names,
+            consistent_read: keys.consistent_read,
+        }
+    }
+}
+
+impl From<KeysAndAttributes> for types::KeysAndAttributes {
+    fn from(keys: KeysAndAttributes) -> Self {
+        types::KeysAndAttributes::builder()
+            .set_keys(Some(keys.keys))
+            .set_projection_expression(keys.projection_expression)
+            .set_expression_attribute_names(keys.expression_attribute_names)
+            .set_consistent_read(keys.consistent_read)
+            .build()
+            .expect("should be built")
+    }
+}
+
+impl TryFrom<BatchGetItemInputBuilder> for BatchGetItemRequest {
+    type Error = RaidenError;
+
+    fn try_from(builder: BatchGetItemInputBuilder) -> Result<Self, Self::Error> {
+        let input = builder.build().map_err(build_error)?;
+        Ok(Self {
+            request_items: input
+                .request_items
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(table_name, keys)| (table_name, keys.into()))
+                .collect(),
+            return_consumed_capacity: input.return_consumed_capacity.map(Into::into),
+        })
+    }
+}
+
+impl From<BatchGetItemRequest> for BatchGetItemInputBuilder {
+    fn from(request: BatchGetItemRequest) -> Self {
+        Self::default()
+            .set_request_items(Some(
+                request
+                    .request_items
+                    .into_iter()
+                    .map(|(table_name, keys)| (table_name, keys.into()))
+                    .collect(),
+            ))
+            .set_return_consumed_capacity(request.return_consumed_capacity.map(Into::into))
+    }
+}
+
+impl From<BatchGetItemOutput> for BatchGetItemResponse {
+    fn from(output: BatchGetItemOutput) -> Self {
+        Self {
+            responses: output.responses,
+            unprocessed_keys: output.unprocessed_keys.map(|unprocessed_keys| {
+                unprocessed_keys
+                    .into_iter()
+                    .map(|(table_name, keys)| (table_name, keys.into()))
+                    .collect()
+            }),
+            consumed_capacity: output.consumed_capacity,
+        }
+    }
+}
+
+// A write request without a put or a delete is invalid, and is dropped.
+fn write_requests(requests: Vec<types::WriteRequest>) -> impl Iterator<Item = WriteRequest> {
+    requests.into_iter().filter_map(
+        |request| match (request.put_request, request.delete_request) {
+            (Some(put), _) => Some(WriteRequest::Put { item: put.item }),
+            (None, Some(delete)) => Some(WriteRequest::Delete { key: delete.key }),
+            (None, None) => None,
+        },
+    )
+}
+
+impl From<WriteRequest> for types::WriteRequest {
+    fn from(request: WriteRequest) -> Self {
+        match request {
+            WriteRequest::Put { item } => types::WriteRequest::builder()
+                .put_request(
+                    types::PutRequest::builder()
+                        .set_item(Some(item))
+                        .build()
+                        .expect("should be built"),
+                )
+                .build(),
+            WriteRequest::Delete { key } => types::WriteRequest::builder()
+                .delete_request(
+                    types::DeleteRequest::builder()
+                        .set_key(Some(key))
+                        .build()
+                        .expect("should be built"),
+                )
+                .build(),
+        }
+    }
+}
+
+impl TryFrom<BatchWriteItemInputBuilder> for BatchWriteItemRequest {
+    type Error = RaidenError;
+
+    fn try_from(builder: BatchWriteItemInputBuilder) -> Result<Self, Self::Error> {
+        let input = builder.build().map_err(build_error)?;
+        Ok(Self {
+            request_items: input
+                .request_items
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(table_name, requests)| (table_name, write_requests(requests).collect()))
+                .collect(),
+            return_consumed_capacity: input.return_consumed_capacity.map(Into::into),
+            return_item_collection_metrics: input.return_item_collection_metrics.map(Into::into),
+        })
+    }
+}
+
+impl From<BatchWriteItemRequest> for BatchWriteItemInputBuilder {
+    fn from(request: BatchWriteItemRequest) -> Self {
+        Self::default()
+            .set_request_items(Some(
+                request
+                    .request_items
+                    .into_iter()
+                    .map(|(table_name, requests)| {
+                        (table_name, requests.into_iter().map(Into::into).collect())
+                    })
+                    .collect(),
+            ))
+            .set_return_consumed_capacity(request.return_consumed_capacity.map(Into::into))
+            .set_return_item_collection_metrics(
+                request.return_item_collection_metrics.map(Into::into),
+            )
+    }
+}
+
+impl From<BatchWriteItemOutput> for BatchWriteItemResponse {
+    fn from(output: BatchWriteItemOutput) -> Self {
+        Self {
+            unprocessed_items: output.unprocessed_items.map(|unprocessed_items| {
+                unprocessed_items
+                    .into_iter()
+                    .map(|(table_name, requests)| (table_name, write_requests(requests).collect()))
+                    .collect()
+            }),
+            item_collection_metrics: output.item_collection_metrics,
+            consumed_capacity: output.consumed_capacity,
+        }
+    }
+}
+
+// A transact item without an operation is invalid, and is dropped.
+fn transact_get_items(items: Vec<types::TransactGetItem>) -> Vec<TransactGetItem> {
+    items
+        .into_iter()
+        .filter_map(|item| item.get)
+        .map(|get| TransactGetItem {
+            table_name: get.table_name,
+            key: get.key,
+            projection_expression: get.projection_expression,
+            expression_attribute_names: get.expression_attribute_names,
+        })
+        .collect()
+}
+
+impl From<TransactGetItem> for types::TransactGetItem {
+    fn from(item: TransactGetItem) -> Self {
+        let get = types::Get::builder()
+            .table_name(item.table_name)
+            .set_key(Some(item.key))
+            .set_projection_expression(item.projection_expression)
+            .set_expression_attribute_names(item.expression_attribute_names)
+            .build()
+            .expect("should be built");
+        types::TransactGetItem::builder().get(get).build()
+    }
+}
+
+impl TryFrom<TransactGetItemsInputBuilder> for TransactGetItemsRequest {
+    type Error = RaidenError;
+
+    fn try_from(builder: TransactGetItemsInputBuilder) -> Result<Self, Self::Error> {
+        let input = builder.build().map_err(build_error)?;
+        Ok(Self {
+            transact_items: transact_get_items(input.transact_items.unwrap_or_default()),
+            return_consumed_capacity: input.return_consumed_capacity.map(Into::into),
+        })
+    }
+}
+
+impl From<TransactGetItemsRequest> for TransactGetItemsInputBuilder {
+    fn from(request: TransactGetItemsRequest) -> Self {
+        Self::default()
+            .set_transact_items(Some(
+                request.transact_items.into_iter().map(Into::into).collect(),
+            ))
+            .set_return_consumed_capacity(request.return_consumed_capacity.map(Into::into))
+    }
+}
+
+impl From<TransactGetItemsOutput> for TransactGetItemsResponse {
+    fn from(output: TransactGetItemsOutput) -> Self {
+        Self {
+            responses: output.responses.map(|responses| {
+                responses
+                    .into_iter()
+                    .map(|response| response.item)
+                    .collect()
+            }),
+            consumed_capacity: output.consumed_capacity,
+        }
+    }
+}
+
+// A transact item without an operation is invalid, and is dropped.
+fn transact_write_item(item: types::TransactWriteItem) -> Option<TransactWriteItem> {
+    if let Some(check) = item.condition_check {
+        return Some(TransactWriteItem::ConditionCheck {
+            table_name: check.table_name,
+            key: check.key,
+            condition_expression: check.condition_expression,
+            expression_attribute_names: check.expression_attribute_names,
+            expression_attribute_values: check.expression_attribute_values,
+            return_old_on_condition_failure: return_old_on_condition_failure(
+                check.return_values_on_condition_check_failure,
+            ),
+        });
+    }
+    if let Some(put) = item.put {
+        return Some(TransactWriteItem::Put {
+            table_name: put.table_name,
+            item: put.item,
+            condition_expression: put.condition_expression,
+            expression_attribute_names: put.expression_attribute_names,
+            expression_attribute_values: put.expression_attribute_values,
+            return_old_on_condition_failure: return_old_on_condition_failure(
+                put.return_values_on_condition_check_failure,
+            ),
+        });
+    }
+    if let Some(delete) = item.delete {
+        return Some(TransactWriteItem::Delete {
+            table_name: delete.table_name,
+            key: delete.key,
+            condition_expression: delete.condition_expression,
+            expression_attribute_names: delete.expression_attribute_names,
+            expression_attribute_values: delete.expression_attribute_values,
+            return_old_on_condition_failure: return_old_on_condition_failure(
+                delete.return_values_on_condition_check_failure,
+            ),
+        });
+    }
+    item.update.map(|update| TransactWriteItem::Update {
+        table_name: update.table_name,
+        key: update.key,
+        update_expression: update.update_expression,
+        condition_expression: update.condition_expression,
+        expression_attribute_names: update.expression_attribute_names,
+        expression_attribute_values: update.expression_attribute_values,
+        return_old_on_condition_failure: return_old_on_condition_failure(
+            update.return_values_on_condition_check_failure,
+        ),
+    })
+}
+
+impl From<TransactWriteItem> for types::TransactWriteItem {
+    fn from(item: TransactWriteItem) -> Self {
+        let builder = types::TransactWriteItem::builder();
+        let builder = match item {
+            TransactWriteItem::ConditionCheck {
+                table_name,
+                key,
+                condition_expression,
+                expression_attribute_names,
+                expression_attribute_values,
+                return_old_on_condition_failure,
+            } => builder.condition_check(
+                types::ConditionCheck::builder()
+                    .table_name(table_name)
+                    .set_key(Some(key))
+                    .condition_expression(condition_expression)
+                    .set_expression_attribute_names(expression_attribute_names)
+                    .set_expression_attribute_values(expression_attribute_values)
+                    .set_return_values_on_condition_check_failure(
+                        return_values_on_condition_check_failure(return_old_on_condition_failure),
+                    )
+                    .build()
+                    .expect("should be built"),
+            ),
+            TransactWriteItem::Put {
+                table_name,
+                item,
+                condition_expression,
+                expression_attribute_names,
+                expression_attribute_values,
+                return_old_on_condition_failure,
+            } => builder.put(
+                types::Put::builder()
+                    .table_name(table_name)
+                    .set_item(Some(item))
+                    .set_condition_expression(condition_expression)
+                    .set_expression_attribute_names(expression_attribute_names)
+                    .set_expression_attribute_values(expression_attribute_values)
+                    .set_return_values_on_condition_check_failure(
+                        return_values_on_condition_check_failure(return_old_on_condition_failure),
+                    )
+                    .build()
+                    .expect("should be built"),
+            ),
+            TransactWriteItem::Delete {
+                table_name,
+                key,
+                condition_expression,
+                expression_attribute_names,
+                expression_attribute_values,
+                return_old_on_condition_failure,
+            } => builder.delete(
+                types::Delete::builder()
+                    .table_name(table_name)
+                    .set_key(Some(key))
+                    .set_condition_expression(condition_expression)
+                    .set_expression_attribute_names(expression_attribute_names)
+                    .set_expression_attribute_values(expression_attribute_values)
+                    .set_return_values_on_condition_check_failure(
+                        return_values_on_condition_check_failure(return_old_on_condition_failure),
+                    )
+                    .build()
+                    .expect("should be built"),
+            ),
+            TransactWriteItem::Update {
+                table_name,
+                key,
+                update_expression,
+                condition_expression,
+                expression_attribute_names,
+                expression_attribute_values,
+                return_old_on_condition_failure,
+            } => builder.update(
+                types::Update::builder()
+                    .table_name(table_name)
+                    .set_key(Some(key))
+                    .update_expression(update_expression)
+                    .set_condition_expression(condition_expression)
+                    .set_expression_attribute_names(expression_attribute_names)
+                    .set_expression_attribute_values(expression_attribute_values)
+                    .set_return_values_on_condition_check_failure(
+                        return_values_on_condition_check_failure(return_old_on_condition_failure),
+                    )
+                    .build()
+                    .expect("should be built"),
+            ),
+        };
+        builder.build()
+    }
+}
+
+impl TryFrom<TransactWriteItemsInputBuilder> for TransactWriteItemsRequest {
+    type Error = RaidenError;
+
+    fn try_from(builder: TransactWriteItemsInputBuilder) -> Result<Self, Self::Error> {
+        let input = builder.build().map_err(build_error)?;
+        Ok(Self {
+            transact_items: input
+                .transact_items
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(transact_write_item)
+                .collect(),
+            client_request_token: input.client_request_token,
+            return_consumed_capacity: input.return_consumed_capacity.map(Into::into),
+            return_item_collection_metrics: input.return_item_collection_metrics.map(Into::into),
+        })
+    }
+}
+
+impl From<TransactWriteItemsRequest> for TransactWriteItemsInputBuilder {
+    fn from(request: TransactWriteItemsRequest) -> Self {
+        Self::default()
+            .set_transact_items(Some(
+                request.transact_items.into_iter().map(Into::into).collect(),
+            ))
+            .set_client_request_token(request.client_request_token)
+            .set_return_consumed_capacity(request.return_consumed_capacity.map(Into::into))
+            .set_return_item_collection_metrics(
+                request.return_item_collection_metrics.map(Into::into),
+            )
+    }
+}
+
+impl From<TransactWriteItemsOutput> for TransactWriteItemsResponse {
+    fn from(output: TransactWriteItemsOutput) -> Self {
+        Self {
+            item_collection_metrics: output.item_collection_metrics,
+            consumed_capacity: output.consumed_capacity,
+        }
+    }
+}
+
+impl From<types::KeySchemaElement> for KeySchemaElement {
+    fn from(element: types::KeySchemaElement) -> Self {
+        Self {
+            attribute_name: element.attribute_name,
+            key_type: match element.key_type {
+                types::KeyType::Range => KeyType::Range,
+                _ => KeyType::Hash,
+            },
+        }
+    }
+}
+
+impl From<KeySchemaElement> for types::KeySchemaElement {
+    fn from(element: KeySchemaElement) -> Self {
+        let key_type = match element.key_type {
+            KeyType::Hash => types::KeyType::Hash,
+            KeyType::Range => types::KeyType::Range,
+        };
+        types::KeySchemaElement::builder()
+            .attribute_name(element.attribute_name)
+            .key_type(key_type)
+            .build()
+            .expect("should be built")
+    }
+}
+
+impl From<types::AttributeDefinition> for AttributeDefinition {
+    fn from(definition: types::AttributeDefinition) -> Self {
+        Self {
+            attribute_name: definition.attribute_name,
+            attribute_type: match definition.attribute_type {
+                types::ScalarAttributeType::N => AttributeType::N,
+                types::ScalarAttributeType::B => AttributeType::B,
+                _ => AttributeType::S,
+            },
+        }
+    }
+}
+
+impl From<AttributeDefinition> for types::AttributeDefinition {
+    fn from(definition: AttributeDefinition) -> Self {
+        types::AttributeDefinition::builder()
+            .attribute_name(definition.attribute_name)
+            .attribute_type(types::ScalarAttributeType::from(
+                definition.attribute_type.to_string().as_str(),
+            ))
+            .build()
+            .expect("should be built")
+    }
+}
+
+impl From<Option<types::Projection>> for Projection {
+    fn from(projection: Option<types::Projection>) -> Self {
+        let Some(projection) = projection else {
+            return Projection::All;
+        };
+        match projection.projection_type {
+            Some(types::ProjectionType::KeysOnly) => Projection::KeysOnly,
+            Some(types::ProjectionType::Include) => {
+                Projection::Include(projection.non_key_attributes.unwrap_or_default())
+            }
+            _ => Projection::All,
+        }
+    }
+}
+
+impl From<Projection> for types::Projection {
+    fn from(projection: Projection) -> Self {
+        match projection {
+            Projection::All => types::Projection::builder()
+                .projection_type(types::ProjectionType::All)
+                .build(),
+            Projection::KeysOnly => types::Projection::builder()
+                .projection_type(types::ProjectionType::KeysOnly)
+                .build(),
+            Projection::Include(non_key_attributes) => types::Projection::builder()
+                .projection_type(types::ProjectionType::Include)
+                .set_non_key_attributes(Some(non_key_attributes))
+                .build(),
+        }
+    }
+}
+
+impl From<types::ProvisionedThroughput> for ProvisionedThroughput {
+    fn from(throughput: types::ProvisionedThroughput) -> Self {
+        Self {
+            read_capacity_units: throughput.read_capacity_units,
+            write_capacity_units: throughput.write_capacity_units,
+        }
+    }
+}
+
+impl From<ProvisionedThroughput> for types::ProvisionedThroughput {
+    fn from(throughput: ProvisionedThroughput) -> Self {
+        types::ProvisionedThroughput::builder()
+            .read_capacity_units(throughput.read_capacity_units)
+            .write_capacity_units(throughput.write_capacity_units)
+            .build()
+            .expect("should be built")
+    }
+}
+
+impl From<types::GlobalSecondaryIndex> for GlobalSecondaryIndex {
+    fn from(index: types::GlobalSecondaryIndex) -> Self {
+        Self {
+            index_name: index.index_name,
+            key_schema: index.key_schema.into_iter().map(Into::into).collect(),
+            projection: index.projection.into(),
+            provisioned_throughput: index.provisioned_throughput.map(Into::into),
+        }
+    }
+}
+
+impl From<GlobalSecondaryIndex> for types::GlobalSecondaryIndex {
+    fn from(index: GlobalSecondaryIndex) -> Self {
+        types::GlobalSecondaryIndex::builder()
+            .index_name(index.index_name)
+            .set_key_schema(Some(index.key_schema.into_iter().map(Into::into).collect()))
+            .projection(index.projection.into())
+            .set_provisioned_throughput(index.provisioned_throughput.map(Into::into))
+            .build()
+            .expect("should be built")
+    }
+}
+
+impl From<types::LocalSecondaryIndex> for LocalSecondaryIndex {
+    fn from(index: types::LocalSecondaryIndex) -> Self {
+        Self {
+            index_name: index.index_name,
+            key_schema: index.key_schema.into_iter().map(Into::into).collect(),
+            projection: index.projection.into(),
+        }
+    }
+}
+
+impl From<LocalSecondaryIndex> for types::LocalSecondaryIndex {
+    fn from(index: LocalSecondaryIndex) -> Self {
+        types::LocalSecondaryIndex::builder()
+            .index_name(index.index_name)
+            .set_key_schema(Some(index.key_schema.into_iter().map(Into::into).collect()))
+            .projection(index.projection.into())
+            .build()
+            .expect("should be built")
+    }
+}
+
+fn into_vec<T, U: From<T>>(values: Option<Vec<T>>) -> Vec<U> {
+    values
+        .unwrap_or_default()
+        .into_iter()
+        .map(Into::into)
+        .collect()
+}
+
+fn into_some_vec<T, U: From<T>>(values: Vec<T>) -> Option<Vec<U>> {
+    if values.is_empty() {
+        return None;
+    }
+    Some(values.into_iter().map(Into::into).collect())
+}
+
+impl TryFrom<CreateTableInputBuilder> for CreateTableRequest {
+    type Error = RaidenError;
+
+    fn try_from(builder: CreateTableInputBuilder) -> Result<Self, Self::Error> {
+        let input = builder.build().map_err(build_error)?;
+        Ok(Self {
+            table_name: table_name(input.table_name)?,
+            attribute_definitions: into_vec(input.attribute_definitions),
+            key_schema: into_vec(input.key_schema),
+            global_secondary_indexes: into_vec(input.global_secondary_indexes),
+            local_secondary_indexes: into_vec(input.local_secondary_indexes),
+            provisioned_throughput: match input.billing_mode {
+                Some(types::BillingMode::PayPerRequest) => None,
+                _ => input.provisioned_throughput.map(Into::into),
+            },
+        })
+    }
+}
+
+impl From<CreateTableRequest> for CreateTableInputBuilder {
+    fn from(request: CreateTableRequest) -> Self {
+        let billing_mode = match request.provisioned_throughput {
+            Some(_) => types::BillingMode::Provisioned,
+            None => types::BillingMode::PayPerRequest,
+        };
+        Self::default()
+            .table_name(request.table_name)
+            .set_attribute_definitions(into_some_vec(request.attribute_definitions))
+            .set_key_schema(into_some_vec(request.key_schema))
+            .set_global_secondary_indexes(into_some_vec(request.global_secondary_indexes))
+            .set_local_secondary_indexes(into_some_vec(request.local_secondary_indexes))
+            .billing_mode(billing_mode)
+            .set_provisioned_throughput(request.provisioned_throughput.map(Into::into))
+    }
+}
+
+impl From<CreateTableOutput> for CreateTableResponse {
+    fn from(output: CreateTableOutput) -> Self {
+        Self {
+            table_description: output.table_description,
+        }
+    }
+}
+
+impl TryFrom<DeleteTableInputBuilder> for DeleteTableRequest {
+    type Error = RaidenError;
+
+    fn try_from(builder: DeleteTableInputBuilder) -> Result<Self, Self::Error> {
+        let input = builder.build().map_err(build_error)?;
+        Ok(Self {
+            table_name: table_name(input.table_name)?,
+        })
+    }
+}
+
+impl From<DeleteTableRequest> for DeleteTableInputBuilder {
+    fn from(request: DeleteTableRequest) -> Self {
+        Self::default().table_name(request.table_name)
+    }
+}
+
+impl From<DeleteTableOutput> for DeleteTableResponse {
+    fn from(output: DeleteTableOutput) -> Self {
+        Self {
+            table_description: output.table_description,
+        }
+    }
+}
+
+impl TryFrom<DescribeTableInputBuilder> for DescribeTableRequest {
+    type Error = RaidenError;
+
+    fn try_from(builder: DescribeTableInputBuilder) -> Result<Self, Self::Error> {
+        let input = builder.build().map_err(build_error)?;
+        Ok(Self {
+            table_name: table_name(input.table_name)?,
+        })
+    }
+}
+
+impl From<DescribeTableRequest> for DescribeTableInputBuilder {
+    fn from(request: DescribeTableRequest) -> Self {
+        Self::default().table_name(request.table_name)
+    }
+}
+
+impl From<DescribeTableOutput> for DescribeTableResponse {
+    fn from(output: DescribeTableOutput) -> Self {
+        Self {
+            table: output.table,
+        }
+    }
+}
+
+impl TryFrom<UpdateTimeToLiveInputBuilder> for UpdateTimeToLiveRequest {
+    type Error = RaidenError;
+
+    fn try_from(builder: UpdateTimeToLiveInputBuilder) -> Result<Self, Self::Error> {
+        let input = builder.build().map_err(build_error)?;
+        let specification = input.time_to_live_specification.ok_or_else(|| {
+            RaidenError::Validation("the time to live of the request is not set".to_owned())
+        })?;
+        Ok(Self {
+            table_name: table_name(input.table_name)?,
+            attribute_name: specification.attribute_name,
+            enabled: specification.enabled,
+        })
+    }
+}
+
+impl From<UpdateTimeToLiveRequest> for UpdateTimeToLiveInputBuilder {
+    fn from(request: UpdateTimeToLiveRequest) -> Self {
+        let specification = types::TimeToLiveSpecification::builder()
+            .attribute_name(request.attribute_name)
+            .enabled(request.enabled)
+            .build()
+            .expect("should be built");
+        Self::default()
+            .table_name(request.table_name)
+            .time_to_live_specification(specification)
+    }
+}
+
+impl From<UpdateTimeToLiveOutput> for UpdateTimeToLiveResponse {
+    fn from(_: UpdateTimeToLiveOutput) -> Self {
+        Self {}
+    }
+}
@@ -3,12 +3,17 @@ use crate::{
         error::{ProvideErrorMetadata, SdkError},
         operation::{
             batch_get_item::BatchGetItemError, batch_write_item::BatchWriteItemError,
-            delete_item::DeleteItemError, get_item::GetItemError, put_item::PutItemError,
-            query::QueryError, scan::ScanError, transact_get_items::TransactGetItemsError,
+            create_table::CreateTableError, delete_item::DeleteItemError,
+            delete_table::DeleteTableError, describe_table::DescribeTableError,
+            get_item::GetItemError, put_item::PutItemError, query::QueryError, scan::ScanError,
+            transact_get_items::TransactGetItemsError,
             transact_write_items::TransactWriteItemsError, update_item::UpdateItemError,
+            update_time_to_live::UpdateTimeToLiveError,
         },
+        types::{error::ConditionalCheckFailedException, CancellationReason},
     },
-    RaidenError, RaidenTransactionCancellationReasons,
+    RaidenError, RaidenTransactionCancellation, RaidenTransactionCancellationReason,
+    RaidenTransactionCancellationReasons,
 };
 
 fn into_raiden_error<E>(error: SdkError<E>) -> RaidenError
@@ -48,6 +53,58 @@ where
     }
 }
 
+fn into_conditional_check_failed(error: &ConditionalCheckFailedException) -> RaidenError {
+    match &error.item {
+        Some(item) => RaidenError::ConditionalCheckFailedWithItem {
+            message: error.to_string(),
+            item: item.clone(),
+        },
+        None => RaidenError::ConditionalCheckFailed(error.to_string()),
+    }
+}
+
+// The reason codes are taken from `CancellationReasons`, falling back to the message when they are missing.
+fn into_transaction_canceled(
+    message: Option<&str>,
+    raw_reasons: Vec<CancellationReason>,
+) -> RaidenError {
+    let cancellations: Vec<_> = raw_reasons
+        .iter()
+        .enumerate()
+        .map(|(index, reason)| RaidenTransactionCancellation {
+            index,
+            reason: match reason.code.as_deref() {
+                None | Some("None") => None,
+                Some(code) => Some(RaidenTransactionCancellationReason::from_str(code)),
+            },
+            message: reason.message.clone(),
+            item: reason.item.clone(),
+        })
+        .collect();
+
+    let reasons = if cancellations.is_empty() {
+        RaidenTransactionCancellationReasons::from_str(message.unwrap_or("transaction canceled"))
+    } else {
+        RaidenTransactionCancellationReasons(
+            cancellations
+                .iter()
+                .map(|cancellation| cancellation.reason.clone())
+                .collect(),
+        )
+    };
+    let cancellations = if cancellations.is_empty() {
+        reasons.cancellations()
+    } else {
+        cancellations
+    };
+
+    RaidenError::TransactionCanceled {
+        reasons,
+        cancellations,
+        raw_reasons,
+    }
+}
+
 impl From<SdkError<BatchGetItemError>> for RaidenError {
     fn from(error: SdkError<BatchGetItemError>) -> Self {
         match &error {
@@ -108,7 +165,7 @@ impl From<SdkError<DeleteItemError>> for RaidenError {
         match &error {
             SdkError::ServiceError(err) => match err.err() {
                 DeleteItemError::ConditionalCheckFailedException(err) => {
-                    RaidenError::ConditionalCheckFailed(err.to_string())
+                    into_conditional_check_failed(err)
                 }
                 DeleteItemError::InternalServerError(err) => {
                     RaidenError::InternalServerError(err.to_string())
@@ -169,7 +226,7 @@ impl From<SdkError<PutItemError>> for RaidenError {
         match &error {
             SdkError::ServiceError(err) => match err.err() {
                 PutItemError::ConditionalCheckFailedException(err) => {
-                    RaidenError::ConditionalCheckFailed(err.to_string())
+                    into_conditional_check_failed(err)
                 }
                 PutItemError::InternalServerError(err) => {
                     RaidenError::InternalServerError(err.to_string())
@@ -274,18 +331,8 @@ impl From<SdkError<TransactWriteItemsError>> for RaidenError {
                     RaidenError::ResourceNotFound(err.to_string())
                 }
                 TransactWriteItemsError::TransactionCanceledException(err) => {
-                    let reasons = RaidenTransactionCancellationReasons::from_str(
-                        err.message
-                            .clone()
-                            .unwrap_or_else(|| "transaction canceled".to_owned())
-                            .as_str(),
-                    );
                     let raw_reasons = err.cancellation_reasons.clone().unwrap_or_default();
-
-                    RaidenError::TransactionCanceled {
-                        reasons,
-                        raw_reasons,
-                    }
+                    into_transaction_canceled(err.message.as_deref(), raw_reasons)
                 }
                 TransactWriteItemsError::TransactionInProgressException(err) => {
                     RaidenError::TransactionInProgress(err.to_string())
@@ -320,18 +367,8 @@ impl From<SdkError<TransactGetItemsError>> for RaidenError {
                     RaidenError::RequestLimitExceeded(err.to_string())
                 }
                 TransactGetItemsError::TransactionCanceledException(err) => {
-                    let reasons = RaidenTransactionCancellationReasons::from_str(
-                        err.message
-                            .clone()
-                            .unwrap_or_else(|| "transaction canceled".to_owned())
-                            .as_str(),
-                    );
                     let raw_reasons = err.cancellation_reasons.clone().unwrap_or_default();
-
-                    RaidenError::TransactionCanceled {
-                        reasons,
-                        raw_reasons,
-                    }
+                    into_transaction_canceled(err.message.as_deref(), raw_reasons)
                 }
                 _ => into_raiden_error(error),
             },
@@ -345,7 +382,7 @@ impl From<SdkError<UpdateItemError>> for RaidenError {
         match &error {
             SdkError::ServiceError(err) => match err.err() {
                 UpdateItemError::ConditionalCheckFailedException(err) => {
-                    RaidenError::ConditionalCheckFailed(err.to_string())
+                    into_conditional_check_failed(err)
                 }
                 UpdateItemError::InternalServerError(err) => {
                     RaidenError::InternalServerError(err.to_string())
@@ -374,3 +411,98 @@ impl From<SdkError<UpdateItemError>> for RaidenError {
         }
     }
 }
+
+impl From<SdkError<CreateTableError>> for RaidenError {
+    fn from(error: SdkError<CreateTableError>) -> Self {
+        match &error {
+            SdkError::ServiceError(err) => match err.err() {
+                CreateTableError::InternalServerError(err) => {
+                    RaidenError::InternalServerError(err.to_string())
+                }
+                CreateTableError::InvalidEndpointException(err) => {
+                    RaidenError::InternalServerError(err.to_string())
+                }
+                CreateTableError::LimitExceededException(err) => {
+                    RaidenError::LimitExceeded(err.to_string())
+                }
+                CreateTableError::ResourceInUseException(err) => {
+                    RaidenError::ResourceInUse(err.to_string())
+                }
+                _ => into_raiden_error(error),
+            },
+            _ => into_raiden_error(error),
+        }
+    }
+}
+
+impl From<SdkError<DeleteTableError>> for RaidenError {
+    fn from(error: SdkError<DeleteTableError>) -> Self {
+        match &error {
+            SdkError::ServiceError(err) => match err.err() {
+                DeleteTableError::InternalServerError(err) => {
+                    RaidenError::InternalServerError(err.to_string())
+                }
+                DeleteTableError::InvalidEndpointException(err) => {
+                    RaidenError::InternalServerError(err.to_string())
+                }
+                DeleteTableError::LimitExceededException(err) => {
+                    RaidenError::LimitExceeded(err.to_string())
+                }
+                DeleteTableError::ResourceInUseException(err) => {
+                    RaidenError::ResourceInUse(err.to_string())
+                }
+                DeleteTableError::ResourceNotFoundException(err) => {
+                    RaidenError::ResourceNotFound(err.to_string())
+                }
+                _ => into_raiden_error(error),
+            },
+            _ => into_raiden_error(error),
+        }
+    }
+}
+
+impl From<SdkError<DescribeTableError>> for RaidenError {
+    fn from(error: SdkError<DescribeTableError>) -> Self {
+        match &error {
+            SdkError::ServiceError(err) => match err.err() {
+                DescribeTableError::InternalServerError(err) => {
+                    RaidenError::InternalServerError(err.to_string())
+                }
+                DescribeTableError::InvalidEndpointException(err) => {
+                    RaidenError::InternalServerError(err.to_string())
+                }
+                DescribeTableError::ResourceNotFoundException(err) => {
+                    RaidenError::ResourceNotFound(err.to_string())
+                }
+                _ => into_raiden_error(error),
+            },
+            _ => into_raiden_error(error),
+        }
+    }
+}
+
+impl From<SdkError<UpdateTimeToLiveError>> for RaidenError {
+    fn from(error: SdkError<UpdateTimeToLiveError>) -> Self {
+        match &error {
+            SdkError::ServiceError(err) => match err.err() {
+                UpdateTimeToLiveError::InternalServerError(err) => {
+                    RaidenError::InternalServerError(err.to_string())
+                }
+                UpdateTimeToLiveError::InvalidEndpointException(err) => {
+                    RaidenError::InternalServerError(err.to_string())
+                }
+                UpdateTimeToLiveError::LimitExceededException(err) => {
+                    RaidenError::LimitExceeded(err.to_string())
+                }
+                UpdateTimeToLiveError::ResourceInUseException(err) => {
+                    RaidenError::ResourceInUse(err.to_string())
+                }
+                UpdateTimeToLiveError::ResourceNotFoundException(err) => {
+                    RaidenError::ResourceNotFound(err.to_string())
+                }
+                _ => into_raiden_error(error),
+            },
+            _ => into_raiden_error(error),
+        }
+    }
+}
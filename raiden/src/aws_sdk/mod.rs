@@ -1,3 +1,4 @@
+pub(crate) mod backend;
 mod errors;
 mod ops;
 pub(crate) mod serialize;
@@ -30,7 +31,10 @@ impl FromAttribute for String {
         match value {
             Some(v) if v.is_null() => Ok("".to_owned()),
             Some(AttributeValue::S(s)) => Ok(s),
-            _ => Err(ConversionError::ValueIsNone),
+            other => Err(ConversionError::unexpected_type(
+                AttributeType::S,
+                other.as_ref(),
+            )),
         }
     }
 }
@@ -65,7 +69,10 @@ impl<'a> FromAttribute for std::borrow::Cow<'a, str> {
         match value {
             Some(v) if v.is_null() => Ok(std::borrow::Cow::Owned("".to_owned())),
             Some(AttributeValue::S(s)) => Ok(std::borrow::Cow::Owned(s)),
-            _ => Err(ConversionError::ValueIsNone),
+            other => Err(ConversionError::unexpected_type(
+                AttributeType::S,
+                other.as_ref(),
+            )),
         }
     }
 }
@@ -80,10 +87,12 @@ macro_rules! default_attr_for_num {
 
         impl FromAttribute for $to {
             fn from_attr(value: Option<AttributeValue>) -> Result<Self, ConversionError> {
-                if let Some(AttributeValue::N(n)) = value {
-                    Ok(n.parse().unwrap())
-                } else {
-                    Err(ConversionError::ValueIsNone)
+                match value {
+                    Some(AttributeValue::N(n)) => n.parse().map_err(|_| ConversionError::ParseInt),
+                    other => Err(ConversionError::unexpected_type(
+                        AttributeType::N,
+                        other.as_ref(),
+                    )),
                 }
             }
         }
@@ -120,10 +129,12 @@ impl IntoAttribute for bool {
 
 impl FromAttribute for bool {
     fn from_attr(value: Option<AttributeValue>) -> Result<Self, ConversionError> {
-        if let Some(AttributeValue::Bool(v)) = value {
-            Ok(v)
-        } else {
-            Err(ConversionError::ValueIsNone)
+        match value {
+            Some(AttributeValue::Bool(v)) => Ok(v),
+            other => Err(ConversionError::unexpected_type(
+                AttributeType::BOOL,
+                other.as_ref(),
+            )),
         }
     }
 }
@@ -142,135 +153,100 @@ impl<A: IntoAttribute> IntoAttribute for Vec<A> {
 impl<A: FromAttribute> FromAttribute for Vec<A> {
     fn from_attr(value: Option<AttributeValue>) -> Result<Self, ConversionError> {
         match value {
-            Some(AttributeValue::L(v)) => {
-                v.into_iter().map(|item| A::from_attr(Some(item))).collect()
-            }
+            Some(AttributeValue::L(v)) => v
+                .into_iter()
+                .enumerate()
+                .map(|(i, item)| {
+                    A::from_attr(Some(item)).map_err(|err| err.nested(format!("[{i}]")))
+                })
+                .collect(),
             // See. https://github.com/raiden-rs/raiden/issues/57
             Some(v) if v.is_null() => Ok(vec![]),
             None => Ok(vec![]),
-            _ => Err(ConversionError::ValueIsNone),
+            other => Err(ConversionError::unexpected_type(
+                AttributeType::L,
+                other.as_ref(),
+            )),
         }
     }
 }
 
-macro_rules! default_number_hash_set_convertor {
-    ($to: ty) => {
-        impl IntoAttribute for std::collections::HashSet<$to> {
-            fn into_attr(self) -> AttributeValue {
-                if self.is_empty() {
-                    // See. https://github.com/raiden-rs/raiden/issues/57
-                    //      https://github.com/raiden-rs/raiden-dynamo/issues/64
-                    AttributeValue::Ns(Default::default())
-                } else {
-                    AttributeValue::Ns(self.into_iter().map(|s| s.to_string()).collect())
-                }
-            }
-        }
-
-        impl FromAttribute for std::collections::HashSet<$to> {
-            fn from_attr(value: Option<AttributeValue>) -> Result<Self, ConversionError> {
-                match value {
-                    Some(AttributeValue::Ns(mut nums)) => {
-                        let mut results: Vec<Result<$to, ConversionError>> = nums
-                            .drain(..)
-                            .map(|ns| ns.parse().map_err(|_| ConversionError::ParseInt))
-                            .collect();
-
-                        results.drain(..).collect()
-                    }
-                    // See. https://github.com/raiden-rs/raiden/issues/57
-                    Some(v) if v.is_null() => Ok(std::collections::HashSet::new()),
-                    None => Ok(std::collections::HashSet::new()),
-                    _ => Err(ConversionError::ValueIsNone),
-                }
-            }
-        }
-    };
+// A set is stored as `NS` when its items are numbers and as `SS` otherwise.
+fn into_set_attr<A: IntoStringSetItem>(items: impl Iterator<Item = A>) -> AttributeValue {
+    // An empty set is stored as an empty set value.
+    // See. https://github.com/raiden-rs/raiden/issues/57
+    //      https://github.com/raiden-rs/raiden-dynamo/issues/64
+    let items = items.map(|s| s.into_ss_item()).collect();
+    if A::IS_NUMBER {
+        AttributeValue::Ns(items)
+    } else {
+        AttributeValue::Ss(items)
+    }
 }
 
-pub(crate) use default_number_hash_set_convertor;
-
-macro_rules! default_number_btree_set_convertor {
-    ($to: ty) => {
-        impl IntoAttribute for std::collections::BTreeSet<$to> {
-            fn into_attr(self) -> AttributeValue {
-                if self.is_empty() {
-                    // See. https://github.com/raiden-rs/raiden/issues/57
-                    //      https://github.com/raiden-rs/raiden-dynamo/issues/64
-                    AttributeValue::Ns(Default::default())
-                } else {
-                    AttributeValue::Ns(self.into_iter().map(|s| s.to_string()).collect())
-                }
-            }
+fn from_set_attr<A: FromStringSetItem, C: Default + FromIterator<A>>(
+    value: Option<AttributeValue>,
+) -> Result<C, ConversionError> {
+    match value {
+        Some(AttributeValue::Ns(mut items)) if A::IS_NUMBER => {
+            items.drain(..).map(A::from_ss_item).collect()
         }
-
-        impl FromAttribute for std::collections::BTreeSet<$to> {
-            fn from_attr(value: Option<AttributeValue>) -> Result<Self, ConversionError> {
-                match value {
-                    Some(AttributeValue::Ns(mut nums)) => {
-                        let mut results: Vec<Result<$to, ConversionError>> = nums
-                            .drain(..)
-                            .map(|ns| ns.parse().map_err(|_| ConversionError::ParseInt))
-                            .collect();
-
-                        results.drain(..).collect()
-                    }
-                    // See. https://github.com/raiden-rs/raiden/issues/57
-                    Some(v) if v.is_null() => Ok(std::collections::BTreeSet::new()),
-                    None => Ok(std::collections::BTreeSet::new()),
-                    _ => Err(ConversionError::ValueIsNone),
-                }
-            }
+        Some(AttributeValue::Ss(mut items)) if !A::IS_NUMBER => {
+            items.drain(..).map(A::from_ss_item).collect()
         }
-    };
+        // See. https://github.com/raiden-rs/raiden/issues/57
+        Some(v) if v.is_null() => Ok(C::default()),
+        None => Ok(C::default()),
+        other => Err(ConversionError::unexpected_type(
+            if A::IS_NUMBER {
+                AttributeType::NS
+            } else {
+                AttributeType::SS
+            },
+            other.as_ref(),
+        )),
+    }
 }
 
-pub(crate) use default_number_btree_set_convertor;
-
 impl<A: std::hash::Hash + IntoStringSetItem> IntoAttribute for HashSet<A> {
     fn into_attr(self) -> AttributeValue {
-        if self.is_empty() {
-            // See. https://github.com/raiden-rs/raiden/issues/57
-            //      https://github.com/raiden-rs/raiden-dynamo/issues/64
-            AttributeValue::Ss(Default::default())
-        } else {
-            AttributeValue::Ss(self.into_iter().map(|s| s.into_ss_item()).collect())
-        }
+        into_set_attr(self.into_iter())
     }
 }
 
 impl<A: std::hash::Hash + std::cmp::Eq + FromStringSetItem> FromAttribute for HashSet<A> {
     fn from_attr(value: Option<AttributeValue>) -> Result<Self, ConversionError> {
-        match value {
-            Some(AttributeValue::Ss(mut ss)) => ss.drain(..).map(A::from_ss_item).collect(),
-            // See. https://github.com/raiden-rs/raiden/issues/57
-            Some(v) if v.is_null() => Ok(HashSet::new()),
-            None => Ok(HashSet::new()),
-            _ => Err(ConversionError::ValueIsNone),
-        }
+        from_set_attr(value)
     }
 }
 
 impl<A: std::cmp::Ord + IntoStringSetItem> IntoAttribute for BTreeSet<A> {
     fn into_attr(self) -> AttributeValue {
-        if self.is_empty() {
-            // See. https://github.com/raiden-rs/raiden/issues/57
-            //      https://github.com/raiden-rs/raiden-dynamo/issues/64
-            AttributeValue::Ss(Default::default())
-        } else {
-            AttributeValue::Ss(self.into_iter().map(|s| s.into_ss_item()).collect())
-        }
+        into_set_attr(self.into_iter())
     }
 }
 
 impl<A: std::cmp::Ord + FromStringSetItem> FromAttribute for BTreeSet<A> {
     fn from_attr(value: Option<AttributeValue>) -> Result<Self, ConversionError> {
+        from_set_attr(value)
+    }
+}
+
+impl AttributeType {
+    /// The type of `value`, `None` for a type this crate does not know.
+    pub fn of(value: &AttributeValue) -> Option<Self> {
         match value {
-            Some(AttributeValue::Ss(mut ss)) => ss.drain(..).map(A::from_ss_item).collect(),
-            // See. https://github.com/raiden-rs/raiden/issues/57
-            Some(v) if v.is_null() => Ok(BTreeSet::new()),
-            None => Ok(BTreeSet::new()),
-            _ => Err(ConversionError::ValueIsNone),
+            AttributeValue::B(_) => Some(AttributeType::B),
+            AttributeValue::Bool(_) => Some(AttributeType::BOOL),
+            AttributeValue::Bs(_) => Some(AttributeType::BS),
+            AttributeValue::L(_) => Some(AttributeType::L),
+            AttributeValue::M(_) => Some(AttributeType::M),
+            AttributeValue::N(_) => Some(AttributeType::N),
+            AttributeValue::Ns(_) => Some(AttributeType::NS),
+            AttributeValue::Null(_) => Some(AttributeType::NULL),
+            AttributeValue::S(_) => Some(AttributeType::S),
+            AttributeValue::Ss(_) => Some(AttributeType::SS),
+            _ => None,
         }
     }
 }
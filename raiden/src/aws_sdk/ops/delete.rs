@@ -0,0 +1,139 @@
+use serde::de::{self, Deserialize, Deserializer, MapAccess, Visitor};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+use crate::ops::delete::DeleteOutput;
+
+impl<T> Serialize for DeleteOutput<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("DeleteOutput", 3)?;
+        state.serialize_field(
+            "consumed_capacity",
+            &self
+                .consumed_capacity
+                .as_ref()
+                .map(crate::aws_sdk::serialize::consumed_capacity_to_value),
+        )?;
+        state.serialize_field("old_item", &self.old_item)?;
+        state.serialize_field(
+            "item_collection_metrics",
+            &self
+                .item_collection_metrics
+                .as_ref()
+                .map(crate::aws_sdk::serialize::item_collection_metrics_to_value),
+        )?;
+        state.end()
+    }
+}
+
+impl<'de, T> Deserialize<'de> for DeleteOutput<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(serde::Deserialize)]
+        #[serde(field_identifier, rename_all = "snake_case")]
+        enum Field {
+            ConsumedCapacity,
+            OldItem,
+            ItemCollectionMetrics,
+        }
+
+        const FIELDS: &[&str] = &["consumed_capacity", "old_item", "item_collection_metrics"];
+
+        struct DeleteOutputVisitor<'de, T>
+        where
+            T: Deserialize<'de>,
+        {
+            marker: std::marker::PhantomData<DeleteOutput<T>>,
+            lifetime: std::marker::PhantomData<&'de ()>,
+        }
+
+        impl<'de, T> Visitor<'de> for DeleteOutputVisitor<'de, T>
+        where
+            T: Deserialize<'de>,
+        {
+            type Value = DeleteOutput<T>;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("struct DeleteOutput")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> Result<Self::Value, V::Error>
+            where
+                V: MapAccess<'de>,
+            {
+                let mut consumed_capacity = None;
+                let mut old_item = None;
+                let mut item_collection_metrics = None;
+
+                while let Some(key) = map.next_key()? {
+                    match key {
+                        Field::ConsumedCapacity => {
+                            if consumed_capacity.is_some() {
+                                return Err(de::Error::duplicate_field("consumed_capacity"));
+                            }
+
+                            let v: Option<serde_json::Value> = map.next_value()?;
+
+                            consumed_capacity = if let Some(v) = v {
+                                Some(
+                                    crate::aws_sdk::serialize::value_to_consumed_capacity(v)
+                                        .map_err(de::Error::custom)?,
+                                )
+                            } else {
+                                None
+                            };
+                        }
+                        Field::OldItem => {
+                            if old_item.is_some() {
+                                return Err(de::Error::duplicate_field("old_item"));
+                            }
+
+                            old_item = map.next_value()?;
+                        }
+                        Field::ItemCollectionMetrics => {
+                            if item_collection_metrics.is_some() {
+                                return Err(de::Error::duplicate_field("item_collection_metrics"));
+                            }
+
+                            let v: Option<serde_json::Value> = map.next_value()?;
+
+                            item_collection_metrics = if let Some(v) = v {
+                                Some(
+                                    crate::aws_sdk::serialize::value_to_item_collection_metrics(v)
+                                        .map_err(de::Error::custom)?,
+                                )
+                            } else {
+                                None
+                            };
+                        }
+                    }
+                }
+
+                Ok(DeleteOutput {
+                    consumed_capacity,
+                    old_item,
+                    item_collection_metrics,
+                })
+            }
+        }
+
+        deserializer.deserialize_struct(
+            "DeleteOutput",
+            FIELDS,
+            DeleteOutputVisitor {
+                marker: std::marker::PhantomData::<DeleteOutput<T>>,
+                lifetime: std::marker::PhantomData,
+            },
+        )
+    }
+}
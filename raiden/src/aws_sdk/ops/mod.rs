@@ -1,6 +1,7 @@
 mod batch_delete;
 mod batch_get;
 mod batch_put;
+mod delete;
 mod get;
 mod put;
 mod query;
@@ -9,4 +10,5 @@ mod transact_get;
 mod transact_write;
 mod update;
 
+pub use transact_get::ReadTx;
 pub use transact_write::*;
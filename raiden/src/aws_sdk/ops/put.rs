@@ -3,15 +3,16 @@ use serde::ser::{Serialize, SerializeStruct, Serializer};
 
 use crate::ops::put::PutOutput;
 
-impl<T> Serialize for PutOutput<T>
+impl<T, O> Serialize for PutOutput<T, O>
 where
     T: Serialize,
+    O: Serialize,
 {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("PutOutput", 2)?;
+        let mut state = serializer.serialize_struct("PutOutput", 4)?;
         state.serialize_field(
             "consumed_capacity",
             &self
@@ -20,13 +21,22 @@ where
                 .map(crate::aws_sdk::serialize::consumed_capacity_to_value),
         )?;
         state.serialize_field("item", &self.item)?;
+        state.serialize_field("old_item", &self.old_item)?;
+        state.serialize_field(
+            "item_collection_metrics",
+            &self
+                .item_collection_metrics
+                .as_ref()
+                .map(crate::aws_sdk::serialize::item_collection_metrics_to_value),
+        )?;
         state.end()
     }
 }
 
-impl<'de, T> Deserialize<'de> for PutOutput<T>
+impl<'de, T, O> Deserialize<'de> for PutOutput<T, O>
 where
     T: Deserialize<'de>,
+    O: Deserialize<'de>,
 {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -37,23 +47,32 @@ where
         enum Field {
             ConsumedCapacity,
             Item,
+            OldItem,
+            ItemCollectionMetrics,
         }
 
-        const FIELDS: &[&str] = &["consumed_capacity", "item"];
+        const FIELDS: &[&str] = &[
+            "consumed_capacity",
+            "item",
+            "old_item",
+            "item_collection_metrics",
+        ];
 
-        struct PutOutputVisitor<'de, T>
+        struct PutOutputVisitor<'de, T, O>
         where
             T: Deserialize<'de>,
+            O: Deserialize<'de>,
         {
-            marker: std::marker::PhantomData<PutOutput<T>>,
+            marker: std::marker::PhantomData<PutOutput<T, O>>,
             lifetime: std::marker::PhantomData<&'de ()>,
         }
 
-        impl<'de, T> Visitor<'de> for PutOutputVisitor<'de, T>
+        impl<'de, T, O> Visitor<'de> for PutOutputVisitor<'de, T, O>
         where
             T: Deserialize<'de>,
+            O: Deserialize<'de>,
         {
-            type Value = PutOutput<T>;
+            type Value = PutOutput<T, O>;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
                 formatter.write_str("struct PutOutput")
@@ -65,6 +84,8 @@ where
             {
                 let mut consumed_capacity = None;
                 let mut item = None;
+                let mut old_item = None;
+                let mut item_collection_metrics = None;
 
                 while let Some(key) = map.next_key()? {
                     match key {
@@ -91,6 +112,29 @@ where
 
                             item = Some(map.next_value()?);
                         }
+                        Field::OldItem => {
+                            if old_item.is_some() {
+                                return Err(de::Error::duplicate_field("old_item"));
+                            }
+
+                            old_item = map.next_value()?;
+                        }
+                        Field::ItemCollectionMetrics => {
+                            if item_collection_metrics.is_some() {
+                                return Err(de::Error::duplicate_field("item_collection_metrics"));
+                            }
+
+                            let v: Option<serde_json::Value> = map.next_value()?;
+
+                            item_collection_metrics = if let Some(v) = v {
+                                Some(
+                                    crate::aws_sdk::serialize::value_to_item_collection_metrics(v)
+                                        .map_err(de::Error::custom)?,
+                                )
+                            } else {
+                                None
+                            };
+                        }
                     }
                 }
 
@@ -99,6 +143,8 @@ where
                 Ok(PutOutput {
                     consumed_capacity,
                     item,
+                    old_item,
+                    item_collection_metrics,
                 })
             }
         }
@@ -107,7 +153,7 @@ where
             "PutOutput",
             FIELDS,
             PutOutputVisitor {
-                marker: std::marker::PhantomData::<PutOutput<T>>,
+                marker: std::marker::PhantomData::<PutOutput<T, O>>,
                 lifetime: std::marker::PhantomData,
             },
         )
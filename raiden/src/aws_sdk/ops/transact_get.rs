@@ -1,7 +1,129 @@
 use serde::de::{Deserialize, Deserializer};
 use serde::ser::{Serialize, SerializeStruct, Serializer};
 
-use crate::ops::transact_get::TransactGetOutput;
+use crate::{
+    aws_sdk::{
+        config::Region,
+        operation::transact_get_items::{
+            builders::TransactGetItemsInputBuilder, TransactGetItemsInput,
+        },
+        types::TransactGetItem,
+    },
+    ops::transact_get::TransactGetOutput,
+    Client, Config, DynamoBackend, ObservedBackend, Observer, RaidenError, ReadTxOutput,
+    RetryCondition, RetryStrategy, ReturnConsumedCapacity, SharedBackend, TransactGetItemBuilder,
+    TransactGetItems, TransactGetPush,
+};
+
+/// Reads items of different models, and from different tables, in one transaction.
+///
+/// Each `get` appends its item type to `T`, and `run` returns one `Option` per get in the same order.
+pub struct ReadTx<T = ()> {
+    items: Vec<TransactGetItem>,
+    client: SharedBackend,
+    retry_condition: RetryCondition,
+    return_consumed_capacity: Option<ReturnConsumedCapacity>,
+    _items: std::marker::PhantomData<fn() -> T>,
+}
+
+impl ReadTx {
+    pub fn new(region: Region) -> Self {
+        let config = Config::builder().region(region).build();
+
+        Self::new_with_client(Client::from_conf(config))
+    }
+
+    pub fn new_with_client(client: Client) -> Self {
+        Self::new_with_backend(client)
+    }
+
+    pub fn new_with_backend(backend: impl DynamoBackend + 'static) -> Self {
+        Self {
+            items: vec![],
+            client: std::sync::Arc::new(backend),
+            // NOTE:
+            // Since the AWS SDK provides a retry option,
+            // configure it to not retry by default.
+            retry_condition: RetryCondition::never(),
+            return_consumed_capacity: None,
+            _items: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T> ReadTx<T> {
+    pub fn with_retries(mut self, s: Box<dyn RetryStrategy + Send + Sync>) -> Self {
+        self.retry_condition.strategy = s;
+        self
+    }
+
+    /// Reports every request attempt of this transaction to `observer`.
+    pub fn with_observer(mut self, observer: std::sync::Arc<dyn Observer>) -> Self {
+        self.client = std::sync::Arc::new(ObservedBackend::new(self.client, observer));
+        self
+    }
+
+    /// Requests the consumed capacity of each table, returned as `consumed_capacity`.
+    pub fn return_consumed_capacity(mut self, value: ReturnConsumedCapacity) -> Self {
+        self.return_consumed_capacity = Some(value);
+        self
+    }
+
+    pub fn get<B>(mut self, builder: B) -> ReadTx<<T as TransactGetPush<B::Item>>::Output>
+    where
+        B: TransactGetItemBuilder,
+        T: TransactGetPush<B::Item>,
+    {
+        self.items
+            .push(TransactGetItem::builder().get(builder.build()).build());
+        ReadTx {
+            items: self.items,
+            client: self.client,
+            retry_condition: self.retry_condition,
+            return_consumed_capacity: self.return_consumed_capacity,
+            _items: std::marker::PhantomData,
+        }
+    }
+
+    pub async fn run(self) -> Result<ReadTxOutput<T::Output>, RaidenError>
+    where
+        T: TransactGetItems,
+    {
+        let client = self.client;
+        let builder = TransactGetItemsInput::builder()
+            .set_transact_items(Some(self.items))
+            .set_return_consumed_capacity(self.return_consumed_capacity.map(Into::into));
+
+        let res = crate::retry_with_backend(
+            self.retry_condition.strategy.policy(),
+            &client,
+            &self.retry_condition,
+            move |client| {
+                let builder = builder.clone();
+                async { ReadTx::<T>::inner_run(client, builder).await }
+            },
+        )
+        .await?;
+
+        Ok(ReadTxOutput {
+            consumed_capacity: res.consumed_capacity,
+            items: T::decode(res.responses.unwrap_or_default())?,
+        })
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        level = tracing::Level::DEBUG,
+        name = "dynamodb::action",
+        skip_all,
+        fields(api = "transact_get_items")
+    ))]
+    async fn inner_run(
+        client: SharedBackend,
+        builder: TransactGetItemsInputBuilder,
+    ) -> Result<crate::TransactGetItemsResponse, RaidenError> {
+        client.transact_get_items(builder.try_into()?).await
+    }
+}
 
 impl<T> Serialize for TransactGetOutput<T>
 where
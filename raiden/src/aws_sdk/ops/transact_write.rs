@@ -1,17 +1,26 @@
 // DynamoDb, DynamoDbClient, TransactWriteItem, TransactWriteItemsInput
 use crate::{
     aws_sdk::{
-        config::Region, operation::transact_write_items::builders::TransactWriteItemsFluentBuilder,
+        config::Region,
+        operation::transact_write_items::{
+            builders::TransactWriteItemsInputBuilder, TransactWriteItemsInput,
+        },
         types::TransactWriteItem,
     },
-    Client, Config, RaidenError, RetryCondition, RetryStrategy, TransactWriteConditionCheckBuilder,
-    TransactWriteDeleteBuilder, TransactWritePutBuilder, TransactWriteUpdateBuilder,
+    Client, Config, DynamoBackend, ObservedBackend, Observer, RaidenError, RetryCondition,
+    RetryStrategy, ReturnConsumedCapacity, ReturnItemCollectionMetrics, SharedBackend,
+    TransactWriteConditionCheckBuilder, TransactWriteDeleteBuilder, TransactWriteOutput,
+    TransactWritePutBuilder, TransactWriteUpdateBuilder,
 };
 
 pub struct WriteTx {
     items: Vec<TransactWriteItem>,
-    client: Client,
+    client: SharedBackend,
     retry_condition: RetryCondition,
+    client_request_token: Option<String>,
+    return_consumed_capacity: Option<ReturnConsumedCapacity>,
+    return_item_collection_metrics: Option<ReturnItemCollectionMetrics>,
+    error: Option<RaidenError>,
 }
 
 impl WriteTx {
@@ -20,22 +29,34 @@ impl WriteTx {
 
         Self {
             items: vec![],
-            client: Client::from_conf(config),
+            client: std::sync::Arc::new(Client::from_conf(config)),
             // NOTE:
             // Since the AWS SDK provides a retry option,
             // configure it to not retry by default.
             retry_condition: RetryCondition::never(),
+            client_request_token: None,
+            return_consumed_capacity: None,
+            return_item_collection_metrics: None,
+            error: None,
         }
     }
 
     pub fn new_with_client(client: Client) -> Self {
+        Self::new_with_backend(client)
+    }
+
+    pub fn new_with_backend(backend: impl DynamoBackend + 'static) -> Self {
         Self {
             items: vec![],
-            client,
+            client: std::sync::Arc::new(backend),
             // NOTE:
             // Since the AWS SDK provides a retry option,
             // configure it to not retry by default.
             retry_condition: RetryCondition::never(),
+            client_request_token: None,
+            return_consumed_capacity: None,
+            return_item_collection_metrics: None,
+            error: None,
         }
     }
 
@@ -44,7 +65,36 @@ impl WriteTx {
         self
     }
 
-    pub fn put(mut self, builder: impl TransactWritePutBuilder) -> Self {
+    /// Reports every request attempt of this transaction to `observer`.
+    pub fn with_observer(mut self, observer: std::sync::Arc<dyn Observer>) -> Self {
+        self.client = std::sync::Arc::new(ObservedBackend::new(self.client, observer));
+        self
+    }
+
+    /// Sets the `ClientRequestToken` which makes the transaction idempotent.
+    ///
+    /// Without it, a token is generated once per `run()` and kept across retries.
+    pub fn idempotency_token(mut self, token: impl Into<String>) -> Self {
+        self.client_request_token = Some(token.into());
+        self
+    }
+
+    /// Requests the consumed capacity of each table, returned as `consumed_capacity`.
+    pub fn return_consumed_capacity(mut self, value: ReturnConsumedCapacity) -> Self {
+        self.return_consumed_capacity = Some(value);
+        self
+    }
+
+    /// Requests the sizes of the modified item collections, returned as `item_collection_metrics`.
+    pub fn return_item_collection_metrics(mut self, value: ReturnItemCollectionMetrics) -> Self {
+        self.return_item_collection_metrics = Some(value);
+        self
+    }
+
+    pub fn put(mut self, mut builder: impl TransactWritePutBuilder) -> Self {
+        if self.error.is_none() {
+            self.error = builder.take_error();
+        }
         let builder = TransactWriteItem::builder().put(builder.build());
 
         self.items.push(builder.build());
@@ -72,22 +122,34 @@ impl WriteTx {
         self
     }
 
-    pub async fn run(self) -> Result<(), RaidenError> {
-        let policy: crate::RetryPolicy = self.retry_condition.strategy.policy().into();
-        let req = self
-            .client
-            .transact_write_items()
-            .set_transact_items(Some(self.items));
-
-        policy
-            .retry_if(
-                move || {
-                    let req = req.clone();
-                    async { WriteTx::inner_run(req).await }
-                },
-                &self.retry_condition,
-            )
-            .await
+    pub async fn run(self) -> Result<TransactWriteOutput, RaidenError> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+        let client = self.client;
+        // Unlike `IdGenerator::gen`, the token is never fixed, since a reused token would make
+        // DynamoDB skip or reject a different transaction.
+        let token = self
+            .client_request_token
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        let builder = TransactWriteItemsInput::builder()
+            .set_transact_items(Some(self.items))
+            .client_request_token(token)
+            .set_return_consumed_capacity(self.return_consumed_capacity.map(Into::into))
+            .set_return_item_collection_metrics(
+                self.return_item_collection_metrics.map(Into::into),
+            );
+
+        crate::retry_with_backend(
+            self.retry_condition.strategy.policy(),
+            &client,
+            &self.retry_condition,
+            move |client| {
+                let builder = builder.clone();
+                async { WriteTx::inner_run(client, builder).await }
+            },
+        )
+        .await
     }
 
     #[cfg_attr(feature = "tracing", tracing::instrument(
@@ -96,10 +158,15 @@ impl WriteTx {
         skip_all,
         fields(api = "transact_write_items")
     ))]
-    async fn inner_run(req: TransactWriteItemsFluentBuilder) -> Result<(), RaidenError> {
-        let _res = req.send().await?;
-
-        // TODO: ADD Resp
-        Ok(())
+    async fn inner_run(
+        client: SharedBackend,
+        builder: TransactWriteItemsInputBuilder,
+    ) -> Result<TransactWriteOutput, RaidenError> {
+        let res = client.transact_write_items(builder.try_into()?).await?;
+
+        Ok(TransactWriteOutput {
+            consumed_capacity: res.consumed_capacity,
+            item_collection_metrics: res.item_collection_metrics,
+        })
     }
 }
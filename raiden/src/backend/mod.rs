@@ -0,0 +1,175 @@
+//! The DynamoDB operations generated clients and transactions call through, see [`DynamoBackend`].
+//!
+//! The requests and responses are built from raiden's own types, so a backend, e.g. a mock or
+//! a wrapper which records the calls, is written once for both SDKs. The client of the enabled
+//! SDK converts them to and from its inputs and outputs. Only the consumed capacity, the item
+//! collection metrics and the table description are the types of the enabled SDK, as in the
+//! outputs of the operations.
+
+use std::{future::Future, pin::Pin, sync::Arc};
+
+use crate::{
+    observer::{Attempt, ObservedBackend, ObservedInput, ObservedOutput},
+    RaidenError,
+};
+
+#[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
+use crate::ConsumedCapacity;
+
+#[cfg(feature = "aws-sdk")]
+use crate::aws_sdk::types::ConsumedCapacity;
+
+mod request;
+mod response;
+
+pub use request::*;
+pub use response::*;
+
+pub type BackendFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, RaidenError>> + Send + 'a>>;
+
+/// The backend every generated client shares, see [`DynamoBackend`].
+pub type SharedBackend = Arc<dyn DynamoBackend>;
+
+macro_rules! backend {
+    ($($operation: ident => $request: ident, $response: ident;)*) => {
+        /// The DynamoDB operations generated clients and transactions call through.
+        ///
+        /// It is implemented for the DynamoDB client of the enabled SDK, and can be implemented
+        /// for a mock to run models without a DynamoDB endpoint.
+        pub trait DynamoBackend: Send + Sync {
+            $(
+                fn $operation(&self, request: $request) -> BackendFuture<'_, $response>;
+            )*
+
+            /// The backend to make the requests of a single attempt of a retried request with.
+            ///
+            /// Only backends which observe requests need one, so that the observers learn the attempt
+            /// number and the retry decision. Wrappers of such a backend should forward this call.
+            fn for_attempt(&self, _attempt: &Arc<Attempt>) -> Option<SharedBackend> {
+                None
+            }
+        }
+
+        impl DynamoBackend for ObservedBackend {
+            $(
+                fn $operation(&self, request: $request) -> BackendFuture<'_, $response> {
+                    self.observe(stringify!($operation), request, |inner, request| inner.$operation(request))
+                }
+            )*
+
+            fn for_attempt(&self, attempt: &Arc<Attempt>) -> Option<SharedBackend> {
+                Some(self.attempt_backend(attempt))
+            }
+        }
+    };
+}
+
+backend! {
+    get_item => GetItemRequest, GetItemResponse;
+    put_item => PutItemRequest, PutItemResponse;
+    update_item => UpdateItemRequest, UpdateItemResponse;
+    delete_item => DeleteItemRequest, DeleteItemResponse;
+    query => QueryRequest, QueryResponse;
+    scan => ScanRequest, ScanResponse;
+    batch_get_item => BatchGetItemRequest, BatchGetItemResponse;
+    batch_write_item => BatchWriteItemRequest, BatchWriteItemResponse;
+    transact_get_items => TransactGetItemsRequest, TransactGetItemsResponse;
+    transact_write_items => TransactWriteItemsRequest, TransactWriteItemsResponse;
+    create_table => CreateTableRequest, CreateTableResponse;
+    delete_table => DeleteTableRequest, DeleteTableResponse;
+    describe_table => DescribeTableRequest, DescribeTableResponse;
+    update_time_to_live => UpdateTimeToLiveRequest, UpdateTimeToLiveResponse;
+}
+
+macro_rules! observed_table {
+    ($($request: ty;)*) => {
+        $(
+            impl ObservedInput for $request {
+                fn table_name(&self) -> Option<String> {
+                    Some(self.table_name.clone())
+                }
+            }
+        )*
+    };
+}
+
+observed_table! {
+    GetItemRequest;
+    PutItemRequest;
+    UpdateItemRequest;
+    DeleteItemRequest;
+    CreateTableRequest;
+    DeleteTableRequest;
+    DescribeTableRequest;
+    UpdateTimeToLiveRequest;
+}
+
+impl ObservedInput for QueryRequest {
+    fn table_name(&self) -> Option<String> {
+        Some(self.table_name.clone())
+    }
+
+    fn index_name(&self) -> Option<String> {
+        self.index_name.clone()
+    }
+}
+
+impl ObservedInput for ScanRequest {
+    fn table_name(&self) -> Option<String> {
+        Some(self.table_name.clone())
+    }
+
+    fn index_name(&self) -> Option<String> {
+        self.index_name.clone()
+    }
+}
+
+impl ObservedInput for BatchGetItemRequest {}
+impl ObservedInput for BatchWriteItemRequest {}
+impl ObservedInput for TransactGetItemsRequest {}
+impl ObservedInput for TransactWriteItemsRequest {}
+
+macro_rules! observed_capacity {
+    ($($response: ty;)*) => {
+        $(
+            impl ObservedOutput for $response {
+                fn consumed_capacity(&self) -> Vec<ConsumedCapacity> {
+                    self.consumed_capacity.iter().cloned().collect()
+                }
+            }
+        )*
+    };
+}
+
+observed_capacity! {
+    GetItemResponse;
+    PutItemResponse;
+    UpdateItemResponse;
+    DeleteItemResponse;
+    QueryResponse;
+    ScanResponse;
+}
+
+macro_rules! observed_capacities {
+    ($($response: ty;)*) => {
+        $(
+            impl ObservedOutput for $response {
+                fn consumed_capacity(&self) -> Vec<ConsumedCapacity> {
+                    self.consumed_capacity.clone().unwrap_or_default()
+                }
+            }
+        )*
+    };
+}
+
+observed_capacities! {
+    BatchGetItemResponse;
+    BatchWriteItemResponse;
+    TransactGetItemsResponse;
+    TransactWriteItemsResponse;
+}
+
+impl ObservedOutput for CreateTableResponse {}
+impl ObservedOutput for DeleteTableResponse {}
+impl ObservedOutput for DescribeTableResponse {}
+impl ObservedOutput for UpdateTimeToLiveResponse {}
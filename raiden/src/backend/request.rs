@@ -0,0 +1,299 @@
+use std::collections::HashMap;
+
+use crate::{AttributeNames, AttributeType, AttributeValues};
+use crate::{ReturnConsumedCapacity, ReturnItemCollectionMetrics};
+
+/// The request of `get_item`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GetItemRequest {
+    pub table_name: String,
+    pub key: AttributeValues,
+    pub projection_expression: Option<String>,
+    pub expression_attribute_names: Option<AttributeNames>,
+    pub consistent_read: Option<bool>,
+    pub return_consumed_capacity: Option<ReturnConsumedCapacity>,
+}
+
+/// The request of `put_item`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PutItemRequest {
+    pub table_name: String,
+    pub item: AttributeValues,
+    pub condition_expression: Option<String>,
+    pub expression_attribute_names: Option<AttributeNames>,
+    pub expression_attribute_values: Option<AttributeValues>,
+    pub return_values: Option<ReturnValues>,
+    pub return_consumed_capacity: Option<ReturnConsumedCapacity>,
+    pub return_item_collection_metrics: Option<ReturnItemCollectionMetrics>,
+    /// Returns the stored item when the condition fails. It is ignored by rusoto.
+    pub return_old_on_condition_failure: bool,
+}
+
+/// The request of `update_item`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UpdateItemRequest {
+    pub table_name: String,
+    pub key: AttributeValues,
+    pub update_expression: Option<String>,
+    pub condition_expression: Option<String>,
+    pub expression_attribute_names: Option<AttributeNames>,
+    pub expression_attribute_values: Option<AttributeValues>,
+    pub return_values: Option<ReturnValues>,
+    pub return_consumed_capacity: Option<ReturnConsumedCapacity>,
+    pub return_item_collection_metrics: Option<ReturnItemCollectionMetrics>,
+    /// Returns the stored item when the condition fails. It is ignored by rusoto.
+    pub return_old_on_condition_failure: bool,
+}
+
+/// The request of `delete_item`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeleteItemRequest {
+    pub table_name: String,
+    pub key: AttributeValues,
+    pub condition_expression: Option<String>,
+    pub expression_attribute_names: Option<AttributeNames>,
+    pub expression_attribute_values: Option<AttributeValues>,
+    pub return_values: Option<ReturnValues>,
+    pub return_consumed_capacity: Option<ReturnConsumedCapacity>,
+    pub return_item_collection_metrics: Option<ReturnItemCollectionMetrics>,
+    /// Returns the stored item when the condition fails. It is ignored by rusoto.
+    pub return_old_on_condition_failure: bool,
+}
+
+/// The request of `query`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QueryRequest {
+    pub table_name: String,
+    pub index_name: Option<String>,
+    pub key_condition_expression: Option<String>,
+    pub filter_expression: Option<String>,
+    pub projection_expression: Option<String>,
+    pub expression_attribute_names: Option<AttributeNames>,
+    pub expression_attribute_values: Option<AttributeValues>,
+    pub exclusive_start_key: Option<AttributeValues>,
+    pub limit: Option<i64>,
+    pub scan_index_forward: Option<bool>,
+    pub consistent_read: Option<bool>,
+    pub return_consumed_capacity: Option<ReturnConsumedCapacity>,
+}
+
+/// The request of `scan`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScanRequest {
+    pub table_name: String,
+    pub index_name: Option<String>,
+    pub filter_expression: Option<String>,
+    pub projection_expression: Option<String>,
+    pub expression_attribute_names: Option<AttributeNames>,
+    pub expression_attribute_values: Option<AttributeValues>,
+    pub exclusive_start_key: Option<AttributeValues>,
+    pub limit: Option<i64>,
+    pub segment: Option<i64>,
+    pub total_segments: Option<i64>,
+    pub consistent_read: Option<bool>,
+    pub return_consumed_capacity: Option<ReturnConsumedCapacity>,
+}
+
+/// The request of `batch_get_item`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BatchGetItemRequest {
+    /// The keys to read, by table name.
+    pub request_items: HashMap<String, KeysAndAttributes>,
+    pub return_consumed_capacity: Option<ReturnConsumedCapacity>,
+}
+
+/// The keys to read from a single table of a `batch_get_item`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KeysAndAttributes {
+    pub keys: Vec<AttributeValues>,
+    pub projection_expression: Option<String>,
+    pub expression_attribute_names: Option<AttributeNames>,
+    pub consistent_read: Option<bool>,
+}
+
+/// The request of `batch_write_item`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BatchWriteItemRequest {
+    /// The writes, by table name.
+    pub request_items: HashMap<String, Vec<WriteRequest>>,
+    pub return_consumed_capacity: Option<ReturnConsumedCapacity>,
+    pub return_item_collection_metrics: Option<ReturnItemCollectionMetrics>,
+}
+
+/// A single write of a `batch_write_item`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WriteRequest {
+    Put { item: AttributeValues },
+    Delete { key: AttributeValues },
+}
+
+/// The request of `transact_get_items`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TransactGetItemsRequest {
+    pub transact_items: Vec<TransactGetItem>,
+    pub return_consumed_capacity: Option<ReturnConsumedCapacity>,
+}
+
+/// A single read of a `transact_get_items`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TransactGetItem {
+    pub table_name: String,
+    pub key: AttributeValues,
+    pub projection_expression: Option<String>,
+    pub expression_attribute_names: Option<AttributeNames>,
+}
+
+/// The request of `transact_write_items`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TransactWriteItemsRequest {
+    pub transact_items: Vec<TransactWriteItem>,
+    /// The token which makes the retries of this transaction idempotent.
+    pub client_request_token: Option<String>,
+    pub return_consumed_capacity: Option<ReturnConsumedCapacity>,
+    pub return_item_collection_metrics: Option<ReturnItemCollectionMetrics>,
+}
+
+/// A single write of a `transact_write_items`.
+///
+/// `return_old_on_condition_failure` returns the stored item in the cancellation reason
+/// of a failed condition. It is ignored by rusoto.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransactWriteItem {
+    ConditionCheck {
+        table_name: String,
+        key: AttributeValues,
+        condition_expression: String,
+        expression_attribute_names: Option<AttributeNames>,
+        expression_attribute_values: Option<AttributeValues>,
+        return_old_on_condition_failure: bool,
+    },
+    Put {
+        table_name: String,
+        item: AttributeValues,
+        condition_expression: Option<String>,
+        expression_attribute_names: Option<AttributeNames>,
+        expression_attribute_values: Option<AttributeValues>,
+        return_old_on_condition_failure: bool,
+    },
+    Delete {
+        table_name: String,
+        key: AttributeValues,
+        condition_expression: Option<String>,
+        expression_attribute_names: Option<AttributeNames>,
+        expression_attribute_values: Option<AttributeValues>,
+        return_old_on_condition_failure: bool,
+    },
+    Update {
+        table_name: String,
+        key: AttributeValues,
+        update_expression: String,
+        condition_expression: Option<String>,
+        expression_attribute_names: Option<AttributeNames>,
+        expression_attribute_values: Option<AttributeValues>,
+        return_old_on_condition_failure: bool,
+    },
+}
+
+/// The request of `create_table`.
+///
+/// The table is created in on-demand capacity mode unless `provisioned_throughput` is set.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CreateTableRequest {
+    pub table_name: String,
+    pub attribute_definitions: Vec<AttributeDefinition>,
+    pub key_schema: Vec<KeySchemaElement>,
+    pub global_secondary_indexes: Vec<GlobalSecondaryIndex>,
+    pub local_secondary_indexes: Vec<LocalSecondaryIndex>,
+    pub provisioned_throughput: Option<ProvisionedThroughput>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttributeDefinition {
+    pub attribute_name: String,
+    /// One of `S`, `N` and `B`.
+    pub attribute_type: AttributeType,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeySchemaElement {
+    pub attribute_name: String,
+    pub key_type: KeyType,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyType {
+    Hash,
+    Range,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlobalSecondaryIndex {
+    pub index_name: String,
+    pub key_schema: Vec<KeySchemaElement>,
+    pub projection: Projection,
+    pub provisioned_throughput: Option<ProvisionedThroughput>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalSecondaryIndex {
+    pub index_name: String,
+    pub key_schema: Vec<KeySchemaElement>,
+    pub projection: Projection,
+}
+
+/// The attributes copied into a secondary index.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Projection {
+    All,
+    KeysOnly,
+    /// The keys and the listed non-key attributes.
+    Include(Vec<String>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProvisionedThroughput {
+    pub read_capacity_units: i64,
+    pub write_capacity_units: i64,
+}
+
+/// The request of `delete_table`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeleteTableRequest {
+    pub table_name: String,
+}
+
+/// The request of `describe_table`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DescribeTableRequest {
+    pub table_name: String,
+}
+
+/// The request of `update_time_to_live`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UpdateTimeToLiveRequest {
+    pub table_name: String,
+    pub attribute_name: String,
+    pub enabled: bool,
+}
+
+/// The attributes a write returns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReturnValues {
+    None,
+    AllOld,
+    UpdatedOld,
+    AllNew,
+    UpdatedNew,
+}
+
+impl ReturnValues {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReturnValues::None => "NONE",
+            ReturnValues::AllOld => "ALL_OLD",
+            ReturnValues::UpdatedOld => "UPDATED_OLD",
+            ReturnValues::AllNew => "ALL_NEW",
+            ReturnValues::UpdatedNew => "UPDATED_NEW",
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+
+use super::{KeysAndAttributes, WriteRequest};
+use crate::AttributeValues;
+
+#[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
+use crate::{ConsumedCapacity, ItemCollectionMetrics, TableDescription};
+
+#[cfg(feature = "aws-sdk")]
+use crate::aws_sdk::types::{ConsumedCapacity, ItemCollectionMetrics, TableDescription};
+
+/// The response of `get_item`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GetItemResponse {
+    pub item: Option<AttributeValues>,
+    pub consumed_capacity: Option<ConsumedCapacity>,
+}
+
+/// The response of `put_item`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PutItemResponse {
+    pub attributes: Option<AttributeValues>,
+    pub consumed_capacity: Option<ConsumedCapacity>,
+    pub item_collection_metrics: Option<ItemCollectionMetrics>,
+}
+
+/// The response of `update_item`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UpdateItemResponse {
+    pub attributes: Option<AttributeValues>,
+    pub consumed_capacity: Option<ConsumedCapacity>,
+    pub item_collection_metrics: Option<ItemCollectionMetrics>,
+}
+
+/// The response of `delete_item`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeleteItemResponse {
+    pub attributes: Option<AttributeValues>,
+    pub consumed_capacity: Option<ConsumedCapacity>,
+    pub item_collection_metrics: Option<ItemCollectionMetrics>,
+}
+
+/// The response of `query`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QueryResponse {
+    pub items: Option<Vec<AttributeValues>>,
+    pub count: Option<i64>,
+    pub scanned_count: Option<i64>,
+    pub last_evaluated_key: Option<AttributeValues>,
+    pub consumed_capacity: Option<ConsumedCapacity>,
+}
+
+/// The response of `scan`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScanResponse {
+    pub items: Option<Vec<AttributeValues>>,
+    pub count: Option<i64>,
+    pub scanned_count: Option<i64>,
+    pub last_evaluated_key: Option<AttributeValues>,
+    pub consumed_capacity: Option<ConsumedCapacity>,
+}
+
+/// The response of `batch_get_item`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BatchGetItemResponse {
+    /// The items read, by table name.
+    pub responses: Option<HashMap<String, Vec<AttributeValues>>>,
+    pub unprocessed_keys: Option<HashMap<String, KeysAndAttributes>>,
+    pub consumed_capacity: Option<Vec<ConsumedCapacity>>,
+}
+
+/// The response of `batch_write_item`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BatchWriteItemResponse {
+    pub unprocessed_items: Option<HashMap<String, Vec<WriteRequest>>>,
+    pub item_collection_metrics: Option<HashMap<String, Vec<ItemCollectionMetrics>>>,
+    pub consumed_capacity: Option<Vec<ConsumedCapacity>>,
+}
+
+/// The response of `transact_get_items`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TransactGetItemsResponse {
+    /// The item of every read in the order of the request, `None` if it does not exist.
+    pub responses: Option<Vec<Option<AttributeValues>>>,
+    pub consumed_capacity: Option<Vec<ConsumedCapacity>>,
+}
+
+/// The response of `transact_write_items`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TransactWriteItemsResponse {
+    pub item_collection_metrics: Option<HashMap<String, Vec<ItemCollectionMetrics>>>,
+    pub consumed_capacity: Option<Vec<ConsumedCapacity>>,
+}
+
+/// The response of `create_table`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CreateTableResponse {
+    pub table_description: Option<TableDescription>,
+}
+
+/// The response of `delete_table`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeleteTableResponse {
+    pub table_description: Option<TableDescription>,
+}
+
+/// The response of `describe_table`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DescribeTableResponse {
+    pub table: Option<TableDescription>,
+}
+
+/// The response of `update_time_to_live`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UpdateTimeToLiveResponse {}
@@ -0,0 +1,234 @@
+//! Typed `ReturnConsumedCapacity` / `ReturnItemCollectionMetrics` and helpers to aggregate
+//! the consumed capacity of requests which span several pages or batches.
+
+use std::collections::HashMap;
+
+#[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
+use crate::{Capacity, ConsumedCapacity};
+
+#[cfg(feature = "aws-sdk")]
+use crate::aws_sdk::types::{Capacity, ConsumedCapacity};
+
+/// The level of detail of the consumed capacity returned by a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReturnConsumedCapacity {
+    /// The total consumed capacity and a breakdown for the table and each index.
+    Indexes,
+    /// The total consumed capacity only.
+    Total,
+    None,
+}
+
+impl ReturnConsumedCapacity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReturnConsumedCapacity::Indexes => "INDEXES",
+            ReturnConsumedCapacity::Total => "TOTAL",
+            ReturnConsumedCapacity::None => "NONE",
+        }
+    }
+}
+
+/// Whether a write returns statistics about the item collections it modified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReturnItemCollectionMetrics {
+    Size,
+    None,
+}
+
+impl ReturnItemCollectionMetrics {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReturnItemCollectionMetrics::Size => "SIZE",
+            ReturnItemCollectionMetrics::None => "NONE",
+        }
+    }
+}
+
+#[cfg(feature = "aws-sdk")]
+impl From<ReturnConsumedCapacity> for crate::aws_sdk::types::ReturnConsumedCapacity {
+    fn from(value: ReturnConsumedCapacity) -> Self {
+        match value {
+            ReturnConsumedCapacity::Indexes => Self::Indexes,
+            ReturnConsumedCapacity::Total => Self::Total,
+            ReturnConsumedCapacity::None => Self::None,
+        }
+    }
+}
+
+#[cfg(feature = "aws-sdk")]
+impl From<ReturnItemCollectionMetrics> for crate::aws_sdk::types::ReturnItemCollectionMetrics {
+    fn from(value: ReturnItemCollectionMetrics) -> Self {
+        match value {
+            ReturnItemCollectionMetrics::Size => Self::Size,
+            ReturnItemCollectionMetrics::None => Self::None,
+        }
+    }
+}
+
+fn add_units(total: &mut Option<f64>, units: Option<f64>) {
+    if let Some(units) = units {
+        *total = Some(total.unwrap_or_default() + units);
+    }
+}
+
+fn sum_capacity(total: &mut Capacity, capacity: Capacity) {
+    add_units(&mut total.capacity_units, capacity.capacity_units);
+    add_units(&mut total.read_capacity_units, capacity.read_capacity_units);
+    add_units(
+        &mut total.write_capacity_units,
+        capacity.write_capacity_units,
+    );
+}
+
+fn add_capacity(total: &mut Option<Capacity>, capacity: Option<Capacity>) {
+    match (total.as_mut(), capacity) {
+        (Some(total), Some(capacity)) => sum_capacity(total, capacity),
+        (None, capacity) => *total = capacity,
+        (Some(_), None) => {}
+    }
+}
+
+fn add_indexes(
+    total: &mut Option<HashMap<String, Capacity>>,
+    indexes: Option<HashMap<String, Capacity>>,
+) {
+    let Some(indexes) = indexes else {
+        return;
+    };
+    let total = total.get_or_insert_with(HashMap::new);
+    for (index_name, capacity) in indexes {
+        match total.get_mut(&index_name) {
+            Some(entry) => sum_capacity(entry, capacity),
+            None => {
+                total.insert(index_name, capacity);
+            }
+        }
+    }
+}
+
+fn sum_consumed_capacity(total: &mut ConsumedCapacity, capacity: ConsumedCapacity) {
+    add_units(&mut total.capacity_units, capacity.capacity_units);
+    add_units(&mut total.read_capacity_units, capacity.read_capacity_units);
+    add_units(
+        &mut total.write_capacity_units,
+        capacity.write_capacity_units,
+    );
+    add_capacity(&mut total.table, capacity.table);
+    add_indexes(
+        &mut total.local_secondary_indexes,
+        capacity.local_secondary_indexes,
+    );
+    add_indexes(
+        &mut total.global_secondary_indexes,
+        capacity.global_secondary_indexes,
+    );
+}
+
+/// Adds `capacity` to `total`, summing the units of the table and of each index.
+pub fn add_consumed_capacity(
+    total: Option<ConsumedCapacity>,
+    capacity: Option<ConsumedCapacity>,
+) -> Option<ConsumedCapacity> {
+    match (total, capacity) {
+        (Some(mut total), Some(capacity)) => {
+            sum_consumed_capacity(&mut total, capacity);
+            Some(total)
+        }
+        (total, capacity) => total.or(capacity),
+    }
+}
+
+/// Adds the per-table `capacities` of a batch or transaction to `total`, keeping one entry per table.
+pub fn merge_consumed_capacities(
+    total: Option<Vec<ConsumedCapacity>>,
+    capacities: Option<Vec<ConsumedCapacity>>,
+) -> Option<Vec<ConsumedCapacity>> {
+    let Some(capacities) = capacities else {
+        return total;
+    };
+
+    let mut total = total.unwrap_or_default();
+    for capacity in capacities {
+        match total
+            .iter_mut()
+            .find(|t| t.table_name == capacity.table_name)
+        {
+            Some(t) => sum_consumed_capacity(t, capacity),
+            None => total.push(capacity),
+        }
+    }
+    Some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
+    fn consumed(table_name: &str, units: f64, index: Option<&str>) -> ConsumedCapacity {
+        ConsumedCapacity {
+            table_name: Some(table_name.to_owned()),
+            capacity_units: Some(units),
+            global_secondary_indexes: index.map(|index| {
+                HashMap::from([(
+                    index.to_owned(),
+                    Capacity {
+                        capacity_units: Some(units),
+                        ..Capacity::default()
+                    },
+                )])
+            }),
+            ..ConsumedCapacity::default()
+        }
+    }
+
+    #[cfg(feature = "aws-sdk")]
+    fn consumed(table_name: &str, units: f64, index: Option<&str>) -> ConsumedCapacity {
+        ConsumedCapacity::builder()
+            .table_name(table_name)
+            .capacity_units(units)
+            .set_global_secondary_indexes(index.map(|index| {
+                HashMap::from([(
+                    index.to_owned(),
+                    Capacity::builder().capacity_units(units).build(),
+                )])
+            }))
+            .build()
+    }
+
+    #[test]
+    fn test_add_consumed_capacity() {
+        assert_eq!(add_consumed_capacity(None, None), None);
+        assert_eq!(
+            add_consumed_capacity(None, Some(consumed("User", 1.0, None))),
+            Some(consumed("User", 1.0, None))
+        );
+        assert_eq!(
+            add_consumed_capacity(
+                Some(consumed("User", 1.0, Some("nameIndex"))),
+                Some(consumed("User", 0.5, Some("nameIndex"))),
+            ),
+            Some(consumed("User", 1.5, Some("nameIndex")))
+        );
+    }
+
+    #[test]
+    fn test_merge_consumed_capacities() {
+        let total = merge_consumed_capacities(
+            Some(vec![consumed("User", 1.0, None)]),
+            Some(vec![
+                consumed("Pet", 2.0, None),
+                consumed("User", 3.0, None),
+            ]),
+        );
+        assert_eq!(
+            total,
+            Some(vec![
+                consumed("User", 4.0, None),
+                consumed("Pet", 2.0, None)
+            ])
+        );
+        assert_eq!(merge_consumed_capacities(total.clone(), None), total);
+    }
+}
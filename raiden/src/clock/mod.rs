@@ -0,0 +1,209 @@
+//! Clock and conversions for `#[raiden(created_at)]` and `#[raiden(updated_at)]`.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::update_expression::SetOrRemove;
+use crate::{AttributeNames, AttributeValue, AttributeValues};
+
+pub trait Clock {
+    /// Returns the current time.
+    ///
+    /// The time can be fixed with `RAIDEN_CLOCK_FIXED_BY`, given in seconds since the unix epoch.
+    #[cfg(not(test))]
+    fn now() -> SystemTime {
+        match std::env::var("RAIDEN_CLOCK_FIXED_BY")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+        {
+            Some(secs) => UNIX_EPOCH + Duration::from_secs(secs),
+            None => SystemTime::now(),
+        }
+    }
+
+    #[cfg(test)]
+    fn now() -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(1_600_000_000)
+    }
+}
+
+/// The clock used where no model is involved, e.g. `ttl::expires_in`.
+pub(crate) struct DefaultClock;
+
+impl Clock for DefaultClock {}
+
+/// A type which can hold a `created_at` / `updated_at` timestamp.
+///
+/// `String` is formatted as RFC 3339 in UTC with millisecond precision,
+/// and integers hold the seconds since the unix epoch.
+pub trait Timestamp: Sized {
+    fn from_system_time(time: SystemTime) -> Self;
+}
+
+impl Timestamp for String {
+    fn from_system_time(time: SystemTime) -> Self {
+        format_rfc3339(time)
+    }
+}
+
+impl Timestamp for i64 {
+    fn from_system_time(time: SystemTime) -> Self {
+        unix_seconds(time) as i64
+    }
+}
+
+impl Timestamp for u64 {
+    fn from_system_time(time: SystemTime) -> Self {
+        unix_seconds(time)
+    }
+}
+
+impl<T: Timestamp> Timestamp for Option<T> {
+    fn from_system_time(time: SystemTime) -> Self {
+        Some(T::from_system_time(time))
+    }
+}
+
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+fn format_rfc3339(time: SystemTime) -> String {
+    let elapsed = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = elapsed.as_secs();
+    let (days, rem) = (secs / 86_400, secs % 86_400);
+    let (year, month, day) = civil_from_days(days as i64);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+        rem / 3_600,
+        rem % 3_600 / 60,
+        rem % 60,
+        elapsed.subsec_millis(),
+    )
+}
+
+// See. http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    (year, month, day)
+}
+
+/// Appends the `SET` action which refreshes a timestamp attribute on update.
+///
+/// With `if_not_exists` the stored value is kept, which is used for `created_at`.
+/// Nothing is appended when the caller already updates the attribute.
+pub fn push_timestamp(
+    set_items: &mut Vec<SetOrRemove>,
+    attr_name: &str,
+    value: AttributeValue,
+    if_not_exists: bool,
+    placeholders: &mut crate::Placeholders,
+) {
+    let name_placeholder = crate::name_placeholder(attr_name);
+    let touched = set_items.iter().any(|item| match item {
+        SetOrRemove::Set(expression, names, _) | SetOrRemove::Remove(expression, names) => {
+            names.contains_key(&name_placeholder)
+                && expression
+                    .split([' ', '='])
+                    .next()
+                    .is_some_and(|target| target == name_placeholder)
+        }
+    });
+    if touched {
+        return;
+    }
+
+    let mut values = AttributeValues::new();
+    let value_placeholder = placeholders.bind(value, &mut values);
+    let expression = if if_not_exists {
+        format!("{name_placeholder} = if_not_exists({name_placeholder}, {value_placeholder})")
+    } else {
+        format!("{name_placeholder} = {value_placeholder}")
+    };
+    set_items.push(SetOrRemove::Set(
+        expression,
+        AttributeNames::from([(name_placeholder, attr_name.to_owned())]),
+        values,
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_rfc3339() {
+        let time = UNIX_EPOCH + Duration::from_millis(1_600_000_000_123);
+        assert_eq!(String::from_system_time(time), "2020-09-13T12:26:40.123Z");
+        assert_eq!(
+            String::from_system_time(UNIX_EPOCH + Duration::from_secs(951_782_400)),
+            "2000-02-29T00:00:00.000Z"
+        );
+        assert_eq!(i64::from_system_time(time), 1_600_000_000);
+    }
+
+    #[test]
+    fn test_push_timestamp_skips_attribute_set_by_caller() {
+        let mut set_items = vec![SetOrRemove::Set(
+            "#updatedAt = :value0".to_owned(),
+            AttributeNames::from([("#updatedAt".to_owned(), "updatedAt".to_owned())]),
+            AttributeValues::new(),
+        )];
+        push_timestamp(
+            &mut set_items,
+            "updatedAt",
+            crate::IntoAttribute::into_attr(1),
+            false,
+            &mut crate::Placeholders::new(),
+        );
+        push_timestamp(
+            &mut set_items,
+            "createdAt",
+            crate::IntoAttribute::into_attr(1),
+            true,
+            &mut crate::Placeholders::new(),
+        );
+
+        assert_eq!(set_items.len(), 2);
+        match &set_items[1] {
+            SetOrRemove::Set(expression, _, _) => {
+                assert!(expression.starts_with("#createdAt = if_not_exists(#createdAt, :value"))
+            }
+            _ => panic!("should be set"),
+        }
+    }
+
+    #[test]
+    fn test_push_timestamp_with_non_identifier_name() {
+        let mut set_items = vec![];
+        push_timestamp(
+            &mut set_items,
+            "updated-at",
+            crate::IntoAttribute::into_attr(1),
+            false,
+            &mut crate::Placeholders::new(),
+        );
+
+        match &set_items[0] {
+            SetOrRemove::Set(expression, names, _) => {
+                assert_eq!(expression, "#_757064617465642d6174 = :value0");
+                assert_eq!(
+                    names.get("#_757064617465642d6174"),
+                    Some(&"updated-at".to_owned())
+                );
+            }
+            _ => panic!("should be set"),
+        }
+    }
+}
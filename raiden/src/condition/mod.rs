@@ -1,5 +1,4 @@
 // https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Expressions.OperatorsAndFunctions.html
-use super::*;
 
 pub type AttrName = super::AttrPath;
 
@@ -15,12 +14,7 @@ pub enum ConditionFunctionExpression {
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ConditionComparisonExpression {
-    Eq(
-        AttrOrPlaceholder,
-        Option<super::AttributeValue>,
-        AttrOrPlaceholder,
-        Option<super::AttributeValue>,
-    ),
+    Eq(AttrOrValue, AttrOrValue),
 }
 
 #[derive(Clone, PartialEq)]
@@ -40,67 +34,87 @@ pub struct ConditionFilled<T: Clone> {
 
 impl<T: Clone> ConditionFilledOrWaitOperator<T> {
     pub fn and(self, cond: impl ConditionBuilder<T>) -> ConditionFilled<T> {
-        let (condition_string, attr_names, attr_values) = cond.build();
         ConditionFilled {
             not: self.not,
             cond: self.cond,
-            operator: Operator::And(condition_string, attr_names, attr_values),
+            operator: Operator::And(Box::new(cond.into_node())),
             _token: self._token,
         }
     }
     pub fn or(self, cond: impl ConditionBuilder<T>) -> ConditionFilled<T> {
-        let (condition_string, attr_names, attr_values) = cond.build();
         ConditionFilled {
             not: self.not,
             cond: self.cond,
-            operator: Operator::Or(condition_string, attr_names, attr_values),
+            operator: Operator::Or(Box::new(cond.into_node())),
             _token: self._token,
         }
     }
 }
 
 impl<T: Clone> ConditionBuilder<T> for ConditionFilledOrWaitOperator<T> {
-    fn build(self) -> (String, super::AttributeNames, super::AttributeValues) {
-        if self.not {
-            (
-                format!("NOT ({})", self.cond),
-                self.cond.to_attr_names(),
-                self.cond.into_attr_values(),
-            )
-        } else {
-            (
-                self.cond.to_string(),
-                self.cond.to_attr_names(),
-                self.cond.into_attr_values(),
-            )
+    fn into_node(self) -> ConditionNode {
+        ConditionNode {
+            not: self.not,
+            cond: self.cond,
+            operator: None,
         }
     }
 }
+
 impl<T: Clone> ConditionBuilder<T> for ConditionFilled<T> {
-    fn build(self) -> (String, super::AttributeNames, super::AttributeValues) {
-        let (right_str, right_names, right_values) = match self.operator {
-            super::condition::Operator::And(s, m, v) => (format!("AND ({s})"), m, v),
-            super::condition::Operator::Or(s, m, v) => (format!("OR ({s})"), m, v),
-        };
-        let left_str = self.cond.to_string();
-        let left_names = self.cond.to_attr_names();
-        let left_values = self.cond.into_attr_values();
-        if self.not {
-            (
-                format!("NOT ({left_str}) {right_str}"),
-                super::merge_map(left_names, right_names),
-                super::merge_map(left_values, right_values),
-            )
-        } else {
-            (
-                format!("{left_str} {right_str}"),
-                super::merge_map(left_names, right_names),
-                super::merge_map(left_values, right_values),
-            )
+    fn into_node(self) -> ConditionNode {
+        ConditionNode {
+            not: self.not,
+            cond: self.cond,
+            operator: Some(self.operator),
         }
     }
 }
 
+/// A condition whose values are not bound to placeholders yet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConditionNode {
+    not: bool,
+    cond: Cond,
+    operator: Option<Operator>,
+}
+
+impl ConditionNode {
+    fn build(
+        self,
+        placeholders: &mut super::Placeholders,
+    ) -> (
+        ConditionString,
+        super::AttributeNames,
+        super::AttributeValues,
+    ) {
+        let (left_str, left_names, left_values) = self.cond.build(placeholders);
+        let left_str = if self.not {
+            format!("NOT ({left_str})")
+        } else {
+            left_str
+        };
+        let Some(operator) = self.operator else {
+            return (left_str, left_names, left_values);
+        };
+        let (right_str, right_names, right_values) = match operator {
+            Operator::And(node) => {
+                let (s, m, v) = node.build(placeholders);
+                (format!("AND ({s})"), m, v)
+            }
+            Operator::Or(node) => {
+                let (s, m, v) = node.build(placeholders);
+                (format!("OR ({s})"), m, v)
+            }
+        };
+        (
+            format!("{left_str} {right_str}"),
+            super::merge_map(left_names, right_names),
+            super::merge_map(left_values, right_values),
+        )
+    }
+}
+
 impl std::fmt::Display for ConditionFunctionExpression {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         use md5::{Digest, Md5};
@@ -214,23 +228,15 @@ impl super::IntoAttrValues for ConditionFunctionExpression {
     }
 }
 
-impl std::fmt::Display for ConditionComparisonExpression {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Eq(left, _, right, _) => write!(f, "{left} = {right}"),
-        }
-    }
-}
-
 impl super::ToAttrNames for ConditionComparisonExpression {
     fn to_attr_names(&self) -> super::AttributeNames {
         let mut m: super::AttributeNames = std::collections::HashMap::new();
         match self {
-            Self::Eq(left, _, right, _) => {
-                if let AttrOrPlaceholder::Attr(l) = left {
+            Self::Eq(left, right) => {
+                if let AttrOrValue::Attr(l) = left {
                     m = super::merge_map(m, l.attribute_names());
                 }
-                if let AttrOrPlaceholder::Attr(r) = right {
+                if let AttrOrValue::Attr(r) = right {
                     m = super::merge_map(m, r.attribute_names());
                 }
             }
@@ -239,53 +245,90 @@ impl super::ToAttrNames for ConditionComparisonExpression {
     }
 }
 
-impl super::IntoAttrValues for ConditionComparisonExpression {
-    fn into_attr_values(self) -> super::AttributeValues {
-        let mut m: super::AttributeValues = std::collections::HashMap::new();
-
+impl ConditionComparisonExpression {
+    fn build(
+        self,
+        placeholders: &mut super::Placeholders,
+    ) -> (
+        ConditionString,
+        super::AttributeNames,
+        super::AttributeValues,
+    ) {
+        let names = super::ToAttrNames::to_attr_names(&self);
+        let mut values: super::AttributeValues = std::collections::HashMap::new();
         match self {
-            Self::Eq(left, left_value, right, right_value) => {
-                if let Some(left_value) = left_value {
-                    m.insert(left.to_string(), left_value);
-                }
-                if let Some(right_value) = right_value {
-                    m.insert(right.to_string(), right_value);
-                }
+            Self::Eq(left, right) => {
+                let left = left.build(placeholders, &mut values);
+                let right = right.build(placeholders, &mut values);
+                (format!("{left} = {right}"), names, values)
             }
         }
-        m
     }
 }
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, PartialEq)]
-pub enum AttrOrPlaceholder {
+pub enum AttrOrValue {
     Attr(super::AttrPath),
-    Placeholder(String),
+    Value(super::AttributeValue),
 }
 
-impl std::fmt::Display for AttrOrPlaceholder {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl AttrOrValue {
+    fn build(
+        self,
+        placeholders: &mut super::Placeholders,
+        values: &mut super::AttributeValues,
+    ) -> String {
         match self {
-            Self::Placeholder(p) => write!(f, ":{p}"),
-            Self::Attr(a) => write!(f, "{a}"),
+            Self::Attr(a) => a.expression(),
+            Self::Value(v) => placeholders.bind(v, values),
         }
     }
 }
 
 pub type ConditionString = String;
 
-#[derive(Debug, Clone, PartialEq)]
-pub enum Operator {
-    And(
-        ConditionString,
-        super::AttributeNames,
-        super::AttributeValues,
-    ),
-    Or(
+/// Combines `condition` with the expression already set on a request, e.g. its
+/// condition or filter expression, so that both of them have to hold.
+///
+/// Returns the expression and the merged attribute names and values,
+/// which are `None` when empty.
+pub fn merge_condition(
+    expression: Option<String>,
+    attr_names: Option<super::AttributeNames>,
+    attr_values: Option<super::AttributeValues>,
+    condition: (
         ConditionString,
         super::AttributeNames,
         super::AttributeValues,
     ),
+) -> (
+    ConditionString,
+    Option<super::AttributeNames>,
+    Option<super::AttributeValues>,
+) {
+    let (condition_expression, condition_names, condition_values) = condition;
+
+    let expression = match expression {
+        Some(expression) if !expression.is_empty() => {
+            format!("({expression}) AND ({condition_expression})")
+        }
+        _ => condition_expression,
+    };
+    let names = super::merge_map(attr_names.unwrap_or_default(), condition_names);
+    let values = super::merge_map(attr_values.unwrap_or_default(), condition_values);
+
+    (
+        expression,
+        (!names.is_empty()).then_some(names),
+        (!values.is_empty()).then_some(values),
+    )
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operator {
+    And(Box<ConditionNode>),
+    Or(Box<ConditionNode>),
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -295,11 +338,22 @@ pub enum Cond {
     Cmp(ConditionComparisonExpression),
 }
 
-impl std::fmt::Display for Cond {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl Cond {
+    fn build(
+        self,
+        placeholders: &mut super::Placeholders,
+    ) -> (
+        ConditionString,
+        super::AttributeNames,
+        super::AttributeValues,
+    ) {
         match self {
-            Self::Func(func) => write!(f, "{func}"),
-            Self::Cmp(cmp) => write!(f, "{cmp}"),
+            Self::Func(func) => (
+                func.to_string(),
+                super::ToAttrNames::to_attr_names(&func),
+                super::IntoAttrValues::into_attr_values(func),
+            ),
+            Self::Cmp(cmp) => cmp.build(placeholders),
         }
     }
 }
@@ -313,21 +367,28 @@ impl super::ToAttrNames for Cond {
     }
 }
 
-impl super::IntoAttrValues for Cond {
-    fn into_attr_values(self) -> super::AttributeValues {
-        match self {
-            Self::Func(cond) => cond.into_attr_values(),
-            Self::Cmp(cmp) => cmp.into_attr_values(),
-        }
+pub trait ConditionBuilder<T>: Sized {
+    fn into_node(self) -> ConditionNode;
+
+    /// Builds the condition, allocating its placeholders from the request's `placeholders`.
+    fn build_with(
+        self,
+        placeholders: &mut super::Placeholders,
+    ) -> (
+        ConditionString,
+        super::AttributeNames,
+        super::AttributeValues,
+    ) {
+        self.into_node().build(placeholders)
     }
-}
 
-pub trait ConditionBuilder<T> {
     fn build(
         self,
     ) -> (
         ConditionString,
         super::AttributeNames,
         super::AttributeValues,
-    );
+    ) {
+        self.build_with(&mut super::Placeholders::new())
+    }
 }
@@ -4,9 +4,8 @@ use std::{
 };
 
 use serde::{de::DeserializeOwned, Serialize};
-use serde_json::Value;
 
-use crate::{AttributeValue, ConversionError, FromAttribute, IntoAttribute};
+use crate::{AttributeType, AttributeValue, ConversionError, FromAttribute, IntoAttribute};
 
 /// Explicit wrapper for values stored as DynamoDB document attributes.
 ///
@@ -82,82 +81,10 @@ impl<T> DerefMut for Document<T> {
     }
 }
 
-fn conversion_error(message: impl Into<String>) -> ConversionError {
-    ConversionError::Serde(message.into())
-}
-
 pub(crate) fn serialize_document<T: Serialize>(
     value: T,
 ) -> Result<AttributeValue, ConversionError> {
-    let value = serde_json::to_value(value)
-        .map_err(|err| conversion_error(format!("document serialization failed: {err}")))?;
-
-    json_value_to_attribute_value(value)
-}
-
-#[cfg(feature = "aws-sdk")]
-fn attr_null() -> AttributeValue {
-    AttributeValue::Null(true)
-}
-
-#[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
-fn attr_null() -> AttributeValue {
-    AttributeValue {
-        null: Some(true),
-        ..Default::default()
-    }
-}
-
-#[cfg(feature = "aws-sdk")]
-fn attr_bool(value: bool) -> AttributeValue {
-    AttributeValue::Bool(value)
-}
-
-#[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
-fn attr_bool(value: bool) -> AttributeValue {
-    AttributeValue {
-        bool: Some(value),
-        ..Default::default()
-    }
-}
-
-#[cfg(feature = "aws-sdk")]
-fn attr_number(value: String) -> AttributeValue {
-    AttributeValue::N(value)
-}
-
-#[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
-fn attr_number(value: String) -> AttributeValue {
-    AttributeValue {
-        n: Some(value),
-        ..Default::default()
-    }
-}
-
-#[cfg(feature = "aws-sdk")]
-fn attr_string(value: String) -> AttributeValue {
-    AttributeValue::S(value)
-}
-
-#[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
-fn attr_string(value: String) -> AttributeValue {
-    AttributeValue {
-        s: Some(value),
-        ..Default::default()
-    }
-}
-
-#[cfg(feature = "aws-sdk")]
-fn attr_list(value: Vec<AttributeValue>) -> AttributeValue {
-    AttributeValue::L(value)
-}
-
-#[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
-fn attr_list(value: Vec<AttributeValue>) -> AttributeValue {
-    AttributeValue {
-        l: Some(value),
-        ..Default::default()
-    }
+    crate::serde_attr::to_attribute_value(&value)
 }
 
 #[cfg(feature = "aws-sdk")]
@@ -173,124 +100,29 @@ fn attr_map(value: HashMap<String, AttributeValue>) -> AttributeValue {
     }
 }
 
-fn number_string_to_json(value: String) -> Result<Value, ConversionError> {
-    let parsed = serde_json::from_str::<Value>(&value)
-        .map_err(|err| conversion_error(format!("invalid DynamoDB number `{value}`: {err}")))?;
-
-    match parsed {
-        Value::Number(_) => Ok(parsed),
-        _ => Err(conversion_error(format!(
-            "invalid DynamoDB number representation `{value}`"
-        ))),
-    }
-}
-
-pub(crate) fn json_value_to_attribute_value(
-    value: Value,
-) -> Result<AttributeValue, ConversionError> {
-    match value {
-        Value::Null => Ok(attr_null()),
-        Value::Bool(value) => Ok(attr_bool(value)),
-        Value::Number(value) => Ok(attr_number(value.to_string())),
-        Value::String(value) => Ok(attr_string(value)),
-        Value::Array(values) => {
-            let values = values
-                .into_iter()
-                .map(json_value_to_attribute_value)
-                .collect::<Result<Vec<_>, _>>()?;
-
-            Ok(attr_list(values))
-        }
-        Value::Object(values) => {
-            let values = values
-                .into_iter()
-                .map(|(key, value)| json_value_to_attribute_value(value).map(|value| (key, value)))
-                .collect::<Result<HashMap<_, _>, _>>()?;
-
-            Ok(attr_map(values))
-        }
-    }
-}
-
-#[cfg(feature = "aws-sdk")]
-pub(crate) fn attribute_value_to_json_value(
-    value: AttributeValue,
-) -> Result<Value, ConversionError> {
-    match value {
-        AttributeValue::Null(_) => Ok(Value::Null),
-        AttributeValue::Bool(value) => Ok(Value::Bool(value)),
-        AttributeValue::N(value) => number_string_to_json(value),
-        AttributeValue::S(value) => Ok(Value::String(value)),
-        AttributeValue::L(values) => Ok(Value::Array(
-            values
-                .into_iter()
-                .map(attribute_value_to_json_value)
-                .collect::<Result<Vec<_>, _>>()?,
-        )),
-        AttributeValue::M(values) => {
-            let values = values
-                .into_iter()
-                .map(|(key, value)| attribute_value_to_json_value(value).map(|value| (key, value)))
-                .collect::<Result<serde_json::Map<_, _>, _>>()?;
-
-            Ok(Value::Object(values))
-        }
-        other => Err(conversion_error(format!(
-            "unsupported DynamoDB attribute for Document: {other:?}"
-        ))),
-    }
-}
-
 pub(crate) fn deserialize_document<T: DeserializeOwned>(
     value: Option<AttributeValue>,
 ) -> Result<T, ConversionError> {
     let value = value.ok_or(ConversionError::ValueIsNone)?;
-    let value = attribute_value_to_json_value(value)?;
-    serde_json::from_value(value).map_err(|err| ConversionError::Serde(err.to_string()))
+    crate::serde_attr::from_attribute_value(value)
 }
 
 #[doc(hidden)]
+#[allow(clippy::result_large_err)]
 pub fn deserialize_document_item<T: DeserializeOwned>(
     item: crate::AttributeValues,
-) -> Result<T, ConversionError> {
-    deserialize_document(Some(attr_map(item)))
-}
-
-#[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
-pub(crate) fn attribute_value_to_json_value(
-    value: AttributeValue,
-) -> Result<Value, ConversionError> {
-    match value {
-        AttributeValue {
-            null: Some(true), ..
-        } => Ok(Value::Null),
-        AttributeValue {
-            bool: Some(value), ..
-        } => Ok(Value::Bool(value)),
-        AttributeValue { n: Some(value), .. } => number_string_to_json(value),
-        AttributeValue { s: Some(value), .. } => Ok(Value::String(value)),
-        AttributeValue {
-            l: Some(values), ..
-        } => Ok(Value::Array(
-            values
-                .into_iter()
-                .map(attribute_value_to_json_value)
-                .collect::<Result<Vec<_>, _>>()?,
-        )),
-        AttributeValue {
-            m: Some(values), ..
-        } => {
-            let values = values
-                .into_iter()
-                .map(|(key, value)| attribute_value_to_json_value(value).map(|value| (key, value)))
-                .collect::<Result<serde_json::Map<_, _>, _>>()?;
-
-            Ok(Value::Object(values))
-        }
-        other => Err(conversion_error(format!(
-            "unsupported DynamoDB attribute for Document: {other:?}"
-        ))),
-    }
+) -> Result<T, crate::RaidenError> {
+    deserialize_document(Some(attr_map(item))).map_err(|err| match err {
+        // The first segment is the attribute of the item.
+        ConversionError::Nested { path, source } => crate::RaidenError::AttributeConvertError {
+            attr_name: path,
+            source: *source,
+        },
+        source => crate::RaidenError::AttributeConvertError {
+            attr_name: String::new(),
+            source,
+        },
+    })
 }
 
 impl<T: IntoAttribute> IntoAttribute for HashMap<String, T> {
@@ -309,7 +141,12 @@ impl<T: FromAttribute> FromAttribute for HashMap<String, T> {
         #[cfg(feature = "aws-sdk")]
         let values = match value {
             Some(AttributeValue::M(values)) => values,
-            _ => return Err(ConversionError::ValueIsNone),
+            other => {
+                return Err(ConversionError::unexpected_type(
+                    AttributeType::M,
+                    other.as_ref(),
+                ))
+            }
         };
 
         #[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
@@ -317,12 +154,20 @@ impl<T: FromAttribute> FromAttribute for HashMap<String, T> {
             Some(AttributeValue {
                 m: Some(values), ..
             }) => values,
-            _ => return Err(ConversionError::ValueIsNone),
+            other => {
+                return Err(ConversionError::unexpected_type(
+                    AttributeType::M,
+                    other.as_ref(),
+                ))
+            }
         };
 
         values
             .into_iter()
-            .map(|(key, value)| T::from_attr(Some(value)).map(|value| (key, value)))
+            .map(|(key, value)| match T::from_attr(Some(value)) {
+                Ok(value) => Ok((key, value)),
+                Err(err) => Err(err.nested(key)),
+            })
             .collect()
     }
 }
@@ -343,7 +188,12 @@ impl<T: FromAttribute> FromAttribute for BTreeMap<String, T> {
         #[cfg(feature = "aws-sdk")]
         let values = match value {
             Some(AttributeValue::M(values)) => values,
-            _ => return Err(ConversionError::ValueIsNone),
+            other => {
+                return Err(ConversionError::unexpected_type(
+                    AttributeType::M,
+                    other.as_ref(),
+                ))
+            }
         };
 
         #[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
@@ -351,12 +201,20 @@ impl<T: FromAttribute> FromAttribute for BTreeMap<String, T> {
             Some(AttributeValue {
                 m: Some(values), ..
             }) => values,
-            _ => return Err(ConversionError::ValueIsNone),
+            other => {
+                return Err(ConversionError::unexpected_type(
+                    AttributeType::M,
+                    other.as_ref(),
+                ))
+            }
         };
 
         values
             .into_iter()
-            .map(|(key, value)| T::from_attr(Some(value)).map(|value| (key, value)))
+            .map(|(key, value)| match T::from_attr(Some(value)) {
+                Ok(value) => Ok((key, value)),
+                Err(err) => Err(err.nested(key)),
+            })
             .collect()
     }
 }
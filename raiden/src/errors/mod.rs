@@ -4,8 +4,11 @@ pub use transaction::*;
 
 #[derive(thiserror::Error, Debug)]
 pub enum RaidenError {
-    #[error("attribute {attr_name:?} convert error")]
-    AttributeConvertError { attr_name: String },
+    #[error("attribute {:?} convert error: {}", crate::join_path(attr_name, &source.path()), source.cause())]
+    AttributeConvertError {
+        attr_name: String,
+        source: crate::ConversionError,
+    },
     #[error("`{0}`")]
     ConditionalCheckFailed(String),
     #[error("`{0}`")]
@@ -14,6 +17,8 @@ pub enum RaidenError {
     InternalServerError(String),
     #[error("`{0}`")]
     ItemCollectionSizeLimitExceeded(String),
+    #[error("`{0}`")]
+    LimitExceeded(String),
     #[error("next_token decode error")]
     NextTokenDecodeError,
     #[error("`{0}`")]
@@ -21,6 +26,8 @@ pub enum RaidenError {
     #[error("`{0}`")]
     RequestLimitExceeded(String),
     #[error("`{0}`")]
+    ResourceInUse(String),
+    #[error("`{0}`")]
     ResourceNotFound(String),
     #[error("`{0}`")]
     SizeLimitExceeded(String),
@@ -30,6 +37,8 @@ pub enum RaidenError {
     TransactionInProgress(String),
     #[error("`{0}`")]
     Validation(String),
+    #[error("version conflict `{0}`")]
+    VersionConflict(String),
     //
     // Following errors are returned only using rusoto.
     //
@@ -45,10 +54,13 @@ pub enum RaidenError {
     #[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
     #[error("`{0}`")]
     ParseError(String),
+    /// rusoto only passes the error message on, so `cancellations` carry the reason codes
+    /// parsed from it, without messages or items.
     #[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
     #[error("`transaction canceled error {reasons}`")]
     TransactionCanceled {
         reasons: RaidenTransactionCancellationReasons,
+        cancellations: Vec<RaidenTransactionCancellation>,
     },
     #[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
     #[error("unknown error")]
@@ -57,6 +69,12 @@ pub enum RaidenError {
     // Following errors are returned only using aws-sdk.
     //
     #[cfg(feature = "aws-sdk")]
+    #[error("`{message}`")]
+    ConditionalCheckFailedWithItem {
+        message: String,
+        item: crate::AttributeValues,
+    },
+    #[cfg(feature = "aws-sdk")]
     #[error("`{0:?}`")]
     Construction(aws_smithy_runtime_api::client::result::ConstructionFailure),
     #[cfg(feature = "aws-sdk")]
@@ -69,6 +87,7 @@ pub enum RaidenError {
     #[error("`transaction canceled error {reasons}: {raw_reasons:?}`")]
     TransactionCanceled {
         reasons: RaidenTransactionCancellationReasons,
+        cancellations: Vec<RaidenTransactionCancellation>,
         raw_reasons: Vec<crate::aws_sdk::types::CancellationReason>,
     },
     #[cfg(feature = "aws-sdk")]
@@ -87,3 +106,40 @@ pub enum RaidenError {
     #[error("`{0}`")]
     TransactionConflictError(String),
 }
+
+impl RaidenError {
+    /// Decodes the current item returned by a failed condition check.
+    ///
+    /// The item is returned only with `return_old_on_condition_failure()`,
+    /// which is supported by aws-sdk.
+    #[allow(clippy::result_large_err)]
+    pub fn current_item<T: crate::RaidenItem>(&self) -> Option<Result<T, RaidenError>> {
+        match self {
+            #[cfg(feature = "aws-sdk")]
+            RaidenError::ConditionalCheckFailedWithItem { item, .. } => {
+                Some(T::from_item(item.clone()))
+            }
+            _ => None,
+        }
+    }
+
+    /// The full path of the attribute which failed to convert, including nested document paths, e.g. `profile.tags[0]`.
+    pub fn attribute_path(&self) -> Option<String> {
+        match self {
+            RaidenError::AttributeConvertError { attr_name, source } => {
+                Some(crate::join_path(attr_name, &source.path()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the per item reasons of a canceled transaction, in the order the items were added.
+    ///
+    /// With rusoto only the reason codes are available.
+    pub fn transaction_cancellations(&self) -> Option<&[RaidenTransactionCancellation]> {
+        match self {
+            RaidenError::TransactionCanceled { cancellations, .. } => Some(cancellations),
+            _ => None,
+        }
+    }
+}
@@ -61,6 +61,20 @@ impl RaidenTransactionCancellationReasons {
     pub fn has_validation_error(&self) -> bool {
         self.has_error(RaidenTransactionCancellationReason::ValidationError)
     }
+
+    /// Expands the reason codes into per item cancellations without messages or items.
+    pub fn cancellations(&self) -> Vec<RaidenTransactionCancellation> {
+        self.0
+            .iter()
+            .enumerate()
+            .map(|(index, reason)| RaidenTransactionCancellation {
+                index,
+                reason: reason.clone(),
+                message: None,
+                item: None,
+            })
+            .collect()
+    }
 }
 
 impl fmt::Display for RaidenTransactionCancellationReasons {
@@ -78,6 +92,28 @@ impl fmt::Display for RaidenTransactionCancellationReasons {
     }
 }
 
+/// Why a single item of a canceled transaction failed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RaidenTransactionCancellation {
+    /// The position of the item in the transaction, i.e. the order its builder was added in.
+    pub index: usize,
+    /// `None` when this item did not cause the cancellation.
+    pub reason: Option<RaidenTransactionCancellationReason>,
+    /// The DynamoDB message of this item. Always `None` with rusoto.
+    pub message: Option<String>,
+    /// The stored item of a failed condition, returned with `return_old_on_condition_failure()`.
+    /// Always `None` with rusoto.
+    pub item: Option<crate::AttributeValues>,
+}
+
+impl RaidenTransactionCancellation {
+    /// Decodes the stored item returned for this transaction item.
+    #[allow(clippy::result_large_err)]
+    pub fn current_item<T: crate::RaidenItem>(&self) -> Option<Result<T, crate::RaidenError>> {
+        self.item.clone().map(T::from_item)
+    }
+}
+
 #[derive(thiserror::Error, Clone, Debug, PartialEq)]
 pub enum RaidenTransactionCancellationReason {
     #[error("Unknown")]
@@ -115,7 +151,10 @@ impl RaidenTransactionCancellationReason {
 
 #[cfg(test)]
 mod tests {
-    use crate::{RaidenTransactionCancellationReason, RaidenTransactionCancellationReasons};
+    use crate::{
+        RaidenTransactionCancellation, RaidenTransactionCancellationReason,
+        RaidenTransactionCancellationReasons,
+    };
 
     #[test]
     fn parse_message_single() {
@@ -170,6 +209,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn cancellations_are_indexed() {
+        let reasons = RaidenTransactionCancellationReasons(vec![
+            None,
+            Some(RaidenTransactionCancellationReason::ConditionalCheckFailed),
+        ]);
+
+        assert_eq!(
+            reasons.cancellations(),
+            vec![
+                RaidenTransactionCancellation {
+                    index: 0,
+                    reason: None,
+                    message: None,
+                    item: None,
+                },
+                RaidenTransactionCancellation {
+                    index: 1,
+                    reason: Some(RaidenTransactionCancellationReason::ConditionalCheckFailed),
+                    message: None,
+                    item: None,
+                },
+            ]
+        );
+    }
+
     #[test]
     fn has_error() {
         let results = RaidenTransactionCancellationReasons(vec![
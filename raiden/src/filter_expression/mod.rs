@@ -7,230 +7,45 @@ pub type FilterExpressionString = String;
 // ref: https://docs.aws.amazon.com/amazondynamodb/latest/developerguide/Query.html
 #[derive(Debug, Clone)]
 pub enum FilterExpressionOperator {
-    And(
-        FilterExpressionString,
-        super::AttributeNames,
-        super::AttributeValues,
-    ),
-    Or(
-        FilterExpressionString,
-        super::AttributeNames,
-        super::AttributeValues,
-    ),
+    And(FilterExpressionNode),
+    Or(FilterExpressionNode),
 }
 
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, PartialEq)]
 pub enum FilterExpressionTypes {
-    Eq(super::Placeholder, super::AttributeValue),
-    Not(super::Placeholder, super::AttributeValue),
-    Le(super::Placeholder, super::AttributeValue),
-    Ge(super::Placeholder, super::AttributeValue),
-    Lt(super::Placeholder, super::AttributeValue),
-    Gt(super::Placeholder, super::AttributeValue),
-    Between(
-        super::Placeholder,
-        super::AttributeValue,
-        super::Placeholder,
-        super::AttributeValue,
-    ),
-    In(Vec<(super::Placeholder, super::AttributeValue)>),
-    BeginsWith(super::Placeholder, super::AttributeValue),
+    Eq(super::AttributeValue),
+    Not(super::AttributeValue),
+    Le(super::AttributeValue),
+    Ge(super::AttributeValue),
+    Lt(super::AttributeValue),
+    Gt(super::AttributeValue),
+    Between(super::AttributeValue, super::AttributeValue),
+    In(Vec<super::AttributeValue>),
+    BeginsWith(super::AttributeValue),
     AttributeExists(),
     AttributeNotExists(),
-    AttributeType(super::Placeholder, super::AttributeType),
-    Contains(super::Placeholder, super::AttributeValue),
-}
-
-pub trait FilterExpressionBuilder<T> {
-    fn build(
-        self,
-    ) -> (
-        FilterExpressionString,
-        super::AttributeNames,
-        super::AttributeValues,
-    );
-}
-
-#[derive(Debug, Clone)]
-pub struct FilterExpression<T> {
-    pub attr: crate::AttrPath,
-    pub is_size: bool,
-    pub _token: std::marker::PhantomData<fn() -> T>,
-}
-
-#[derive(Debug, Clone)]
-pub struct FilterExpressionFilledOrWaitOperator<T> {
-    attr: crate::AttrPath,
-    is_size: bool,
-    cond: FilterExpressionTypes,
-    _token: std::marker::PhantomData<fn() -> T>,
+    AttributeType(super::AttributeType),
+    Contains(super::AttributeValue),
 }
 
+/// A filter expression whose values are not bound to placeholders yet.
 #[derive(Debug, Clone)]
-pub struct FilterExpressionFilled<T> {
+pub struct FilterExpressionNode {
     attr: crate::AttrPath,
     is_size: bool,
     cond: FilterExpressionTypes,
-    operator: FilterExpressionOperator,
-    _token: std::marker::PhantomData<fn() -> T>,
-}
-
-impl<T> FilterExpressionFilledOrWaitOperator<T> {
-    pub fn and(self, cond: impl FilterExpressionBuilder<T>) -> FilterExpressionFilled<T> {
-        let (condition_string, attr_names, attr_values) = cond.build();
-        FilterExpressionFilled {
-            attr: self.attr,
-            is_size: self.is_size,
-            cond: self.cond,
-            operator: FilterExpressionOperator::And(condition_string, attr_names, attr_values),
-            _token: self._token,
-        }
-    }
-    pub fn or(self, cond: impl FilterExpressionBuilder<T>) -> FilterExpressionFilled<T> {
-        let (condition_string, attr_names, attr_values) = cond.build();
-        FilterExpressionFilled {
-            attr: self.attr,
-            is_size: self.is_size,
-            cond: self.cond,
-            operator: FilterExpressionOperator::Or(condition_string, attr_names, attr_values),
-            _token: self._token,
-        }
-    }
+    operator: Option<Box<FilterExpressionOperator>>,
 }
 
-impl<T> FilterExpressionBuilder<T> for FilterExpressionFilledOrWaitOperator<T> {
-    fn build(self) -> (String, super::AttributeNames, super::AttributeValues) {
+impl FilterExpressionNode {
+    fn build(
+        self,
+        placeholders: &mut super::Placeholders,
+    ) -> (String, super::AttributeNames, super::AttributeValues) {
         let attr = self.attr;
-        let attr_names = attr.attribute_names();
+        let mut attr_names = attr.attribute_names();
         let mut attr_values: super::AttributeValues = std::collections::HashMap::new();
-
-        let left_cond = if self.is_size {
-            format!("size({})", attr.expression())
-        } else {
-            attr.expression()
-        };
-        match self.cond {
-            FilterExpressionTypes::Eq(placeholder, value) => {
-                attr_values.insert(placeholder.to_string(), value);
-                (
-                    format!("{left_cond} = {placeholder}"),
-                    attr_names,
-                    attr_values,
-                )
-            }
-            FilterExpressionTypes::Not(placeholder, value) => {
-                attr_values.insert(placeholder.to_string(), value);
-                (
-                    format!("{left_cond} <> {placeholder}"),
-                    attr_names,
-                    attr_values,
-                )
-            }
-            FilterExpressionTypes::Gt(placeholder, value) => {
-                attr_values.insert(placeholder.to_string(), value);
-                (
-                    format!("{left_cond} > {placeholder}"),
-                    attr_names,
-                    attr_values,
-                )
-            }
-            FilterExpressionTypes::Ge(placeholder, value) => {
-                attr_values.insert(placeholder.to_string(), value);
-                (
-                    format!("{left_cond} >= {placeholder}"),
-                    attr_names,
-                    attr_values,
-                )
-            }
-            FilterExpressionTypes::Le(placeholder, value) => {
-                attr_values.insert(placeholder.to_string(), value);
-                (
-                    format!("{left_cond} <= {placeholder}"),
-                    attr_names,
-                    attr_values,
-                )
-            }
-            FilterExpressionTypes::Lt(placeholder, value) => {
-                attr_values.insert(placeholder.to_string(), value);
-                (
-                    format!("{left_cond} < {placeholder}"),
-                    attr_names,
-                    attr_values,
-                )
-            }
-            FilterExpressionTypes::Between(placeholder1, value1, placeholder2, value2) => {
-                attr_values.insert(placeholder1.to_string(), value1);
-                attr_values.insert(placeholder2.to_string(), value2);
-                (
-                    format!("{left_cond} BETWEEN {placeholder1} AND {placeholder2}"),
-                    attr_names,
-                    attr_values,
-                )
-            }
-            FilterExpressionTypes::In(attributes) => {
-                let placeholders = attributes
-                    .iter()
-                    .map(|(placeholder, _)| placeholder.clone())
-                    .collect::<Vec<_>>()
-                    .join(",");
-                for (placeholder, value) in attributes {
-                    attr_values.insert(placeholder, value);
-                }
-                (
-                    format!("{left_cond} IN ({placeholders})"),
-                    attr_names,
-                    attr_values,
-                )
-            }
-            FilterExpressionTypes::BeginsWith(placeholder, value) => {
-                attr_values.insert(placeholder.to_string(), value);
-                (
-                    format!("begins_with({}, {placeholder})", attr.expression()),
-                    attr_names,
-                    attr_values,
-                )
-            }
-            FilterExpressionTypes::AttributeExists() => (
-                format!("attribute_exists({})", attr.expression()),
-                attr_names,
-                attr_values,
-            ),
-            FilterExpressionTypes::AttributeNotExists() => (
-                format!("attribute_not_exists({})", attr.expression()),
-                attr_names,
-                attr_values,
-            ),
-            FilterExpressionTypes::AttributeType(placeholder, attribute_type) => {
-                attr_values.insert(placeholder.to_string(), attribute_type.into_attr());
-                (
-                    format!("attribute_type({}, {placeholder})", attr.expression()),
-                    attr_names,
-                    attr_values,
-                )
-            }
-            FilterExpressionTypes::Contains(placeholder, value) => {
-                attr_values.insert(placeholder.to_string(), value);
-                (
-                    format!("contains({}, {placeholder})", attr.expression()),
-                    attr_names,
-                    attr_values,
-                )
-            }
-        }
-    }
-}
-
-impl<T> FilterExpressionBuilder<T> for FilterExpressionFilled<T> {
-    fn build(self) -> (String, super::AttributeNames, super::AttributeValues) {
-        let (right_str, right_names, right_values) = match self.operator {
-            FilterExpressionOperator::And(s, m, v) => (format!("AND ({s})"), m, v),
-            FilterExpressionOperator::Or(s, m, v) => (format!("OR ({s})"), m, v),
-        };
-
-        let attr = self.attr;
-        let left_names = attr.attribute_names();
-        let mut left_values: super::AttributeValues = std::collections::HashMap::new();
         let left_cond = if self.is_size {
             format!("size({})", attr.expression())
         } else {
@@ -238,48 +53,45 @@ impl<T> FilterExpressionBuilder<T> for FilterExpressionFilled<T> {
         };
 
         let left_str = match self.cond {
-            FilterExpressionTypes::Eq(placeholder, value) => {
-                left_values.insert(placeholder.clone(), value);
+            FilterExpressionTypes::Eq(value) => {
+                let placeholder = placeholders.bind(value, &mut attr_values);
                 format!("{left_cond} = {placeholder}")
             }
-            FilterExpressionTypes::Not(placeholder, value) => {
-                left_values.insert(placeholder.clone(), value);
+            FilterExpressionTypes::Not(value) => {
+                let placeholder = placeholders.bind(value, &mut attr_values);
                 format!("{left_cond} <> {placeholder}")
             }
-            FilterExpressionTypes::Gt(placeholder, value) => {
-                left_values.insert(placeholder.clone(), value);
+            FilterExpressionTypes::Gt(value) => {
+                let placeholder = placeholders.bind(value, &mut attr_values);
                 format!("{left_cond} > {placeholder}")
             }
-            FilterExpressionTypes::Ge(placeholder, value) => {
-                left_values.insert(placeholder.clone(), value);
+            FilterExpressionTypes::Ge(value) => {
+                let placeholder = placeholders.bind(value, &mut attr_values);
                 format!("{left_cond} >= {placeholder}")
             }
-            FilterExpressionTypes::Le(placeholder, value) => {
-                left_values.insert(placeholder.clone(), value);
+            FilterExpressionTypes::Le(value) => {
+                let placeholder = placeholders.bind(value, &mut attr_values);
                 format!("{left_cond} <= {placeholder}")
             }
-            FilterExpressionTypes::Lt(placeholder, value) => {
-                left_values.insert(placeholder.clone(), value);
+            FilterExpressionTypes::Lt(value) => {
+                let placeholder = placeholders.bind(value, &mut attr_values);
                 format!("{left_cond} < {placeholder}")
             }
-            FilterExpressionTypes::Between(placeholder1, value1, placeholder2, value2) => {
-                left_values.insert(placeholder1.clone(), value1);
-                left_values.insert(placeholder2.clone(), value2);
+            FilterExpressionTypes::Between(value1, value2) => {
+                let placeholder1 = placeholders.bind(value1, &mut attr_values);
+                let placeholder2 = placeholders.bind(value2, &mut attr_values);
                 format!("{left_cond} BETWEEN {placeholder1} AND {placeholder2}")
             }
-            FilterExpressionTypes::In(attributes) => {
-                let placeholders = attributes
-                    .iter()
-                    .map(|(placeholder, _)| placeholder.clone())
+            FilterExpressionTypes::In(values) => {
+                let list = values
+                    .into_iter()
+                    .map(|value| placeholders.bind(value, &mut attr_values))
                     .collect::<Vec<_>>()
                     .join(",");
-                for (placeholder, value) in attributes {
-                    left_values.insert(placeholder, value);
-                }
-                format!("{left_cond} IN ({placeholders})")
+                format!("{left_cond} IN ({list})")
             }
-            FilterExpressionTypes::BeginsWith(placeholder, value) => {
-                left_values.insert(placeholder.clone(), value);
+            FilterExpressionTypes::BeginsWith(value) => {
+                let placeholder = placeholders.bind(value, &mut attr_values);
                 format!("begins_with({}, {placeholder})", attr.expression())
             }
             FilterExpressionTypes::AttributeExists() => {
@@ -288,92 +100,151 @@ impl<T> FilterExpressionBuilder<T> for FilterExpressionFilled<T> {
             FilterExpressionTypes::AttributeNotExists() => {
                 format!("attribute_not_exists({})", attr.expression())
             }
-            FilterExpressionTypes::AttributeType(placeholder, attribute_type) => {
-                left_values.insert(placeholder.clone(), attribute_type.into_attr());
+            FilterExpressionTypes::AttributeType(attribute_type) => {
+                let placeholder = placeholders.bind(attribute_type.into_attr(), &mut attr_values);
                 format!("attribute_type({}, {placeholder})", attr.expression())
             }
-            FilterExpressionTypes::Contains(placeholder, value) => {
-                left_values.insert(placeholder.clone(), value);
+            FilterExpressionTypes::Contains(value) => {
+                let placeholder = placeholders.bind(value, &mut attr_values);
                 format!("contains({}, {placeholder})", attr.expression())
             }
         };
+
+        let Some(operator) = self.operator else {
+            return (left_str, attr_names, attr_values);
+        };
+        let (right_str, right_names, right_values) = match *operator {
+            FilterExpressionOperator::And(node) => {
+                let (s, m, v) = node.build(placeholders);
+                (format!("AND ({s})"), m, v)
+            }
+            FilterExpressionOperator::Or(node) => {
+                let (s, m, v) = node.build(placeholders);
+                (format!("OR ({s})"), m, v)
+            }
+        };
+        attr_names = super::merge_map(attr_names, right_names);
         (
             format!("{left_str} {right_str}"),
-            super::merge_map(left_names, right_names),
-            super::merge_map(left_values, right_values),
+            attr_names,
+            super::merge_map(attr_values, right_values),
         )
     }
 }
 
+pub trait FilterExpressionBuilder<T>: Sized {
+    fn into_node(self) -> FilterExpressionNode;
+
+    /// Builds the expression, allocating its placeholders from the request's `placeholders`.
+    fn build_with(
+        self,
+        placeholders: &mut super::Placeholders,
+    ) -> (
+        FilterExpressionString,
+        super::AttributeNames,
+        super::AttributeValues,
+    ) {
+        self.into_node().build(placeholders)
+    }
+
+    fn build(
+        self,
+    ) -> (
+        FilterExpressionString,
+        super::AttributeNames,
+        super::AttributeValues,
+    ) {
+        self.build_with(&mut super::Placeholders::new())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FilterExpression<T> {
+    pub attr: crate::AttrPath,
+    pub is_size: bool,
+    pub _token: std::marker::PhantomData<fn() -> T>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FilterExpressionFilledOrWaitOperator<T> {
+    node: FilterExpressionNode,
+    _token: std::marker::PhantomData<fn() -> T>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FilterExpressionFilled<T> {
+    node: FilterExpressionNode,
+    _token: std::marker::PhantomData<fn() -> T>,
+}
+
+impl<T> FilterExpressionFilledOrWaitOperator<T> {
+    pub fn and(self, cond: impl FilterExpressionBuilder<T>) -> FilterExpressionFilled<T> {
+        self.operator(FilterExpressionOperator::And(cond.into_node()))
+    }
+    pub fn or(self, cond: impl FilterExpressionBuilder<T>) -> FilterExpressionFilled<T> {
+        self.operator(FilterExpressionOperator::Or(cond.into_node()))
+    }
+
+    fn operator(mut self, operator: FilterExpressionOperator) -> FilterExpressionFilled<T> {
+        self.node.operator = Some(Box::new(operator));
+        FilterExpressionFilled {
+            node: self.node,
+            _token: self._token,
+        }
+    }
+}
+
+impl<T> FilterExpressionBuilder<T> for FilterExpressionFilledOrWaitOperator<T> {
+    fn into_node(self) -> FilterExpressionNode {
+        self.node
+    }
+}
+
+impl<T> FilterExpressionBuilder<T> for FilterExpressionFilled<T> {
+    fn into_node(self) -> FilterExpressionNode {
+        self.node
+    }
+}
+
 impl<T> FilterExpression<T> {
     pub fn size(mut self) -> Self {
         self.is_size = true;
         self
     }
 
-    pub fn eq(self, value: impl super::IntoAttribute) -> FilterExpressionFilledOrWaitOperator<T> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let cond = FilterExpressionTypes::Eq(placeholder, value.into_attr());
+    fn filled(self, cond: FilterExpressionTypes) -> FilterExpressionFilledOrWaitOperator<T> {
         FilterExpressionFilledOrWaitOperator {
-            attr: self.attr,
-            is_size: self.is_size,
-            cond,
+            node: FilterExpressionNode {
+                attr: self.attr,
+                is_size: self.is_size,
+                cond,
+                operator: None,
+            },
             _token: std::marker::PhantomData,
         }
     }
 
+    pub fn eq(self, value: impl super::IntoAttribute) -> FilterExpressionFilledOrWaitOperator<T> {
+        self.filled(FilterExpressionTypes::Eq(value.into_attr()))
+    }
+
     pub fn not(self, value: impl super::IntoAttribute) -> FilterExpressionFilledOrWaitOperator<T> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let cond = FilterExpressionTypes::Not(placeholder, value.into_attr());
-        FilterExpressionFilledOrWaitOperator {
-            attr: self.attr,
-            is_size: self.is_size,
-            cond,
-            _token: std::marker::PhantomData,
-        }
+        self.filled(FilterExpressionTypes::Not(value.into_attr()))
     }
 
     pub fn gt(self, value: impl super::IntoAttribute) -> FilterExpressionFilledOrWaitOperator<T> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let cond = FilterExpressionTypes::Gt(placeholder, value.into_attr());
-        FilterExpressionFilledOrWaitOperator {
-            attr: self.attr,
-            is_size: self.is_size,
-            cond,
-            _token: std::marker::PhantomData,
-        }
+        self.filled(FilterExpressionTypes::Gt(value.into_attr()))
     }
     pub fn ge(self, value: impl super::IntoAttribute) -> FilterExpressionFilledOrWaitOperator<T> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let cond = FilterExpressionTypes::Ge(placeholder, value.into_attr());
-        FilterExpressionFilledOrWaitOperator {
-            attr: self.attr,
-            is_size: self.is_size,
-            cond,
-            _token: std::marker::PhantomData,
-        }
+        self.filled(FilterExpressionTypes::Ge(value.into_attr()))
     }
 
     pub fn le(self, value: impl super::IntoAttribute) -> FilterExpressionFilledOrWaitOperator<T> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let cond = FilterExpressionTypes::Le(placeholder, value.into_attr());
-        FilterExpressionFilledOrWaitOperator {
-            attr: self.attr,
-            is_size: self.is_size,
-            cond,
-            _token: std::marker::PhantomData,
-        }
+        self.filled(FilterExpressionTypes::Le(value.into_attr()))
     }
 
     pub fn lt(self, value: impl super::IntoAttribute) -> FilterExpressionFilledOrWaitOperator<T> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let cond = FilterExpressionTypes::Lt(placeholder, value.into_attr());
-        FilterExpressionFilledOrWaitOperator {
-            attr: self.attr,
-            is_size: self.is_size,
-            cond,
-            _token: std::marker::PhantomData,
-        }
+        self.filled(FilterExpressionTypes::Lt(value.into_attr()))
     }
 
     pub fn between(
@@ -381,37 +252,18 @@ impl<T> FilterExpression<T> {
         value1: impl super::IntoAttribute,
         value2: impl super::IntoAttribute,
     ) -> FilterExpressionFilledOrWaitOperator<T> {
-        let placeholder1 = format!(":value{}", super::generate_value_id());
-        let placeholder2 = format!(":value{}", super::generate_value_id());
-        let cond = FilterExpressionTypes::Between(
-            placeholder1,
+        self.filled(FilterExpressionTypes::Between(
             value1.into_attr(),
-            placeholder2,
             value2.into_attr(),
-        );
-        FilterExpressionFilledOrWaitOperator {
-            attr: self.attr,
-            is_size: self.is_size,
-            cond,
-            _token: std::marker::PhantomData,
-        }
+        ))
     }
 
     pub fn r#in(
         self,
         values: Vec<impl super::IntoAttribute>,
     ) -> FilterExpressionFilledOrWaitOperator<T> {
-        let attributes = values.into_iter().map(|value| {
-            let placeholder = format!(":value{}", super::generate_value_id());
-            (placeholder, value.into_attr())
-        });
-        let cond = FilterExpressionTypes::In(attributes.collect());
-        FilterExpressionFilledOrWaitOperator {
-            attr: self.attr,
-            is_size: self.is_size,
-            cond,
-            _token: std::marker::PhantomData,
-        }
+        let values = values.into_iter().map(|value| value.into_attr());
+        self.filled(FilterExpressionTypes::In(values.collect()))
     }
 
     // We can use `begins_with` only with a range key after specifying an EQ condition for the primary key.
@@ -419,61 +271,28 @@ impl<T> FilterExpression<T> {
         self,
         value: impl super::IntoAttribute,
     ) -> FilterExpressionFilledOrWaitOperator<T> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let cond = FilterExpressionTypes::BeginsWith(placeholder, value.into_attr());
-        FilterExpressionFilledOrWaitOperator {
-            attr: self.attr,
-            is_size: self.is_size,
-            cond,
-            _token: std::marker::PhantomData,
-        }
+        self.filled(FilterExpressionTypes::BeginsWith(value.into_attr()))
     }
 
     pub fn attribute_exists(self) -> FilterExpressionFilledOrWaitOperator<T> {
-        let cond = FilterExpressionTypes::AttributeExists();
-        FilterExpressionFilledOrWaitOperator {
-            attr: self.attr,
-            is_size: self.is_size,
-            cond,
-            _token: std::marker::PhantomData,
-        }
+        self.filled(FilterExpressionTypes::AttributeExists())
     }
 
     pub fn attribute_not_exists(self) -> FilterExpressionFilledOrWaitOperator<T> {
-        let cond = FilterExpressionTypes::AttributeNotExists();
-        FilterExpressionFilledOrWaitOperator {
-            attr: self.attr,
-            is_size: self.is_size,
-            cond,
-            _token: std::marker::PhantomData,
-        }
+        self.filled(FilterExpressionTypes::AttributeNotExists())
     }
 
     pub fn attribute_type(
         self,
         attribute_type: super::AttributeType,
     ) -> FilterExpressionFilledOrWaitOperator<T> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let cond = FilterExpressionTypes::AttributeType(placeholder, attribute_type);
-        FilterExpressionFilledOrWaitOperator {
-            attr: self.attr,
-            is_size: self.is_size,
-            cond,
-            _token: std::marker::PhantomData,
-        }
+        self.filled(FilterExpressionTypes::AttributeType(attribute_type))
     }
 
     pub fn contains(
         self,
         value: impl super::IntoAttribute,
     ) -> FilterExpressionFilledOrWaitOperator<T> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let cond = FilterExpressionTypes::Contains(placeholder, value.into_attr());
-        FilterExpressionFilledOrWaitOperator {
-            attr: self.attr,
-            is_size: self.is_size,
-            cond,
-            _token: std::marker::PhantomData,
-        }
+        self.filled(FilterExpressionTypes::Contains(value.into_attr()))
     }
 }
@@ -2,38 +2,112 @@ pub type KeyConditionString = String;
 
 #[derive(Debug, Clone)]
 pub enum KeyConditionOperator {
-    And(
-        KeyConditionString,
-        super::AttributeNames,
-        super::AttributeValues,
-    ),
+    And(KeyConditionNode),
 }
 
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, PartialEq)]
 pub enum KeyConditionTypes {
-    Eq(super::Placeholder, super::AttributeValue),
-    Le(super::Placeholder, super::AttributeValue),
-    Ge(super::Placeholder, super::AttributeValue),
-    Lt(super::Placeholder, super::AttributeValue),
-    Gt(super::Placeholder, super::AttributeValue),
-    Between(
-        super::Placeholder,
-        super::AttributeValue,
-        super::Placeholder,
-        super::AttributeValue,
-    ),
-    BeginsWith(super::Placeholder, super::AttributeValue),
+    Eq(super::AttributeValue),
+    Le(super::AttributeValue),
+    Ge(super::AttributeValue),
+    Lt(super::AttributeValue),
+    Gt(super::AttributeValue),
+    Between(super::AttributeValue, super::AttributeValue),
+    BeginsWith(super::AttributeValue),
+}
+
+/// A key condition whose values are not bound to placeholders yet.
+#[derive(Debug, Clone)]
+pub struct KeyConditionNode {
+    attr: String,
+    cond: KeyConditionTypes,
+    operators: Vec<KeyConditionOperator>,
+}
+
+impl KeyConditionNode {
+    fn build(
+        self,
+        placeholders: &mut super::Placeholders,
+    ) -> (String, super::AttributeNames, super::AttributeValues) {
+        let attr_name = self.attr;
+        let mut attr_names: super::AttributeNames = std::collections::HashMap::new();
+        let mut attr_values: super::AttributeValues = std::collections::HashMap::new();
+        let name_placeholder = super::name_placeholder(&attr_name);
+        attr_names.insert(name_placeholder.clone(), attr_name.clone());
+
+        let left_str = match self.cond {
+            KeyConditionTypes::Eq(value) => {
+                let placeholder = placeholders.bind(value, &mut attr_values);
+                format!("{name_placeholder} = {placeholder}")
+            }
+            KeyConditionTypes::Gt(value) => {
+                let placeholder = placeholders.bind(value, &mut attr_values);
+                format!("{name_placeholder} > {placeholder}")
+            }
+            KeyConditionTypes::Ge(value) => {
+                let placeholder = placeholders.bind(value, &mut attr_values);
+                format!("{name_placeholder} >= {placeholder}")
+            }
+            KeyConditionTypes::Le(value) => {
+                let placeholder = placeholders.bind(value, &mut attr_values);
+                format!("{name_placeholder} <= {placeholder}")
+            }
+            KeyConditionTypes::Lt(value) => {
+                let placeholder = placeholders.bind(value, &mut attr_values);
+                format!("{name_placeholder} < {placeholder}")
+            }
+            KeyConditionTypes::Between(value1, value2) => {
+                let placeholder1 = placeholders.bind(value1, &mut attr_values);
+                let placeholder2 = placeholders.bind(value2, &mut attr_values);
+                format!("{name_placeholder} BETWEEN {placeholder1} AND {placeholder2}")
+            }
+            KeyConditionTypes::BeginsWith(value) => {
+                let placeholder = placeholders.bind(value, &mut attr_values);
+                format!("begins_with({name_placeholder}, {placeholder})")
+            }
+        };
+
+        let mut condition_strings = vec![left_str];
+        for operator in self.operators {
+            match operator {
+                KeyConditionOperator::And(node) => {
+                    let (s, m, v) = node.build(placeholders);
+                    condition_strings.push(format!("AND ({s})"));
+                    attr_names = super::merge_map(attr_names, m);
+                    attr_values = super::merge_map(attr_values, v);
+                }
+            }
+        }
+
+        (condition_strings.join(" "), attr_names, attr_values)
+    }
 }
 
-pub trait KeyConditionBuilder<T, U> {
+pub trait KeyConditionBuilder<T, U>: Sized {
+    fn into_node(self) -> KeyConditionNode;
+
+    /// Builds the condition, allocating its placeholders from the request's `placeholders`.
+    fn build_with(
+        self,
+        placeholders: &mut super::Placeholders,
+    ) -> (
+        KeyConditionString,
+        super::AttributeNames,
+        super::AttributeValues,
+    ) {
+        self.into_node().build(placeholders)
+    }
+
     fn build(
         self,
     ) -> (
         KeyConditionString,
         super::AttributeNames,
         super::AttributeValues,
-    );
+    ) {
+        self.build_with(&mut super::Placeholders::new())
+    }
 }
 
 pub trait SupportsEqCondition {}
@@ -48,32 +122,25 @@ pub struct KeyCondition<T, U = T> {
 
 #[derive(Debug, Clone)]
 pub struct KeyConditionFilledOrWaitOperator<T, U> {
-    attr: String,
-    cond: KeyConditionTypes,
+    node: KeyConditionNode,
     _token: std::marker::PhantomData<fn() -> T>,
     _next_token: std::marker::PhantomData<fn() -> U>,
 }
 
 #[derive(Debug, Clone)]
 pub struct KeyConditionFilled<T, U> {
-    attr: String,
-    cond: KeyConditionTypes,
-    operators: Vec<KeyConditionOperator>,
+    node: KeyConditionNode,
     _token: std::marker::PhantomData<fn() -> T>,
     _next_token: std::marker::PhantomData<fn() -> U>,
 }
 
 impl<T, U> KeyConditionFilledOrWaitOperator<T, U> {
-    pub fn and<V>(self, cond: impl KeyConditionBuilder<U, V>) -> KeyConditionFilled<T, V> {
-        let (condition_string, attr_names, attr_values) = cond.build();
+    pub fn and<V>(mut self, cond: impl KeyConditionBuilder<U, V>) -> KeyConditionFilled<T, V> {
+        self.node
+            .operators
+            .push(KeyConditionOperator::And(cond.into_node()));
         KeyConditionFilled {
-            attr: self.attr,
-            cond: self.cond,
-            operators: vec![KeyConditionOperator::And(
-                condition_string,
-                attr_names,
-                attr_values,
-            )],
+            node: self.node,
             _token: self._token,
             _next_token: std::marker::PhantomData,
         }
@@ -82,16 +149,11 @@ impl<T, U> KeyConditionFilledOrWaitOperator<T, U> {
 
 impl<T, U> KeyConditionFilled<T, U> {
     pub fn and<V>(mut self, cond: impl KeyConditionBuilder<U, V>) -> KeyConditionFilled<T, V> {
-        let (condition_string, attr_names, attr_values) = cond.build();
-        self.operators.push(KeyConditionOperator::And(
-            condition_string,
-            attr_names,
-            attr_values,
-        ));
+        self.node
+            .operators
+            .push(KeyConditionOperator::And(cond.into_node()));
         KeyConditionFilled {
-            attr: self.attr,
-            cond: self.cond,
-            operators: self.operators,
+            node: self.node,
             _token: self._token,
             _next_token: std::marker::PhantomData,
         }
@@ -99,138 +161,28 @@ impl<T, U> KeyConditionFilled<T, U> {
 }
 
 impl<T, U> KeyConditionBuilder<T, U> for KeyConditionFilledOrWaitOperator<T, U> {
-    fn build(self) -> (String, super::AttributeNames, super::AttributeValues) {
-        let attr_name = self.attr;
-        let mut attr_names: super::AttributeNames = std::collections::HashMap::new();
-        let mut attr_values: super::AttributeValues = std::collections::HashMap::new();
-
-        attr_names.insert(format!("#{attr_name}"), attr_name.clone());
-        match self.cond {
-            super::key_condition::KeyConditionTypes::Eq(placeholder, value) => {
-                attr_values.insert(placeholder.to_string(), value);
-                (
-                    format!("#{attr_name} = {placeholder}"),
-                    attr_names,
-                    attr_values,
-                )
-            }
-            super::key_condition::KeyConditionTypes::Gt(placeholder, value) => {
-                attr_values.insert(placeholder.to_string(), value);
-                (
-                    format!("#{attr_name} > {placeholder}"),
-                    attr_names,
-                    attr_values,
-                )
-            }
-            super::key_condition::KeyConditionTypes::Ge(placeholder, value) => {
-                attr_values.insert(placeholder.to_string(), value);
-                (
-                    format!("#{attr_name} >= {placeholder}"),
-                    attr_names,
-                    attr_values,
-                )
-            }
-            super::key_condition::KeyConditionTypes::Le(placeholder, value) => {
-                attr_values.insert(placeholder.to_string(), value);
-                (
-                    format!("#{attr_name} <= {placeholder}"),
-                    attr_names,
-                    attr_values,
-                )
-            }
-            super::key_condition::KeyConditionTypes::Lt(placeholder, value) => {
-                attr_values.insert(placeholder.to_string(), value);
-                (
-                    format!("#{attr_name} < {placeholder}"),
-                    attr_names,
-                    attr_values,
-                )
-            }
-            super::key_condition::KeyConditionTypes::Between(
-                placeholder1,
-                value1,
-                placeholder2,
-                value2,
-            ) => {
-                attr_values.insert(placeholder1.to_string(), value1);
-                attr_values.insert(placeholder2.to_string(), value2);
-                (
-                    format!("#{attr_name} BETWEEN {placeholder1} AND {placeholder2}"),
-                    attr_names,
-                    attr_values,
-                )
-            }
-            super::key_condition::KeyConditionTypes::BeginsWith(placeholder, value) => {
-                attr_values.insert(placeholder.to_string(), value);
-                (
-                    format!("begins_with(#{attr_name}, {placeholder})"),
-                    attr_names,
-                    attr_values,
-                )
-            }
-        }
+    fn into_node(self) -> KeyConditionNode {
+        self.node
     }
 }
 
 impl<T, U> KeyConditionBuilder<T, U> for KeyConditionFilled<T, U> {
-    fn build(self) -> (String, super::AttributeNames, super::AttributeValues) {
-        let attr_name = self.attr;
-        let mut left_names: super::AttributeNames = std::collections::HashMap::new();
-        let mut left_values: super::AttributeValues = std::collections::HashMap::new();
-        left_names.insert(format!("#{attr_name}"), attr_name.clone());
-
-        let left_str = match self.cond {
-            super::key_condition::KeyConditionTypes::Eq(placeholder, value) => {
-                left_values.insert(placeholder.clone(), value);
-                format!("#{attr_name} = {placeholder}")
-            }
-            super::key_condition::KeyConditionTypes::Gt(placeholder, value) => {
-                left_values.insert(placeholder.clone(), value);
-                format!("#{attr_name} > {placeholder}")
-            }
-            super::key_condition::KeyConditionTypes::Ge(placeholder, value) => {
-                left_values.insert(placeholder.clone(), value);
-                format!("#{attr_name} >= {placeholder}")
-            }
-            super::key_condition::KeyConditionTypes::Le(placeholder, value) => {
-                left_values.insert(placeholder.clone(), value);
-                format!("#{attr_name} <= {placeholder}")
-            }
-            super::key_condition::KeyConditionTypes::Lt(placeholder, value) => {
-                left_values.insert(placeholder.clone(), value);
-                format!("#{attr_name} < {placeholder}")
-            }
-            super::key_condition::KeyConditionTypes::Between(
-                placeholder1,
-                value1,
-                placeholder2,
-                value2,
-            ) => {
-                left_values.insert(placeholder1.clone(), value1);
-                left_values.insert(placeholder2.clone(), value2);
-                format!("#{attr_name} BETWEEN {placeholder1} AND {placeholder2}")
-            }
-            super::key_condition::KeyConditionTypes::BeginsWith(placeholder, value) => {
-                left_values.insert(placeholder.clone(), value);
-                format!("begins_with(#{attr_name}, {placeholder})")
-            }
-        };
-
-        let mut condition_strings = vec![left_str];
-        let mut merged_names = left_names;
-        let mut merged_values = left_values;
+    fn into_node(self) -> KeyConditionNode {
+        self.node
+    }
+}
 
-        for operator in self.operators {
-            match operator {
-                super::key_condition::KeyConditionOperator::And(s, m, v) => {
-                    condition_strings.push(format!("AND ({s})"));
-                    merged_names = super::merge_map(merged_names, m);
-                    merged_values = super::merge_map(merged_values, v);
-                }
-            }
+impl<T, U> KeyCondition<T, U> {
+    fn filled(self, cond: KeyConditionTypes) -> KeyConditionFilledOrWaitOperator<T, U> {
+        KeyConditionFilledOrWaitOperator {
+            node: KeyConditionNode {
+                attr: self.attr,
+                cond,
+                operators: vec![],
+            },
+            _token: std::marker::PhantomData,
+            _next_token: std::marker::PhantomData,
         }
-
-        (condition_strings.join(" "), merged_names, merged_values)
     }
 }
 
@@ -239,14 +191,7 @@ where
     T: SupportsEqCondition,
 {
     pub fn eq(self, value: impl super::IntoAttribute) -> KeyConditionFilledOrWaitOperator<T, U> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let cond = super::key_condition::KeyConditionTypes::Eq(placeholder, value.into_attr());
-        KeyConditionFilledOrWaitOperator {
-            attr: self.attr,
-            cond,
-            _token: std::marker::PhantomData,
-            _next_token: std::marker::PhantomData,
-        }
+        self.filled(KeyConditionTypes::Eq(value.into_attr()))
     }
 }
 
@@ -255,46 +200,18 @@ where
     T: SupportsRangeCondition,
 {
     pub fn gt(self, value: impl super::IntoAttribute) -> KeyConditionFilledOrWaitOperator<T, U> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let cond = super::key_condition::KeyConditionTypes::Gt(placeholder, value.into_attr());
-        KeyConditionFilledOrWaitOperator {
-            attr: self.attr,
-            cond,
-            _token: std::marker::PhantomData,
-            _next_token: std::marker::PhantomData,
-        }
+        self.filled(KeyConditionTypes::Gt(value.into_attr()))
     }
     pub fn ge(self, value: impl super::IntoAttribute) -> KeyConditionFilledOrWaitOperator<T, U> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let cond = super::key_condition::KeyConditionTypes::Ge(placeholder, value.into_attr());
-        KeyConditionFilledOrWaitOperator {
-            attr: self.attr,
-            cond,
-            _token: std::marker::PhantomData,
-            _next_token: std::marker::PhantomData,
-        }
+        self.filled(KeyConditionTypes::Ge(value.into_attr()))
     }
 
     pub fn le(self, value: impl super::IntoAttribute) -> KeyConditionFilledOrWaitOperator<T, U> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let cond = super::key_condition::KeyConditionTypes::Le(placeholder, value.into_attr());
-        KeyConditionFilledOrWaitOperator {
-            attr: self.attr,
-            cond,
-            _token: std::marker::PhantomData,
-            _next_token: std::marker::PhantomData,
-        }
+        self.filled(KeyConditionTypes::Le(value.into_attr()))
     }
 
     pub fn lt(self, value: impl super::IntoAttribute) -> KeyConditionFilledOrWaitOperator<T, U> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let cond = super::key_condition::KeyConditionTypes::Lt(placeholder, value.into_attr());
-        KeyConditionFilledOrWaitOperator {
-            attr: self.attr,
-            cond,
-            _token: std::marker::PhantomData,
-            _next_token: std::marker::PhantomData,
-        }
+        self.filled(KeyConditionTypes::Lt(value.into_attr()))
     }
 
     pub fn between(
@@ -302,20 +219,10 @@ where
         value1: impl super::IntoAttribute,
         value2: impl super::IntoAttribute,
     ) -> KeyConditionFilledOrWaitOperator<T, U> {
-        let placeholder1 = format!(":value{}", super::generate_value_id());
-        let placeholder2 = format!(":value{}", super::generate_value_id());
-        let cond = super::key_condition::KeyConditionTypes::Between(
-            placeholder1,
+        self.filled(KeyConditionTypes::Between(
             value1.into_attr(),
-            placeholder2,
             value2.into_attr(),
-        );
-        KeyConditionFilledOrWaitOperator {
-            attr: self.attr,
-            cond,
-            _token: std::marker::PhantomData,
-            _next_token: std::marker::PhantomData,
-        }
+        ))
     }
 
     // We can use `begins_with` only with a range key after specifying an EQ condition for the primary key.
@@ -323,14 +230,35 @@ where
         self,
         value: impl super::IntoAttribute,
     ) -> KeyConditionFilledOrWaitOperator<T, U> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let cond =
-            super::key_condition::KeyConditionTypes::BeginsWith(placeholder, value.into_attr());
-        KeyConditionFilledOrWaitOperator {
-            attr: self.attr,
-            cond,
-            _token: std::marker::PhantomData,
-            _next_token: std::marker::PhantomData,
-        }
+        self.filled(KeyConditionTypes::BeginsWith(value.into_attr()))
+    }
+}
+
+impl<T, U> KeyCondition<T, U>
+where
+    T: SupportsEqCondition + super::KeyTemplate,
+{
+    /// Matches the key formatted from the values of every placeholder of its template.
+    pub fn eq_template(self, values: T::Values) -> KeyConditionFilledOrWaitOperator<T, U> {
+        let key = super::key_template::format(
+            T::TEMPLATE,
+            &super::KeyTemplateValues::into_values(values),
+        );
+        self.eq(key)
+    }
+}
+
+impl<T, U> KeyCondition<T, U>
+where
+    T: SupportsRangeCondition + super::KeyTemplate,
+{
+    /// Matches the keys beginning with the values of the leading placeholders of its template,
+    /// e.g. `("2024-01-02".to_owned(),)` matches `ORDER#2024-01-02#...` for `ORDER#{date}#{order_id}`.
+    pub fn begins_with_template(
+        self,
+        values: impl super::KeyTemplateValues<T>,
+    ) -> KeyConditionFilledOrWaitOperator<T, U> {
+        let prefix = super::key_template::format(T::TEMPLATE, &values.into_values());
+        self.begins_with(prefix)
     }
 }
@@ -0,0 +1,202 @@
+//! Helpers for composite keys declared with a template, e.g.
+//! `#[raiden(sort_key = "ORDER#{date}#{order_id}")]`.
+//!
+//! The key attribute is formatted from the fields named by the placeholders on put, and the
+//! fields are parsed back from it on decode when the item does not hold them.
+
+use std::str::FromStr;
+
+use crate::{AttributeValues, ConversionError, FromAttribute, RaidenError};
+
+/// Implemented by the key condition token of a key declared with a template.
+pub trait KeyTemplate {
+    const TEMPLATE: &'static str;
+    /// The values of every placeholder, typed after the fields they name.
+    type Values: KeyTemplateValues<Self>;
+}
+
+/// The values of the leading placeholders of the template of `T`, typed after the fields they
+/// name, e.g. `(String,)` for `ORDER#{date}#{order_id}`. Implemented by the derive.
+pub trait KeyTemplateValues<T: ?Sized> {
+    fn into_values(self) -> Vec<String>;
+}
+
+enum Segment<'a> {
+    Literal(&'a str),
+    Placeholder,
+}
+
+// The template is validated by the derive, so an unclosed brace is taken literally.
+fn segments(template: &str) -> Vec<Segment<'_>> {
+    let mut segments = vec![];
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        if start > 0 {
+            segments.push(Segment::Literal(&rest[..start]));
+        }
+        segments.push(Segment::Placeholder);
+        rest = &rest[start + end + 1..];
+    }
+    if !rest.is_empty() {
+        segments.push(Segment::Literal(rest));
+    }
+    segments
+}
+
+/// Formats the key from the values of the placeholders, in order.
+///
+/// Given fewer values, the key ends with the literal before the first placeholder without a
+/// value, e.g. `ORDER#2024-01-02#` for `ORDER#{date}#{order_id}`.
+pub fn format(template: &str, values: &[String]) -> String {
+    let mut key = String::new();
+    let mut values = values.iter();
+    for segment in segments(template) {
+        match segment {
+            Segment::Literal(literal) => key.push_str(literal),
+            Segment::Placeholder => match values.next() {
+                Some(value) => key.push_str(value),
+                None => break,
+            },
+        }
+    }
+    key
+}
+
+/// Formats the key attribute `attr_name`, or returns an error if a value would not be parsed
+/// back from it, i.e. if the literal following its placeholder would be found within the value.
+#[allow(clippy::result_large_err)]
+pub fn format_attr(
+    template: &str,
+    attr_name: &str,
+    values: &[String],
+) -> Result<String, RaidenError> {
+    let segments = segments(template);
+    let mut rest = values.iter();
+    for (i, segment) in segments.iter().enumerate() {
+        if !matches!(segment, Segment::Placeholder) {
+            continue;
+        }
+        let Some(value) = rest.next() else {
+            break;
+        };
+        let Some(Segment::Literal(literal)) = segments.get(i + 1) else {
+            continue;
+        };
+        if format!("{value}{literal}").find(literal) != Some(value.len()) {
+            return Err(RaidenError::AttributeConvertError {
+                attr_name: attr_name.to_string(),
+                source: ConversionError::message(format!(
+                    "`{value}` would be split at `{literal}` when parsed back from the key template `{template}`"
+                )),
+            });
+        }
+    }
+    Ok(format(template, values))
+}
+
+/// Splits `key` into the values of the placeholders, or returns `None` if it does not match.
+///
+/// A value ends at the first occurrence of the literal following its placeholder.
+pub fn parse(template: &str, key: &str) -> Option<Vec<String>> {
+    let segments = segments(template);
+    let mut values = vec![];
+    let mut rest = key;
+    for (i, segment) in segments.iter().enumerate() {
+        match segment {
+            Segment::Literal(literal) => rest = rest.strip_prefix(literal)?,
+            Segment::Placeholder => {
+                let end = match segments.get(i + 1) {
+                    Some(Segment::Literal(literal)) => rest.find(literal)?,
+                    _ => rest.len(),
+                };
+                values.push(rest[..end].to_string());
+                rest = &rest[end..];
+            }
+        }
+    }
+    rest.is_empty().then_some(values)
+}
+
+/// Parses the key attribute `attr_name` of `item`, or returns `None` if the item does not hold it.
+#[allow(clippy::result_large_err)]
+pub fn parse_attr(
+    template: &str,
+    attr_name: &str,
+    item: &AttributeValues,
+) -> Result<Option<Vec<String>>, RaidenError> {
+    let Some(value) = item.get(attr_name) else {
+        return Ok(None);
+    };
+    let key: String = FromAttribute::from_attr(Some(value.clone())).map_err(|source| {
+        RaidenError::AttributeConvertError {
+            attr_name: attr_name.to_string(),
+            source,
+        }
+    })?;
+    match parse(template, &key) {
+        Some(values) => Ok(Some(values)),
+        None => Err(RaidenError::AttributeConvertError {
+            attr_name: attr_name.to_string(),
+            source: ConversionError::message(format!(
+                "`{key}` does not match the key template `{template}`"
+            )),
+        }),
+    }
+}
+
+/// Parses a placeholder value taken from the key attribute `attr_name`.
+#[allow(clippy::result_large_err)]
+pub fn parse_value<T: FromStr>(attr_name: &str, value: &str) -> Result<T, RaidenError> {
+    value
+        .parse()
+        .map_err(|_| RaidenError::AttributeConvertError {
+            attr_name: attr_name.to_string(),
+            source: ConversionError::message(format!(
+                "can not parse `{value}` as `{}`",
+                std::any::type_name::<T>()
+            )),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATE: &str = "ORDER#{date}#{order_id}";
+
+    #[test]
+    fn test_format_and_parse_round_trip() {
+        let values = vec!["2024-01-02".to_string(), "42".to_string()];
+        let key = format(TEMPLATE, &values);
+        assert_eq!(key, "ORDER#2024-01-02#42");
+        assert_eq!(parse(TEMPLATE, &key), Some(values));
+        assert_eq!(parse("{id}", "a#b"), Some(vec!["a#b".to_string()]));
+        assert_eq!(parse(TEMPLATE, "USER#42"), None);
+        assert_eq!(parse("USER#{id}#PROFILE", "USER#42#ORDER"), None);
+    }
+
+    #[test]
+    fn test_format_stops_before_the_first_missing_value() {
+        assert_eq!(format(TEMPLATE, &[]), "ORDER#");
+        assert_eq!(
+            format(TEMPLATE, &["2024-01-02".to_string()]),
+            "ORDER#2024-01-02#"
+        );
+    }
+
+    #[test]
+    fn test_format_attr_rejects_values_split_when_parsed() {
+        let values = vec!["2024-01-02".to_string(), "4#2".to_string()];
+        assert_eq!(
+            format_attr(TEMPLATE, "SK", &values).unwrap(),
+            "ORDER#2024-01-02#4#2"
+        );
+        let err =
+            format_attr(TEMPLATE, "SK", &["2024#01".to_string(), "42".to_string()]).unwrap_err();
+        assert_eq!(err.attribute_path(), Some("SK".to_owned()));
+        assert!(format_attr("A#{a}#B#{b}", "SK", &["1#B".to_string(), "2".to_string()]).is_err());
+    }
+}
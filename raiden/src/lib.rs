@@ -12,19 +12,31 @@ compile_error!(
 #[macro_use]
 extern crate serde_derive;
 
+pub mod backend;
+pub mod capacity;
+pub mod clock;
 pub mod condition;
 mod document;
 pub mod errors;
 pub mod filter_expression;
 pub mod id_generator;
 pub mod key_condition;
+pub mod key_template;
+#[cfg(feature = "memory")]
+pub mod memory;
 pub mod next_token;
+pub mod observer;
 pub mod ops;
+pub mod pagination;
 mod path;
+pub mod placeholder;
 pub mod retry;
+pub mod serde_attr;
+pub mod ttl;
 pub mod types;
 pub mod update_expression;
 pub mod value_id;
+pub mod version;
 
 #[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
 mod rusoto;
@@ -38,21 +50,40 @@ pub mod aws_sdk;
 #[cfg(feature = "aws-sdk")]
 pub use self::aws_sdk::{types::AttributeValue, *};
 
+// The types the requests are built from are only exported from `backend`, since the SDKs
+// export types of the same names.
+pub use backend::{
+    BackendFuture, BatchGetItemRequest, BatchGetItemResponse, BatchWriteItemRequest,
+    BatchWriteItemResponse, CreateTableRequest, CreateTableResponse, DeleteItemRequest,
+    DeleteItemResponse, DeleteTableRequest, DeleteTableResponse, DescribeTableRequest,
+    DescribeTableResponse, DynamoBackend, GetItemRequest, GetItemResponse, PutItemRequest,
+    PutItemResponse, QueryRequest, QueryResponse, ScanRequest, ScanResponse, SharedBackend,
+    TransactGetItemsRequest, TransactGetItemsResponse, TransactWriteItemsRequest,
+    TransactWriteItemsResponse, UpdateItemRequest, UpdateItemResponse, UpdateTimeToLiveRequest,
+    UpdateTimeToLiveResponse,
+};
+pub use capacity::{ReturnConsumedCapacity, ReturnItemCollectionMetrics};
+pub use clock::{Clock, Timestamp};
 pub use condition::*;
 pub use document::*;
 pub use errors::*;
 pub use filter_expression::*;
 pub use key_condition::*;
+pub use key_template::{KeyTemplate, KeyTemplateValues};
 pub use next_token::*;
+pub use observer::{Attempt, ErrorClass, ObservedBackend, ObservedRequest, Observer};
 pub use ops::*;
 pub use path::*;
 pub use retry::*;
+pub use serde_attr::{from_attribute_value, to_attribute_value, BinarySet, NumberSet, StringSet};
 
 pub use id_generator::*;
+pub use placeholder::*;
 pub use raiden_derive::*;
 pub use types::*;
 pub use value_id::*;
 
+pub use futures;
 pub use safe_builder::Builder;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -92,9 +123,24 @@ pub trait IntoAttrValues: Sized {
 }
 
 pub trait IntoStringSetItem: Sized {
+    /// Whether the items are numbers, whose sets are stored as `NS` instead of `SS`.
+    const IS_NUMBER: bool = false;
+
     fn into_ss_item(self) -> String;
 }
 
+/// A type which can be stored as a key attribute of a table or an index.
+///
+/// Implementations for newtypes and unit enums are generated by `#[derive(RaidenAttribute)]`.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can not be stored as a key attribute",
+    note = "derive `RaidenAttribute` for it, or implement `raiden::KeyAttribute`"
+)]
+pub trait KeyAttribute {
+    /// The scalar type the key is stored as, `S` or `N`.
+    const KEY_TYPE: AttributeType;
+}
+
 pub trait ToAttrMaps: Sized {
     fn to_attr_maps(&self) -> (AttributeNames, AttributeValues);
 }
@@ -120,6 +166,17 @@ pub enum ConversionError {
     ValueIsNone,
     ParseInt,
     Serde(String),
+    /// The attribute holds another DynamoDB type than the one the value is decoded from.
+    UnexpectedType {
+        expected: AttributeType,
+        actual: Option<AttributeType>,
+    },
+    /// The conversion of a map entry or a list element failed.
+    Nested {
+        /// The map key, or the list index as `[index]`.
+        path: String,
+        source: Box<ConversionError>,
+    },
 }
 
 impl ConversionError {
@@ -132,6 +189,49 @@ impl ConversionError {
     pub fn message(message: impl Into<String>) -> Self {
         ConversionError::Serde(message.into())
     }
+
+    /// The error for `value` which is not of the `expected` type, [`ConversionError::ValueIsNone`] if it is missing.
+    pub fn unexpected_type(expected: AttributeType, value: Option<&AttributeValue>) -> Self {
+        match value {
+            Some(value) => ConversionError::UnexpectedType {
+                expected,
+                actual: AttributeType::of(value),
+            },
+            None => ConversionError::ValueIsNone,
+        }
+    }
+
+    /// Wraps the error of the map entry `key`, or of the list element `[index]`.
+    pub fn nested(self, path: impl Into<String>) -> Self {
+        ConversionError::Nested {
+            path: path.into(),
+            source: Box::new(self),
+        }
+    }
+
+    /// The path of the nested value which failed to convert, e.g. `profile.tags[0]`.
+    pub fn path(&self) -> String {
+        match self {
+            ConversionError::Nested { path, source } => join_path(path, &source.path()),
+            _ => String::new(),
+        }
+    }
+
+    /// The innermost error.
+    pub fn cause(&self) -> &ConversionError {
+        match self {
+            ConversionError::Nested { source, .. } => source.cause(),
+            _ => self,
+        }
+    }
+}
+
+pub(crate) fn join_path(parent: &str, path: &str) -> String {
+    if parent.is_empty() || path.is_empty() || path.starts_with('[') {
+        format!("{parent}{path}")
+    } else {
+        format!("{parent}.{path}")
+    }
 }
 
 impl std::fmt::Display for ConversionError {
@@ -140,11 +240,27 @@ impl std::fmt::Display for ConversionError {
             ConversionError::ValueIsNone => write!(f, "Value is none"),
             ConversionError::ParseInt => write!(f, "Parsing error of integer"),
             ConversionError::Serde(message) => write!(f, "{message}"),
+            ConversionError::UnexpectedType {
+                expected,
+                actual: Some(actual),
+            } => write!(f, "expected {expected}, found {actual}"),
+            ConversionError::UnexpectedType {
+                expected,
+                actual: None,
+            } => write!(f, "expected {expected}, found an unknown type"),
+            ConversionError::Nested { .. } => write!(f, "{}: {}", self.path(), self.cause()),
         }
     }
 }
 
-impl std::error::Error for ConversionError {}
+impl std::error::Error for ConversionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConversionError::Nested { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
 
 pub trait FromAttribute: Sized {
     fn from_attr(value: Option<AttributeValue>) -> Result<Self, ConversionError>;
@@ -165,6 +281,9 @@ pub trait FromDocumentAttr: serde::de::DeserializeOwned + Sized {
 impl<T: FromAttribute> ResolveAttribute for T {}
 
 pub trait FromStringSetItem: Sized {
+    /// Whether the items are numbers, whose sets are stored as `NS` instead of `SS`.
+    const IS_NUMBER: bool = false;
+
     fn from_ss_item(value: String) -> Result<Self, ConversionError>;
 }
 
@@ -183,6 +302,26 @@ default_attr_for_num!(i8);
 default_attr_for_num!(f32);
 default_attr_for_num!(f64);
 
+impl KeyAttribute for String {
+    const KEY_TYPE: AttributeType = AttributeType::S;
+}
+
+impl KeyAttribute for &'_ str {
+    const KEY_TYPE: AttributeType = AttributeType::S;
+}
+
+macro_rules! key_attribute_for_num {
+    ($($to: ty),*) => {
+        $(
+            impl KeyAttribute for $to {
+                const KEY_TYPE: AttributeType = AttributeType::N;
+            }
+        )*
+    };
+}
+
+key_attribute_for_num!(usize, u64, u32, u16, u8, isize, i64, i32, i16, i8, f32, f64);
+
 impl IntoStringSetItem for String {
     fn into_ss_item(self) -> String {
         self
@@ -195,29 +334,29 @@ impl FromStringSetItem for String {
     }
 }
 
-default_number_hash_set_convertor!(usize);
-default_number_hash_set_convertor!(u64);
-default_number_hash_set_convertor!(u32);
-default_number_hash_set_convertor!(u16);
-default_number_hash_set_convertor!(u8);
-
-default_number_hash_set_convertor!(isize);
-default_number_hash_set_convertor!(i64);
-default_number_hash_set_convertor!(i32);
-default_number_hash_set_convertor!(i16);
-default_number_hash_set_convertor!(i8);
-
-default_number_btree_set_convertor!(usize);
-default_number_btree_set_convertor!(u64);
-default_number_btree_set_convertor!(u32);
-default_number_btree_set_convertor!(u16);
-default_number_btree_set_convertor!(u8);
-
-default_number_btree_set_convertor!(isize);
-default_number_btree_set_convertor!(i64);
-default_number_btree_set_convertor!(i32);
-default_number_btree_set_convertor!(i16);
-default_number_btree_set_convertor!(i8);
+macro_rules! number_set_item {
+    ($($to: ty),*) => {
+        $(
+            impl IntoStringSetItem for $to {
+                const IS_NUMBER: bool = true;
+
+                fn into_ss_item(self) -> String {
+                    self.to_string()
+                }
+            }
+
+            impl FromStringSetItem for $to {
+                const IS_NUMBER: bool = true;
+
+                fn from_ss_item(value: String) -> Result<Self, ConversionError> {
+                    value.parse().map_err(|_| ConversionError::ParseInt)
+                }
+            }
+        )*
+    };
+}
+
+number_set_item!(usize, u64, u32, u16, u8, isize, i64, i32, i16, i8);
 
 pub trait ResolveAttribute: Sized + FromAttribute {
     fn resolve_attr(
@@ -277,4 +416,30 @@ mod tests {
         assert_eq!("custom validation failed", error.to_string());
         assert!(matches!(error, ConversionError::Serde(_)));
     }
+
+    #[test]
+    fn conversion_error_reports_path_and_types() {
+        let value = vec!["a".to_owned(), "b".to_owned()].into_attr();
+        let error = Vec::<usize>::from_attr(Some(value)).unwrap_err();
+
+        assert_eq!("[0]", error.path());
+        assert!(matches!(
+            error.cause(),
+            ConversionError::UnexpectedType {
+                expected: AttributeType::N,
+                actual: Some(AttributeType::S),
+            }
+        ));
+        assert_eq!("[0]: expected N, found S", error.to_string());
+
+        let error = RaidenError::AttributeConvertError {
+            attr_name: "scores".to_owned(),
+            source: error,
+        };
+        assert_eq!(Some("scores[0]".to_owned()), error.attribute_path());
+        assert_eq!(
+            "attribute \"scores[0]\" convert error: expected N, found S",
+            error.to_string()
+        );
+    }
 }
@@ -0,0 +1,47 @@
+use aws_smithy_runtime_api::client::http::{
+    http_client_fn, HttpConnector, HttpConnectorFuture, SharedHttpConnector,
+};
+use aws_smithy_runtime_api::client::orchestrator::{HttpRequest, HttpResponse};
+use aws_smithy_runtime_api::http::StatusCode;
+use aws_smithy_types::body::SdkBody;
+
+use super::MemoryDb;
+use crate::aws_sdk::config::{BehaviorVersion, Credentials, Region};
+
+impl HttpConnector for MemoryDb {
+    fn call(&self, request: HttpRequest) -> HttpConnectorFuture {
+        let target = request.headers().get("x-amz-target").unwrap_or_default();
+        let (status, body) = self.handle(target, request.body().bytes().unwrap_or_default());
+
+        let mut response = HttpResponse::new(
+            StatusCode::try_from(status).expect("status should be valid"),
+            SdkBody::from(body),
+        );
+        response
+            .headers_mut()
+            .insert("content-type", "application/x-amz-json-1.0");
+        HttpConnectorFuture::ready(Ok(response))
+    }
+}
+
+impl MemoryDb {
+    /// Returns a client which sends every request to this database.
+    pub fn client(&self) -> crate::Client {
+        let connector = SharedHttpConnector::new(self.clone());
+        let config = crate::Config::builder()
+            .behavior_version(BehaviorVersion::latest())
+            .region(Region::new("memory"))
+            .credentials_provider(Credentials::new("memory", "memory", None, None, "raiden"))
+            .http_client(http_client_fn(move |_, _| connector.clone()))
+            .build();
+
+        crate::Client::from_conf(config)
+    }
+
+    /// Returns a [`DynamoBackend`](crate::DynamoBackend) which sends every request to this database.
+    ///
+    /// It is the same as `client`, and only makes the code using it independent of the SDK.
+    pub fn backend(&self) -> crate::Client {
+        self.client()
+    }
+}
@@ -0,0 +1,780 @@
+//! Parses and evaluates key condition, filter, condition, projection and update expressions.
+
+use std::cmp::Ordering;
+
+use serde_json::{Map, Value};
+
+use super::value::{self, Element, Item, Path};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Name(String),
+    Value(String),
+    Number(usize),
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Comma,
+    Dot,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Plus,
+    Minus,
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    let mut chars = expression.chars().peekable();
+    let word = |chars: &mut std::iter::Peekable<std::str::Chars>| {
+        let mut word = String::new();
+        while let Some(c) = chars.next_if(|c| c.is_ascii_alphanumeric() || *c == '_') {
+            word.push(c);
+        }
+        word
+    };
+
+    while let Some(&c) = chars.peek() {
+        let token = match c {
+            c if c.is_whitespace() => {
+                chars.next();
+                continue;
+            }
+            '#' => {
+                chars.next();
+                Token::Name(format!("#{}", word(&mut chars)))
+            }
+            ':' => {
+                chars.next();
+                Token::Value(format!(":{}", word(&mut chars)))
+            }
+            c if c.is_ascii_digit() => {
+                let digits = word(&mut chars);
+                Token::Number(
+                    digits
+                        .parse()
+                        .map_err(|_| format!("Invalid token: {digits}"))?,
+                )
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => Token::Ident(word(&mut chars)),
+            _ => {
+                chars.next();
+                match c {
+                    '(' => Token::LParen,
+                    ')' => Token::RParen,
+                    '[' => Token::LBracket,
+                    ']' => Token::RBracket,
+                    ',' => Token::Comma,
+                    '.' => Token::Dot,
+                    '=' => Token::Eq,
+                    '+' => Token::Plus,
+                    '-' => Token::Minus,
+                    '<' => match chars.next_if(|c| *c == '=' || *c == '>') {
+                        Some('=') => Token::Le,
+                        Some(_) => Token::Ne,
+                        None => Token::Lt,
+                    },
+                    '>' => match chars.next_if_eq(&'=') {
+                        Some(_) => Token::Ge,
+                        None => Token::Gt,
+                    },
+                    _ => return Err(format!("Invalid token: {c}")),
+                }
+            }
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum Comparator {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone)]
+pub(super) enum Operand {
+    Path(Path),
+    Value(Value),
+    Size(Path),
+    IfNotExists(Path, Box<Operand>),
+    ListAppend(Box<Operand>, Box<Operand>),
+    Plus(Box<Operand>, Box<Operand>),
+    Minus(Box<Operand>, Box<Operand>),
+}
+
+#[derive(Debug, Clone)]
+pub(super) enum Condition {
+    Compare(Operand, Comparator, Operand),
+    Between(Operand, Operand, Operand),
+    In(Operand, Vec<Operand>),
+    AttributeExists(Path),
+    AttributeNotExists(Path),
+    AttributeType(Path, Operand),
+    BeginsWith(Operand, Operand),
+    Contains(Operand, Operand),
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+    Not(Box<Condition>),
+}
+
+#[derive(Debug, Clone)]
+pub(super) enum Action {
+    Set(Path, Operand),
+    Remove(Path),
+    Add(Path, Operand),
+    Delete(Path, Operand),
+}
+
+impl Action {
+    fn path(&self) -> &Path {
+        match self {
+            Action::Set(path, _)
+            | Action::Remove(path)
+            | Action::Add(path, _)
+            | Action::Delete(path, _) => path,
+        }
+    }
+
+    /// The top-level attribute which this action updates.
+    pub(super) fn attr_name(&self) -> &str {
+        match self.path().first() {
+            Some(Element::Attr(name)) => name,
+            _ => unreachable!("paths always start with an attribute name"),
+        }
+    }
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    pos: usize,
+    names: Option<&'a Map<String, Value>>,
+    values: Option<&'a Map<String, Value>>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(
+        expression: &str,
+        names: Option<&'a Map<String, Value>>,
+        values: Option<&'a Map<String, Value>>,
+    ) -> Result<Self, String> {
+        Ok(Self {
+            tokens: tokenize(expression)?,
+            pos: 0,
+            names,
+            values,
+        })
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &Token) -> Result<(), String> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(format!(
+                "Invalid expression: expected {token:?}, found {:?}",
+                self.peek()
+            ))
+        }
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Ident(ident)) if ident.eq_ignore_ascii_case(keyword))
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found = self.is_keyword(keyword);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn finish<T>(&self, parsed: T) -> Result<T, String> {
+        match self.peek() {
+            None => Ok(parsed),
+            Some(token) => Err(format!("Invalid expression: unexpected token {token:?}")),
+        }
+    }
+
+    fn name(&self, token: Token) -> Result<String, String> {
+        match token {
+            Token::Ident(name) => Ok(name),
+            Token::Name(placeholder) => self
+                .names
+                .and_then(|names| names.get(&placeholder))
+                .and_then(Value::as_str)
+                .map(str::to_owned)
+                .ok_or_else(|| {
+                    format!("An expression attribute name used in the document path is not defined; attribute name: {placeholder}")
+                }),
+            token => Err(format!("Invalid expression: expected an attribute name, found {token:?}")),
+        }
+    }
+
+    fn path(&mut self) -> Result<Path, String> {
+        let first = self.next().ok_or("Invalid expression: unexpected end")?;
+        let mut path = vec![Element::Attr(self.name(first)?)];
+        loop {
+            if self.eat(&Token::Dot) {
+                let name = self.next().ok_or("Invalid expression: unexpected end")?;
+                path.push(Element::Attr(self.name(name)?));
+            } else if self.eat(&Token::LBracket) {
+                match self.next() {
+                    Some(Token::Number(i)) => path.push(Element::Index(i)),
+                    token => return Err(format!("Invalid list index: {token:?}")),
+                }
+                self.expect(&Token::RBracket)?;
+            } else {
+                return Ok(path);
+            }
+        }
+    }
+
+    fn value(&self, placeholder: &str) -> Result<Value, String> {
+        self.values
+            .and_then(|values| values.get(placeholder))
+            .cloned()
+            .ok_or_else(|| {
+                format!("An expression attribute value used in expression is not defined; attribute value: {placeholder}")
+            })
+    }
+
+    fn function(&mut self) -> Option<String> {
+        match (self.peek(), self.tokens.get(self.pos + 1)) {
+            (Some(Token::Ident(name)), Some(Token::LParen)) => {
+                let name = name.clone();
+                self.pos += 2;
+                Some(name)
+            }
+            _ => None,
+        }
+    }
+
+    fn operand(&mut self) -> Result<Operand, String> {
+        if let Some(Token::Value(placeholder)) = self.peek() {
+            let value = self.value(&placeholder.clone())?;
+            self.pos += 1;
+            return Ok(Operand::Value(value));
+        }
+
+        match self.function() {
+            Some(name) if name == "size" => {
+                let path = self.path()?;
+                self.expect(&Token::RParen)?;
+                Ok(Operand::Size(path))
+            }
+            Some(name) => Err(format!("Invalid function name; function: {name}")),
+            None => Ok(Operand::Path(self.path()?)),
+        }
+    }
+
+    fn condition(&mut self) -> Result<Condition, String> {
+        let mut condition = self.and()?;
+        while self.eat_keyword("OR") {
+            condition = Condition::Or(Box::new(condition), Box::new(self.and()?));
+        }
+        Ok(condition)
+    }
+
+    fn and(&mut self) -> Result<Condition, String> {
+        let mut condition = self.not()?;
+        while self.eat_keyword("AND") {
+            condition = Condition::And(Box::new(condition), Box::new(self.not()?));
+        }
+        Ok(condition)
+    }
+
+    fn not(&mut self) -> Result<Condition, String> {
+        if self.eat_keyword("NOT") {
+            return Ok(Condition::Not(Box::new(self.not()?)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Condition, String> {
+        if self.eat(&Token::LParen) {
+            let condition = self.condition()?;
+            self.expect(&Token::RParen)?;
+            return Ok(condition);
+        }
+
+        let start = self.pos;
+        if let Some(name) = self.function() {
+            let condition = match name.as_str() {
+                "attribute_exists" => Condition::AttributeExists(self.path()?),
+                "attribute_not_exists" => Condition::AttributeNotExists(self.path()?),
+                "attribute_type" => {
+                    let path = self.path()?;
+                    self.expect(&Token::Comma)?;
+                    Condition::AttributeType(path, self.operand()?)
+                }
+                "begins_with" | "contains" => {
+                    let target = self.operand()?;
+                    self.expect(&Token::Comma)?;
+                    let operand = self.operand()?;
+                    if name == "contains" {
+                        Condition::Contains(target, operand)
+                    } else {
+                        Condition::BeginsWith(target, operand)
+                    }
+                }
+                _ => {
+                    // e.g. `size(#a) > :v` which starts with an operand function.
+                    self.pos = start;
+                    return self.comparison();
+                }
+            };
+            self.expect(&Token::RParen)?;
+            return Ok(condition);
+        }
+
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<Condition, String> {
+        let left = self.operand()?;
+        if self.eat_keyword("BETWEEN") {
+            let lower = self.operand()?;
+            if !self.eat_keyword("AND") {
+                return Err("Invalid expression: BETWEEN requires AND".to_owned());
+            }
+            return Ok(Condition::Between(left, lower, self.operand()?));
+        }
+        if self.eat_keyword("IN") {
+            self.expect(&Token::LParen)?;
+            let mut candidates = vec![self.operand()?];
+            while self.eat(&Token::Comma) {
+                candidates.push(self.operand()?);
+            }
+            self.expect(&Token::RParen)?;
+            return Ok(Condition::In(left, candidates));
+        }
+
+        let comparator = match self.next() {
+            Some(Token::Eq) => Comparator::Eq,
+            Some(Token::Ne) => Comparator::Ne,
+            Some(Token::Lt) => Comparator::Lt,
+            Some(Token::Le) => Comparator::Le,
+            Some(Token::Gt) => Comparator::Gt,
+            Some(Token::Ge) => Comparator::Ge,
+            token => return Err(format!("Invalid expression: unexpected token {token:?}")),
+        };
+        Ok(Condition::Compare(left, comparator, self.operand()?))
+    }
+
+    fn update_value(&mut self) -> Result<Operand, String> {
+        let left = self.update_operand()?;
+        if self.eat(&Token::Plus) {
+            return Ok(Operand::Plus(
+                Box::new(left),
+                Box::new(self.update_operand()?),
+            ));
+        }
+        if self.eat(&Token::Minus) {
+            return Ok(Operand::Minus(
+                Box::new(left),
+                Box::new(self.update_operand()?),
+            ));
+        }
+        Ok(left)
+    }
+
+    fn update_operand(&mut self) -> Result<Operand, String> {
+        let start = self.pos;
+        match self.function().as_deref() {
+            Some("if_not_exists") => {
+                let path = self.path()?;
+                self.expect(&Token::Comma)?;
+                let operand = self.update_operand()?;
+                self.expect(&Token::RParen)?;
+                Ok(Operand::IfNotExists(path, Box::new(operand)))
+            }
+            Some("list_append") => {
+                let first = self.update_operand()?;
+                self.expect(&Token::Comma)?;
+                let second = self.update_operand()?;
+                self.expect(&Token::RParen)?;
+                Ok(Operand::ListAppend(Box::new(first), Box::new(second)))
+            }
+            Some(name) => Err(format!("Invalid function name; function: {name}")),
+            None => {
+                self.pos = start;
+                self.operand()
+            }
+        }
+    }
+
+    fn actions(&mut self) -> Result<Vec<Action>, String> {
+        let mut actions = vec![];
+        while self.peek().is_some() {
+            let clause = match self.next() {
+                Some(Token::Ident(clause)) => clause.to_ascii_uppercase(),
+                token => return Err(format!("Invalid UpdateExpression: unexpected {token:?}")),
+            };
+            loop {
+                let path = self.path()?;
+                let action = match clause.as_str() {
+                    "SET" => {
+                        self.expect(&Token::Eq)?;
+                        Action::Set(path, self.update_value()?)
+                    }
+                    "REMOVE" => Action::Remove(path),
+                    "ADD" => Action::Add(path, self.operand()?),
+                    "DELETE" => Action::Delete(path, self.operand()?),
+                    _ => return Err(format!("Invalid UpdateExpression: unknown clause {clause}")),
+                };
+                actions.push(action);
+                if !self.eat(&Token::Comma) {
+                    break;
+                }
+            }
+        }
+        Ok(actions)
+    }
+}
+
+pub(super) fn parse_condition(
+    expression: &str,
+    names: Option<&Map<String, Value>>,
+    values: Option<&Map<String, Value>>,
+) -> Result<Condition, String> {
+    let mut parser = Parser::new(expression, names, values)?;
+    let condition = parser.condition()?;
+    parser.finish(condition)
+}
+
+pub(super) fn parse_update(
+    expression: &str,
+    names: Option<&Map<String, Value>>,
+    values: Option<&Map<String, Value>>,
+) -> Result<Vec<Action>, String> {
+    let mut parser = Parser::new(expression, names, values)?;
+    let actions = parser.actions()?;
+    parser.finish(actions)
+}
+
+pub(super) fn parse_projection(
+    expression: &str,
+    names: Option<&Map<String, Value>>,
+) -> Result<Vec<Path>, String> {
+    let mut parser = Parser::new(expression, names, None)?;
+    let mut paths = vec![parser.path()?];
+    while parser.eat(&Token::Comma) {
+        paths.push(parser.path()?);
+    }
+    parser.finish(paths)
+}
+
+impl Operand {
+    pub(super) fn eval(&self, item: &Item) -> Result<Option<Value>, String> {
+        let missing =
+            || "The provided expression refers to an attribute that does not exist in the item";
+        let invalid_type = || "An operand in the update expression has an incorrect data type";
+
+        Ok(match self {
+            Operand::Path(path) => value::get(item, path).cloned(),
+            Operand::Value(value) => Some(value.clone()),
+            Operand::Size(path) => value::get(item, path).and_then(|v| {
+                let size = match value::type_of(v)? {
+                    "S" => value::string(v)?.chars().count(),
+                    "B" => value::binary(v)?.len(),
+                    "L" => value::list(v)?.len(),
+                    "M" => v.get("M")?.as_object()?.len(),
+                    "SS" | "NS" | "BS" => value::set_members(v)?.len(),
+                    _ => return None,
+                };
+                Some(value::number_value(size))
+            }),
+            Operand::IfNotExists(path, operand) => match value::get(item, path) {
+                Some(value) => Some(value.clone()),
+                None => operand.eval(item)?,
+            },
+            Operand::ListAppend(first, second) => {
+                let (first, second) = (
+                    first.eval(item)?.ok_or_else(missing)?,
+                    second.eval(item)?.ok_or_else(missing)?,
+                );
+                let (Some(first), Some(second)) = (value::list(&first), value::list(&second))
+                else {
+                    return Err(invalid_type().to_owned());
+                };
+                let appended = [first.as_slice(), second.as_slice()].concat();
+                Some(serde_json::json!({ "L": appended }))
+            }
+            Operand::Plus(left, right) | Operand::Minus(left, right) => {
+                let (left, right) = (
+                    left.eval(item)?.ok_or_else(missing)?,
+                    right.eval(item)?.ok_or_else(missing)?,
+                );
+                let (Some(left), Some(right)) = (value::number(&left), value::number(&right))
+                else {
+                    return Err(invalid_type().to_owned());
+                };
+                let n = value::add_numbers(left, right, matches!(self, Operand::Minus(..)))
+                    .ok_or("Number overflow")?;
+                Some(serde_json::json!({ "N": n }))
+            }
+        })
+    }
+}
+
+impl Condition {
+    pub(super) fn eval(&self, item: &Item) -> bool {
+        let operand = |operand: &Operand| operand.eval(item).ok().flatten();
+        let compare = |a: &Operand, b: &Operand| value::compare(&operand(a)?, &operand(b)?);
+
+        match self {
+            Condition::Compare(a, Comparator::Eq, b) => match (operand(a), operand(b)) {
+                (Some(a), Some(b)) => value::equals(&a, &b),
+                _ => false,
+            },
+            Condition::Compare(a, Comparator::Ne, b) => match (operand(a), operand(b)) {
+                (Some(a), Some(b)) => !value::equals(&a, &b),
+                _ => true,
+            },
+            Condition::Compare(a, comparator, b) => {
+                compare(a, b).is_some_and(|ordering| match comparator {
+                    Comparator::Lt => ordering == Ordering::Less,
+                    Comparator::Le => ordering != Ordering::Greater,
+                    Comparator::Gt => ordering == Ordering::Greater,
+                    Comparator::Ge => ordering != Ordering::Less,
+                    Comparator::Eq | Comparator::Ne => unreachable!(),
+                })
+            }
+            Condition::Between(a, lower, upper) => {
+                compare(a, lower).is_some_and(|o| o != Ordering::Less)
+                    && compare(a, upper).is_some_and(|o| o != Ordering::Greater)
+            }
+            Condition::In(a, candidates) => operand(a).is_some_and(|a| {
+                candidates
+                    .iter()
+                    .filter_map(operand)
+                    .any(|candidate| value::equals(&a, &candidate))
+            }),
+            Condition::AttributeExists(path) => value::get(item, path).is_some(),
+            Condition::AttributeNotExists(path) => value::get(item, path).is_none(),
+            Condition::AttributeType(path, ty) => match (value::get(item, path), operand(ty)) {
+                (Some(v), Some(ty)) => value::string(&ty) == value::type_of(v),
+                _ => false,
+            },
+            Condition::BeginsWith(a, prefix) => match (operand(a), operand(prefix)) {
+                (Some(a), Some(prefix)) => match (value::string(&a), value::string(&prefix)) {
+                    (Some(a), Some(prefix)) => a.starts_with(prefix),
+                    _ => match (value::binary(&a), value::binary(&prefix)) {
+                        (Some(a), Some(prefix)) => a.starts_with(&prefix),
+                        _ => false,
+                    },
+                },
+                _ => false,
+            },
+            Condition::Contains(a, member) => match (operand(a), operand(member)) {
+                (Some(a), Some(member)) => {
+                    if let (Some(a), Some(member)) = (value::string(&a), value::string(&member)) {
+                        a.contains(member)
+                    } else if let Some(members) = value::set_members(&a) {
+                        members.iter().any(|v| value::equals(v, &member))
+                    } else if let Some(list) = value::list(&a) {
+                        list.iter().any(|v| value::equals(v, &member))
+                    } else {
+                        false
+                    }
+                }
+                _ => false,
+            },
+            Condition::And(a, b) => a.eval(item) && b.eval(item),
+            Condition::Or(a, b) => a.eval(item) || b.eval(item),
+            Condition::Not(a) => !a.eval(item),
+        }
+    }
+}
+
+/// Applies update actions to `item`.
+///
+/// Every operand is evaluated against the item before the update, as DynamoDB does.
+pub(super) fn apply(actions: &[Action], item: &mut Item) -> Result<(), String> {
+    let original = item.clone();
+    let invalid_type = || "An operand in the update expression has an incorrect data type";
+
+    for action in actions {
+        match action {
+            Action::Set(path, operand) => {
+                let value = operand.eval(&original)?.ok_or(
+                    "The provided expression refers to an attribute that does not exist in the item",
+                )?;
+                value::set(item, path, value)?;
+            }
+            Action::Remove(path) => value::remove(item, path),
+            Action::Add(path, operand) => {
+                let value = operand.eval(&original)?.ok_or(invalid_type())?;
+                let updated = match value::get(item, path) {
+                    None => value,
+                    Some(current) => match (value::number(current), value::number(&value)) {
+                        (Some(current), Some(n)) => value::number_value(
+                            value::add_numbers(current, n, false).ok_or("Number overflow")?,
+                        ),
+                        _ => {
+                            let ty = value::type_of(current).unwrap_or_default().to_owned();
+                            let (Some(mut members), Some(added)) =
+                                (value::set_members(current), value::set_members(&value))
+                            else {
+                                return Err(invalid_type().to_owned());
+                            };
+                            if value::type_of(&value) != Some(ty.as_str()) {
+                                return Err(invalid_type().to_owned());
+                            }
+                            for v in added {
+                                if !members.iter().any(|m| value::equals(m, &v)) {
+                                    members.push(v);
+                                }
+                            }
+                            value::set_value(&ty, members)
+                        }
+                    },
+                };
+                value::set(item, path, updated)?;
+            }
+            Action::Delete(path, operand) => {
+                let value = operand.eval(&original)?.ok_or(invalid_type())?;
+                let Some(current) = value::get(item, path) else {
+                    continue;
+                };
+                let ty = value::type_of(current).unwrap_or_default().to_owned();
+                let (Some(members), Some(deleted)) =
+                    (value::set_members(current), value::set_members(&value))
+                else {
+                    return Err(invalid_type().to_owned());
+                };
+                let members: Vec<Value> = members
+                    .into_iter()
+                    .filter(|m| !deleted.iter().any(|v| value::equals(m, v)))
+                    .collect();
+                if members.is_empty() {
+                    value::remove(item, path);
+                } else {
+                    value::set(item, path, value::set_value(&ty, members))?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn map(value: Value) -> Map<String, Value> {
+        value.as_object().cloned().unwrap()
+    }
+
+    fn eval(expression: &str, values: Value, item: Value) -> bool {
+        let names = map(json!({"#name": "name"}));
+        parse_condition(expression, Some(&names), Some(&map(values)))
+            .unwrap()
+            .eval(&map(item))
+    }
+
+    #[test]
+    fn test_condition() {
+        let item = json!({
+            "name": {"S": "bokuweb"},
+            "age": {"N": "36"},
+            "tags": {"SS": ["a", "b"]},
+        });
+        let values =
+            json!({":a": {"N": "30"}, ":b": {"N": "40"}, ":t": {"S": "a"}, ":s": {"S": "boku"}});
+
+        assert!(eval("age BETWEEN :a AND :b", values.clone(), item.clone()));
+        assert!(eval(
+            "begins_with(#name, :s) AND contains(tags, :t)",
+            values.clone(),
+            item.clone()
+        ));
+        assert!(eval(
+            "NOT (age < :a) AND (attribute_not_exists(missing) OR age = :b)",
+            values.clone(),
+            item.clone()
+        ));
+        assert!(eval("missing <> :a", values.clone(), item.clone()));
+        assert!(!eval("size(#name) > :t", values.clone(), item.clone()));
+        assert!(!eval(
+            "age IN (:b, :a) OR #name = :s",
+            values.clone(),
+            item.clone()
+        ));
+        assert!(parse_condition("age = :undefined", None, None).is_err());
+        assert!(parse_condition("age = :a AND", None, Some(&map(values))).is_err());
+    }
+
+    #[test]
+    fn test_update() {
+        let mut item = map(json!({
+            "id": {"S": "id0"},
+            "count": {"N": "1"},
+            "list": {"L": [{"N": "1"}]},
+            "tags": {"SS": ["a", "b"]},
+            "old": {"S": "old"},
+        }));
+        let values = map(json!({
+            ":one": {"N": "1"},
+            ":list": {"L": [{"N": "2"}]},
+            ":tags": {"SS": ["b", "c"]},
+            ":name": {"S": "bokuweb"},
+        }));
+        let actions = parse_update(
+            "SET #count = #count + :one, list = list_append(list, :list), #name = if_not_exists(#name, :name) REMOVE old ADD tags :tags",
+            Some(&map(json!({"#count": "count", "#name": "name"}))),
+            Some(&values),
+        )
+        .unwrap();
+        apply(&actions, &mut item).unwrap();
+
+        assert_eq!(
+            Value::Object(item.clone()),
+            json!({
+                "id": {"S": "id0"},
+                "count": {"N": "2"},
+                "list": {"L": [{"N": "1"}, {"N": "2"}]},
+                "tags": {"SS": ["a", "b", "c"]},
+                "name": {"S": "bokuweb"},
+            })
+        );
+
+        let actions = parse_update("delete tags :tags", None, Some(&values)).unwrap();
+        apply(&actions, &mut item).unwrap();
+        assert_eq!(item.get("tags"), Some(&json!({"SS": ["a"]})));
+
+        let actions = parse_update("SET x = missing - :one", None, Some(&values)).unwrap();
+        assert!(apply(&actions, &mut item).is_err());
+    }
+}
@@ -0,0 +1,1016 @@
+//! An in-memory DynamoDB for unit tests, enabled with the `memory` feature.
+//!
+//! `MemoryDb` answers the DynamoDB JSON protocol behind the SDK client, so generated clients,
+//! builders and expressions run unchanged without dynamodb-local or Docker.
+//!
+//! ```ignore
+//! let db = raiden::memory::MemoryDb::new();
+//! // aws-sdk
+//! let client = User::client_with(db.client());
+//! // rusoto
+//! let client = User::client_with(db.client(), Region::ApNortheast1);
+//! // either
+//! let client = User::client_with_backend(db.backend());
+//! ```
+//!
+//! Each request consumes one capacity unit per table when `ReturnConsumedCapacity` is set.
+//! Throughput and size limits are not simulated, and TTL never deletes items,
+//! which behaves like DynamoDB before its background deletion runs.
+
+mod expression;
+mod value;
+
+#[cfg(feature = "aws-sdk")]
+mod aws_sdk;
+
+#[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
+mod rusoto;
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use serde_json::{json, Map, Value};
+
+use self::expression::Condition;
+use self::value::{Item, Path};
+
+const TARGET_PREFIX: &str = "DynamoDB_20120810.";
+
+type Tables = BTreeMap<String, Table>;
+
+/// An in-memory DynamoDB. Clones share the same tables.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryDb {
+    tables: Arc<Mutex<Tables>>,
+    // The `TransactItems` of each succeeded `ClientRequestToken`.
+    transactions: Arc<Mutex<BTreeMap<String, Value>>>,
+}
+
+impl MemoryDb {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Handles a request of the operation named by `target`, e.g. `DynamoDB_20120810.PutItem`.
+    ///
+    /// Returns the HTTP status and the JSON body of the response.
+    pub(crate) fn handle(&self, target: &str, body: &[u8]) -> (u16, Vec<u8>) {
+        let request = serde_json::from_slice::<Value>(body).unwrap_or_else(|_| json!({}));
+        let operation = target.strip_prefix(TARGET_PREFIX).unwrap_or(target);
+        let mut tables = self.tables.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let token = request["ClientRequestToken"]
+            .as_str()
+            .filter(|_| operation == "TransactWriteItems");
+        let result = match token {
+            Some(token) => self.idempotent(token, &request["TransactItems"], || {
+                dispatch(&mut tables, operation, &request)
+            }),
+            None => dispatch(&mut tables, operation, &request),
+        };
+
+        match result {
+            Ok(mut response) => {
+                report_capacity(&request, &mut response);
+                (200, response.to_string().into_bytes())
+            }
+            Err(error) => (400, error.into_body().to_string().into_bytes()),
+        }
+    }
+
+    // A repeated token succeeds without applying the transaction again,
+    // and fails when it is reused for different items.
+    fn idempotent(
+        &self,
+        token: &str,
+        items: &Value,
+        run: impl FnOnce() -> Result<Value, Error>,
+    ) -> Result<Value, Error> {
+        let mut transactions = self
+            .transactions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        match transactions.get(token) {
+            Some(applied) if applied == items => Ok(json!({})),
+            Some(_) => Err(Error::IdempotentParameterMismatch),
+            None => {
+                let res = run()?;
+                transactions.insert(token.to_owned(), items.clone());
+                Ok(res)
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Error {
+    Validation(String),
+    ConditionalCheckFailed(Option<Item>),
+    ResourceNotFound,
+    ResourceInUse(String),
+    TransactionCanceled(Vec<Value>),
+    IdempotentParameterMismatch,
+}
+
+impl Error {
+    fn into_body(self) -> Value {
+        let (ty, message) = match &self {
+            Error::Validation(message) => ("ValidationException", message.clone()),
+            Error::ConditionalCheckFailed(_) => (
+                "ConditionalCheckFailedException",
+                "The conditional request failed".to_owned(),
+            ),
+            Error::ResourceNotFound => (
+                "ResourceNotFoundException",
+                "Requested resource not found".to_owned(),
+            ),
+            Error::IdempotentParameterMismatch => (
+                "IdempotentParameterMismatchException",
+                "The request uses the same client token as a previous, but non-identical request"
+                    .to_owned(),
+            ),
+            Error::ResourceInUse(table_name) => (
+                "ResourceInUseException",
+                format!("Table already exists: {table_name}"),
+            ),
+            Error::TransactionCanceled(reasons) => {
+                let codes = reasons
+                    .iter()
+                    .map(|reason| reason["Code"].as_str().unwrap_or("None"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                (
+                    "TransactionCanceledException",
+                    format!("Transaction cancelled, please refer cancellation reasons for specific reasons [{codes}]"),
+                )
+            }
+        };
+
+        let mut body = json!({
+            "__type": format!("com.amazonaws.dynamodb.v20120810#{ty}"),
+            "message": message,
+        });
+        match self {
+            Error::ConditionalCheckFailed(Some(item)) => body["Item"] = Value::Object(item),
+            Error::TransactionCanceled(reasons) => {
+                body["CancellationReasons"] = Value::Array(reasons)
+            }
+            _ => {}
+        }
+        body
+    }
+}
+
+#[derive(Debug, Clone)]
+struct KeySchema {
+    hash: String,
+    range: Option<String>,
+}
+
+impl KeySchema {
+    fn from_request(key_schema: &Value) -> Result<Self, Error> {
+        let (mut hash, mut range) = (None, None);
+        for element in key_schema.as_array().into_iter().flatten() {
+            let name = element["AttributeName"].as_str().map(str::to_owned);
+            match element["KeyType"].as_str() {
+                Some("HASH") => hash = name,
+                Some("RANGE") => range = name,
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            hash: hash
+                .ok_or_else(|| Error::Validation("No Hash Key specified in schema".to_owned()))?,
+            range,
+        })
+    }
+
+    fn attrs(&self) -> impl Iterator<Item = &String> {
+        std::iter::once(&self.hash).chain(&self.range)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Projection {
+    All,
+    KeysOnly,
+    Include(Vec<String>),
+}
+
+#[derive(Debug, Clone)]
+struct Index {
+    name: String,
+    key: KeySchema,
+    projection: Projection,
+}
+
+#[derive(Debug, Clone)]
+struct Table {
+    description: Value,
+    key: KeySchema,
+    indexes: Vec<Index>,
+    /// Sorted by the primary key.
+    items: Vec<Item>,
+}
+
+/// Orders items by `attrs` in turn, as DynamoDB sorts items by their keys.
+fn compare_by(a: &Item, b: &Item, attrs: &[String]) -> Ordering {
+    attrs
+        .iter()
+        .map(|attr| match (a.get(attr), b.get(attr)) {
+            (Some(a), Some(b)) => value::compare(a, b).unwrap_or(Ordering::Equal),
+            (a, b) => a.is_some().cmp(&b.is_some()),
+        })
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+fn pick(item: &Item, attrs: &[String]) -> Item {
+    attrs
+        .iter()
+        .filter_map(|attr| Some((attr.clone(), item.get(attr)?.clone())))
+        .collect()
+}
+
+impl Table {
+    fn key_attrs(&self) -> Vec<String> {
+        self.key.attrs().cloned().collect()
+    }
+
+    fn key_of(&self, item: &Item) -> Item {
+        pick(item, &self.key_attrs())
+    }
+
+    fn validate_key(&self, key: &Item) -> Result<(), Error> {
+        if key.len() != self.key.attrs().count()
+            || !self.key.attrs().all(|attr| key.contains_key(attr))
+        {
+            return Err(Error::Validation(
+                "The provided key element does not match the schema".to_owned(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn validate_item(&self, item: &Item) -> Result<(), Error> {
+        match self.key.attrs().find(|attr| !item.contains_key(*attr)) {
+            Some(attr) => Err(Error::Validation(format!(
+                "One or more parameter values were invalid: Missing the key {attr} in the item"
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    fn position(&self, key: &Item) -> Option<usize> {
+        self.items.iter().position(|item| {
+            self.key
+                .attrs()
+                .all(|attr| match (item.get(attr), key.get(attr)) {
+                    (Some(a), Some(b)) => value::equals(a, b),
+                    _ => false,
+                })
+        })
+    }
+
+    fn get(&self, key: &Item) -> Option<&Item> {
+        self.position(key).map(|i| &self.items[i])
+    }
+
+    /// Inserts or replaces an item, returning the replaced one.
+    fn put(&mut self, item: Item) -> Option<Item> {
+        let old = match self.position(&self.key_of(&item)) {
+            Some(i) => Some(std::mem::replace(&mut self.items[i], item)),
+            None => {
+                self.items.push(item);
+                None
+            }
+        };
+        let attrs = self.key_attrs();
+        self.items.sort_by(|a, b| compare_by(a, b, &attrs));
+        old
+    }
+
+    fn delete(&mut self, key: &Item) -> Option<Item> {
+        self.position(key).map(|i| self.items.remove(i))
+    }
+
+    /// Returns the items of the table or of an index, sorted by their keys,
+    /// and the attributes which form `LastEvaluatedKey`.
+    fn source(&self, index_name: Option<&str>) -> Result<(Vec<Item>, Vec<String>), Error> {
+        let Some(index_name) = index_name else {
+            return Ok((self.items.clone(), self.key_attrs()));
+        };
+        let index = self
+            .indexes
+            .iter()
+            .find(|index| index.name == index_name)
+            .ok_or_else(|| {
+                Error::Validation(format!(
+                    "The table does not have the specified index: {index_name}"
+                ))
+            })?;
+
+        let mut attrs: Vec<String> = index.key.attrs().cloned().collect();
+        attrs.extend(
+            self.key
+                .attrs()
+                .filter(|attr| !attrs.contains(attr))
+                .cloned()
+                .collect::<Vec<_>>(),
+        );
+        let mut items: Vec<Item> = self
+            .items
+            .iter()
+            .filter(|item| index.key.attrs().all(|attr| item.contains_key(attr)))
+            .map(|item| match &index.projection {
+                Projection::All => item.clone(),
+                Projection::KeysOnly => pick(item, &attrs),
+                Projection::Include(non_key_attrs) => {
+                    pick(item, &[attrs.as_slice(), non_key_attrs.as_slice()].concat())
+                }
+            })
+            .collect();
+        items.sort_by(|a, b| compare_by(a, b, &attrs));
+        Ok((items, attrs))
+    }
+
+    fn description(&self) -> Value {
+        let mut description = self.description.clone();
+        description["ItemCount"] = json!(self.items.len());
+        description
+    }
+}
+
+/// Reports one capacity unit for each table of a request which asks for `ReturnConsumedCapacity`.
+fn report_capacity(request: &Value, response: &mut Value) {
+    let mode = request["ReturnConsumedCapacity"].as_str();
+    if !matches!(mode, Some("TOTAL") | Some("INDEXES")) {
+        return;
+    }
+    let capacity = |table_name: &str| {
+        let mut capacity = json!({ "TableName": table_name, "CapacityUnits": 1.0 });
+        if mode == Some("INDEXES") {
+            capacity["Table"] = json!({ "CapacityUnits": 1.0 });
+        }
+        capacity
+    };
+
+    if let Some(table_name) = request["TableName"].as_str() {
+        response["ConsumedCapacity"] = capacity(table_name);
+        return;
+    }
+    let mut table_names: Vec<&str> = match request["RequestItems"].as_object() {
+        Some(request_items) => request_items.keys().map(String::as_str).collect(),
+        None => request["TransactItems"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|item| item.as_object()?.values().next()?["TableName"].as_str())
+            .collect(),
+    };
+    table_names.sort_unstable();
+    table_names.dedup();
+    response["ConsumedCapacity"] = Value::Array(table_names.into_iter().map(capacity).collect());
+}
+
+fn dispatch(tables: &mut Tables, operation: &str, request: &Value) -> Result<Value, Error> {
+    match operation {
+        "CreateTable" => create_table(tables, request),
+        "DeleteTable" => {
+            let table_name = table_name(request)?;
+            let table = tables.remove(table_name).ok_or(Error::ResourceNotFound)?;
+            let mut description = table.description();
+            description["TableStatus"] = json!("DELETING");
+            Ok(json!({ "TableDescription": description }))
+        }
+        "DescribeTable" => Ok(json!({ "Table": table(tables, request)?.description() })),
+        "UpdateTimeToLive" => {
+            table(tables, request)?;
+            Ok(json!({ "TimeToLiveSpecification": request["TimeToLiveSpecification"] }))
+        }
+        "PutItem" => put_item(table_mut(tables, request)?, request),
+        "GetItem" => get_item(table(tables, request)?, request),
+        "UpdateItem" => update_item(table_mut(tables, request)?, request),
+        "DeleteItem" => delete_item(table_mut(tables, request)?, request),
+        "Query" => query(table(tables, request)?, request),
+        "Scan" => scan(table(tables, request)?, request),
+        "BatchGetItem" => batch_get_item(tables, request),
+        "BatchWriteItem" => batch_write_item(tables, request),
+        "TransactGetItems" => transact_get_items(tables, request),
+        "TransactWriteItems" => transact_write_items(tables, request),
+        _ => Err(Error::Validation(format!(
+            "Unsupported operation: {operation}"
+        ))),
+    }
+}
+
+fn table_name(request: &Value) -> Result<&str, Error> {
+    request["TableName"]
+        .as_str()
+        .ok_or_else(|| Error::Validation("TableName is required".to_owned()))
+}
+
+fn table<'a>(tables: &'a Tables, request: &Value) -> Result<&'a Table, Error> {
+    tables
+        .get(table_name(request)?)
+        .ok_or(Error::ResourceNotFound)
+}
+
+fn table_mut<'a>(tables: &'a mut Tables, request: &Value) -> Result<&'a mut Table, Error> {
+    tables
+        .get_mut(table_name(request)?)
+        .ok_or(Error::ResourceNotFound)
+}
+
+fn object<'a>(request: &'a Value, name: &str) -> Result<&'a Item, Error> {
+    request[name]
+        .as_object()
+        .ok_or_else(|| Error::Validation(format!("{name} is required")))
+}
+
+fn names(request: &Value) -> Option<&Map<String, Value>> {
+    request["ExpressionAttributeNames"].as_object()
+}
+
+fn values(request: &Value) -> Option<&Map<String, Value>> {
+    request["ExpressionAttributeValues"].as_object()
+}
+
+fn parse_condition(request: &Value, name: &str) -> Result<Option<Condition>, Error> {
+    request[name]
+        .as_str()
+        .map(|expression| expression::parse_condition(expression, names(request), values(request)))
+        .transpose()
+        .map_err(Error::Validation)
+}
+
+fn projection(request: &Value) -> Result<Option<Vec<Path>>, Error> {
+    request["ProjectionExpression"]
+        .as_str()
+        .map(|expression| expression::parse_projection(expression, names(request)))
+        .transpose()
+        .map_err(Error::Validation)
+}
+
+fn project(item: &Item, paths: &Option<Vec<Path>>) -> Item {
+    match paths {
+        Some(paths) => value::project(item, paths),
+        None => item.clone(),
+    }
+}
+
+/// Checks `ConditionExpression` against the current item.
+fn check(request: &Value, item: Option<&Item>) -> Result<(), Error> {
+    let Some(condition) = parse_condition(request, "ConditionExpression")? else {
+        return Ok(());
+    };
+    if condition.eval(item.unwrap_or(&Item::new())) {
+        return Ok(());
+    }
+
+    let return_old = request["ReturnValuesOnConditionCheckFailure"] == "ALL_OLD";
+    Err(Error::ConditionalCheckFailed(
+        item.filter(|_| return_old).cloned(),
+    ))
+}
+
+fn attributes(item: Option<Item>) -> Value {
+    match item {
+        Some(item) if !item.is_empty() => json!({ "Attributes": item }),
+        _ => json!({}),
+    }
+}
+
+fn create_table(tables: &mut Tables, request: &Value) -> Result<Value, Error> {
+    let table_name = table_name(request)?;
+    if tables.contains_key(table_name) {
+        return Err(Error::ResourceInUse(table_name.to_owned()));
+    }
+
+    let key = KeySchema::from_request(&request["KeySchema"])?;
+    let mut indexes = vec![];
+    let mut description = json!({
+        "TableName": table_name,
+        "TableArn": format!("arn:aws:dynamodb:memory:000000000000:table/{table_name}"),
+        "TableStatus": "ACTIVE",
+        "KeySchema": request["KeySchema"],
+        "AttributeDefinitions": request["AttributeDefinitions"],
+        "ItemCount": 0,
+        "TableSizeBytes": 0,
+    });
+    if let Some(billing_mode) = request["BillingMode"].as_str() {
+        description["BillingModeSummary"] = json!({ "BillingMode": billing_mode });
+    }
+    if let Some(throughput) = request["ProvisionedThroughput"].as_object() {
+        description["ProvisionedThroughput"] = Value::Object(throughput.clone());
+    }
+
+    for kind in ["GlobalSecondaryIndexes", "LocalSecondaryIndexes"] {
+        let Some(requested) = request[kind].as_array() else {
+            continue;
+        };
+        let mut described = vec![];
+        for index in requested {
+            let projection = &index["Projection"];
+            indexes.push(Index {
+                name: index["IndexName"].as_str().unwrap_or_default().to_owned(),
+                key: KeySchema::from_request(&index["KeySchema"])?,
+                projection: match projection["ProjectionType"].as_str() {
+                    Some("KEYS_ONLY") => Projection::KeysOnly,
+                    Some("INCLUDE") => Projection::Include(
+                        projection["NonKeyAttributes"]
+                            .as_array()
+                            .into_iter()
+                            .flatten()
+                            .filter_map(|attr| attr.as_str().map(str::to_owned))
+                            .collect(),
+                    ),
+                    _ => Projection::All,
+                },
+            });
+
+            let mut index = index.clone();
+            index["IndexStatus"] = json!("ACTIVE");
+            described.push(index);
+        }
+        description[kind] = Value::Array(described);
+    }
+
+    let table = Table {
+        description,
+        key,
+        indexes,
+        items: vec![],
+    };
+    let description = table.description();
+    tables.insert(table_name.to_owned(), table);
+    Ok(json!({ "TableDescription": description }))
+}
+
+fn put_item(table: &mut Table, request: &Value) -> Result<Value, Error> {
+    let item = object(request, "Item")?.clone();
+    table.validate_item(&item)?;
+    check(request, table.get(&table.key_of(&item)))?;
+
+    let old = table.put(item);
+    Ok(match request["ReturnValues"].as_str() {
+        Some("ALL_OLD") => attributes(old),
+        _ => json!({}),
+    })
+}
+
+fn get_item(table: &Table, request: &Value) -> Result<Value, Error> {
+    let key = object(request, "Key")?;
+    table.validate_key(key)?;
+
+    let paths = projection(request)?;
+    Ok(match table.get(key) {
+        Some(item) => json!({ "Item": project(item, &paths) }),
+        None => json!({}),
+    })
+}
+
+fn update_item(table: &mut Table, request: &Value) -> Result<Value, Error> {
+    let key = object(request, "Key")?.clone();
+    table.validate_key(&key)?;
+    let old = table.get(&key).cloned();
+    check(request, old.as_ref())?;
+
+    let mut new = old.clone().unwrap_or_else(|| key.clone());
+    let mut updated = vec![];
+    if let Some(expression) = request["UpdateExpression"].as_str() {
+        let actions = expression::parse_update(expression, names(request), values(request))
+            .map_err(Error::Validation)?;
+        if let Some(action) = actions
+            .iter()
+            .find(|action| table.key.attrs().any(|attr| attr == action.attr_name()))
+        {
+            return Err(Error::Validation(format!(
+                "One or more parameter values were invalid: Cannot update attribute {}. This attribute is part of the key",
+                action.attr_name()
+            )));
+        }
+        expression::apply(&actions, &mut new).map_err(Error::Validation)?;
+        updated = actions
+            .iter()
+            .map(|action| action.attr_name().to_owned())
+            .collect();
+    }
+    table.put(new.clone());
+
+    let updated_only = |item: Option<Item>| item.map(|item| pick(&item, &updated));
+    Ok(match request["ReturnValues"].as_str() {
+        Some("ALL_OLD") => attributes(old),
+        Some("ALL_NEW") => attributes(Some(new)),
+        Some("UPDATED_OLD") => attributes(updated_only(old)),
+        Some("UPDATED_NEW") => attributes(updated_only(Some(new))),
+        _ => json!({}),
+    })
+}
+
+fn delete_item(table: &mut Table, request: &Value) -> Result<Value, Error> {
+    let key = object(request, "Key")?;
+    table.validate_key(key)?;
+    check(request, table.get(key))?;
+
+    let old = table.delete(key);
+    Ok(match request["ReturnValues"].as_str() {
+        Some("ALL_OLD") => attributes(old),
+        _ => json!({}),
+    })
+}
+
+fn query(table: &Table, request: &Value) -> Result<Value, Error> {
+    let key_condition = parse_condition(request, "KeyConditionExpression")?.ok_or_else(|| {
+        Error::Validation(
+            "Either the KeyConditions or KeyConditionExpression parameter must be specified in the request.".to_owned(),
+        )
+    })?;
+    let (mut items, key_attrs) = table.source(request["IndexName"].as_str())?;
+    items.retain(|item| key_condition.eval(item));
+
+    let forward = request["ScanIndexForward"].as_bool().unwrap_or(true);
+    if !forward {
+        items.reverse();
+    }
+    page(items, &key_attrs, forward, request)
+}
+
+fn scan(table: &Table, request: &Value) -> Result<Value, Error> {
+    let (mut items, key_attrs) = table.source(request["IndexName"].as_str())?;
+    if let (Some(segment), Some(total_segments)) = (
+        request["Segment"].as_u64(),
+        request["TotalSegments"].as_u64(),
+    ) {
+        items.retain(|item| segment_of(item.get(&table.key.hash), total_segments) == segment);
+    }
+    page(items, &key_attrs, true, request)
+}
+
+/// Assigns an item to a scan segment by a FNV-1a hash of its partition key.
+fn segment_of(hash_key: Option<&Value>, total_segments: u64) -> u64 {
+    let hash = hash_key
+        .map(Value::to_string)
+        .unwrap_or_default()
+        .bytes()
+        .fold(0xcbf29ce484222325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+        });
+    hash % total_segments.max(1)
+}
+
+/// Reads a page of `items` after `ExclusiveStartKey`, evaluating at most `Limit` items.
+fn page(
+    items: Vec<Item>,
+    key_attrs: &[String],
+    forward: bool,
+    request: &Value,
+) -> Result<Value, Error> {
+    let start = match request["ExclusiveStartKey"].as_object() {
+        Some(start_key) => items
+            .iter()
+            .position(|item| {
+                let ordering = compare_by(item, start_key, key_attrs);
+                if forward {
+                    ordering.is_gt()
+                } else {
+                    ordering.is_lt()
+                }
+            })
+            .unwrap_or(items.len()),
+        None => 0,
+    };
+    let limit = request["Limit"]
+        .as_u64()
+        .map_or(usize::MAX, |limit| limit as usize);
+    let scanned = &items[start..(start.saturating_add(limit)).min(items.len())];
+
+    let filter = parse_condition(request, "FilterExpression")?;
+    let paths = projection(request)?;
+    let matched: Vec<Item> = scanned
+        .iter()
+        .filter(|item| filter.as_ref().is_none_or(|filter| filter.eval(item)))
+        .map(|item| project(item, &paths))
+        .collect();
+
+    let mut response = json!({
+        "Count": matched.len(),
+        "ScannedCount": scanned.len(),
+    });
+    if request["Select"] != "COUNT" {
+        response["Items"] = json!(matched);
+    }
+    if start + scanned.len() < items.len() {
+        if let Some(last) = scanned.last() {
+            response["LastEvaluatedKey"] = Value::Object(pick(last, key_attrs));
+        }
+    }
+    Ok(response)
+}
+
+fn batch_get_item(tables: &Tables, request: &Value) -> Result<Value, Error> {
+    let mut responses = Map::new();
+    for (table_name, keys_and_attributes) in object(request, "RequestItems")? {
+        let table = tables.get(table_name).ok_or(Error::ResourceNotFound)?;
+        let paths = projection(keys_and_attributes)?;
+        let mut items = vec![];
+        for key in keys_and_attributes["Keys"].as_array().into_iter().flatten() {
+            let key = key
+                .as_object()
+                .ok_or_else(|| Error::Validation("Invalid key".to_owned()))?;
+            table.validate_key(key)?;
+            if let Some(item) = table.get(key) {
+                items.push(Value::Object(project(item, &paths)));
+            }
+        }
+        responses.insert(table_name.clone(), Value::Array(items));
+    }
+    Ok(json!({ "Responses": responses, "UnprocessedKeys": {} }))
+}
+
+fn batch_write_item(tables: &mut Tables, request: &Value) -> Result<Value, Error> {
+    for (table_name, requests) in object(request, "RequestItems")? {
+        let table = tables.get_mut(table_name).ok_or(Error::ResourceNotFound)?;
+        for write in requests.as_array().into_iter().flatten() {
+            if let Some(item) = write["PutRequest"]["Item"].as_object() {
+                table.validate_item(item)?;
+                table.put(item.clone());
+            } else if let Some(key) = write["DeleteRequest"]["Key"].as_object() {
+                table.validate_key(key)?;
+                table.delete(key);
+            }
+        }
+    }
+    Ok(json!({ "UnprocessedItems": {} }))
+}
+
+fn transact_get_items(tables: &Tables, request: &Value) -> Result<Value, Error> {
+    let mut responses = vec![];
+    for transact_item in request["TransactItems"].as_array().into_iter().flatten() {
+        let get = &transact_item["Get"];
+        responses.push(get_item(table(tables, get)?, get)?);
+    }
+    Ok(json!({ "Responses": responses }))
+}
+
+/// Applies every action to a copy of the tables, which replaces them only when all succeed.
+fn transact_write_items(tables: &mut Tables, request: &Value) -> Result<Value, Error> {
+    let mut staged = tables.clone();
+    let mut touched: Vec<(String, Item)> = vec![];
+    let mut reasons = vec![];
+
+    for transact_item in request["TransactItems"].as_array().into_iter().flatten() {
+        let (kind, operation) = ["Put", "Update", "Delete", "ConditionCheck"]
+            .into_iter()
+            .find_map(|kind| transact_item.get(kind).map(|operation| (kind, operation)))
+            .ok_or_else(|| Error::Validation("Invalid TransactItem".to_owned()))?;
+        let table = table_mut(&mut staged, operation)?;
+
+        let key = match kind {
+            "Put" => table.key_of(object(operation, "Item")?),
+            _ => object(operation, "Key")?.clone(),
+        };
+        let table_name = table_name(operation)?.to_owned();
+        if touched.iter().any(|(name, touched_key)| {
+            *name == table_name
+                && table
+                    .key
+                    .attrs()
+                    .all(|attr| match (touched_key.get(attr), key.get(attr)) {
+                        (Some(a), Some(b)) => value::equals(a, b),
+                        _ => false,
+                    })
+        }) {
+            return Err(Error::Validation(
+                "Transaction request cannot include multiple operations on one item".to_owned(),
+            ));
+        }
+        touched.push((table_name, key.clone()));
+
+        let result = match kind {
+            "Put" => put_item(table, operation),
+            "Update" => update_item(table, operation),
+            "Delete" => delete_item(table, operation),
+            _ => table
+                .validate_key(&key)
+                .and_then(|_| check(operation, table.get(&key)))
+                .map(|_| json!({})),
+        };
+        match result {
+            Ok(_) => reasons.push(json!({ "Code": "None" })),
+            Err(Error::ConditionalCheckFailed(item)) => {
+                let mut reason = json!({
+                    "Code": "ConditionalCheckFailed",
+                    "Message": "The conditional request failed",
+                });
+                if let Some(item) = item {
+                    reason["Item"] = Value::Object(item);
+                }
+                reasons.push(reason);
+            }
+            Err(error) => return Err(error),
+        }
+    }
+
+    if reasons.iter().any(|reason| reason["Code"] != "None") {
+        return Err(Error::TransactionCanceled(reasons));
+    }
+    *tables = staged;
+    Ok(json!({}))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(db: &MemoryDb, operation: &str, request: Value) -> (u16, Value) {
+        let (status, body) = db.handle(
+            &format!("{TARGET_PREFIX}{operation}"),
+            request.to_string().as_bytes(),
+        );
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    fn setup() -> MemoryDb {
+        let db = MemoryDb::new();
+        call(
+            &db,
+            "CreateTable",
+            json!({
+                "TableName": "User",
+                "KeySchema": [
+                    {"AttributeName": "id", "KeyType": "HASH"},
+                    {"AttributeName": "age", "KeyType": "RANGE"},
+                ],
+                "AttributeDefinitions": [
+                    {"AttributeName": "id", "AttributeType": "S"},
+                    {"AttributeName": "age", "AttributeType": "N"},
+                    {"AttributeName": "name", "AttributeType": "S"},
+                ],
+                "GlobalSecondaryIndexes": [{
+                    "IndexName": "nameIndex",
+                    "KeySchema": [{"AttributeName": "name", "KeyType": "HASH"}],
+                    "Projection": {"ProjectionType": "KEYS_ONLY"},
+                }],
+            }),
+        );
+        for age in 0..5 {
+            call(
+                &db,
+                "PutItem",
+                json!({
+                    "TableName": "User",
+                    "Item": {
+                        "id": {"S": "user"},
+                        "age": {"N": age.to_string()},
+                        "name": {"S": if age % 2 == 0 { "even" } else { "odd" }},
+                    },
+                }),
+            );
+        }
+        db
+    }
+
+    #[test]
+    fn test_query_pages_in_key_order() {
+        let db = setup();
+        let request = json!({
+            "TableName": "User",
+            "KeyConditionExpression": "#id = :id AND age > :age",
+            "ExpressionAttributeNames": {"#id": "id"},
+            "ExpressionAttributeValues": {":id": {"S": "user"}, ":age": {"N": "0"}},
+            "ScanIndexForward": false,
+            "Limit": 3,
+        });
+
+        let (status, response) = call(&db, "Query", request.clone());
+        assert_eq!(status, 200);
+        assert_eq!(response["Count"], 3);
+        assert_eq!(response["Items"][0]["age"], json!({"N": "4"}));
+        assert_eq!(
+            response["LastEvaluatedKey"],
+            json!({"id": {"S": "user"}, "age": {"N": "2"}})
+        );
+
+        let mut request = request;
+        request["ExclusiveStartKey"] = response["LastEvaluatedKey"].clone();
+        let (_, response) = call(&db, "Query", request);
+        assert_eq!(response["Count"], 1);
+        assert_eq!(response["Items"][0]["age"], json!({"N": "1"}));
+        assert!(response.get("LastEvaluatedKey").is_none());
+    }
+
+    #[test]
+    fn test_index_projection() {
+        let db = setup();
+        let (_, response) = call(
+            &db,
+            "Query",
+            json!({
+                "TableName": "User",
+                "IndexName": "nameIndex",
+                "KeyConditionExpression": "#name = :name",
+                "ExpressionAttributeNames": {"#name": "name"},
+                "ExpressionAttributeValues": {":name": {"S": "odd"}},
+            }),
+        );
+        assert_eq!(response["Count"], 2);
+        assert_eq!(
+            response["Items"][0],
+            json!({"id": {"S": "user"}, "age": {"N": "1"}, "name": {"S": "odd"}})
+        );
+    }
+
+    #[test]
+    fn test_conditional_write() {
+        let db = setup();
+        let key = json!({"id": {"S": "user"}, "age": {"N": "1"}});
+        let (status, response) = call(
+            &db,
+            "DeleteItem",
+            json!({
+                "TableName": "User",
+                "Key": key,
+                "ConditionExpression": "#name = :name",
+                "ExpressionAttributeNames": {"#name": "name"},
+                "ExpressionAttributeValues": {":name": {"S": "even"}},
+                "ReturnValuesOnConditionCheckFailure": "ALL_OLD",
+            }),
+        );
+        assert_eq!(status, 400);
+        assert_eq!(
+            response["__type"],
+            "com.amazonaws.dynamodb.v20120810#ConditionalCheckFailedException"
+        );
+        assert_eq!(response["Item"]["name"], json!({"S": "odd"}));
+
+        let (status, response) = call(
+            &db,
+            "UpdateItem",
+            json!({
+                "TableName": "User",
+                "Key": key,
+                "UpdateExpression": "SET #name = :name",
+                "ExpressionAttributeNames": {"#name": "name"},
+                "ExpressionAttributeValues": {":name": {"S": "one"}},
+                "ReturnValues": "UPDATED_OLD",
+            }),
+        );
+        assert_eq!(status, 200);
+        assert_eq!(response, json!({"Attributes": {"name": {"S": "odd"}}}));
+    }
+
+    #[test]
+    fn test_transaction_is_atomic() {
+        let db = setup();
+        let (status, response) = call(
+            &db,
+            "TransactWriteItems",
+            json!({
+                "TransactItems": [
+                    {"Delete": {"TableName": "User", "Key": {"id": {"S": "user"}, "age": {"N": "0"}}}},
+                    {"ConditionCheck": {
+                        "TableName": "User",
+                        "Key": {"id": {"S": "user"}, "age": {"N": "9"}},
+                        "ConditionExpression": "attribute_exists(id)",
+                    }},
+                ],
+            }),
+        );
+        assert_eq!(status, 400);
+        assert_eq!(
+            response["message"],
+            "Transaction cancelled, please refer cancellation reasons for specific reasons [None, ConditionalCheckFailed]"
+        );
+
+        let (_, response) = call(&db, "Scan", json!({"TableName": "User"}));
+        assert_eq!(response["Count"], 5);
+    }
+
+    #[test]
+    fn test_client_request_token_is_idempotent() {
+        let db = setup();
+        let delete = json!([
+            {"Delete": {"TableName": "User", "Key": {"id": {"S": "user"}, "age": {"N": "0"}}}},
+        ]);
+        let request = json!({"TransactItems": delete, "ClientRequestToken": "token"});
+
+        assert_eq!(call(&db, "TransactWriteItems", request.clone()).0, 200);
+        assert_eq!(call(&db, "TransactWriteItems", request).0, 200);
+        let (_, response) = call(&db, "Scan", json!({"TableName": "User"}));
+        assert_eq!(response["Count"], 4);
+
+        let (status, response) = call(
+            &db,
+            "TransactWriteItems",
+            json!({"TransactItems": [], "ClientRequestToken": "token"}),
+        );
+        assert_eq!(status, 400);
+        assert_eq!(
+            response["__type"],
+            "com.amazonaws.dynamodb.v20120810#IdempotentParameterMismatchException"
+        );
+    }
+}
@@ -0,0 +1,52 @@
+use std::time::Duration;
+
+use rusoto_core::request::{DispatchSignedRequestFuture, HttpResponse};
+use rusoto_core::signature::{SignedRequest, SignedRequestPayload};
+use rusoto_core::{ByteStream, DispatchSignedRequest};
+use rusoto_credential::StaticProvider;
+
+use super::MemoryDb;
+
+impl DispatchSignedRequest for MemoryDb {
+    fn dispatch(
+        &self,
+        request: SignedRequest,
+        _timeout: Option<Duration>,
+    ) -> DispatchSignedRequestFuture {
+        let target = request
+            .headers
+            .get("x-amz-target")
+            .and_then(|values| values.first())
+            .map(|value| String::from_utf8_lossy(value).into_owned())
+            .unwrap_or_default();
+        let body = match &request.payload {
+            Some(SignedRequestPayload::Buffer(bytes)) => bytes.to_vec(),
+            _ => vec![],
+        };
+        let (status, body) = self.handle(&target, &body);
+
+        let response = HttpResponse {
+            status: http::StatusCode::from_u16(status).expect("status should be valid"),
+            body: ByteStream::from(body),
+            headers: http::HeaderMap::default(),
+        };
+        Box::pin(async move { Ok::<_, rusoto_core::HttpDispatchError>(response) })
+    }
+}
+
+impl MemoryDb {
+    /// Returns a client which sends every request to this database.
+    ///
+    /// Any region can be given to `client_with` since nothing is sent over the network.
+    pub fn client(&self) -> crate::Client {
+        crate::Client::new_with(
+            StaticProvider::new_minimal("memory".to_owned(), "memory".to_owned()),
+            self.clone(),
+        )
+    }
+
+    /// Returns a [`DynamoBackend`](crate::DynamoBackend) which sends every request to this database.
+    pub fn backend(&self) -> crate::DynamoDbClient {
+        crate::DynamoDbClient::new_with_client(self.client(), crate::Region::ApNortheast1)
+    }
+}
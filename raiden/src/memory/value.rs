@@ -0,0 +1,359 @@
+//! Attribute values in the DynamoDB JSON wire format, e.g. `{"S": "bokuweb"}`.
+
+use std::cmp::Ordering;
+
+use base64::Engine;
+use serde_json::{Map, Value};
+
+pub(super) type Item = Map<String, Value>;
+
+/// An element of a document path, e.g. `a.b[0]` is `[Attr("a"), Attr("b"), Index(0)]`.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Element {
+    Attr(String),
+    Index(usize),
+}
+
+pub(super) type Path = Vec<Element>;
+
+/// Returns the type descriptor of a value, e.g. `"S"` for `{"S": "bokuweb"}`.
+pub(super) fn type_of(value: &Value) -> Option<&str> {
+    value
+        .as_object()
+        .and_then(|v| v.keys().next())
+        .map(String::as_str)
+}
+
+fn inner<'a>(value: &'a Value, ty: &str) -> Option<&'a Value> {
+    value.as_object().and_then(|v| v.get(ty))
+}
+
+pub(super) fn string(value: &Value) -> Option<&str> {
+    inner(value, "S").and_then(Value::as_str)
+}
+
+pub(super) fn number(value: &Value) -> Option<&str> {
+    inner(value, "N").and_then(Value::as_str)
+}
+
+pub(super) fn binary(value: &Value) -> Option<Vec<u8>> {
+    inner(value, "B")
+        .and_then(Value::as_str)
+        .and_then(|v| base64::engine::general_purpose::STANDARD.decode(v).ok())
+}
+
+pub(super) fn list(value: &Value) -> Option<&Vec<Value>> {
+    inner(value, "L").and_then(Value::as_array)
+}
+
+pub(super) fn number_value(n: impl ToString) -> Value {
+    serde_json::json!({ "N": n.to_string() })
+}
+
+enum Number {
+    Int(i128),
+    Float(f64),
+}
+
+impl Number {
+    fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+        s.parse::<i128>()
+            .map(Number::Int)
+            .ok()
+            .or_else(|| s.parse::<f64>().ok().map(Number::Float))
+    }
+
+    fn as_f64(&self) -> f64 {
+        match self {
+            Number::Int(v) => *v as f64,
+            Number::Float(v) => *v,
+        }
+    }
+}
+
+fn compare_numbers(a: &str, b: &str) -> Option<Ordering> {
+    match (Number::parse(a)?, Number::parse(b)?) {
+        (Number::Int(a), Number::Int(b)) => Some(a.cmp(&b)),
+        (a, b) => a.as_f64().partial_cmp(&b.as_f64()),
+    }
+}
+
+/// Adds (or subtracts) two numbers, keeping integers exact.
+pub(super) fn add_numbers(a: &str, b: &str, subtract: bool) -> Option<String> {
+    match (Number::parse(a)?, Number::parse(b)?) {
+        (Number::Int(a), Number::Int(b)) => {
+            let sum = if subtract {
+                a.checked_sub(b)
+            } else {
+                a.checked_add(b)
+            };
+            sum.map(|v| v.to_string())
+        }
+        (a, b) => {
+            let (a, b) = (a.as_f64(), b.as_f64());
+            Some((if subtract { a - b } else { a + b }).to_string())
+        }
+    }
+}
+
+/// Orders two scalar values of the same type, as `<`, `BETWEEN` and sort keys do.
+pub(super) fn compare(a: &Value, b: &Value) -> Option<Ordering> {
+    match (type_of(a)?, type_of(b)?) {
+        ("S", "S") => Some(string(a)?.cmp(string(b)?)),
+        ("N", "N") => compare_numbers(number(a)?, number(b)?),
+        ("B", "B") => Some(binary(a)?.cmp(&binary(b)?)),
+        _ => None,
+    }
+}
+
+fn members(value: &Value, ty: &str) -> Vec<Value> {
+    inner(value, ty)
+        .and_then(Value::as_array)
+        .map(|v| {
+            v.iter()
+                .map(|member| {
+                    let scalar = &ty[..1];
+                    serde_json::json!({ scalar: member })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The members of a set value as scalar values, e.g. `{"SS": ["a"]}` yields `{"S": "a"}`.
+pub(super) fn set_members(value: &Value) -> Option<Vec<Value>> {
+    match type_of(value)? {
+        ty @ ("SS" | "NS" | "BS") => Some(members(value, ty)),
+        _ => None,
+    }
+}
+
+/// Builds a set value of type `ty` from scalar values.
+pub(super) fn set_value(ty: &str, members: Vec<Value>) -> Value {
+    let scalar = &ty[..1];
+    let members: Vec<Value> = members
+        .into_iter()
+        .filter_map(|v| inner(&v, scalar).cloned())
+        .collect();
+    serde_json::json!({ ty: members })
+}
+
+/// Compares two values as DynamoDB does, i.e. numbers by value and sets regardless of order.
+pub(super) fn equals(a: &Value, b: &Value) -> bool {
+    let (Some(ta), Some(tb)) = (type_of(a), type_of(b)) else {
+        return false;
+    };
+    if ta != tb {
+        return false;
+    }
+
+    match ta {
+        "S" | "N" | "B" => compare(a, b) == Some(Ordering::Equal),
+        "SS" | "NS" | "BS" => {
+            let (a, b) = (members(a, ta), members(b, tb));
+            a.len() == b.len() && a.iter().all(|v| b.iter().any(|w| equals(v, w)))
+        }
+        "L" => match (list(a), list(b)) {
+            (Some(a), Some(b)) => a.len() == b.len() && a.iter().zip(b).all(|(v, w)| equals(v, w)),
+            _ => false,
+        },
+        "M" => match (
+            inner(a, "M").and_then(Value::as_object),
+            inner(b, "M").and_then(Value::as_object),
+        ) {
+            (Some(a), Some(b)) => {
+                a.len() == b.len()
+                    && a.iter()
+                        .all(|(k, v)| b.get(k).is_some_and(|w| equals(v, w)))
+            }
+            _ => false,
+        },
+        _ => a == b,
+    }
+}
+
+/// Returns the value at `path`.
+pub(super) fn get<'a>(item: &'a Item, path: &[Element]) -> Option<&'a Value> {
+    let (Element::Attr(first), rest) = path.split_first()? else {
+        return None;
+    };
+
+    rest.iter()
+        .try_fold(item.get(first)?, |value, element| match element {
+            Element::Attr(name) => inner(value, "M")?.get(name),
+            Element::Index(i) => list(value)?.get(*i),
+        })
+}
+
+fn get_mut<'a>(item: &'a mut Item, path: &[Element]) -> Option<&'a mut Value> {
+    let (Element::Attr(first), rest) = path.split_first()? else {
+        return None;
+    };
+
+    rest.iter()
+        .try_fold(item.get_mut(first)?, |value, element| match element {
+            Element::Attr(name) => value.get_mut("M")?.as_object_mut()?.get_mut(name),
+            Element::Index(i) => value.get_mut("L")?.as_array_mut()?.get_mut(*i),
+        })
+}
+
+/// Sets the value at `path`. The parent of a nested path must exist.
+pub(super) fn set(item: &mut Item, path: &[Element], value: Value) -> Result<(), String> {
+    let invalid = || "The document path provided in the update expression is invalid for update";
+
+    match path {
+        [Element::Attr(name)] => {
+            item.insert(name.clone(), value);
+            Ok(())
+        }
+        [parent @ .., last] => {
+            let parent = get_mut(item, parent).ok_or_else(invalid)?;
+            match last {
+                Element::Attr(name) => {
+                    parent
+                        .get_mut("M")
+                        .and_then(Value::as_object_mut)
+                        .ok_or_else(invalid)?
+                        .insert(name.clone(), value);
+                }
+                Element::Index(i) => {
+                    let list = parent
+                        .get_mut("L")
+                        .and_then(Value::as_array_mut)
+                        .ok_or_else(invalid)?;
+                    match list.get_mut(*i) {
+                        Some(v) => *v = value,
+                        None => list.push(value),
+                    }
+                }
+            }
+            Ok(())
+        }
+        [] => Err(invalid().to_owned()),
+    }
+}
+
+/// Removes the value at `path`, shifting later list elements.
+pub(super) fn remove(item: &mut Item, path: &[Element]) {
+    match path {
+        [Element::Attr(name)] => {
+            item.remove(name);
+        }
+        [parent @ .., last] => {
+            let Some(parent) = get_mut(item, parent) else {
+                return;
+            };
+            match last {
+                Element::Attr(name) => {
+                    if let Some(map) = parent.get_mut("M").and_then(Value::as_object_mut) {
+                        map.remove(name);
+                    }
+                }
+                Element::Index(i) => {
+                    if let Some(list) = parent.get_mut("L").and_then(Value::as_array_mut) {
+                        if *i < list.len() {
+                            list.remove(*i);
+                        }
+                    }
+                }
+            }
+        }
+        [] => {}
+    }
+}
+
+/// Keeps only the attributes at `paths`, as `ProjectionExpression` does.
+///
+/// Nested paths keep their enclosing maps, and list elements keep the whole list.
+pub(super) fn project(item: &Item, paths: &[Path]) -> Item {
+    let mut projected = Item::new();
+    'paths: for path in paths {
+        let path: Path = path
+            .iter()
+            .take_while(|element| matches!(element, Element::Attr(_)))
+            .cloned()
+            .collect();
+        let Some(value) = get(item, &path) else {
+            continue;
+        };
+
+        let mut target = &mut projected;
+        for element in &path[..path.len() - 1] {
+            let Element::Attr(name) = element else {
+                unreachable!()
+            };
+            let entry = target
+                .entry(name.clone())
+                .or_insert_with(|| serde_json::json!({ "M": {} }));
+            target = match entry.get_mut("M").and_then(Value::as_object_mut) {
+                Some(map) => map,
+                // The enclosing attribute is already projected as a whole.
+                None => continue 'paths,
+            };
+        }
+        if let Some(Element::Attr(name)) = path.last() {
+            target.insert(name.clone(), value.clone());
+        }
+    }
+    projected
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn item(value: Value) -> Item {
+        value.as_object().cloned().unwrap()
+    }
+
+    #[test]
+    fn test_equals_and_compare() {
+        assert!(equals(&json!({"N": "1"}), &json!({"N": "1.0"})));
+        assert!(equals(
+            &json!({"SS": ["a", "b"]}),
+            &json!({"SS": ["b", "a"]})
+        ));
+        assert!(!equals(&json!({"S": "1"}), &json!({"N": "1"})));
+        assert_eq!(
+            compare(&json!({"N": "9"}), &json!({"N": "10"})),
+            Some(Ordering::Less)
+        );
+        assert_eq!(compare(&json!({"S": "a"}), &json!({"N": "1"})), None);
+        assert_eq!(add_numbers("1", "2", false).as_deref(), Some("3"));
+        assert_eq!(add_numbers("1.5", "2", true).as_deref(), Some("-0.5"));
+    }
+
+    #[test]
+    fn test_document_path() {
+        let mut item = item(json!({
+            "a": {"M": {"b": {"L": [{"N": "1"}, {"N": "2"}]}}},
+            "c": {"S": "c"},
+        }));
+        let path = vec![
+            Element::Attr("a".to_owned()),
+            Element::Attr("b".to_owned()),
+            Element::Index(1),
+        ];
+
+        assert_eq!(get(&item, &path), Some(&json!({"N": "2"})));
+        set(&mut item, &path, json!({"N": "3"})).unwrap();
+        assert_eq!(get(&item, &path), Some(&json!({"N": "3"})));
+        assert!(set(
+            &mut item,
+            &[Element::Attr("x".to_owned()), Element::Attr("y".to_owned())],
+            json!({"N": "1"})
+        )
+        .is_err());
+
+        let projected = project(&item, std::slice::from_ref(&path));
+        assert_eq!(
+            Value::Object(projected),
+            json!({"a": {"M": {"b": {"L": [{"N": "1"}, {"N": "3"}]}}}})
+        );
+
+        remove(&mut item, &path[..2]);
+        assert_eq!(item.get("a"), Some(&json!({"M": {}})));
+    }
+}
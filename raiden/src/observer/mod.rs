@@ -0,0 +1,253 @@
+//! Hooks to observe every request sent to DynamoDB, e.g. to export latency and capacity metrics.
+//!
+//! An [`Observer`] is registered with `with_observer` on a generated client, `WriteTx` or `ReadTx`.
+//! It is called once per attempt, after the retry decision for that attempt has been made.
+
+use std::{
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use crate::{BackendFuture, DynamoBackend, RaidenError, SharedBackend};
+
+#[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
+use crate::ConsumedCapacity;
+
+#[cfg(feature = "aws-sdk")]
+use crate::aws_sdk::types::ConsumedCapacity;
+
+/// Receives an [`ObservedRequest`] after every request attempt.
+pub trait Observer: Send + Sync {
+    fn observe(&self, request: &ObservedRequest);
+}
+
+/// A single attempt of a DynamoDB request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObservedRequest {
+    /// The operation name, e.g. `put_item`.
+    pub api: &'static str,
+    /// The table, unless the operation spans several tables like batches and transactions.
+    pub table_name: Option<String>,
+    pub index_name: Option<String>,
+    /// Starts from 1 and is incremented by every retry.
+    pub attempt: usize,
+    pub latency: Duration,
+    /// Returned only when the request asked for it with `return_consumed_capacity`.
+    pub consumed_capacity: Vec<ConsumedCapacity>,
+    /// Whether the request is going to be retried after this attempt.
+    pub will_retry: bool,
+    pub error: Option<ErrorClass>,
+}
+
+/// A coarse classification of [`RaidenError`] to label metrics with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorClass {
+    ConditionalCheckFailed,
+    Throttling,
+    TransactionCanceled,
+    TransactionConflict,
+    Validation,
+    Resource,
+    Server,
+    Network,
+    Other,
+}
+
+impl ErrorClass {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorClass::ConditionalCheckFailed => "conditional_check_failed",
+            ErrorClass::Throttling => "throttling",
+            ErrorClass::TransactionCanceled => "transaction_canceled",
+            ErrorClass::TransactionConflict => "transaction_conflict",
+            ErrorClass::Validation => "validation",
+            ErrorClass::Resource => "resource",
+            ErrorClass::Server => "server",
+            ErrorClass::Network => "network",
+            ErrorClass::Other => "other",
+        }
+    }
+}
+
+impl From<&RaidenError> for ErrorClass {
+    fn from(error: &RaidenError) -> Self {
+        match error {
+            RaidenError::ConditionalCheckFailed(_) | RaidenError::VersionConflict(_) => {
+                ErrorClass::ConditionalCheckFailed
+            }
+            #[cfg(feature = "aws-sdk")]
+            RaidenError::ConditionalCheckFailedWithItem { .. } => {
+                ErrorClass::ConditionalCheckFailed
+            }
+            RaidenError::ProvisionedThroughputExceeded(_)
+            | RaidenError::RequestLimitExceeded(_)
+            | RaidenError::LimitExceeded(_) => ErrorClass::Throttling,
+            RaidenError::TransactionCanceled { .. } => ErrorClass::TransactionCanceled,
+            RaidenError::TransactionConflict(_) | RaidenError::TransactionInProgress(_) => {
+                ErrorClass::TransactionConflict
+            }
+            RaidenError::Validation(_)
+            | RaidenError::SizeLimitExceeded(_)
+            | RaidenError::IdempotentParameterMismatch(_) => ErrorClass::Validation,
+            RaidenError::ResourceNotFound(_) | RaidenError::ResourceInUse(_) => {
+                ErrorClass::Resource
+            }
+            RaidenError::InternalServerError(_) => ErrorClass::Server,
+            RaidenError::HttpDispatch(_) => ErrorClass::Network,
+            #[cfg(feature = "aws-sdk")]
+            RaidenError::Timeout(_) => ErrorClass::Network,
+            _ => ErrorClass::Other,
+        }
+    }
+}
+
+/// A [`DynamoBackend`] which reports every operation to an [`Observer`].
+pub struct ObservedBackend {
+    inner: SharedBackend,
+    observer: Arc<dyn Observer>,
+    // The attempt of a retried request this backend was made for, see `for_attempt`.
+    attempt: Option<Arc<Attempt>>,
+}
+
+impl ObservedBackend {
+    pub fn new(inner: SharedBackend, observer: Arc<dyn Observer>) -> Self {
+        Self {
+            inner,
+            observer,
+            attempt: None,
+        }
+    }
+
+    /// Returns a backend which holds the requests back in `attempt` instead of reporting them.
+    pub(crate) fn attempt_backend(&self, attempt: &Arc<Attempt>) -> SharedBackend {
+        Arc::new(Self {
+            inner: self
+                .inner
+                .for_attempt(attempt)
+                .unwrap_or_else(|| self.inner.clone()),
+            observer: self.observer.clone(),
+            attempt: Some(attempt.clone()),
+        })
+    }
+
+    pub(crate) fn observe<'a, I, T>(
+        &'a self,
+        api: &'static str,
+        input: I,
+        call: impl FnOnce(&'a dyn DynamoBackend, I) -> BackendFuture<'a, T>,
+    ) -> BackendFuture<'a, T>
+    where
+        I: ObservedInput,
+        T: ObservedOutput + Send + 'a,
+    {
+        let table_name = input.table_name();
+        let index_name = input.index_name();
+        let fut = call(self.inner.as_ref(), input);
+        Box::pin(async move {
+            let started = Instant::now();
+            let res = fut.await;
+            let request = ObservedRequest {
+                api,
+                table_name,
+                index_name,
+                attempt: 1,
+                latency: started.elapsed(),
+                consumed_capacity: res
+                    .as_ref()
+                    .map(ObservedOutput::consumed_capacity)
+                    .unwrap_or_default(),
+                will_retry: false,
+                error: res.as_ref().err().map(ErrorClass::from),
+            };
+            match &self.attempt {
+                Some(attempt) => attempt.hold(self.observer.clone(), request),
+                // Outside of a retry loop, the request is the one and only attempt.
+                None => self.observer.observe(&request),
+            }
+            res
+        })
+    }
+}
+
+/// The table and the index an operation input targets.
+pub(crate) trait ObservedInput {
+    fn table_name(&self) -> Option<String> {
+        None
+    }
+
+    fn index_name(&self) -> Option<String> {
+        None
+    }
+}
+
+/// The capacity an operation output consumed.
+pub(crate) trait ObservedOutput {
+    fn consumed_capacity(&self) -> Vec<ConsumedCapacity> {
+        Vec::new()
+    }
+}
+
+/// A single attempt of a retried request, see [`retry_with_backend`](crate::retry_with_backend).
+///
+/// The requests made during the attempt are held back until its retry decision is known.
+pub struct Attempt {
+    number: usize,
+    pending: Mutex<Vec<(Arc<dyn Observer>, ObservedRequest)>>,
+}
+
+impl Attempt {
+    pub(crate) fn new(number: usize) -> Self {
+        Self {
+            number,
+            pending: Mutex::default(),
+        }
+    }
+
+    /// Starts from 1 and is incremented by every retry.
+    pub fn number(&self) -> usize {
+        self.number
+    }
+
+    fn hold(&self, observer: Arc<dyn Observer>, request: ObservedRequest) {
+        self.pending
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push((observer, request));
+    }
+
+    /// Reports the requests held back by this attempt.
+    pub(crate) fn report(&self, will_retry: bool) {
+        let requests =
+            std::mem::take(&mut *self.pending.lock().unwrap_or_else(PoisonError::into_inner));
+        for (observer, mut request) in requests {
+            request.attempt = self.number;
+            request.will_retry = will_retry;
+            observer.observe(&request);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_class() {
+        assert_eq!(
+            ErrorClass::from(&RaidenError::ConditionalCheckFailed("".to_owned())),
+            ErrorClass::ConditionalCheckFailed
+        );
+        assert_eq!(
+            ErrorClass::from(&RaidenError::ProvisionedThroughputExceeded("".to_owned())),
+            ErrorClass::Throttling
+        );
+        assert_eq!(
+            ErrorClass::from(&RaidenError::InternalServerError("".to_owned())),
+            ErrorClass::Server
+        );
+        assert_eq!(
+            ErrorClass::from(&RaidenError::NextTokenDecodeError),
+            ErrorClass::Other
+        );
+    }
+}
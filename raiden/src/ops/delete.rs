@@ -0,0 +1,18 @@
+#[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
+use crate::{ConsumedCapacity, ItemCollectionMetrics};
+
+#[cfg(feature = "aws-sdk")]
+use crate::aws_sdk::types::{ConsumedCapacity, ItemCollectionMetrics};
+
+#[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(
+    any(feature = "rusoto", feature = "rusoto_rustls"),
+    derive(serde::Deserialize, serde::Serialize)
+)]
+pub struct DeleteOutput<T> {
+    pub consumed_capacity: Option<ConsumedCapacity>,
+    /// The deleted item, set only with `return_old()`.
+    pub old_item: Option<T>,
+    /// Set only with `return_item_collection_metrics(ReturnItemCollectionMetrics::Size)`.
+    pub item_collection_metrics: Option<ItemCollectionMetrics>,
+}
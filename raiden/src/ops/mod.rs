@@ -1,12 +1,15 @@
 pub mod batch_delete;
 pub mod batch_get;
 pub mod batch_put;
+pub mod delete;
 pub mod get;
 pub mod put;
 pub mod query;
 pub mod scan;
+pub mod table;
 pub mod transact_get;
 pub mod transact_write;
 pub mod update;
 
+pub use transact_get::{ReadTxOutput, TransactGetItemBuilder, TransactGetItems, TransactGetPush};
 pub use transact_write::*;
@@ -1,8 +1,8 @@
 #[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
-use crate::ConsumedCapacity;
+use crate::{ConsumedCapacity, ItemCollectionMetrics};
 
 #[cfg(feature = "aws-sdk")]
-use crate::aws_sdk::types::ConsumedCapacity;
+use crate::aws_sdk::types::{ConsumedCapacity, ItemCollectionMetrics};
 
 // See. https://github.com/rusoto/rusoto/blob/cf22a4348ae717a20760bb9934cfd118ddb4437e/rusoto/services/dynamodb/src/generated.rs#L1168
 #[derive(Default, Debug, Clone, PartialEq)]
@@ -10,7 +10,11 @@ use crate::aws_sdk::types::ConsumedCapacity;
     any(feature = "rusoto", feature = "rusoto_rustls"),
     derive(serde::Deserialize, serde::Serialize)
 )]
-pub struct PutOutput<T> {
+pub struct PutOutput<T, O> {
     pub consumed_capacity: Option<ConsumedCapacity>,
     pub item: T,
+    /// The item replaced by the put, set only with `return_old()`.
+    pub old_item: Option<O>,
+    /// Set only with `return_item_collection_metrics(ReturnItemCollectionMetrics::Size)`.
+    pub item_collection_metrics: Option<ItemCollectionMetrics>,
 }
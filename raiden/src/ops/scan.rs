@@ -17,3 +17,81 @@ pub struct ScanOutput<T> {
     pub last_evaluated_key: Option<::std::collections::HashMap<String, AttributeValue>>,
     pub scanned_count: Option<i64>,
 }
+
+/// Position of a single segment of a parallel scan.
+///
+/// The segments of a `ParallelScanOutput` can be stored and passed back
+/// to `resume` to continue an interrupted scan.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScanSegment {
+    pub segment: i64,
+    pub total_segments: i64,
+    pub state: ScanSegmentState,
+}
+
+/// How far a segment of a parallel scan has been scanned.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ScanSegmentState {
+    /// The segment has not been scanned yet.
+    Pending,
+    /// The segment has been scanned up to the token.
+    InProgress(crate::NextToken),
+    /// The segment has been scanned to the end.
+    Done,
+}
+
+impl ScanSegment {
+    pub fn is_completed(&self) -> bool {
+        self.state == ScanSegmentState::Done
+    }
+
+    /// The token to continue the segment from, if it has been scanned partially.
+    pub fn next_token(&self) -> Option<&crate::NextToken> {
+        match &self.state {
+            ScanSegmentState::InProgress(token) => Some(token),
+            ScanSegmentState::Pending | ScanSegmentState::Done => None,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(
+    any(feature = "rusoto", feature = "rusoto_rustls"),
+    derive(serde::Deserialize, serde::Serialize)
+)]
+pub struct ParallelScanOutput<T> {
+    /// The sum of the capacity consumed by every segment.
+    pub consumed_capacity: Option<ConsumedCapacity>,
+    pub items: Vec<T>,
+    pub count: Option<i64>,
+    pub scanned_count: Option<i64>,
+    pub segments: Vec<ScanSegment>,
+}
+
+/// A page of a parallel scan along with the position of its segment after the page.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    any(feature = "rusoto", feature = "rusoto_rustls"),
+    derive(serde::Deserialize, serde::Serialize)
+)]
+pub struct ParallelScanPage<T> {
+    pub segment: ScanSegment,
+    pub output: ScanOutput<T>,
+}
+
+/// The error of a parallel scan along with what the segments scanned before it.
+///
+/// `output` holds the items of the segments which finished before the failure, and the
+/// position of every segment, so it can be passed back to `resume` to retry the rest.
+#[derive(thiserror::Error, Debug)]
+#[error("{source}")]
+pub struct ParallelScanError<T> {
+    pub source: crate::RaidenError,
+    pub output: ParallelScanOutput<T>,
+}
+
+impl<T> From<ParallelScanError<T>> for crate::RaidenError {
+    fn from(error: ParallelScanError<T>) -> Self {
+        error.source
+    }
+}
@@ -0,0 +1,15 @@
+#[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
+use crate::TableDescription;
+
+#[cfg(feature = "aws-sdk")]
+use crate::aws_sdk::types::TableDescription;
+
+// Returned by `create_table`, `delete_table` and `describe_table`.
+#[derive(Default, Debug, Clone, PartialEq)]
+#[cfg_attr(
+    any(feature = "rusoto", feature = "rusoto_rustls"),
+    derive(serde::Deserialize, serde::Serialize)
+)]
+pub struct TableOutput {
+    pub table_description: Option<TableDescription>,
+}
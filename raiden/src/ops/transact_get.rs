@@ -1,8 +1,10 @@
 #[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
-use crate::ConsumedCapacity;
+use crate::{ConsumedCapacity, Get};
 
 #[cfg(feature = "aws-sdk")]
-use crate::aws_sdk::types::ConsumedCapacity;
+use crate::aws_sdk::types::{ConsumedCapacity, Get};
+
+use crate::{AttributeValues, RaidenError, RaidenItem};
 
 #[derive(Default, Debug, Clone, PartialEq)]
 #[cfg_attr(
@@ -13,3 +15,66 @@ pub struct TransactGetOutput<T> {
     pub consumed_capacity: Option<Vec<ConsumedCapacity>>,
     pub items: Vec<Option<T>>,
 }
+
+/// The result of a `ReadTx`, holding one `Option` per get in the order they were added.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ReadTxOutput<T> {
+    pub consumed_capacity: Option<Vec<ConsumedCapacity>>,
+    pub items: T,
+}
+
+pub trait TransactGetItemBuilder {
+    type Item: RaidenItem;
+
+    fn build(self) -> Get;
+}
+
+/// A tuple of the item types read by a `ReadTx`.
+pub trait TransactGetItems {
+    type Output;
+
+    #[allow(clippy::result_large_err)]
+    fn decode(items: Vec<Option<AttributeValues>>) -> Result<Self::Output, RaidenError>;
+}
+
+/// Appends the item type `I` to a tuple of item types.
+pub trait TransactGetPush<I> {
+    type Output;
+}
+
+macro_rules! impl_transact_get_items {
+    ($($item:ident),*) => {
+        impl<$($item: RaidenItem),*> TransactGetItems for ($($item,)*) {
+            type Output = ($(Option<$item>,)*);
+
+            #[allow(unused_mut, unused_variables, clippy::unused_unit)]
+            fn decode(items: Vec<Option<AttributeValues>>) -> Result<Self::Output, RaidenError> {
+                let mut items = items.into_iter();
+                Ok(($(
+                    match items.next().flatten() {
+                        Some(item) => Some(<$item as RaidenItem>::from_item(item)?),
+                        None => None,
+                    },
+                )*))
+            }
+        }
+
+        impl<$($item,)* Next> TransactGetPush<Next> for ($($item,)*) {
+            type Output = ($($item,)* Next,);
+        }
+    };
+}
+
+impl_transact_get_items!();
+impl_transact_get_items!(A);
+impl_transact_get_items!(A, B);
+impl_transact_get_items!(A, B, C);
+impl_transact_get_items!(A, B, C, D);
+impl_transact_get_items!(A, B, C, D, E);
+impl_transact_get_items!(A, B, C, D, E, F);
+impl_transact_get_items!(A, B, C, D, E, F, G);
+impl_transact_get_items!(A, B, C, D, E, F, G, H);
+impl_transact_get_items!(A, B, C, D, E, F, G, H, I);
+impl_transact_get_items!(A, B, C, D, E, F, G, H, I, J);
+impl_transact_get_items!(A, B, C, D, E, F, G, H, I, J, K);
+impl_transact_get_items!(A, B, C, D, E, F, G, H, I, J, K, L);
@@ -1,11 +1,25 @@
 #[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
-use crate::{ConditionCheck, Delete, Put, Update};
+use crate::{ConditionCheck, ConsumedCapacity, Delete, ItemCollectionMetrics, Put, Update};
 
 #[cfg(feature = "aws-sdk")]
-use crate::aws_sdk::types::{ConditionCheck, Delete, Put, Update};
+use crate::aws_sdk::types::{
+    ConditionCheck, ConsumedCapacity, Delete, ItemCollectionMetrics, Put, Update,
+};
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct TransactWriteOutput {
+    pub consumed_capacity: Option<Vec<ConsumedCapacity>>,
+    pub item_collection_metrics:
+        Option<std::collections::HashMap<String, Vec<ItemCollectionMetrics>>>,
+}
 
 pub trait TransactWritePutBuilder {
     fn build(self) -> Put;
+
+    /// Takes the error found while building the item, which fails the transaction on `run()`.
+    fn take_error(&mut self) -> Option<crate::RaidenError> {
+        None
+    }
 }
 
 pub trait TransactWriteUpdateBuilder {
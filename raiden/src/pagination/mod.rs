@@ -0,0 +1,115 @@
+mod parallel;
+
+pub use parallel::*;
+
+use futures::{Stream, TryStreamExt};
+
+use crate::{AttributeValues, NextToken, RaidenError};
+
+/// A single page returned by a paginated operation such as `query` or `scan`.
+pub trait Page {
+    type Item;
+
+    fn scanned_count(&self) -> Option<i64>;
+
+    #[allow(clippy::result_large_err)]
+    fn last_evaluated_key(&self) -> Result<Option<AttributeValues>, RaidenError>;
+
+    fn into_items(self) -> Vec<Self::Item>;
+}
+
+impl<T> Page for crate::query::QueryOutput<T> {
+    type Item = T;
+
+    fn scanned_count(&self) -> Option<i64> {
+        self.scanned_count
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn last_evaluated_key(&self) -> Result<Option<AttributeValues>, RaidenError> {
+        self.next_token
+            .clone()
+            .map(|token| token.into_attr_values())
+            .transpose()
+    }
+
+    fn into_items(self) -> Vec<T> {
+        self.items
+    }
+}
+
+impl<T> Page for crate::scan::ScanOutput<T> {
+    type Item = T;
+
+    fn scanned_count(&self) -> Option<i64> {
+        self.scanned_count
+    }
+
+    fn last_evaluated_key(&self) -> Result<Option<AttributeValues>, RaidenError> {
+        Ok(self.last_evaluated_key.clone())
+    }
+
+    fn into_items(self) -> Vec<T> {
+        self.items
+    }
+}
+
+enum Cursor {
+    Start(Option<NextToken>),
+    Next(AttributeValues),
+}
+
+/// Lazily follows `LastEvaluatedKey` and yields each page as it is fetched.
+///
+/// `fetch` receives the exclusive start key and the remaining limit of the next page.
+/// When `limit` is given, pages are requested until that many items have been evaluated.
+pub fn paginate<'a, P, F, Fut>(
+    next_token: Option<NextToken>,
+    limit: Option<i64>,
+    fetch: F,
+) -> impl Stream<Item = Result<P, RaidenError>> + 'a
+where
+    P: Page + 'a,
+    F: Fn(Option<AttributeValues>, Option<i64>) -> Fut + 'a,
+    Fut: std::future::Future<Output = Result<P, RaidenError>> + 'a,
+{
+    futures::stream::try_unfold(
+        Some((Cursor::Start(next_token), limit, fetch)),
+        |state| async move {
+            let Some((cursor, limit, fetch)) = state else {
+                return Ok(None);
+            };
+
+            let exclusive_start_key = match cursor {
+                Cursor::Start(Some(token)) => Some(token.into_attr_values()?),
+                Cursor::Start(None) => None,
+                Cursor::Next(key) => Some(key),
+            };
+
+            let page = fetch(exclusive_start_key, limit).await?;
+            let limit = limit.map(|limit| limit - page.scanned_count().unwrap_or(0));
+
+            let next = match page.last_evaluated_key()? {
+                Some(key) if limit.is_none_or(|limit| limit > 0) => {
+                    Some((Cursor::Next(key), limit, fetch))
+                }
+                _ => None,
+            };
+
+            Ok(Some((page, next)))
+        },
+    )
+}
+
+/// Flattens a stream of pages into a stream of items.
+pub fn items<'a, P>(
+    pages: impl Stream<Item = Result<P, RaidenError>> + 'a,
+) -> impl Stream<Item = Result<P::Item, RaidenError>> + 'a
+where
+    P: Page + 'a,
+    P::Item: 'a,
+{
+    pages
+        .map_ok(|page| futures::stream::iter(page.into_items().into_iter().map(Ok)))
+        .try_flatten()
+}
@@ -0,0 +1,204 @@
+use futures::{Stream, StreamExt, TryStreamExt};
+
+use crate::scan::{
+    ParallelScanError, ParallelScanOutput, ParallelScanPage, ScanOutput, ScanSegment,
+    ScanSegmentState,
+};
+use crate::{NextToken, RaidenError};
+
+fn advance<T>(segment: &ScanSegment, output: &ScanOutput<T>) -> ScanSegment {
+    let state = match &output.last_evaluated_key {
+        Some(key) => ScanSegmentState::InProgress(NextToken::from_attr(key)),
+        None => ScanSegmentState::Done,
+    };
+    ScanSegment {
+        state,
+        ..segment.clone()
+    }
+}
+
+// DynamoDB requires `TotalSegments >= 1`, and a scan without segments would look like an
+// empty table.
+#[allow(clippy::result_large_err)]
+fn check_segments(segments: &[ScanSegment]) -> Result<(), RaidenError> {
+    if segments.is_empty() {
+        return Err(RaidenError::Validation(
+            "a parallel scan needs at least one segment".to_owned(),
+        ));
+    }
+    Ok(())
+}
+
+/// Runs every segment with at most `concurrency` segments in flight and merges
+/// the results in segment order.
+///
+/// Segments which are already done are not run, but are returned along with the others
+/// so that the output can be passed back to `resume`. It fails if there is no segment.
+///
+/// It stops at the first failed segment. The error carries the output of the segments
+/// which succeeded before it, and the segments which did not finish keep their position.
+pub async fn run_segments<T, F, Fut>(
+    segments: Vec<ScanSegment>,
+    concurrency: usize,
+    mut run: F,
+) -> Result<ParallelScanOutput<T>, ParallelScanError<T>>
+where
+    F: FnMut(ScanSegment) -> Fut,
+    Fut: std::future::Future<Output = Result<ScanOutput<T>, RaidenError>>,
+{
+    let mut merged = ParallelScanOutput {
+        consumed_capacity: None,
+        items: vec![],
+        count: None,
+        scanned_count: None,
+        segments: vec![],
+    };
+    if let Err(source) = check_segments(&segments) {
+        return Err(ParallelScanError {
+            source,
+            output: merged,
+        });
+    }
+    let (done, segments): (Vec<_>, Vec<_>) =
+        segments.into_iter().partition(ScanSegment::is_completed);
+    let runs = segments.clone().into_iter().map(|segment| {
+        let fut = run(segment.clone());
+        async move { fut.await.map(|output| (advance(&segment, &output), output)) }
+    });
+
+    // Dropping the stream on a failure cancels the segments which are still in flight.
+    let mut results: Vec<(ScanSegment, ScanOutput<T>)> = vec![];
+    let mut failure = None;
+    let mut stream = futures::stream::iter(runs).buffer_unordered(concurrency.max(1));
+    while let Some(result) = stream.next().await {
+        match result {
+            Ok(result) => results.push(result),
+            Err(e) => {
+                failure = Some(e);
+                break;
+            }
+        }
+    }
+    drop(stream);
+    results.sort_by_key(|(segment, _)| segment.segment);
+
+    merged.segments = done;
+    merged
+        .segments
+        .extend(segments.into_iter().filter(|segment| {
+            !results
+                .iter()
+                .any(|(scanned, _)| scanned.segment == segment.segment)
+        }));
+    for (segment, output) in results {
+        merged.consumed_capacity = crate::capacity::add_consumed_capacity(
+            merged.consumed_capacity,
+            output.consumed_capacity,
+        );
+        merged.count = Some(merged.count.unwrap_or(0) + output.count.unwrap_or(0));
+        merged.scanned_count =
+            Some(merged.scanned_count.unwrap_or(0) + output.scanned_count.unwrap_or(0));
+        merged.items.extend(output.items);
+        merged.segments.push(segment);
+    }
+    merged.segments.sort_by_key(|segment| segment.segment);
+
+    match failure {
+        Some(source) => Err(ParallelScanError {
+            source,
+            output: merged,
+        }),
+        None => Ok(merged),
+    }
+}
+
+/// Interleaves the page streams of every segment which is not done yet, polling at most
+/// `concurrency` segments at the same time. The stream only yields an error if there is no segment.
+pub fn segment_pages<'a, T, F, S>(
+    segments: Vec<ScanSegment>,
+    concurrency: usize,
+    mut pages: F,
+) -> impl Stream<Item = Result<ParallelScanPage<T>, RaidenError>> + 'a
+where
+    T: 'a,
+    F: FnMut(ScanSegment) -> S,
+    S: Stream<Item = Result<ScanOutput<T>, RaidenError>> + 'a,
+{
+    if let Err(e) = check_segments(&segments) {
+        return futures::stream::once(async move { Err(e) }).left_stream();
+    }
+    let streams: Vec<_> = segments
+        .into_iter()
+        .filter(|segment| !segment.is_completed())
+        .map(|segment| {
+            Box::pin(
+                pages(segment.clone()).map_ok(move |output| ParallelScanPage {
+                    segment: advance(&segment, &output),
+                    output,
+                }),
+            )
+        })
+        .collect();
+
+    futures::stream::iter(streams)
+        .flatten_unordered(concurrency.max(1))
+        .right_stream()
+}
+
+/// Flattens a stream of parallel scan pages into a stream of items.
+pub fn segment_items<'a, T: 'a>(
+    pages: impl Stream<Item = Result<ParallelScanPage<T>, RaidenError>> + 'a,
+) -> impl Stream<Item = Result<T, RaidenError>> + 'a {
+    pages
+        .map_ok(|page| futures::stream::iter(page.output.items.into_iter().map(Ok)))
+        .try_flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(segment: i64, state: ScanSegmentState) -> ScanSegment {
+        ScanSegment {
+            segment,
+            total_segments: 3,
+            state,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_segments_with_failed_segment() {
+        let segments = vec![
+            segment(0, ScanSegmentState::Pending),
+            segment(1, ScanSegmentState::Pending),
+            segment(2, ScanSegmentState::Pending),
+        ];
+        // With a concurrency of 1, the last segment is never started.
+        let res = run_segments(segments, 1, |segment| async move {
+            match segment.segment {
+                1 => Err(RaidenError::InternalServerError("failed".to_owned())),
+                n => Ok(ScanOutput {
+                    consumed_capacity: None,
+                    items: vec![n],
+                    count: Some(1),
+                    last_evaluated_key: None,
+                    scanned_count: Some(1),
+                }),
+            }
+        })
+        .await;
+
+        let err = res.unwrap_err();
+        assert!(matches!(err.source, RaidenError::InternalServerError(_)));
+        assert_eq!(err.output.items, vec![0]);
+        assert_eq!(err.output.count, Some(1));
+        assert_eq!(
+            err.output.segments,
+            vec![
+                segment(0, ScanSegmentState::Done),
+                segment(1, ScanSegmentState::Pending),
+                segment(2, ScanSegmentState::Pending),
+            ]
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{AttributeNames, IntoAttrName};
+use crate::{AttributeNames, IntoAttrName, Placeholder};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum AttrPathSegment {
@@ -60,16 +60,17 @@ impl AttrPath {
         self
     }
 
-    pub(crate) fn expression(&self) -> String {
+    /// Returns the expression for the path, e.g. `#profile.#level[0]`.
+    pub fn expression(&self) -> String {
         let mut expression = String::new();
 
-        for (index, segment) in self.segments.iter().enumerate() {
+        for segment in self.segments.iter() {
             match segment {
                 AttrPathSegment::Name(name) => {
                     if !expression.is_empty() {
                         expression.push('.');
                     }
-                    expression.push_str(&placeholder_for(name, index));
+                    expression.push_str(&name_placeholder(name));
                 }
                 AttrPathSegment::Index(index) => {
                     expression.push('[');
@@ -82,12 +83,13 @@ impl AttrPath {
         expression
     }
 
-    pub(crate) fn attribute_names(&self) -> AttributeNames {
+    /// Returns the placeholders used by [`AttrPath::expression`] with the names they stand for.
+    pub fn attribute_names(&self) -> AttributeNames {
         let mut attribute_names = AttributeNames::new();
 
-        for (index, segment) in self.segments.iter().enumerate() {
+        for segment in self.segments.iter() {
             if let AttrPathSegment::Name(name) = segment {
-                attribute_names.insert(placeholder_for(name, index), name.clone());
+                attribute_names.insert(name_placeholder(name), name.clone());
             }
         }
 
@@ -101,11 +103,20 @@ impl std::fmt::Display for AttrPath {
     }
 }
 
-fn placeholder_for(name: &str, index: usize) -> String {
-    if name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+/// Returns the placeholder standing for the attribute `name`, e.g. `#profile`.
+///
+/// A name which can not be written after `#` as is, such as `first-name`, is
+/// hex-encoded behind `#_` instead, so two different names never share a
+/// placeholder wherever they appear in a request.
+pub fn name_placeholder(name: &str) -> Placeholder {
+    let mut chars = name.chars();
+    let plain = chars.next().is_some_and(|c| c.is_ascii_alphanumeric())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if plain {
         format!("#{name}")
     } else {
-        format!("#path{index}")
+        let encoded: String = name.bytes().map(|b| format!("{b:02x}")).collect();
+        format!("#_{encoded}")
     }
 }
 
@@ -0,0 +1,49 @@
+use crate::{AttributeValue, AttributeValues, Placeholder};
+
+/// Allocates the `:value{n}` placeholders of a single request.
+///
+/// Placeholders are numbered from zero in the order they are allocated, and an
+/// equal value reuses the placeholder it got first, so the same request always
+/// renders the same expressions whatever else runs concurrently.
+#[derive(Debug, Clone, Default)]
+pub struct Placeholders {
+    values: Vec<(Placeholder, AttributeValue)>,
+}
+
+impl Placeholders {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the placeholder standing for `value`.
+    pub fn value(&mut self, value: AttributeValue) -> Placeholder {
+        if let Some((placeholder, _)) = self.values.iter().find(|(_, v)| *v == value) {
+            return placeholder.clone();
+        }
+        let placeholder = format!(":value{}", self.values.len());
+        self.values.push((placeholder.clone(), value));
+        placeholder
+    }
+
+    /// Like [`Placeholders::value`], and inserts the value into `values` as well.
+    pub fn bind(&mut self, value: AttributeValue, values: &mut AttributeValues) -> Placeholder {
+        let placeholder = self.value(value.clone());
+        values.insert(placeholder.clone(), value);
+        placeholder
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IntoAttribute;
+
+    #[test]
+    fn test_placeholders_are_deduplicated() {
+        let mut placeholders = Placeholders::new();
+        assert_eq!(placeholders.value(1.into_attr()), ":value0");
+        assert_eq!(placeholders.value("1".into_attr()), ":value1");
+        assert_eq!(placeholders.value(1.into_attr()), ":value0");
+        assert_eq!(Placeholders::new().value(2.into_attr()), ":value0");
+    }
+}
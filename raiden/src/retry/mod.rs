@@ -1,8 +1,84 @@
 use again::Condition;
-pub use again::RetryPolicy;
-use std::time::Duration;
+use std::{
+    future::Future,
+    sync::{Arc, Mutex, PoisonError},
+    time::Duration,
+};
 
 use super::RaidenError;
+use crate::{observer::Attempt, SharedBackend};
+
+pub use again::RetryPolicy;
+
+/// Runs `task` with the retries of `policy` while `condition` holds.
+///
+/// Every attempt gets its own backend, so that the [`Observer`](crate::Observer)s of `client`
+/// learn which attempt a request belongs to and whether it is going to be retried.
+pub async fn retry_with_backend<T, F, Fut, C>(
+    policy: Policy,
+    client: &SharedBackend,
+    condition: C,
+    mut task: F,
+) -> Result<T, RaidenError>
+where
+    F: FnMut(SharedBackend) -> Fut,
+    Fut: Future<Output = Result<T, RaidenError>>,
+    C: Condition<RaidenError>,
+{
+    let max_retries = policy.max_retries();
+    let retry_policy: RetryPolicy = policy.into();
+    let last: Mutex<Option<Arc<Attempt>>> = Mutex::new(None);
+    let last = &last;
+    retry_policy
+        .retry_if(
+            move || {
+                let mut last = last.lock().unwrap_or_else(PoisonError::into_inner);
+                let number = last.as_ref().map_or(1, |attempt| attempt.number() + 1);
+                let attempt = Arc::new(Attempt::new(number));
+                *last = Some(attempt.clone());
+                let fut = task(
+                    client
+                        .for_attempt(&attempt)
+                        .unwrap_or_else(|| client.clone()),
+                );
+                async move {
+                    let res = fut.await;
+                    if res.is_ok() {
+                        attempt.report(false);
+                    }
+                    res
+                }
+            },
+            ObservedCondition {
+                condition,
+                last,
+                max_retries,
+            },
+        )
+        .await
+}
+
+// Reports a failed attempt once it is known whether it is going to be retried.
+struct ObservedCondition<'a, C> {
+    condition: C,
+    last: &'a Mutex<Option<Arc<Attempt>>>,
+    max_retries: usize,
+}
+
+impl<C: Condition<RaidenError>> Condition<RaidenError> for ObservedCondition<'_, C> {
+    fn is_retryable(&mut self, error: &RaidenError) -> bool {
+        let retryable = self.condition.is_retryable(error);
+        if let Some(attempt) = self
+            .last
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()
+        {
+            attempt.report(retryable && attempt.number() <= self.max_retries);
+        }
+        retryable
+    }
+}
 
 #[derive(Clone, Copy, PartialEq, Debug)]
 pub enum Policy {
@@ -18,6 +94,18 @@ impl Default for Policy {
     }
 }
 
+impl Policy {
+    /// The number of retries after the first attempt.
+    pub fn max_retries(&self) -> usize {
+        match self {
+            Policy::None => 0,
+            Policy::Limit(times) | Policy::Pause(times, _) | Policy::Exponential(times, _) => {
+                *times
+            }
+        }
+    }
+}
+
 #[allow(clippy::from_over_into)]
 impl Into<RetryPolicy> for Policy {
     fn into(self) -> RetryPolicy {
@@ -0,0 +1,1025 @@
+use crate::{
+    backend::*, AttributeType, DynamoDb, DynamoDbClient, ReturnConsumedCapacity,
+    ReturnItemCollectionMetrics,
+};
+
+macro_rules! backend {
+    ($($operation: ident => $request: ident($input: ident), $response: ident;)*) => {
+        impl DynamoBackend for DynamoDbClient {
+            $(
+                fn $operation(&self, request: $request) -> BackendFuture<'_, $response> {
+                    Box::pin(async move {
+                        let input = crate::$input::from(request);
+                        Ok(DynamoDb::$operation(self, input).await?.into())
+                    })
+                }
+            )*
+        }
+    };
+}
+
+backend! {
+    get_item => GetItemRequest(GetItemInput), GetItemResponse;
+    put_item => PutItemRequest(PutItemInput), PutItemResponse;
+    update_item => UpdateItemRequest(UpdateItemInput), UpdateItemResponse;
+    delete_item => DeleteItemRequest(DeleteItemInput), DeleteItemResponse;
+    query => QueryRequest(QueryInput), QueryResponse;
+    scan => ScanRequest(ScanInput), ScanResponse;
+    batch_get_item => BatchGetItemRequest(BatchGetItemInput), BatchGetItemResponse;
+    batch_write_item => BatchWriteItemRequest(BatchWriteItemInput), BatchWriteItemResponse;
+    transact_get_items => TransactGetItemsRequest(TransactGetItemsInput), TransactGetItemsResponse;
+    transact_write_items => TransactWriteItemsRequest(TransactWriteItemsInput), TransactWriteItemsResponse;
+    create_table => CreateTableRequest(CreateTableInput), CreateTableResponse;
+    delete_table => DeleteTableRequest(DeleteTableInput), DeleteTableResponse;
+    describe_table => DescribeTableRequest(DescribeTableInput), DescribeTableResponse;
+    update_time_to_live => UpdateTimeToLiveRequest(UpdateTimeToLiveInput), UpdateTimeToLiveResponse;
+}
+
+// rusoto takes the enums of the API as strings, and unknown values are read as the default of
+// the API.
+
+fn return_consumed_capacity(value: Option<String>) -> Option<ReturnConsumedCapacity> {
+    value.map(|value| match value.as_str() {
+        "INDEXES" => ReturnConsumedCapacity::Indexes,
+        "TOTAL" => ReturnConsumedCapacity::Total,
+        _ => ReturnConsumedCapacity::None,
+    })
+}
+
+fn return_item_collection_metrics(value: Option<String>) -> Option<ReturnItemCollectionMetrics> {
+    value.map(|value| match value.as_str() {
+        "SIZE" => ReturnItemCollectionMetrics::Size,
+        _ => ReturnItemCollectionMetrics::None,
+    })
+}
+
+fn return_values(value: Option<String>) -> Option<ReturnValues> {
+    value.map(|value| match value.as_str() {
+        "ALL_OLD" => ReturnValues::AllOld,
+        "UPDATED_OLD" => ReturnValues::UpdatedOld,
+        "ALL_NEW" => ReturnValues::AllNew,
+        "UPDATED_NEW" => ReturnValues::UpdatedNew,
+        _ => ReturnValues::None,
+    })
+}
+
+fn return_old_on_condition_failure(value: Option<String>) -> bool {
+    value.as_deref() == Some("ALL_OLD")
+}
+
+fn return_values_on_condition_check_failure(return_old: bool) -> Option<String> {
+    return_old.then(|| "ALL_OLD".to_owned())
+}
+
+fn to_string<T>(value: Option<T>, as_str: fn(&T) -> &'static str) -> Option<String> {
+    value.as_ref().map(|value| as_str(value).to_owned())
+}
+
+// The generated builders hold the inputs of rusoto, which are converted into requests when they
+// are sent.
+
+impl From<crate::GetItemInput> for GetItemRequest {
+    fn from(input: crate::GetItemInput) -> Self {
+        Self {
+            table_name: input.table_name,
+            key: input.key,
+            projection_expression: input.projection_expression,
+            expression_attribute_names: input.expression_attribute_names,
+            consistent_read: input.consistent_read,
+            return_consumed_capacity: return_consumed_capacity(input.return_consumed_capacity),
+        }
+    }
+}
+
+impl From<GetItemRequest> for crate::GetItemInput {
+    fn from(request: GetItemRequest) -> Self {
+        Self {
+            table_name: request.table_name,
+            key: request.key,
+            projection_expression: request.projection_expression,
+            expression_attribute_names: request.expression_attribute_names,
+            consistent_read: request.consistent_read,
+            return_consumed_capacity: to_string(
+                request.return_consumed_capacity,
+                ReturnConsumedCapacity::as_str,
+            ),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<crate::GetItemOutput> for GetItemResponse {
+    fn from(output: crate::GetItemOutput) -> Self {
+        Self {
+            item: output.item,
+            consumed_capacity: output.consumed_capacity,
+        }
+    }
+}
+
+impl From<crate::PutItemInput> for PutItemRequest {
+    fn from(input: crate::PutItemInput) -> Self {
+        Self {
+            table_name: input.table_name,
+            item: input.item,
+            condition_expression: input.condition_expression,
+            expression_attribute_names: input.expression_attribute_names,
+            expression_attribute_values: input.expression_attribute_values,
+            return_values: return_values(input.return_values),
+            return_consumed_capacity: return_consumed_capacity(input.return_consumed_capacity),
+            return_item_collection_metrics: return_item_collection_metrics(
+                input.return_item_collection_metrics,
+            ),
+            return_old_on_condition_failure: false,
+        }
+    }
+}
+
+impl From<PutItemRequest> for crate::PutItemInput {
+    fn from(request: PutItemRequest) -> Self {
+        Self {
+            table_name: request.table_name,
+            item: request.item,
+            condition_expression: request.condition_expression,
+            expression_attribute_names: request.expression_attribute_names,
+            expression_attribute_values: request.expression_attribute_values,
+            return_values: to_string(request.return_values, ReturnValues::as_str),
+            return_consumed_capacity: to_string(
+                request.return_consumed_capacity,
+                ReturnConsumedCapacity::as_str,
+            ),
+            return_item_collection_metrics: to_string(
+                request.return_item_collection_metrics,
+                ReturnItemCollectionMetrics::as_str,
+            ),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<crate::PutItemOutput> for PutItemResponse {
+    fn from(output: crate::PutItemOutput) -> Self {
+        Self {
+            attributes: output.attributes,
+            consumed_capacity: output.consumed_capacity,
+            item_collection_metrics: output.item_collection_metrics,
+        }
+    }
+}
+
+impl From<crate::UpdateItemInput> for UpdateItemRequest {
+    fn from(input: crate::UpdateItemInput) -> Self {
+        Self {
+            table_name: input.table_name,
+            key: input.key,
+            update_expression: input.update_expression,
+            condition_expression: input.condition_expression,
+            expression_attribute_names: input.expression_attribute_names,
+            expression_attribute_values: input.expression_attribute_values,
+            return_values: return_values(input.return_values),
+            return_consumed_capacity: return_consumed_capacity(input.return_consumed_capacity),
+            return_item_collection_metrics: return_item_collection_metrics(
+                input.return_item_collection_metrics,
+            ),
+            return_old_on_condition_failure: false,
+        }
+    }
+}
+
+impl From<UpdateItemRequest> for crate::UpdateItemInput {
+    fn from(request: UpdateItemRequest) -> Self {
+        Self {
+            table_name: request.table_name,
+            key: request.key,
+            update_expression: request.update_expression,
+            condition_expression: request.condition_expression,
+            expression_attribute_names: request.expression_attribute_names,
+            expression_attribute_values: request.expression_attribute_values,
+            return_values: to_string(request.return_values, ReturnValues::as_str),
+            return_consumed_capacity: to_string(
+                request.return_consumed_capacity,
+                ReturnConsumedCapacity::as_str,
+            ),
+            return_item_collection_metrics: to_string(
+                request.return_item_collection_metrics,
+                ReturnItemCollectionMetrics::as_str,
+            ),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<crate::UpdateItemOutput> for UpdateItemResponse {
+    fn from(output: crate::UpdateItemOutput) -> Self {
+        Self {
+            attributes: output.attributes,
+            consumed_capacity: output.consumed_capacity,
+            item_collection_metrics: output.item_collection_metrics,
+        }
+    }
+}
+
+impl From<crate::DeleteItemInput> for DeleteItemRequest {
+    fn from(input: crate::DeleteItemInput) -> Self {
+        Self {
+            table_name: input.table_name,
+            key: input.key,
+            condition_expression: input.condition_expression,
+            expression_attribute_names: input.expression_attribute_names,
+            expression_attribute_values: input.expression_attribute_values,
+            return_values: return_values(input.return_values),
+            return_consumed_capacity: return_consumed_capacity(input.return_consumed_capacity),
+            return_item_collection_metrics: return_item_collection_metrics(
+                input.return_item_collection_metrics,
+            ),
+            return_old_on_condition_failure: false,
+        }
+    }
+}
+
+impl From<DeleteItemRequest> for crate::DeleteItemInput {
+    fn from(request: DeleteItemRequest) -> Self {
+        Self {
+            table_name: request.table_name,
+            key: request.key,
+            condition_expression: request.condition_expression,
+            expression_attribute_names: request.expression_attribute_names,
+            expression_attribute_values: request.expression_attribute_values,
+            return_values: to_string(request.return_values, ReturnValues::as_str),
+            return_consumed_capacity: to_string(
+                request.return_consumed_capacity,
+                ReturnConsumedCapacity::as_str,
+            ),
+            return_item_collection_metrics: to_string(
+                request.return_item_collection_metrics,
+                ReturnItemCollectionMetrics::as_str,
+            ),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<crate::DeleteItemOutput> for DeleteItemResponse {
+    fn from(output: crate::DeleteItemOutput) -> Self {
+        Self {
+            attributes: output.attributes,
+            consumed_capacity: output.consumed_capacity,
+            item_collection_metrics: output.item_collection_metrics,
+        }
+    }
+}
+
+impl From<crate::QueryInput> for QueryRequest {
+    fn from(input: crate::QueryInput) -> Self {
+        Self {
+            table_name: input.table_name,
+            index_name: input.index_name,
+            key_condition_expression: input.key_condition_expression,
+            filter_expression: input.filter_expression,
+            projection_expression: input.projection_expression,
+            expression_attribute_names: input.expression_attribute_names,
+            expression_attribute_values: input.expression_attribute_values,
+            exclusive_start_key: input.exclusive_start_key,
+            limit: input.limit,
+            scan_index_forward: input.scan_index_forward,
+            consistent_read: input.consistent_read,
+            return_consumed_capacity: return_consumed_capacity(input.return_consumed_capacity),
+        }
+    }
+}
+
+impl From<QueryRequest> for crate::QueryInput {
+    fn from(request: QueryRequest) -> Self {
+        Self {
+            table_name: request.table_name,
+            index_name: request.index_name,
+            key_condition_expression: request.key_condition_expression,
+            filter_expression: request.filter_expression,
+            projection_expression: request.projection_expression,
+            expression_attribute_names: request.expression_attribute_names,
+            expression_attribute_values: request.expression_attribute_values,
+            exclusive_start_key: request.exclusive_start_key,
+            limit: request.limit,
+            scan_index_forward: request.scan_index_forward,
+            consistent_read: request.consistent_read,
+            return_consumed_capacity: to_string(
+                request.return_consumed_capacity,
+                ReturnConsumedCapacity::as_str,
+            ),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<crate::QueryOutput> for QueryResponse {
+    fn from(output: crate::QueryOutput) -> Self {
+        Self {
+            items: output.items,
+            count: output.count,
+            scanned_count: output.scanned_count,
+            last_evaluated_key: output.last_evaluated_key,
+            consumed_capacity: output.consumed_capacity,
+        }
+    }
+}
+
+impl From<crate::ScanInput> for ScanRequest {
+    fn from(input: crate::ScanInput) -> Self {
+        Self {
+            table_name: input.table_name,
+            index_name: input.index_name,
+            filter_expression: input.filter_expression,
+            projection_expression: input.projection_expression,
+            expression_attribute_names: input.expression_attribute_names,
+            expression_attribute_values: input.expression_attribute_values,
+            exclusive_start_key: input.exclusive_start_key,
+            limit: input.limit,
+            segment: input.segment,
+            total_segments: input.total_segments,
+            consistent_read: input.consistent_read,
+            return_consumed_capacity: return_consumed_capacity(input.return_consumed_capacity),
+        }
+    }
+}
+
+impl From<ScanRequest> for crate::ScanInput {
+    fn from(request: ScanRequest) -> Self {
+        Self {
+            table_name: request.table_name,
+            index_name: request.index_name,
+            filter_expression: request.filter_expression,
+            projection_expression: request.projection_expression,
+            expression_attribute_names: request.expression_attribute_names,
+            expression_attribute_values: request.expression_attribute_values,
+            exclusive_start_key: request.exclusive_start_key,
+            limit: request.limit,
+            segment: request.segment,
+            total_segments: request.total_segments,
+            consistent_read: request.consistent_read,
+            return_consumed_capacity: to_string(
+                request.return_consumed_capacity,
+                ReturnConsumedCapacity::as_str,
+            ),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<crate::ScanOutput> for ScanResponse {
+    fn from(output: crate::ScanOutput) -> Self {
+        Self {
+            items: output.items,
+            count: output.count,
+            scanned_count: output.scanned_count,
+            last_evaluated_key: output.last_evaluated_key,
+            consumed_capacity: output.consumed_capacity,
+        }
+    }
+}
+
+impl From<crate::KeysAndAttributes> for KeysAndAttributes {
+    fn from(keys: crate::KeysAndAttributes) -> Self {
+        Self {
+            keys: keys.keys,
+            projection_expression: keys.projection_expression,
+            expression_attribute_names: keys.expression_attribute_names,
+            consistent_read: keys.consistent_read,
+        }
+    }
+}
+
+impl From<KeysAndAttributes> for crate::KeysAndAttributes {
+    fn from(keys: KeysAndAttributes) -> Self {
+        Self {
+            keys: keys.keys,
+            projection_expression: keys.projection_expression,
+            expression_attribute_names: keys.expression_attribute_names,
+            consistent_read: keys.consistent_read,
+            ..Default::default()
+        }
+    }
+}
+
+impl From<crate::BatchGetItemInput> for BatchGetItemRequest {
+    fn from(input: crate::BatchGetItemInput) -> Self {
+        Self {
+            request_items: input
+                .request_items
+                .into_iter()
+                .map(|(table_name, keys)| (table_name, keys.into()))
+                .collect(),
+            return_consumed_capacity: return_consumed_capacity(input.return_consumed_capacity),
+        }
+    }
+}
+
+impl From<BatchGetItemRequest> for crate::BatchGetItemInput {
+    fn from(request: BatchGetItemRequest) -> Self {
+        Self {
+            request_items: request
+                .request_items
+                .into_iter()
+                .map(|(table_name, keys)| (table_name, keys.into()))
+                .collect(),
+            return_consumed_capacity: to_string(
+                request.return_consumed_capacity,
+                ReturnConsumedCapacity::as_str,
+            ),
+        }
+    }
+}
+
+impl From<crate::BatchGetItemOutput> for BatchGetItemResponse {
+    fn from(output: crate::BatchGetItemOutput) -> Self {
+        Self {
+            responses: output.responses,
+            unprocessed_keys: output.unprocessed_keys.map(|unprocessed_keys| {
+                unprocessed_keys
+                    .into_iter()
+                    .map(|(table_name, keys)| (table_name, keys.into()))
+                    .collect()
+            }),
+            consumed_capacity: output.consumed_capacity,
+        }
+    }
+}
+
+// A write request without a put or a delete is invalid, and is dropped.
+fn write_requests(requests: Vec<crate::WriteRequest>) -> impl Iterator<Item = WriteRequest> {
+    requests.into_iter().filter_map(
+        |request| match (request.put_request, request.delete_request) {
+            (Some(put), _) => Some(WriteRequest::Put { item: put.item }),
+            (None, Some(delete)) => Some(WriteRequest::Delete { key: delete.key }),
+            (None, None) => None,
+        },
+    )
+}
+
+impl From<WriteRequest> for crate::WriteRequest {
+    fn from(request: WriteRequest) -> Self {
+        match request {
+            WriteRequest::Put { item } => Self {
+                put_request: Some(crate::PutRequest { item }),
+                delete_request: None,
+            },
+            WriteRequest::Delete { key } => Self {
+                put_request: None,
+                delete_request: Some(crate::DeleteRequest { key }),
+            },
+        }
+    }
+}
+
+impl From<crate::BatchWriteItemInput> for BatchWriteItemRequest {
+    fn from(input: crate::BatchWriteItemInput) -> Self {
+        Self {
+            request_items: input
+                .request_items
+                .into_iter()
+                .map(|(table_name, requests)| (table_name, write_requests(requests).collect()))
+                .collect(),
+            return_consumed_capacity: return_consumed_capacity(input.return_consumed_capacity),
+            return_item_collection_metrics: return_item_collection_metrics(
+                input.return_item_collection_metrics,
+            ),
+        }
+    }
+}
+
+impl From<BatchWriteItemRequest> for crate::BatchWriteItemInput {
+    fn from(request: BatchWriteItemRequest) -> Self {
+        Self {
+            request_items: request
+                .request_items
+                .into_iter()
+                .map(|(table_name, requests)| {
+                    (table_name, requests.into_iter().map(Into::into).collect())
+                })
+                .collect(),
+            return_consumed_capacity: to_string(
+                request.return_consumed_capacity,
+                ReturnConsumedCapacity::as_str,
+            ),
+            return_item_collection_metrics: to_string(
+                request.return_item_collection_metrics,
+                ReturnItemCollectionMetrics::as_str,
+            ),
+        }
+    }
+}
+
+impl From<crate::BatchWriteItemOutput> for BatchWriteItemResponse {
+    fn from(output: crate::BatchWriteItemOutput) -> Self {
+        Self {
+            unprocessed_items: output.unprocessed_items.map(|unprocessed_items| {
+                unprocessed_items
+                    .into_iter()
+                    .map(|(table_name, requests)| (table_name, write_requests(requests).collect()))
+                    .collect()
+            }),
+            item_collection_metrics: output.item_collection_metrics,
+            consumed_capacity: output.consumed_capacity,
+        }
+    }
+}
+
+impl From<crate::TransactGetItem> for TransactGetItem {
+    fn from(item: crate::TransactGetItem) -> Self {
+        Self {
+            table_name: item.get.table_name,
+            key: item.get.key,
+            projection_expression: item.get.projection_expression,
+            expression_attribute_names: item.get.expression_attribute_names,
+        }
+    }
+}
+
+impl From<TransactGetItem> for crate::TransactGetItem {
+    fn from(item: TransactGetItem) -> Self {
+        Self {
+            get: crate::Get {
+                table_name: item.table_name,
+                key: item.key,
+                projection_expression: item.projection_expression,
+                expression_attribute_names: item.expression_attribute_names,
+            },
+        }
+    }
+}
+
+impl From<crate::TransactGetItemsInput> for TransactGetItemsRequest {
+    fn from(input: crate::TransactGetItemsInput) -> Self {
+        Self {
+            transact_items: input.transact_items.into_iter().map(Into::into).collect(),
+            return_consumed_capacity: return_consumed_capacity(input.return_consumed_capacity),
+        }
+    }
+}
+
+impl From<TransactGetItemsRequest> for crate::TransactGetItemsInput {
+    fn from(request: TransactGetItemsRequest) -> Self {
+        Self {
+            transact_items: request.transact_items.into_iter().map(Into::into).collect(),
+            return_consumed_capacity: to_string(
+                request.return_consumed_capacity,
+                ReturnConsumedCapacity::as_str,
+            ),
+        }
+    }
+}
+
+impl From<crate::TransactGetItemsOutput> for TransactGetItemsResponse {
+    fn from(output: crate::TransactGetItemsOutput) -> Self {
+        Self {
+            responses: output.responses.map(|responses| {
+                responses
+                    .into_iter()
+                    .map(|response| response.item)
+                    .collect()
+            }),
+            consumed_capacity: output.consumed_capacity,
+        }
+    }
+}
+
+// A transact item without an operation is invalid, and is dropped.
+fn transact_write_item(item: crate::TransactWriteItem) -> Option<TransactWriteItem> {
+    if let Some(check) = item.condition_check {
+        return Some(TransactWriteItem::ConditionCheck {
+            table_name: check.table_name,
+            key: check.key,
+            condition_expression: check.condition_expression,
+            expression_attribute_names: check.expression_attribute_names,
+            expression_attribute_values: check.expression_attribute_values,
+            return_old_on_condition_failure: return_old_on_condition_failure(
+                check.return_values_on_condition_check_failure,
+            ),
+        });
+    }
+    if let Some(put) = item.put {
+        return Some(TransactWriteItem::Put {
+            table_name: put.table_name,
+            item: put.item,
+            condition_expression: put.condition_expression,
+            expression_attribute_names: put.expression_attribute_names,
+            expression_attribute_values: put.expression_attribute_values,
+            return_old_on_condition_failure: return_old_on_condition_failure(
+                put.return_values_on_condition_check_failure,
+            ),
+        });
+    }
+    if let Some(delete) = item.delete {
+        return Some(TransactWriteItem::Delete {
+            table_name: delete.table_name,
+            key: delete.key,
+            condition_expression: delete.condition_expression,
+            expression_attribute_names: delete.expression_attribute_names,
+            expression_attribute_values: delete.expression_attribute_values,
+            return_old_on_condition_failure: return_old_on_condition_failure(
+                delete.return_values_on_condition_check_failure,
+            ),
+        });
+    }
+    item.update.map(|update| TransactWriteItem::Update {
+        table_name: update.table_name,
+        key: update.key,
+        update_expression: update.update_expression,
+        condition_expression: update.condition_expression,
+        expression_attribute_names: update.expression_attribute_names,
+        expression_attribute_values: update.expression_attribute_values,
+        return_old_on_condition_failure: return_old_on_condition_failure(
+            update.return_values_on_condition_check_failure,
+        ),
+    })
+}
+
+impl From<TransactWriteItem> for crate::TransactWriteItem {
+    fn from(item: TransactWriteItem) -> Self {
+        match item {
+            TransactWriteItem::ConditionCheck {
+                table_name,
+                key,
+                condition_expression,
+                expression_attribute_names,
+                expression_attribute_values,
+                return_old_on_condition_failure,
+            } => Self {
+                condition_check: Some(crate::ConditionCheck {
+                    table_name,
+                    key,
+                    condition_expression,
+                    expression_attribute_names,
+                    expression_attribute_values,
+                    return_values_on_condition_check_failure:
+                        return_values_on_condition_check_failure(return_old_on_condition_failure),
+                }),
+                ..Default::default()
+            },
+            TransactWriteItem::Put {
+                table_name,
+                item,
+                condition_expression,
+                expression_attribute_names,
+                expression_attribute_values,
+                return_old_on_condition_failure,
+            } => Self {
+                put: Some(crate::Put {
+                    table_name,
+                    item,
+                    condition_expression,
+                    expression_attribute_names,
+                    expression_attribute_values,
+                    return_values_on_condition_check_failure:
+                        return_values_on_condition_check_failure(return_old_on_condition_failure),
+                }),
+                ..Default::default()
+            },
+            TransactWriteItem::Delete {
+                table_name,
+                key,
+                condition_expression,
+                expression_attribute_names,
+                expression_attribute_values,
+                return_old_on_condition_failure,
+            } => Self {
+                delete: Some(crate::Delete {
+                    table_name,
+                    key,
+                    condition_expression,
+                    expression_attribute_names,
+                    expression_attribute_values,
+                    return_values_on_condition_check_failure:
+                        return_values_on_condition_check_failure(return_old_on_condition_failure),
+                }),
+                ..Default::default()
+            },
+            TransactWriteItem::Update {
+                table_name,
+                key,
+                update_expression,
+                condition_expression,
+                expression_attribute_names,
+                expression_attribute_values,
+                return_old_on_condition_failure,
+            } => Self {
+                update: Some(crate::Update {
+                    table_name,
+                    key,
+                    update_expression,
+                    condition_expression,
+                    expression_attribute_names,
+                    expression_attribute_values,
+                    return_values_on_condition_check_failure:
+                        return_values_on_condition_check_failure(return_old_on_condition_failure),
+                }),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl From<crate::TransactWriteItemsInput> for TransactWriteItemsRequest {
+    fn from(input: crate::TransactWriteItemsInput) -> Self {
+        Self {
+            transact_items: input
+                .transact_items
+                .into_iter()
+                .filter_map(transact_write_item)
+                .collect(),
+            client_request_token: input.client_request_token,
+            return_consumed_capacity: return_consumed_capacity(input.return_consumed_capacity),
+            return_item_collection_metrics: return_item_collection_metrics(
+                input.return_item_collection_metrics,
+            ),
+        }
+    }
+}
+
+impl From<TransactWriteItemsRequest> for crate::TransactWriteItemsInput {
+    fn from(request: TransactWriteItemsRequest) -> Self {
+        Self {
+            transact_items: request.transact_items.into_iter().map(Into::into).collect(),
+            client_request_token: request.client_request_token,
+            return_consumed_capacity: to_string(
+                request.return_consumed_capacity,
+                ReturnConsumedCapacity::as_str,
+            ),
+            return_item_collection_metrics: to_string(
+                request.return_item_collection_metrics,
+                ReturnItemCollectionMetrics::as_str,
+            ),
+        }
+    }
+}
+
+impl From<crate::TransactWriteItemsOutput> for TransactWriteItemsResponse {
+    fn from(output: crate::TransactWriteItemsOutput) -> Self {
+        Self {
+            item_collection_metrics: output.item_collection_metrics,
+            consumed_capacity: output.consumed_capacity,
+        }
+    }
+}
+
+impl From<crate::KeySchemaElement> for KeySchemaElement {
+    fn from(element: crate::KeySchemaElement) -> Self {
+        Self {
+            attribute_name: element.attribute_name,
+            key_type: match element.key_type.as_str() {
+                "RANGE" => KeyType::Range,
+                _ => KeyType::Hash,
+            },
+        }
+    }
+}
+
+impl From<KeySchemaElement> for crate::KeySchemaElement {
+    fn from(element: KeySchemaElement) -> Self {
+        let key_type = match element.key_type {
+            KeyType::Hash => "HASH",
+            KeyType::Range => "RANGE",
+        };
+        Self {
+            attribute_name: element.attribute_name,
+            key_type: key_type.to_owned(),
+        }
+    }
+}
+
+impl From<crate::AttributeDefinition> for AttributeDefinition {
+    fn from(definition: crate::AttributeDefinition) -> Self {
+        Self {
+            attribute_name: definition.attribute_name,
+            attribute_type: match definition.attribute_type.as_str() {
+                "N" => AttributeType::N,
+                "B" => AttributeType::B,
+                _ => AttributeType::S,
+            },
+        }
+    }
+}
+
+impl From<AttributeDefinition> for crate::AttributeDefinition {
+    fn from(definition: AttributeDefinition) -> Self {
+        Self {
+            attribute_name: definition.attribute_name,
+            attribute_type: definition.attribute_type.to_string(),
+        }
+    }
+}
+
+impl From<crate::Projection> for Projection {
+    fn from(projection: crate::Projection) -> Self {
+        match projection.projection_type.as_deref() {
+            Some("KEYS_ONLY") => Projection::KeysOnly,
+            Some("INCLUDE") => {
+                Projection::Include(projection.non_key_attributes.unwrap_or_default())
+            }
+            _ => Projection::All,
+        }
+    }
+}
+
+impl From<Projection> for crate::Projection {
+    fn from(projection: Projection) -> Self {
+        let (projection_type, non_key_attributes) = match projection {
+            Projection::All => ("ALL", None),
+            Projection::KeysOnly => ("KEYS_ONLY", None),
+            Projection::Include(non_key_attributes) => ("INCLUDE", Some(non_key_attributes)),
+        };
+        Self {
+            projection_type: Some(projection_type.to_owned()),
+            non_key_attributes,
+        }
+    }
+}
+
+impl From<crate::ProvisionedThroughput> for ProvisionedThroughput {
+    fn from(throughput: crate::ProvisionedThroughput) -> Self {
+        Self {
+            read_capacity_units: throughput.read_capacity_units,
+            write_capacity_units: throughput.write_capacity_units,
+        }
+    }
+}
+
+impl From<ProvisionedThroughput> for crate::ProvisionedThroughput {
+    fn from(throughput: ProvisionedThroughput) -> Self {
+        Self {
+            read_capacity_units: throughput.read_capacity_units,
+            write_capacity_units: throughput.write_capacity_units,
+        }
+    }
+}
+
+impl From<crate::GlobalSecondaryIndex> for GlobalSecondaryIndex {
+    fn from(index: crate::GlobalSecondaryIndex) -> Self {
+        Self {
+            index_name: index.index_name,
+            key_schema: index.key_schema.into_iter().map(Into::into).collect(),
+            projection: index.projection.into(),
+            provisioned_throughput: index.provisioned_throughput.map(Into::into),
+        }
+    }
+}
+
+impl From<GlobalSecondaryIndex> for crate::GlobalSecondaryIndex {
+    fn from(index: GlobalSecondaryIndex) -> Self {
+        Self {
+            index_name: index.index_name,
+            key_schema: index.key_schema.into_iter().map(Into::into).collect(),
+            projection: index.projection.into(),
+            provisioned_throughput: index.provisioned_throughput.map(Into::into),
+        }
+    }
+}
+
+impl From<crate::LocalSecondaryIndex> for LocalSecondaryIndex {
+    fn from(index: crate::LocalSecondaryIndex) -> Self {
+        Self {
+            index_name: index.index_name,
+            key_schema: index.key_schema.into_iter().map(Into::into).collect(),
+            projection: index.projection.into(),
+        }
+    }
+}
+
+impl From<LocalSecondaryIndex> for crate::LocalSecondaryIndex {
+    fn from(index: LocalSecondaryIndex) -> Self {
+        Self {
+            index_name: index.index_name,
+            key_schema: index.key_schema.into_iter().map(Into::into).collect(),
+            projection: index.projection.into(),
+        }
+    }
+}
+
+fn into_vec<T, U: From<T>>(values: Vec<T>) -> Vec<U> {
+    values.into_iter().map(Into::into).collect()
+}
+
+fn into_some_vec<T, U: From<T>>(values: Vec<T>) -> Option<Vec<U>> {
+    if values.is_empty() {
+        return None;
+    }
+    Some(into_vec(values))
+}
+
+impl From<crate::CreateTableInput> for CreateTableRequest {
+    fn from(input: crate::CreateTableInput) -> Self {
+        Self {
+            table_name: input.table_name,
+            attribute_definitions: into_vec(input.attribute_definitions),
+            key_schema: into_vec(input.key_schema),
+            global_secondary_indexes: into_vec(input.global_secondary_indexes.unwrap_or_default()),
+            local_secondary_indexes: into_vec(input.local_secondary_indexes.unwrap_or_default()),
+            provisioned_throughput: match input.billing_mode.as_deref() {
+                Some("PAY_PER_REQUEST") => None,
+                _ => input.provisioned_throughput.map(Into::into),
+            },
+        }
+    }
+}
+
+impl From<CreateTableRequest> for crate::CreateTableInput {
+    fn from(request: CreateTableRequest) -> Self {
+        let billing_mode = match request.provisioned_throughput {
+            Some(_) => "PROVISIONED",
+            None => "PAY_PER_REQUEST",
+        };
+        Self {
+            table_name: request.table_name,
+            attribute_definitions: into_vec(request.attribute_definitions),
+            key_schema: into_vec(request.key_schema),
+            global_secondary_indexes: into_some_vec(request.global_secondary_indexes),
+            local_secondary_indexes: into_some_vec(request.local_secondary_indexes),
+            billing_mode: Some(billing_mode.to_owned()),
+            provisioned_throughput: request.provisioned_throughput.map(Into::into),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<crate::CreateTableOutput> for CreateTableResponse {
+    fn from(output: crate::CreateTableOutput) -> Self {
+        Self {
+            table_description: output.table_description,
+        }
+    }
+}
+
+impl From<crate::DeleteTableInput> for DeleteTableRequest {
+    fn from(input: crate::DeleteTableInput) -> Self {
+        Self {
+            table_name: input.table_name,
+        }
+    }
+}
+
+impl From<DeleteTableRequest> for crate::DeleteTableInput {
+    fn from(request: DeleteTableRequest) -> Self {
+        Self {
+            table_name: request.table_name,
+        }
+    }
+}
+
+impl From<crate::DeleteTableOutput> for DeleteTableResponse {
+    fn from(output: crate::DeleteTableOutput) -> Self {
+        Self {
+            table_description: output.table_description,
+        }
+    }
+}
+
+impl From<crate::DescribeTableInput> for DescribeTableRequest {
+    fn from(input: crate::DescribeTableInput) -> Self {
+        Self {
+            table_name: input.table_name,
+        }
+    }
+}
+
+impl From<DescribeTableRequest> for crate::DescribeTableInput {
+    fn from(request: DescribeTableRequest) -> Self {
+        Self {
+            table_name: request.table_name,
+        }
+    }
+}
+
+impl From<crate::DescribeTableOutput> for DescribeTableResponse {
+    fn from(output: crate::DescribeTableOutput) -> Self {
+        Self {
+            table: output.table,
+        }
+    }
+}
+
+impl From<crate::UpdateTimeToLiveInput> for UpdateTimeToLiveRequest {
+    fn from(input: crate::UpdateTimeToLiveInput) -> Self {
+        Self {
+            table_name: input.table_name,
+            attribute_name: input.time_to_live_specification.attribute_name,
+            enabled: input.time_to_live_specification.enabled,
+        }
+    }
+}
+
+impl From<UpdateTimeToLiveRequest> for crate::UpdateTimeToLiveInput {
+    fn from(request: UpdateTimeToLiveRequest) -> Self {
+        Self {
+            table_name: request.table_name,
+            time_to_live_specification: crate::TimeToLiveSpecification {
+                attribute_name: request.attribute_name,
+                enabled: request.enabled,
+            },
+        }
+    }
+}
+
+impl From<crate::UpdateTimeToLiveOutput> for UpdateTimeToLiveResponse {
+    fn from(_: crate::UpdateTimeToLiveOutput) -> Self {
+        Self {}
+    }
+}
@@ -196,8 +196,14 @@ impl From<RusotoError<TransactWriteItemsError>> for RaidenError {
                     RaidenError::ResourceNotFound(msg)
                 }
                 TransactWriteItemsError::TransactionCanceled(msg) => {
+                    // rusoto drops the `CancellationReasons` of the response body, so only the
+                    // codes listed in the message are known.
                     let reasons = RaidenTransactionCancellationReasons::from_str(&msg);
-                    RaidenError::TransactionCanceled { reasons }
+                    let cancellations = reasons.cancellations();
+                    RaidenError::TransactionCanceled {
+                        reasons,
+                        cancellations,
+                    }
                 }
                 TransactWriteItemsError::TransactionInProgress(msg) => {
                     RaidenError::TransactionInProgress(msg)
@@ -223,11 +229,74 @@ impl From<RusotoError<TransactGetItemsError>> for RaidenError {
                 }
                 TransactGetItemsError::ResourceNotFound(msg) => RaidenError::ResourceNotFound(msg),
                 TransactGetItemsError::TransactionCanceled(msg) => {
+                    // rusoto drops the `CancellationReasons` of the response body, so only the
+                    // codes listed in the message are known.
                     let reasons = RaidenTransactionCancellationReasons::from_str(&msg);
-                    RaidenError::TransactionCanceled { reasons }
+                    let cancellations = reasons.cancellations();
+                    RaidenError::TransactionCanceled {
+                        reasons,
+                        cancellations,
+                    }
                 }
             },
             _ => into_raiden_error(error),
         }
     }
 }
+
+impl From<RusotoError<CreateTableError>> for RaidenError {
+    fn from(error: RusotoError<CreateTableError>) -> Self {
+        match error {
+            RusotoError::Service(error) => match error {
+                CreateTableError::InternalServerError(msg) => RaidenError::InternalServerError(msg),
+                CreateTableError::LimitExceeded(msg) => RaidenError::LimitExceeded(msg),
+                CreateTableError::ResourceInUse(msg) => RaidenError::ResourceInUse(msg),
+            },
+            _ => into_raiden_error(error),
+        }
+    }
+}
+
+impl From<RusotoError<DeleteTableError>> for RaidenError {
+    fn from(error: RusotoError<DeleteTableError>) -> Self {
+        match error {
+            RusotoError::Service(error) => match error {
+                DeleteTableError::InternalServerError(msg) => RaidenError::InternalServerError(msg),
+                DeleteTableError::LimitExceeded(msg) => RaidenError::LimitExceeded(msg),
+                DeleteTableError::ResourceInUse(msg) => RaidenError::ResourceInUse(msg),
+                DeleteTableError::ResourceNotFound(msg) => RaidenError::ResourceNotFound(msg),
+            },
+            _ => into_raiden_error(error),
+        }
+    }
+}
+
+impl From<RusotoError<DescribeTableError>> for RaidenError {
+    fn from(error: RusotoError<DescribeTableError>) -> Self {
+        match error {
+            RusotoError::Service(error) => match error {
+                DescribeTableError::InternalServerError(msg) => {
+                    RaidenError::InternalServerError(msg)
+                }
+                DescribeTableError::ResourceNotFound(msg) => RaidenError::ResourceNotFound(msg),
+            },
+            _ => into_raiden_error(error),
+        }
+    }
+}
+
+impl From<RusotoError<UpdateTimeToLiveError>> for RaidenError {
+    fn from(error: RusotoError<UpdateTimeToLiveError>) -> Self {
+        match error {
+            RusotoError::Service(error) => match error {
+                UpdateTimeToLiveError::InternalServerError(msg) => {
+                    RaidenError::InternalServerError(msg)
+                }
+                UpdateTimeToLiveError::LimitExceeded(msg) => RaidenError::LimitExceeded(msg),
+                UpdateTimeToLiveError::ResourceInUse(msg) => RaidenError::ResourceInUse(msg),
+                UpdateTimeToLiveError::ResourceNotFound(msg) => RaidenError::ResourceNotFound(msg),
+            },
+            _ => into_raiden_error(error),
+        }
+    }
+}
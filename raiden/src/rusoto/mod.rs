@@ -1,3 +1,4 @@
+pub(crate) mod backend;
 mod errors;
 mod ops;
 
@@ -43,7 +44,10 @@ impl FromAttribute for String {
             // See. https://github.com/raiden-rs/raiden/issues/58
             Some(AttributeValue { null: Some(v), .. }) if v => Ok("".to_owned()),
             Some(AttributeValue { s: Some(v), .. }) => Ok(v),
-            _ => Err(ConversionError::ValueIsNone),
+            other => Err(ConversionError::unexpected_type(
+                AttributeType::S,
+                other.as_ref(),
+            )),
         }
     }
 }
@@ -95,7 +99,10 @@ impl<'a> FromAttribute for std::borrow::Cow<'a, str> {
                 Ok(std::borrow::Cow::Owned("".to_owned()))
             }
             Some(AttributeValue { s: Some(v), .. }) => Ok(std::borrow::Cow::Owned(v)),
-            _ => Err(ConversionError::ValueIsNone),
+            other => Err(ConversionError::unexpected_type(
+                AttributeType::S,
+                other.as_ref(),
+            )),
         }
     }
 }
@@ -113,10 +120,14 @@ macro_rules! default_attr_for_num {
 
         impl FromAttribute for $to {
             fn from_attr(value: Option<AttributeValue>) -> Result<Self, ConversionError> {
-                if let Some(AttributeValue { n: Some(v), .. }) = value {
-                    Ok(v.parse().unwrap())
-                } else {
-                    Err(ConversionError::ValueIsNone)
+                match value {
+                    Some(AttributeValue { n: Some(v), .. }) => {
+                        v.parse().map_err(|_| ConversionError::ParseInt)
+                    }
+                    other => Err(ConversionError::unexpected_type(
+                        AttributeType::N,
+                        other.as_ref(),
+                    )),
                 }
             }
         }
@@ -159,10 +170,12 @@ impl IntoAttribute for bool {
 
 impl FromAttribute for bool {
     fn from_attr(value: Option<AttributeValue>) -> Result<Self, ConversionError> {
-        if let Some(AttributeValue { bool: Some(v), .. }) = value {
-            Ok(v)
-        } else {
-            Err(ConversionError::ValueIsNone)
+        match value {
+            Some(AttributeValue { bool: Some(v), .. }) => Ok(v),
+            other => Err(ConversionError::unexpected_type(
+                AttributeType::BOOL,
+                other.as_ref(),
+            )),
         }
     }
 }
@@ -187,165 +200,113 @@ impl<A: IntoAttribute> IntoAttribute for Vec<A> {
 impl<A: FromAttribute> FromAttribute for Vec<A> {
     fn from_attr(value: Option<AttributeValue>) -> Result<Self, ConversionError> {
         match value {
-            Some(AttributeValue { l: Some(v), .. }) => {
-                v.into_iter().map(|item| A::from_attr(Some(item))).collect()
-            }
+            Some(AttributeValue { l: Some(v), .. }) => v
+                .into_iter()
+                .enumerate()
+                .map(|(i, item)| {
+                    A::from_attr(Some(item)).map_err(|err| err.nested(format!("[{i}]")))
+                })
+                .collect(),
             // See. https://github.com/raiden-rs/raiden/issues/57
             Some(AttributeValue {
                 null: Some(true), ..
             })
             | None => Ok(vec![]),
-            _ => Err(ConversionError::ValueIsNone),
+            other => Err(ConversionError::unexpected_type(
+                AttributeType::L,
+                other.as_ref(),
+            )),
         }
     }
 }
 
-macro_rules! default_number_hash_set_convertor {
-    ($to: ty) => {
-        impl IntoAttribute for std::collections::HashSet<$to> {
-            fn into_attr(self) -> AttributeValue {
-                if self.is_empty() {
-                    // See. https://github.com/raiden-rs/raiden/issues/57
-                    //      https://github.com/raiden-rs/raiden-dynamo/issues/64
-                    AttributeValue::default()
-                } else {
-                    AttributeValue {
-                        ns: Some(self.into_iter().map(|s| s.to_string()).collect()),
-                        ..AttributeValue::default()
-                    }
-                }
-            }
+// A set is stored as `NS` when its items are numbers and as `SS` otherwise.
+fn into_set_attr<A: IntoStringSetItem>(items: impl Iterator<Item = A>) -> AttributeValue {
+    let items: Vec<String> = items.map(|s| s.into_ss_item()).collect();
+    if items.is_empty() {
+        // See. https://github.com/raiden-rs/raiden/issues/57
+        //      https://github.com/raiden-rs/raiden-dynamo/issues/64
+        AttributeValue::default()
+    } else if A::IS_NUMBER {
+        AttributeValue {
+            ns: Some(items),
+            ..AttributeValue::default()
         }
-
-        impl FromAttribute for std::collections::HashSet<$to> {
-            fn from_attr(value: Option<AttributeValue>) -> Result<Self, ConversionError> {
-                match value {
-                    Some(AttributeValue {
-                        ns: Some(mut nums), ..
-                    }) => {
-                        let mut results: Vec<Result<$to, ConversionError>> = nums
-                            .drain(..)
-                            .map(|ns| ns.parse().map_err(|_| ConversionError::ParseInt))
-                            .collect();
-
-                        results.drain(..).collect()
-                    }
-                    // See. https://github.com/raiden-rs/raiden/issues/57
-                    Some(AttributeValue {
-                        null: Some(true), ..
-                    })
-                    | None => Ok(std::collections::HashSet::new()),
-                    _ => Err(ConversionError::ValueIsNone),
-                }
-            }
+    } else {
+        AttributeValue {
+            ss: Some(items),
+            ..AttributeValue::default()
         }
-    };
+    }
 }
 
-pub(crate) use default_number_hash_set_convertor;
-
-macro_rules! default_number_btree_set_convertor {
-    ($to: ty) => {
-        impl IntoAttribute for std::collections::BTreeSet<$to> {
-            fn into_attr(self) -> AttributeValue {
-                if self.is_empty() {
-                    // See. https://github.com/raiden-rs/raiden/issues/57
-                    //      https://github.com/raiden-rs/raiden-dynamo/issues/64
-                    AttributeValue::default()
-                } else {
-                    AttributeValue {
-                        ns: Some(self.into_iter().map(|s| s.to_string()).collect()),
-                        ..AttributeValue::default()
-                    }
-                }
-            }
-        }
-
-        impl FromAttribute for std::collections::BTreeSet<$to> {
-            fn from_attr(value: Option<AttributeValue>) -> Result<Self, ConversionError> {
-                match value {
-                    Some(AttributeValue {
-                        ns: Some(mut nums), ..
-                    }) => {
-                        let mut results: Vec<Result<$to, ConversionError>> = nums
-                            .drain(..)
-                            .map(|ns| ns.parse().map_err(|_| ConversionError::ParseInt))
-                            .collect();
-
-                        results.drain(..).collect()
-                    }
-                    // See. https://github.com/raiden-rs/raiden/issues/57
-                    Some(AttributeValue {
-                        null: Some(true), ..
-                    })
-                    | None => Ok(std::collections::BTreeSet::new()),
-                    _ => Err(ConversionError::ValueIsNone),
-                }
-            }
-        }
-    };
+fn from_set_attr<A: FromStringSetItem, C: Default + FromIterator<A>>(
+    value: Option<AttributeValue>,
+) -> Result<C, ConversionError> {
+    match value {
+        Some(AttributeValue {
+            ns: Some(mut items),
+            ..
+        }) if A::IS_NUMBER => items.drain(..).map(A::from_ss_item).collect(),
+        Some(AttributeValue {
+            ss: Some(mut items),
+            ..
+        }) if !A::IS_NUMBER => items.drain(..).map(A::from_ss_item).collect(),
+        // See. https://github.com/raiden-rs/raiden/issues/57
+        Some(AttributeValue {
+            null: Some(true), ..
+        })
+        | None => Ok(C::default()),
+        other => Err(ConversionError::unexpected_type(
+            if A::IS_NUMBER {
+                AttributeType::NS
+            } else {
+                AttributeType::SS
+            },
+            other.as_ref(),
+        )),
+    }
 }
 
-pub(crate) use default_number_btree_set_convertor;
-
 impl<A: std::hash::Hash + IntoStringSetItem> IntoAttribute for HashSet<A> {
     fn into_attr(self) -> AttributeValue {
-        if self.is_empty() {
-            // See. https://github.com/raiden-rs/raiden/issues/57
-            //      https://github.com/raiden-rs/raiden-dynamo/issues/64
-            AttributeValue::default()
-        } else {
-            AttributeValue {
-                ss: Some(self.into_iter().map(|s| s.into_ss_item()).collect()),
-                ..AttributeValue::default()
-            }
-        }
+        into_set_attr(self.into_iter())
     }
 }
 
 impl<A: std::hash::Hash + std::cmp::Eq + FromStringSetItem> FromAttribute for HashSet<A> {
     fn from_attr(value: Option<AttributeValue>) -> Result<Self, ConversionError> {
-        match value {
-            Some(AttributeValue {
-                ss: Some(mut ss), ..
-            }) => ss.drain(..).map(A::from_ss_item).collect(),
-            // See. https://github.com/raiden-rs/raiden/issues/57
-            Some(AttributeValue {
-                null: Some(true), ..
-            })
-            | None => Ok(HashSet::new()),
-            _ => Err(ConversionError::ValueIsNone),
-        }
+        from_set_attr(value)
     }
 }
 
 impl<A: std::cmp::Ord + IntoStringSetItem> IntoAttribute for BTreeSet<A> {
     fn into_attr(self) -> AttributeValue {
-        if self.is_empty() {
-            // See. https://github.com/raiden-rs/raiden/issues/57
-            //      https://github.com/raiden-rs/raiden-dynamo/issues/64
-            AttributeValue::default()
-        } else {
-            AttributeValue {
-                ss: Some(self.into_iter().map(|s| s.into_ss_item()).collect()),
-                ..AttributeValue::default()
-            }
-        }
+        into_set_attr(self.into_iter())
     }
 }
 
 impl<A: std::cmp::Ord + FromStringSetItem> FromAttribute for BTreeSet<A> {
     fn from_attr(value: Option<AttributeValue>) -> Result<Self, ConversionError> {
+        from_set_attr(value)
+    }
+}
+
+impl AttributeType {
+    /// The type of `value`, `None` when no type is set.
+    pub fn of(value: &AttributeValue) -> Option<Self> {
         match value {
-            Some(AttributeValue {
-                ss: Some(mut ss), ..
-            }) => ss.drain(..).map(A::from_ss_item).collect(),
-            // See. https://github.com/raiden-rs/raiden/issues/57
-            Some(AttributeValue {
-                null: Some(true), ..
-            })
-            | None => Ok(BTreeSet::new()),
-            _ => Err(ConversionError::ValueIsNone),
+            AttributeValue { b: Some(_), .. } => Some(AttributeType::B),
+            AttributeValue { bool: Some(_), .. } => Some(AttributeType::BOOL),
+            AttributeValue { bs: Some(_), .. } => Some(AttributeType::BS),
+            AttributeValue { l: Some(_), .. } => Some(AttributeType::L),
+            AttributeValue { m: Some(_), .. } => Some(AttributeType::M),
+            AttributeValue { n: Some(_), .. } => Some(AttributeType::N),
+            AttributeValue { ns: Some(_), .. } => Some(AttributeType::NS),
+            AttributeValue { null: Some(_), .. } => Some(AttributeType::NULL),
+            AttributeValue { s: Some(_), .. } => Some(AttributeType::S),
+            AttributeValue { ss: Some(_), .. } => Some(AttributeType::SS),
+            _ => None,
         }
     }
 }
@@ -1,31 +1,160 @@
+pub use transact_get::*;
 pub use transact_write::*;
 
-mod transact_write {
+mod transact_get {
     use crate::{
-        DynamoDb, TransactWriteConditionCheckBuilder, TransactWriteDeleteBuilder,
-        TransactWriteItem, TransactWritePutBuilder, TransactWriteUpdateBuilder,
+        DynamoBackend, ReadTxOutput, TransactGetItem, TransactGetItemBuilder, TransactGetItems,
+        TransactGetPush,
     };
 
-    pub struct WriteTx {
-        items: Vec<crate::TransactWriteItem>,
-        client: crate::DynamoDbClient,
+    /// Reads items of different models, and from different tables, in one transaction.
+    ///
+    /// Each `get` appends its item type to `T`, and `run` returns one `Option` per get in the same order.
+    pub struct ReadTx<T = ()> {
+        items: Vec<crate::TransactGetItem>,
+        client: crate::SharedBackend,
         retry_condition: crate::RetryCondition,
+        return_consumed_capacity: Option<crate::ReturnConsumedCapacity>,
+        _items: std::marker::PhantomData<fn() -> T>,
     }
 
-    impl WriteTx {
+    impl ReadTx {
         pub fn new(region: crate::Region) -> Self {
+            Self::new_with_backend(crate::DynamoDbClient::new(region))
+        }
+
+        pub fn new_with_client(client: crate::Client, region: crate::Region) -> Self {
+            Self::new_with_backend(crate::DynamoDbClient::new_with_client(client, region))
+        }
+
+        pub fn new_with_backend(backend: impl DynamoBackend + 'static) -> Self {
             Self {
                 items: vec![],
-                client: crate::DynamoDbClient::new(region),
+                client: std::sync::Arc::new(backend),
                 retry_condition: crate::RetryCondition::new(),
+                return_consumed_capacity: None,
+                _items: std::marker::PhantomData,
+            }
+        }
+    }
+
+    impl<T> ReadTx<T> {
+        pub fn with_retries(
+            mut self,
+            s: Box<dyn crate::retry::RetryStrategy + Send + Sync>,
+        ) -> Self {
+            self.retry_condition.strategy = s;
+            self
+        }
+
+        /// Reports every request attempt of this transaction to `observer`.
+        pub fn with_observer(mut self, observer: std::sync::Arc<dyn crate::Observer>) -> Self {
+            self.client = std::sync::Arc::new(crate::ObservedBackend::new(self.client, observer));
+            self
+        }
+
+        /// Requests the consumed capacity of each table, returned as `consumed_capacity`.
+        pub fn return_consumed_capacity(mut self, value: crate::ReturnConsumedCapacity) -> Self {
+            self.return_consumed_capacity = Some(value);
+            self
+        }
+
+        pub fn get<B>(mut self, builder: B) -> ReadTx<<T as TransactGetPush<B::Item>>::Output>
+        where
+            B: TransactGetItemBuilder,
+            T: TransactGetPush<B::Item>,
+        {
+            self.items.push(TransactGetItem {
+                get: builder.build(),
+            });
+            ReadTx {
+                items: self.items,
+                client: self.client,
+                retry_condition: self.retry_condition,
+                return_consumed_capacity: self.return_consumed_capacity,
+                _items: std::marker::PhantomData,
             }
         }
 
+        pub async fn run(self) -> Result<ReadTxOutput<T::Output>, crate::RaidenError>
+        where
+            T: TransactGetItems,
+        {
+            let client = self.client;
+            let input = crate::TransactGetItemsInput {
+                return_consumed_capacity: self
+                    .return_consumed_capacity
+                    .map(|v| v.as_str().to_owned()),
+                transact_items: self.items,
+            };
+
+            let res = crate::retry_with_backend(
+                self.retry_condition.strategy.policy(),
+                &client,
+                &self.retry_condition,
+                move |client| {
+                    let input = input.clone();
+                    async { ReadTx::<T>::inner_run(client, input).await }
+                },
+            )
+            .await?;
+
+            Ok(ReadTxOutput {
+                consumed_capacity: res.consumed_capacity,
+                items: T::decode(res.responses.unwrap_or_default())?,
+            })
+        }
+
+        #[cfg_attr(feature = "tracing", tracing::instrument(
+            level = tracing::Level::DEBUG,
+            name = "dynamodb::action",
+            skip_all,
+            fields(api = "transact_get_items")
+        ))]
+        async fn inner_run(
+            client: crate::SharedBackend,
+            input: crate::TransactGetItemsInput,
+        ) -> Result<crate::TransactGetItemsResponse, crate::RaidenError> {
+            client.transact_get_items(input.into()).await
+        }
+    }
+}
+
+mod transact_write {
+    use crate::{
+        DynamoBackend, TransactWriteConditionCheckBuilder, TransactWriteDeleteBuilder,
+        TransactWriteItem, TransactWriteOutput, TransactWritePutBuilder,
+        TransactWriteUpdateBuilder,
+    };
+
+    pub struct WriteTx {
+        items: Vec<crate::TransactWriteItem>,
+        client: crate::SharedBackend,
+        retry_condition: crate::RetryCondition,
+        client_request_token: Option<String>,
+        return_consumed_capacity: Option<crate::ReturnConsumedCapacity>,
+        return_item_collection_metrics: Option<crate::ReturnItemCollectionMetrics>,
+        error: Option<crate::RaidenError>,
+    }
+
+    impl WriteTx {
+        pub fn new(region: crate::Region) -> Self {
+            Self::new_with_backend(crate::DynamoDbClient::new(region))
+        }
+
         pub fn new_with_client(client: crate::Client, region: crate::Region) -> Self {
+            Self::new_with_backend(crate::DynamoDbClient::new_with_client(client, region))
+        }
+
+        pub fn new_with_backend(backend: impl DynamoBackend + 'static) -> Self {
             Self {
                 items: vec![],
-                client: crate::DynamoDbClient::new_with_client(client, region),
+                client: std::sync::Arc::new(backend),
                 retry_condition: crate::RetryCondition::new(),
+                client_request_token: None,
+                return_consumed_capacity: None,
+                return_item_collection_metrics: None,
+                error: None,
             }
         }
 
@@ -37,7 +166,39 @@ mod transact_write {
             self
         }
 
-        pub fn put(mut self, builder: impl TransactWritePutBuilder) -> Self {
+        /// Reports every request attempt of this transaction to `observer`.
+        pub fn with_observer(mut self, observer: std::sync::Arc<dyn crate::Observer>) -> Self {
+            self.client = std::sync::Arc::new(crate::ObservedBackend::new(self.client, observer));
+            self
+        }
+
+        /// Sets the `ClientRequestToken` which makes the transaction idempotent.
+        ///
+        /// Without it, a token is generated once per `run()` and kept across retries.
+        pub fn idempotency_token(mut self, token: impl Into<String>) -> Self {
+            self.client_request_token = Some(token.into());
+            self
+        }
+
+        /// Requests the consumed capacity of each table, returned as `consumed_capacity`.
+        pub fn return_consumed_capacity(mut self, value: crate::ReturnConsumedCapacity) -> Self {
+            self.return_consumed_capacity = Some(value);
+            self
+        }
+
+        /// Requests the sizes of the modified item collections, returned as `item_collection_metrics`.
+        pub fn return_item_collection_metrics(
+            mut self,
+            value: crate::ReturnItemCollectionMetrics,
+        ) -> Self {
+            self.return_item_collection_metrics = Some(value);
+            self
+        }
+
+        pub fn put(mut self, mut builder: impl TransactWritePutBuilder) -> Self {
+            if self.error.is_none() {
+                self.error = builder.take_error();
+            }
             self.items.push(TransactWriteItem {
                 condition_check: None,
                 delete: None,
@@ -77,26 +238,37 @@ mod transact_write {
             self
         }
 
-        pub async fn run(self) -> Result<(), crate::RaidenError> {
-            let policy: crate::RetryPolicy = self.retry_condition.strategy.policy().into();
+        pub async fn run(self) -> Result<TransactWriteOutput, crate::RaidenError> {
+            if let Some(e) = self.error {
+                return Err(e);
+            }
             let client = self.client;
+            // Unlike `IdGenerator::gen`, the token is never fixed, since a reused token would make
+            // DynamoDB skip or reject a different transaction.
+            let token = self
+                .client_request_token
+                .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
             let input = crate::TransactWriteItemsInput {
-                client_request_token: None,
-                return_consumed_capacity: None,
-                return_item_collection_metrics: None,
+                client_request_token: Some(token),
+                return_consumed_capacity: self
+                    .return_consumed_capacity
+                    .map(|v| v.as_str().to_owned()),
+                return_item_collection_metrics: self
+                    .return_item_collection_metrics
+                    .map(|v| v.as_str().to_owned()),
                 transact_items: self.items,
             };
 
-            policy
-                .retry_if(
-                    move || {
-                        let client = client.clone();
-                        let input = input.clone();
-                        async { WriteTx::inner_run(client, input).await }
-                    },
-                    &self.retry_condition,
-                )
-                .await
+            crate::retry_with_backend(
+                self.retry_condition.strategy.policy(),
+                &client,
+                &self.retry_condition,
+                move |client| {
+                    let input = input.clone();
+                    async { WriteTx::inner_run(client, input).await }
+                },
+            )
+            .await
         }
 
         #[cfg_attr(feature = "tracing", tracing::instrument(
@@ -106,12 +278,15 @@ mod transact_write {
             fields(api = "transact_write_items")
         ))]
         async fn inner_run(
-            client: crate::DynamoDbClient,
+            client: crate::SharedBackend,
             input: crate::TransactWriteItemsInput,
-        ) -> Result<(), crate::RaidenError> {
-            let _res = client.transact_write_items(input).await?;
-            // TODO: ADD Resp
-            Ok(())
+        ) -> Result<TransactWriteOutput, crate::RaidenError> {
+            let res = client.transact_write_items(input.into()).await?;
+
+            Ok(TransactWriteOutput {
+                consumed_capacity: res.consumed_capacity,
+                item_collection_metrics: res.item_collection_metrics,
+            })
         }
     }
 }
@@ -0,0 +1,361 @@
+use std::collections::hash_map;
+
+use serde::{
+    de::{self, DeserializeSeed, IntoDeserializer, Visitor},
+    forward_to_deserialize_any,
+};
+
+use super::Attr;
+use crate::{AttributeValue, ConversionError};
+
+/// A `serde::Deserializer` which reads an `AttributeValue`.
+pub struct Deserializer {
+    value: AttributeValue,
+}
+
+impl Deserializer {
+    pub fn new(value: AttributeValue) -> Self {
+        Self { value }
+    }
+}
+
+fn invalid_number(value: &str) -> ConversionError {
+    ConversionError::Serde(format!("invalid DynamoDB number `{value}`"))
+}
+
+fn visit_number<'de, V: Visitor<'de>>(
+    value: String,
+    visitor: V,
+) -> Result<V::Value, ConversionError> {
+    if let Ok(v) = value.parse::<u64>() {
+        visitor.visit_u64(v)
+    } else if let Ok(v) = value.parse::<i64>() {
+        visitor.visit_i64(v)
+    } else if let Ok(v) = value.parse::<u128>() {
+        visitor.visit_u128(v)
+    } else if let Ok(v) = value.parse::<i128>() {
+        visitor.visit_i128(v)
+    } else if let Ok(v) = value.parse::<f64>() {
+        visitor.visit_f64(v)
+    } else {
+        Err(invalid_number(&value))
+    }
+}
+
+fn into_list(values: Vec<Attr>) -> Vec<AttributeValue> {
+    values.into_iter().map(Into::into).collect()
+}
+
+macro_rules! deserialize_number {
+    ($($method: ident => $visit: ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                match Attr::try_from(self.value)? {
+                    // Parse the exact representation into the requested type, not through f64.
+                    Attr::N(value) => visitor.$visit(value.parse().map_err(|_| invalid_number(&value))?),
+                    other => Deserializer::new(other.into()).deserialize_any(visitor),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer {
+    type Error = ConversionError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match Attr::try_from(self.value)? {
+            Attr::Null => visitor.visit_unit(),
+            Attr::Bool(value) => visitor.visit_bool(value),
+            Attr::N(value) => visit_number(value, visitor),
+            Attr::S(value) => visitor.visit_string(value),
+            Attr::B(value) => visitor.visit_byte_buf(value),
+            Attr::Ss(values) => visitor.visit_seq(ListAccess::new(into_list(
+                values.into_iter().map(Attr::S).collect(),
+            ))),
+            Attr::Ns(values) => visitor.visit_seq(ListAccess::new(into_list(
+                values.into_iter().map(Attr::N).collect(),
+            ))),
+            Attr::Bs(values) => visitor.visit_seq(ListAccess::new(into_list(
+                values.into_iter().map(Attr::B).collect(),
+            ))),
+            Attr::L(values) => visitor.visit_seq(ListAccess::new(values)),
+            Attr::M(values) => visitor.visit_map(MapAccess::new(values)),
+        }
+    }
+
+    deserialize_number! {
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match Attr::try_from(self.value)? {
+            // Keeps the exact number, e.g. for decimals which do not fit in f64.
+            Attr::N(value) => visitor.visit_string(value),
+            other => Deserializer::new(other.into()).deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match Attr::try_from(self.value)? {
+            Attr::Null => visitor.visit_none(),
+            other => visitor.visit_some(Deserializer::new(other.into())),
+        }
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match Attr::try_from(self.value)? {
+            // Empty sets can not be stored, see `IntoAttribute` for `HashSet`.
+            Attr::Null => visitor.visit_seq(ListAccess::new(vec![])),
+            // e.g. `Vec<u8>` in a `BinarySet`.
+            Attr::B(bytes) => visitor.visit_seq(ListAccess::new(into_list(
+                bytes.into_iter().map(|b| Attr::N(b.to_string())).collect(),
+            ))),
+            other => Deserializer::new(other.into()).deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match Attr::try_from(self.value)? {
+            Attr::S(variant) => visitor.visit_enum(variant.into_deserializer()),
+            Attr::M(values) if values.len() == 1 => {
+                let (variant, value) = values.into_iter().next().expect("should have an entry");
+                visitor.visit_enum(EnumAccess { variant, value })
+            }
+            _ => Err(ConversionError::Serde(
+                "an enum must be a string or a map with a single entry".to_owned(),
+            )),
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool char bytes byte_buf unit unit_struct tuple tuple_struct map struct identifier
+    }
+}
+
+struct ListAccess {
+    values: std::iter::Enumerate<std::vec::IntoIter<AttributeValue>>,
+}
+
+impl ListAccess {
+    fn new(values: Vec<AttributeValue>) -> Self {
+        Self {
+            values: values.into_iter().enumerate(),
+        }
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for ListAccess {
+    type Error = ConversionError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        self.values
+            .next()
+            .map(|(i, value)| {
+                seed.deserialize(Deserializer::new(value))
+                    .map_err(|err| err.nested(format!("[{i}]")))
+            })
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.values.len())
+    }
+}
+
+struct MapAccess {
+    entries: hash_map::IntoIter<String, AttributeValue>,
+    entry: Option<(String, AttributeValue)>,
+}
+
+impl MapAccess {
+    fn new(entries: std::collections::HashMap<String, AttributeValue>) -> Self {
+        Self {
+            entries: entries.into_iter(),
+            entry: None,
+        }
+    }
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess {
+    type Error = ConversionError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        match self.entries.next() {
+            Some((key, value)) => {
+                self.entry = Some((key.clone(), value));
+                seed.deserialize(KeyDeserializer(key)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (key, value) = self
+            .entry
+            .take()
+            .ok_or_else(|| ConversionError::Serde("map value without a key".to_owned()))?;
+        seed.deserialize(Deserializer::new(value))
+            .map_err(|err| err.nested(key))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+struct EnumAccess {
+    variant: String,
+    value: AttributeValue,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumAccess {
+    type Error = ConversionError;
+    type Variant = Deserializer;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), Self::Error> {
+        let variant = seed.deserialize(KeyDeserializer(self.variant))?;
+        Ok((variant, Deserializer::new(self.value)))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for Deserializer {
+    type Error = ConversionError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, Self::Error> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        de::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        de::Deserializer::deserialize_map(self, visitor)
+    }
+}
+
+// Deserializes map keys, which may hold numbers as strings.
+struct KeyDeserializer(String);
+
+macro_rules! deserialize_key {
+    ($($method: ident => $visit: ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                match self.0.parse() {
+                    Ok(value) => visitor.$visit(value),
+                    Err(_) => visitor.visit_string(self.0),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for KeyDeserializer {
+    type Error = ConversionError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_string(self.0)
+    }
+
+    deserialize_key! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_enum(self.0.into_deserializer())
+    }
+
+    forward_to_deserialize_any! {
+        char str string bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
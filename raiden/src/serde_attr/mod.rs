@@ -0,0 +1,409 @@
+//! A `serde` data format over `AttributeValue`.
+//!
+//! Values are mapped to DynamoDB attributes directly, without going through JSON:
+//!
+//! - numbers are written with their exact string representation, and are parsed from it
+//!   into the requested type, so `i128`, `u128` and `String` fields keep every digit.
+//! - bytes, e.g. `Vec<u8>` with `#[serde(with = "raiden::serde_attr::bytes")]`, are stored as `B`.
+//! - [`StringSet`], [`NumberSet`] and [`BinarySet`] are stored as `SS`, `NS` and `BS`.
+//! - enums are externally tagged by default, so unit variants are stored as `S`
+//!   and other variants as a single entry `M`.
+
+mod de;
+mod ser;
+
+use std::{
+    collections::HashMap,
+    ops::{Deref, DerefMut},
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{AttributeValue, ConversionError};
+
+pub use de::Deserializer;
+pub use ser::Serializer;
+
+// Names of the newtype structs the serializer recognizes as sets.
+const STRING_SET: &str = "$raiden::StringSet";
+const NUMBER_SET: &str = "$raiden::NumberSet";
+const BINARY_SET: &str = "$raiden::BinarySet";
+
+/// Serializes `value` into an attribute value.
+pub fn to_attribute_value<T: Serialize + ?Sized>(
+    value: &T,
+) -> Result<AttributeValue, ConversionError> {
+    value.serialize(Serializer)
+}
+
+/// Deserializes an attribute value into `T`.
+pub fn from_attribute_value<T: DeserializeOwned>(
+    value: AttributeValue,
+) -> Result<T, ConversionError> {
+    T::deserialize(Deserializer::new(value))
+}
+
+macro_rules! set_wrapper {
+    ($name: ident, $serde_name: expr, $doc: expr) => {
+        #[doc = $doc]
+        ///
+        /// Other `serde` formats see the inner collection as is.
+        #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+        pub struct $name<T>(pub T);
+
+        impl<T> $name<T> {
+            pub fn into_inner(self) -> T {
+                self.0
+            }
+        }
+
+        impl<T> From<T> for $name<T> {
+            fn from(value: T) -> Self {
+                Self(value)
+            }
+        }
+
+        impl<T> Deref for $name<T> {
+            type Target = T;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl<T> DerefMut for $name<T> {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+
+        impl<T: Serialize> Serialize for $name<T> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_newtype_struct($serde_name, &self.0)
+            }
+        }
+
+        impl<'de, T: Deserialize<'de>> Deserialize<'de> for $name<T> {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct Visitor<T>(std::marker::PhantomData<T>);
+
+                impl<'de, T: Deserialize<'de>> serde::de::Visitor<'de> for Visitor<T> {
+                    type Value = $name<T>;
+
+                    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                        f.write_str(stringify!($name))
+                    }
+
+                    fn visit_newtype_struct<D: serde::Deserializer<'de>>(
+                        self,
+                        deserializer: D,
+                    ) -> Result<Self::Value, D::Error> {
+                        T::deserialize(deserializer).map($name)
+                    }
+
+                    fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                        self,
+                        seq: A,
+                    ) -> Result<Self::Value, A::Error> {
+                        T::deserialize(serde::de::value::SeqAccessDeserializer::new(seq)).map($name)
+                    }
+                }
+
+                deserializer
+                    .deserialize_newtype_struct($serde_name, Visitor(std::marker::PhantomData))
+            }
+        }
+    };
+}
+
+set_wrapper!(
+    StringSet,
+    STRING_SET,
+    "Stores a collection of strings, e.g. `HashSet<String>`, as a string set (`SS`)."
+);
+set_wrapper!(
+    NumberSet,
+    NUMBER_SET,
+    "Stores a collection of numbers, e.g. `BTreeSet<u64>`, as a number set (`NS`)."
+);
+set_wrapper!(
+    BinarySet,
+    BINARY_SET,
+    "Stores a collection of byte strings, e.g. `Vec<Vec<u8>>`, as a binary set (`BS`)."
+);
+
+/// Stores `Vec<u8>` fields as binary (`B`) with `#[serde(with = "raiden::serde_attr::bytes")]`.
+pub mod bytes {
+    use serde::de::{Deserializer, SeqAccess, Visitor};
+
+    pub fn serialize<S: serde::Serializer>(value: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(value)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        struct BytesVisitor;
+
+        impl<'de> Visitor<'de> for BytesVisitor {
+            type Value = Vec<u8>;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("bytes")
+            }
+
+            fn visit_bytes<E: serde::de::Error>(self, value: &[u8]) -> Result<Self::Value, E> {
+                Ok(value.to_vec())
+            }
+
+            fn visit_byte_buf<E: serde::de::Error>(self, value: Vec<u8>) -> Result<Self::Value, E> {
+                Ok(value)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+                while let Some(byte) = seq.next_element()? {
+                    bytes.push(byte);
+                }
+                Ok(bytes)
+            }
+        }
+
+        deserializer.deserialize_byte_buf(BytesVisitor)
+    }
+}
+
+impl serde::ser::Error for ConversionError {
+    fn custom<T: std::fmt::Display>(message: T) -> Self {
+        ConversionError::Serde(message.to_string())
+    }
+}
+
+impl serde::de::Error for ConversionError {
+    fn custom<T: std::fmt::Display>(message: T) -> Self {
+        ConversionError::Serde(message.to_string())
+    }
+}
+
+/// The shape of an `AttributeValue`, independent of the backend.
+enum Attr {
+    Null,
+    Bool(bool),
+    N(String),
+    S(String),
+    B(Vec<u8>),
+    Ss(Vec<String>),
+    Ns(Vec<String>),
+    Bs(Vec<Vec<u8>>),
+    L(Vec<AttributeValue>),
+    M(HashMap<String, AttributeValue>),
+}
+
+#[cfg(feature = "aws-sdk")]
+impl From<Attr> for AttributeValue {
+    fn from(value: Attr) -> Self {
+        use crate::aws_sdk::primitives::Blob;
+
+        match value {
+            Attr::Null => AttributeValue::Null(true),
+            Attr::Bool(v) => AttributeValue::Bool(v),
+            Attr::N(v) => AttributeValue::N(v),
+            Attr::S(v) => AttributeValue::S(v),
+            Attr::B(v) => AttributeValue::B(Blob::new(v)),
+            Attr::Ss(v) => AttributeValue::Ss(v),
+            Attr::Ns(v) => AttributeValue::Ns(v),
+            Attr::Bs(v) => AttributeValue::Bs(v.into_iter().map(Blob::new).collect()),
+            Attr::L(v) => AttributeValue::L(v),
+            Attr::M(v) => AttributeValue::M(v),
+        }
+    }
+}
+
+#[cfg(feature = "aws-sdk")]
+impl TryFrom<AttributeValue> for Attr {
+    type Error = ConversionError;
+
+    fn try_from(value: AttributeValue) -> Result<Self, Self::Error> {
+        Ok(match value {
+            AttributeValue::Null(_) => Attr::Null,
+            AttributeValue::Bool(v) => Attr::Bool(v),
+            AttributeValue::N(v) => Attr::N(v),
+            AttributeValue::S(v) => Attr::S(v),
+            AttributeValue::B(v) => Attr::B(v.into_inner()),
+            AttributeValue::Ss(v) => Attr::Ss(v),
+            AttributeValue::Ns(v) => Attr::Ns(v),
+            AttributeValue::Bs(v) => Attr::Bs(v.into_iter().map(|v| v.into_inner()).collect()),
+            AttributeValue::L(v) => Attr::L(v),
+            AttributeValue::M(v) => Attr::M(v),
+            other => {
+                return Err(ConversionError::Serde(format!(
+                    "unsupported DynamoDB attribute: {other:?}"
+                )))
+            }
+        })
+    }
+}
+
+#[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
+impl From<Attr> for AttributeValue {
+    fn from(value: Attr) -> Self {
+        let mut attr = AttributeValue::default();
+        match value {
+            Attr::Null => attr.null = Some(true),
+            Attr::Bool(v) => attr.bool = Some(v),
+            Attr::N(v) => attr.n = Some(v),
+            Attr::S(v) => attr.s = Some(v),
+            Attr::B(v) => attr.b = Some(v.into()),
+            Attr::Ss(v) => attr.ss = Some(v),
+            Attr::Ns(v) => attr.ns = Some(v),
+            Attr::Bs(v) => attr.bs = Some(v.into_iter().map(Into::into).collect()),
+            Attr::L(v) => attr.l = Some(v),
+            Attr::M(v) => attr.m = Some(v),
+        }
+        attr
+    }
+}
+
+#[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
+impl TryFrom<AttributeValue> for Attr {
+    type Error = ConversionError;
+
+    fn try_from(value: AttributeValue) -> Result<Self, Self::Error> {
+        Ok(match value {
+            AttributeValue {
+                null: Some(true), ..
+            } => Attr::Null,
+            AttributeValue { bool: Some(v), .. } => Attr::Bool(v),
+            AttributeValue { n: Some(v), .. } => Attr::N(v),
+            AttributeValue { s: Some(v), .. } => Attr::S(v),
+            AttributeValue { b: Some(v), .. } => Attr::B(v.to_vec()),
+            AttributeValue { ss: Some(v), .. } => Attr::Ss(v),
+            AttributeValue { ns: Some(v), .. } => Attr::Ns(v),
+            AttributeValue { bs: Some(v), .. } => {
+                Attr::Bs(v.into_iter().map(|v| v.to_vec()).collect())
+            }
+            AttributeValue { l: Some(v), .. } => Attr::L(v),
+            AttributeValue { m: Some(v), .. } => Attr::M(v),
+            other => {
+                return Err(ConversionError::Serde(format!(
+                    "unsupported DynamoDB attribute: {other:?}"
+                )))
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::collections::{BTreeSet, HashSet};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Item {
+        #[serde(with = "bytes")]
+        payload: Vec<u8>,
+        tags: StringSet<BTreeSet<String>>,
+        scores: NumberSet<BTreeSet<u64>>,
+        keys: BinarySet<Vec<Vec<u8>>>,
+        big: u128,
+        decimal: String,
+        note: Option<String>,
+        kind: Kind,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    enum Kind {
+        Empty,
+        Count(i64),
+        Named { name: String },
+    }
+
+    fn item() -> Item {
+        Item {
+            payload: vec![0, 1, 255],
+            tags: StringSet(BTreeSet::from(["a".to_owned(), "b".to_owned()])),
+            scores: NumberSet(BTreeSet::from([1, 20])),
+            keys: BinarySet(vec![vec![1, 2], vec![3]]),
+            big: u128::MAX,
+            decimal: "0.1".to_owned(),
+            note: None,
+            kind: Kind::Named {
+                name: "bokuweb".to_owned(),
+            },
+        }
+    }
+
+    fn attr(value: Attr) -> AttributeValue {
+        value.into()
+    }
+
+    #[test]
+    fn test_to_attribute_value() {
+        let Ok(Attr::M(values)) = Attr::try_from(to_attribute_value(&item()).unwrap()) else {
+            panic!("should be a map");
+        };
+        assert_eq!(values["payload"], attr(Attr::B(vec![0, 1, 255])));
+        assert_eq!(
+            values["tags"],
+            attr(Attr::Ss(vec!["a".to_owned(), "b".to_owned()]))
+        );
+        assert_eq!(
+            values["scores"],
+            attr(Attr::Ns(vec!["1".to_owned(), "20".to_owned()]))
+        );
+        assert_eq!(values["keys"], attr(Attr::Bs(vec![vec![1, 2], vec![3]])));
+        assert_eq!(values["big"], attr(Attr::N(u128::MAX.to_string())));
+        assert_eq!(values["note"], attr(Attr::Null));
+        assert_eq!(
+            values["kind"],
+            attr(Attr::M(HashMap::from([(
+                "Named".to_owned(),
+                attr(Attr::M(HashMap::from([(
+                    "name".to_owned(),
+                    attr(Attr::S("bokuweb".to_owned()))
+                )])))
+            )])))
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let value = to_attribute_value(&item()).unwrap();
+        assert_eq!(from_attribute_value::<Item>(value).unwrap(), item());
+
+        for kind in [Kind::Empty, Kind::Count(-3)] {
+            let value = to_attribute_value(&kind).unwrap();
+            assert_eq!(from_attribute_value::<Kind>(value).unwrap(), kind);
+        }
+    }
+
+    #[test]
+    fn test_numbers_are_exact() {
+        let value = attr(Attr::N("12345678901234567890.123456789".to_owned()));
+        assert_eq!(
+            from_attribute_value::<String>(value.clone()).unwrap(),
+            "12345678901234567890.123456789"
+        );
+        assert!(from_attribute_value::<u64>(value).is_err());
+
+        let value = attr(Attr::N("18446744073709551617".to_owned()));
+        assert_eq!(
+            from_attribute_value::<u128>(value).unwrap(),
+            18446744073709551617
+        );
+        assert!(to_attribute_value(&f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_sets() {
+        let value = attr(Attr::Ss(vec!["a".to_owned()]));
+        assert_eq!(
+            from_attribute_value::<HashSet<String>>(value).unwrap(),
+            HashSet::from(["a".to_owned()])
+        );
+        assert_eq!(
+            from_attribute_value::<StringSet<Vec<String>>>(attr(Attr::Null)).unwrap(),
+            StringSet(vec![])
+        );
+        assert!(to_attribute_value(&NumberSet(vec!["a"])).is_err());
+    }
+}
@@ -0,0 +1,522 @@
+use std::collections::HashMap;
+
+use serde::ser::{self, Impossible, Serialize};
+
+use super::{Attr, BINARY_SET, NUMBER_SET, STRING_SET};
+use crate::{AttributeValue, ConversionError};
+
+/// A `serde::Serializer` which produces an `AttributeValue`.
+pub struct Serializer;
+
+macro_rules! serialize_number {
+    ($($method: ident => $ty: ty,)*) => {
+        $(
+            fn $method(self, value: $ty) -> Result<Self::Ok, Self::Error> {
+                Ok(Attr::N(value.to_string()).into())
+            }
+        )*
+    };
+}
+
+impl ser::Serializer for Serializer {
+    type Ok = AttributeValue;
+    type Error = ConversionError;
+    type SerializeSeq = SerializeList;
+    type SerializeTuple = SerializeList;
+    type SerializeTupleStruct = SerializeList;
+    type SerializeTupleVariant = SerializeVariant<SerializeList>;
+    type SerializeMap = SerializeMap;
+    type SerializeStruct = SerializeMap;
+    type SerializeStructVariant = SerializeVariant<SerializeMap>;
+
+    fn serialize_bool(self, value: bool) -> Result<Self::Ok, Self::Error> {
+        Ok(Attr::Bool(value).into())
+    }
+
+    serialize_number! {
+        serialize_i8 => i8,
+        serialize_i16 => i16,
+        serialize_i32 => i32,
+        serialize_i64 => i64,
+        serialize_i128 => i128,
+        serialize_u8 => u8,
+        serialize_u16 => u16,
+        serialize_u32 => u32,
+        serialize_u64 => u64,
+        serialize_u128 => u128,
+    }
+
+    fn serialize_f32(self, value: f32) -> Result<Self::Ok, Self::Error> {
+        if !value.is_finite() {
+            return Err(ConversionError::Serde(format!(
+                "DynamoDB can not store the number {value}"
+            )));
+        }
+        Ok(Attr::N(value.to_string()).into())
+    }
+
+    fn serialize_f64(self, value: f64) -> Result<Self::Ok, Self::Error> {
+        if !value.is_finite() {
+            return Err(ConversionError::Serde(format!(
+                "DynamoDB can not store the number {value}"
+            )));
+        }
+        Ok(Attr::N(value.to_string()).into())
+    }
+
+    fn serialize_char(self, value: char) -> Result<Self::Ok, Self::Error> {
+        Ok(Attr::S(value.to_string()).into())
+    }
+
+    fn serialize_str(self, value: &str) -> Result<Self::Ok, Self::Error> {
+        Ok(Attr::S(value.to_owned()).into())
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Ok(Attr::B(value.to_vec()).into())
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Attr::Null.into())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Attr::Null.into())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Ok(Attr::Null.into())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        Ok(Attr::S(variant.to_owned()).into())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        match name {
+            STRING_SET | NUMBER_SET | BINARY_SET => serialize_set(name, value),
+            _ => value.serialize(self),
+        }
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        let value = value.serialize(Serializer)?;
+        Ok(Attr::M(HashMap::from([(variant.to_owned(), value)])).into())
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(SerializeList {
+            items: Vec::with_capacity(len.unwrap_or_default()),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Ok(SerializeVariant {
+            variant,
+            inner: self.serialize_seq(Some(len))?,
+        })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(SerializeMap {
+            entries: HashMap::with_capacity(len.unwrap_or_default()),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Ok(SerializeVariant {
+            variant,
+            inner: self.serialize_map(Some(len))?,
+        })
+    }
+}
+
+fn set_item_error(name: &str, item: &AttributeValue) -> ConversionError {
+    ConversionError::Serde(format!(
+        "{} can not contain {item:?}",
+        name.trim_start_matches("$raiden::")
+    ))
+}
+
+fn serialize_set<T: Serialize + ?Sized>(
+    name: &'static str,
+    value: &T,
+) -> Result<AttributeValue, ConversionError> {
+    let value = value.serialize(Serializer)?;
+    let items = match Attr::try_from(value.clone())? {
+        Attr::L(items) => items,
+        Attr::Null => vec![],
+        _ => return Err(set_item_error(name, &value)),
+    };
+
+    let set = match name {
+        STRING_SET => Attr::Ss(
+            items
+                .into_iter()
+                .map(|item| match Attr::try_from(item.clone())? {
+                    Attr::S(value) => Ok(value),
+                    _ => Err(set_item_error(name, &item)),
+                })
+                .collect::<Result<_, _>>()?,
+        ),
+        NUMBER_SET => Attr::Ns(
+            items
+                .into_iter()
+                .map(|item| match Attr::try_from(item.clone())? {
+                    Attr::N(value) => Ok(value),
+                    _ => Err(set_item_error(name, &item)),
+                })
+                .collect::<Result<_, _>>()?,
+        ),
+        _ => Attr::Bs(
+            items
+                .into_iter()
+                .map(|item| match Attr::try_from(item.clone())? {
+                    Attr::B(value) => Ok(value),
+                    // `Vec<u8>` is serialized as a list of numbers.
+                    Attr::L(bytes) => bytes
+                        .into_iter()
+                        .map(|byte| {
+                            match Attr::try_from(byte)? {
+                                Attr::N(byte) => byte.parse::<u8>().ok(),
+                                _ => None,
+                            }
+                            .ok_or_else(|| set_item_error(name, &item))
+                        })
+                        .collect(),
+                    _ => Err(set_item_error(name, &item)),
+                })
+                .collect::<Result<_, _>>()?,
+        ),
+    };
+    Ok(set.into())
+}
+
+pub struct SerializeList {
+    items: Vec<AttributeValue>,
+}
+
+impl SerializeList {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), ConversionError> {
+        self.items.push(value.serialize(Serializer)?);
+        Ok(())
+    }
+}
+
+impl ser::SerializeSeq for SerializeList {
+    type Ok = AttributeValue;
+    type Error = ConversionError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Attr::L(self.items).into())
+    }
+}
+
+impl ser::SerializeTuple for SerializeList {
+    type Ok = AttributeValue;
+    type Error = ConversionError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeList {
+    type Ok = AttributeValue;
+    type Error = ConversionError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+pub struct SerializeMap {
+    entries: HashMap<String, AttributeValue>,
+    key: Option<String>,
+}
+
+impl ser::SerializeMap for SerializeMap {
+    type Ok = AttributeValue;
+    type Error = ConversionError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Self::Error> {
+        self.key = Some(key.serialize(KeySerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| ConversionError::Serde("map value without a key".to_owned()))?;
+        self.entries.insert(key, value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Attr::M(self.entries).into())
+    }
+}
+
+impl ser::SerializeStruct for SerializeMap {
+    type Ok = AttributeValue;
+    type Error = ConversionError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        self.entries
+            .insert(key.to_owned(), value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        ser::SerializeMap::end(self)
+    }
+}
+
+/// Wraps the value of an enum variant into a map keyed by the variant name.
+pub struct SerializeVariant<T> {
+    variant: &'static str,
+    inner: T,
+}
+
+impl ser::SerializeTupleVariant for SerializeVariant<SerializeList> {
+    type Ok = AttributeValue;
+    type Error = ConversionError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.inner.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        let value = ser::SerializeSeq::end(self.inner)?;
+        Ok(Attr::M(HashMap::from([(self.variant.to_owned(), value)])).into())
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeVariant<SerializeMap> {
+    type Ok = AttributeValue;
+    type Error = ConversionError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        ser::SerializeStruct::serialize_field(&mut self.inner, key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        let value = ser::SerializeMap::end(self.inner)?;
+        Ok(Attr::M(HashMap::from([(self.variant.to_owned(), value)])).into())
+    }
+}
+
+fn key_error() -> ConversionError {
+    ConversionError::Serde("map keys must be strings or numbers".to_owned())
+}
+
+// Serializes map keys, which are always strings in DynamoDB.
+struct KeySerializer;
+
+macro_rules! serialize_key {
+    ($($method: ident => $ty: ty,)*) => {
+        $(
+            fn $method(self, value: $ty) -> Result<Self::Ok, Self::Error> {
+                Ok(value.to_string())
+            }
+        )*
+    };
+}
+
+impl ser::Serializer for KeySerializer {
+    type Ok = String;
+    type Error = ConversionError;
+    type SerializeSeq = Impossible<String, ConversionError>;
+    type SerializeTuple = Impossible<String, ConversionError>;
+    type SerializeTupleStruct = Impossible<String, ConversionError>;
+    type SerializeTupleVariant = Impossible<String, ConversionError>;
+    type SerializeMap = Impossible<String, ConversionError>;
+    type SerializeStruct = Impossible<String, ConversionError>;
+    type SerializeStructVariant = Impossible<String, ConversionError>;
+
+    serialize_key! {
+        serialize_bool => bool,
+        serialize_i8 => i8,
+        serialize_i16 => i16,
+        serialize_i32 => i32,
+        serialize_i64 => i64,
+        serialize_i128 => i128,
+        serialize_u8 => u8,
+        serialize_u16 => u16,
+        serialize_u32 => u32,
+        serialize_u64 => u64,
+        serialize_u128 => u128,
+        serialize_f32 => f32,
+        serialize_f64 => f64,
+        serialize_char => char,
+        serialize_str => &str,
+    }
+
+    fn serialize_bytes(self, _value: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Err(key_error())
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Err(key_error())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Err(key_error())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Err(key_error())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        Ok(variant.to_owned())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        Err(key_error())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Err(key_error())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Err(key_error())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Err(key_error())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(key_error())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(key_error())
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Err(key_error())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(key_error())
+    }
+}
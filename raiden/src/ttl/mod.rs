@@ -0,0 +1,145 @@
+//! Helpers for TTL attributes marked with `#[raiden(ttl)]`.
+//!
+//! DynamoDB expects TTL attributes to hold the expiry as seconds since the unix epoch,
+//! so `SystemTime` is stored as a number.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::clock::{Clock, DefaultClock};
+use crate::{
+    AttributeNames, AttributeValue, AttributeValues, ConversionError, FromAttribute, IntoAttribute,
+    Timestamp,
+};
+
+pub type ExpiredFilter = (String, AttributeNames, AttributeValues);
+
+/// Returns the expiry `duration` from now.
+///
+/// The clock can be fixed with `RAIDEN_CLOCK_FIXED_BY` in the same way as timestamp fields.
+pub fn expires_in(duration: Duration) -> SystemTime {
+    DefaultClock::now() + duration
+}
+
+/// A type which can be used as a TTL attribute.
+pub trait Expiry {
+    /// Returns the expiry, or `None` if the item never expires.
+    fn expires_at(&self) -> Option<SystemTime>;
+
+    fn is_expired(&self, now: SystemTime) -> bool {
+        self.expires_at()
+            .is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+impl Expiry for SystemTime {
+    fn expires_at(&self) -> Option<SystemTime> {
+        Some(*self)
+    }
+}
+
+impl Expiry for i64 {
+    fn expires_at(&self) -> Option<SystemTime> {
+        Some(UNIX_EPOCH + Duration::from_secs((*self).max(0) as u64))
+    }
+}
+
+impl Expiry for u64 {
+    fn expires_at(&self) -> Option<SystemTime> {
+        Some(UNIX_EPOCH + Duration::from_secs(*self))
+    }
+}
+
+impl<T: Expiry> Expiry for Option<T> {
+    fn expires_at(&self) -> Option<SystemTime> {
+        self.as_ref().and_then(Expiry::expires_at)
+    }
+}
+
+impl IntoAttribute for SystemTime {
+    fn into_attr(self) -> AttributeValue {
+        u64::from_system_time(self).into_attr()
+    }
+}
+
+impl FromAttribute for SystemTime {
+    fn from_attr(value: Option<AttributeValue>) -> Result<Self, ConversionError> {
+        u64::from_attr(value).map(|secs| UNIX_EPOCH + Duration::from_secs(secs))
+    }
+}
+
+impl Timestamp for SystemTime {
+    fn from_system_time(time: SystemTime) -> Self {
+        time
+    }
+}
+
+/// Builds the filter which drops items expired at `now` but not deleted by DynamoDB yet.
+///
+/// Like DynamoDB TTL, an item without the attribute or with a `NULL` one, e.g. put from `None`,
+/// never expires.
+pub fn not_expired_filter(
+    attr_name: &str,
+    now: SystemTime,
+    placeholders: &mut crate::Placeholders,
+) -> ExpiredFilter {
+    let name_placeholder = crate::name_placeholder(attr_name);
+    let mut values = AttributeValues::new();
+    let value_placeholder = placeholders.bind(now.into_attr(), &mut values);
+
+    (
+        format!("NOT ({name_placeholder} <= {value_placeholder})"),
+        AttributeNames::from([(name_placeholder, attr_name.to_owned())]),
+        values,
+    )
+}
+
+/// Combines the expired filter with the filter expression already set on a request.
+pub fn apply_expired_filter(
+    filter_expression: Option<String>,
+    attr_names: Option<AttributeNames>,
+    attr_values: Option<AttributeValues>,
+    expired_filter: ExpiredFilter,
+) -> (String, Option<AttributeNames>, Option<AttributeValues>) {
+    crate::merge_condition(filter_expression, attr_names, attr_values, expired_filter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_time_attribute() {
+        let time = UNIX_EPOCH + Duration::from_secs(1_600_000_000);
+
+        assert_eq!(SystemTime::from_attr(Some(time.into_attr())).unwrap(), time);
+        assert!(time.is_expired(time));
+        assert!(!Some(time).is_expired(time - Duration::from_secs(1)));
+        assert!(!None::<SystemTime>.is_expired(time));
+    }
+
+    #[test]
+    fn test_apply_expired_filter() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_600_000_000);
+        let filter = not_expired_filter("expiresAt", now, &mut crate::Placeholders::new());
+        let placeholder = filter.2.keys().next().cloned().unwrap();
+        let (expression, names, values) = apply_expired_filter(
+            Some("#name = :name".to_owned()),
+            Some(AttributeNames::from([(
+                "#name".to_owned(),
+                "name".to_owned(),
+            )])),
+            Some(AttributeValues::from([(
+                ":name".to_owned(),
+                "bokuweb".into_attr(),
+            )])),
+            filter,
+        );
+
+        assert_eq!(
+            expression,
+            format!("(#name = :name) AND (NOT (#expiresAt <= {placeholder}))")
+        );
+        assert_eq!(names.unwrap().len(), 2);
+        assert_eq!(values.unwrap().len(), 2);
+    }
+}
@@ -1,35 +1,46 @@
 use super::*;
 
 pub struct Add<T: super::IntoAttrName> {
-    target: T,
+    target: super::AttrPath,
+    _token: std::marker::PhantomData<fn() -> T>,
 }
 
 pub struct AddExpressionFilled<T: super::IntoAttrName> {
-    target: T,
-    value: (super::Placeholder, super::AttributeValue),
+    target: super::AttrPath,
+    value: super::AttributeValue,
+    _token: std::marker::PhantomData<fn() -> T>,
 }
 
 impl<T: super::IntoAttrName> Add<T> {
     pub fn new(target: T) -> Self {
-        Self { target }
+        Self::path(target)
+    }
+
+    /// Targets a nested document path, e.g. `#profile.#tags`.
+    pub fn path(target: impl super::IntoAttrPath) -> Self {
+        Self {
+            target: target.into_attr_path(),
+            _token: std::marker::PhantomData,
+        }
     }
 
     pub fn value(self, value: impl super::IntoAttribute) -> AddExpressionFilled<T> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let value = (placeholder, value.into_attr());
-        let Add { target } = self;
-        AddExpressionFilled::<T> { target, value }
+        AddExpressionFilled::<T> {
+            target: self.target,
+            value: value.into_attr(),
+            _token: std::marker::PhantomData,
+        }
     }
 }
 
 impl<T: super::IntoAttrName> UpdateAddExpressionBuilder for AddExpressionFilled<T> {
-    fn build(self) -> (String, super::AttributeNames, super::AttributeValues) {
-        let attr = self.target.into_attr_name();
-        let attr_name = format!("#{attr}");
-
+    fn build_with(
+        self,
+        placeholders: &mut super::Placeholders,
+    ) -> (String, super::AttributeNames, super::AttributeValues) {
         let mut names: super::AttributeNames = std::collections::HashMap::new();
         let mut values: super::AttributeValues = std::collections::HashMap::new();
-        let (placeholder, value) = self.value;
+        let value = self.value;
 
         // See. https://github.com/raiden-rs/raiden/issues/57
         //      https://github.com/raiden-rs/raiden/issues/58
@@ -42,9 +53,9 @@ impl<T: super::IntoAttrName> UpdateAddExpressionBuilder for AddExpressionFilled<
             return ("".to_owned(), names, values);
         }
 
-        names.insert(attr_name.clone(), attr);
+        let attr_name = path_name(&self.target, &mut names);
+        let placeholder = placeholders.bind(value, &mut values);
         let expression = format!("{attr_name} {placeholder}");
-        values.insert(placeholder, value);
         (expression, names, values)
     }
 }
@@ -73,7 +84,6 @@ mod tests {
 
     #[test]
     fn test_add_value_expression() {
-        crate::value_id::reset_value_id();
         let (expression, names, values) = Add::new(UserAttrNames::Age).value(42).build();
         let mut expected_names = std::collections::HashMap::new();
         let mut expected_values = std::collections::HashMap::new();
@@ -7,8 +7,8 @@ pub use delete::*;
 pub use set::*;
 
 use super::{
-    generate_value_id, AttributeNames, AttributeValue, AttributeValues, IntoAttrName,
-    IntoAttribute, Placeholder,
+    AttrPath, AttributeNames, AttributeValue, AttributeValues, IntoAttrName, IntoAttrPath,
+    IntoAttribute, Placeholders,
 };
 
 pub enum SetOrRemove {
@@ -16,14 +16,41 @@ pub enum SetOrRemove {
     Remove(String, AttributeNames),
 }
 
-pub trait UpdateSetExpressionBuilder {
-    fn build(self) -> SetOrRemove;
+pub trait UpdateSetExpressionBuilder: Sized {
+    /// Builds the action, allocating its placeholders from the request's `placeholders`.
+    fn build_with(self, placeholders: &mut Placeholders) -> SetOrRemove;
+
+    fn build(self) -> SetOrRemove {
+        self.build_with(&mut Placeholders::new())
+    }
 }
 
-pub trait UpdateAddExpressionBuilder {
-    fn build(self) -> (String, AttributeNames, AttributeValues);
+pub trait UpdateAddExpressionBuilder: Sized {
+    /// Builds the action, allocating its placeholders from the request's `placeholders`.
+    fn build_with(
+        self,
+        placeholders: &mut Placeholders,
+    ) -> (String, AttributeNames, AttributeValues);
+
+    fn build(self) -> (String, AttributeNames, AttributeValues) {
+        self.build_with(&mut Placeholders::new())
+    }
+}
+
+pub trait UpdateDeleteExpressionBuilder: Sized {
+    /// Builds the action, allocating its placeholders from the request's `placeholders`.
+    fn build_with(
+        self,
+        placeholders: &mut Placeholders,
+    ) -> (String, AttributeNames, AttributeValues);
+
+    fn build(self) -> (String, AttributeNames, AttributeValues) {
+        self.build_with(&mut Placeholders::new())
+    }
 }
 
-pub trait UpdateDeleteExpressionBuilder {
-    fn build(self) -> (String, AttributeNames, AttributeValues);
+// Registers the names of `path` and returns its expression, e.g. `#profile.#level[0]`.
+fn path_name(path: &AttrPath, names: &mut AttributeNames) -> String {
+    names.extend(path.attribute_names());
+    path.expression()
 }
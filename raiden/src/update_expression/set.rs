@@ -1,89 +1,118 @@
 use super::*;
 
 pub struct Set<T: super::IntoAttrName> {
-    target: T,
-    index: Option<usize>,
-    _value: Option<SetValue<T>>,
+    target: super::AttrPath,
+    _token: std::marker::PhantomData<fn() -> T>,
 }
 
 pub struct SetExpressionFilledWithoutOperation<T: super::IntoAttrName> {
-    target: T,
-    index: Option<usize>,
-    value: SetValue<T>,
+    target: super::AttrPath,
+    value: SetValue,
     if_not_exists: bool,
+    _token: std::marker::PhantomData<fn() -> T>,
 }
 
 pub struct SetExpressionFilled<T: super::IntoAttrName> {
-    target: T,
-    _index: Option<usize>,
-    value: SetValue<T>,
-    _if_not_exists: bool,
+    target: super::AttrPath,
+    value: SetValue,
+    if_not_exists: bool,
     operation: SetOperation,
-    operand: Operand<T>,
+    operand: Operand,
+    _token: std::marker::PhantomData<fn() -> T>,
+}
+
+/// `SET target = list_append(...)`, built with [`Set::list_append`] or [`Set::list_prepend`].
+pub struct SetListAppend<T: super::IntoAttrName> {
+    target: super::AttrPath,
+    values: super::AttributeValue,
+    front: bool,
+    if_not_exists: bool,
+    _token: std::marker::PhantomData<fn() -> T>,
 }
 
 enum SetOperation {
     Add,
-    _Sub,
+    Sub,
 }
 
 impl std::fmt::Display for SetOperation {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match *self {
             SetOperation::Add => write!(f, "+"),
-            SetOperation::_Sub => write!(f, "-"),
+            SetOperation::Sub => write!(f, "-"),
         }
     }
 }
 
 #[allow(clippy::large_enum_variant)]
-enum SetValue<T: super::IntoAttrName> {
-    Attr(T),
-    Value(super::Placeholder, super::AttributeValue),
-    // ListAppend(T, List)
+enum SetValue {
+    Attr(super::AttrPath),
+    Value(super::AttributeValue),
 }
 
 #[allow(clippy::large_enum_variant)]
-enum Operand<T: super::IntoAttrName> {
-    _Attr(T),
-    Value(super::Placeholder, super::AttributeValue),
+enum Operand {
+    Attr(super::AttrPath),
+    Value(super::AttributeValue),
 }
 
 impl<T: super::IntoAttrName> Set<T> {
     pub fn new(target: T) -> Self {
+        Self::path(target)
+    }
+
+    /// Targets a nested document path, e.g. `#profile.#level` or `#tags[2]`.
+    pub fn path(target: impl super::IntoAttrPath) -> Self {
         Self {
-            target,
-            index: None,
-            _value: None,
+            target: target.into_attr_path(),
+            _token: std::marker::PhantomData,
         }
     }
 
     // For LIST/SET
     pub fn index(mut self, index: usize) -> Self {
-        self.index = Some(index);
+        self.target = self.target.index(index);
         self
     }
 
     pub fn value(self, value: impl super::IntoAttribute) -> SetExpressionFilledWithoutOperation<T> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let value = SetValue::<T>::Value(placeholder, value.into_attr());
-        let Set { target, index, .. } = self;
+        self.filled(SetValue::Value(value.into_attr()))
+    }
+
+    pub fn attr(self, attr: impl super::IntoAttrPath) -> SetExpressionFilledWithoutOperation<T> {
+        self.filled(SetValue::Attr(attr.into_attr_path()))
+    }
+
+    fn filled(self, value: SetValue) -> SetExpressionFilledWithoutOperation<T> {
         SetExpressionFilledWithoutOperation::<T> {
-            target,
-            index,
+            target: self.target,
             value,
             if_not_exists: false,
+            _token: std::marker::PhantomData,
         }
     }
 
-    pub fn attr(self, attr: T) -> SetExpressionFilledWithoutOperation<T> {
-        let value = SetValue::<T>::Attr(attr);
-        let Set { target, index, .. } = self;
-        SetExpressionFilledWithoutOperation::<T> {
-            target,
-            index,
-            value,
+    /// Appends `values` to the end of the list, e.g. `#events = list_append(#events, :value0)`.
+    ///
+    /// `values` must be a non-empty list.
+    pub fn list_append(self, values: impl super::IntoAttribute) -> SetListAppend<T> {
+        self.into_list_append(values, false)
+    }
+
+    /// Prepends `values` to the front of the list, e.g. `#events = list_append(:value0, #events)`.
+    ///
+    /// `values` must be a non-empty list.
+    pub fn list_prepend(self, values: impl super::IntoAttribute) -> SetListAppend<T> {
+        self.into_list_append(values, true)
+    }
+
+    fn into_list_append(self, values: impl super::IntoAttribute, front: bool) -> SetListAppend<T> {
+        SetListAppend::<T> {
+            target: self.target,
+            values: values.into_attr(),
+            front,
             if_not_exists: false,
+            _token: std::marker::PhantomData,
         }
     }
 }
@@ -95,58 +124,75 @@ impl<T: super::IntoAttrName> SetExpressionFilledWithoutOperation<T> {
     }
 
     pub fn add_value(self, value: impl super::IntoAttribute) -> SetExpressionFilled<T> {
-        let placeholder = format!(":value{}", super::generate_value_id());
-        let operand = Operand::<T>::Value(placeholder, value.into_attr());
+        self.operation(SetOperation::Add, Operand::Value(value.into_attr()))
+    }
+
+    pub fn sub_value(self, value: impl super::IntoAttribute) -> SetExpressionFilled<T> {
+        self.operation(SetOperation::Sub, Operand::Value(value.into_attr()))
+    }
+
+    pub fn add_attr(self, attr: impl super::IntoAttrPath) -> SetExpressionFilled<T> {
+        self.operation(SetOperation::Add, Operand::Attr(attr.into_attr_path()))
+    }
+
+    pub fn sub_attr(self, attr: impl super::IntoAttrPath) -> SetExpressionFilled<T> {
+        self.operation(SetOperation::Sub, Operand::Attr(attr.into_attr_path()))
+    }
+
+    fn operation(self, operation: SetOperation, operand: Operand) -> SetExpressionFilled<T> {
         let SetExpressionFilledWithoutOperation {
             target,
-            index,
             value,
             if_not_exists,
+            ..
         } = self;
         SetExpressionFilled::<T> {
             target,
-            _index: index,
             value,
-            _if_not_exists: if_not_exists,
-            operation: SetOperation::Add,
+            if_not_exists,
+            operation,
             operand,
+            _token: std::marker::PhantomData,
         }
     }
 }
 
-impl<T: super::IntoAttrName> UpdateSetExpressionBuilder for SetExpressionFilledWithoutOperation<T> {
-    fn build(self) -> SetOrRemove {
-        let attr = self.target.into_attr_name();
-        let attr_name = format!("#{attr}");
+impl<T: super::IntoAttrName> SetListAppend<T> {
+    /// Starts from an empty list when the target does not exist yet.
+    pub fn if_not_exists(mut self) -> Self {
+        self.if_not_exists = true;
+        self
+    }
+}
 
+impl<T: super::IntoAttrName> UpdateSetExpressionBuilder for SetExpressionFilledWithoutOperation<T> {
+    fn build_with(self, placeholders: &mut super::Placeholders) -> SetOrRemove {
         let mut names: super::AttributeNames = std::collections::HashMap::new();
-        names.insert(attr_name.clone(), attr);
+        let attr_name = path_name(&self.target, &mut names);
         let mut values: super::AttributeValues = std::collections::HashMap::new();
         match self.value {
             SetValue::Attr(a) => {
-                let set_attr = a.into_attr_name();
-                let set_attr_name = format!("#{set_attr}");
+                let set_attr_name = path_name(&a, &mut names);
                 let expression = if self.if_not_exists {
                     format!("{attr_name} = if_not_exists({attr_name}, {set_attr_name})")
                 } else {
                     format!("{attr_name} = {set_attr_name}")
                 };
-                names.insert(set_attr_name, set_attr);
                 SetOrRemove::Set(expression, names, values)
             }
-            SetValue::Value(placeholder, value) => {
+            SetValue::Value(value) => {
                 // See. https://github.com/raiden-rs/raiden/issues/57
                 //      https://github.com/raiden-rs/raiden/issues/58
                 if crate::is_attr_value_empty(&value) {
                     // Use remove instead of set
                     return SetOrRemove::Remove(attr_name, names);
                 }
+                let placeholder = placeholders.bind(value, &mut values);
                 let expression = if self.if_not_exists {
                     format!("{attr_name} = if_not_exists({attr_name}, {placeholder})")
                 } else {
                     format!("{attr_name} = {placeholder}")
                 };
-                values.insert(placeholder, value);
                 SetOrRemove::Set(expression, names, values)
             }
         }
@@ -154,47 +200,74 @@ impl<T: super::IntoAttrName> UpdateSetExpressionBuilder for SetExpressionFilledW
 }
 
 impl<T: super::IntoAttrName> UpdateSetExpressionBuilder for SetExpressionFilled<T> {
-    fn build(self) -> SetOrRemove {
-        let attr = self.target.into_attr_name();
-        let attr_name = format!("#{attr}");
-
+    fn build_with(self, placeholders: &mut super::Placeholders) -> SetOrRemove {
         let mut names: super::AttributeNames = std::collections::HashMap::new();
-        names.insert(attr_name.clone(), attr);
+        let attr_name = path_name(&self.target, &mut names);
         let mut values: super::AttributeValues = std::collections::HashMap::new();
 
+        let left = match self.value {
+            SetValue::Attr(a) => path_name(&a, &mut names),
+            SetValue::Value(value) => placeholders.bind(value, &mut values),
+        };
+        let left = if self.if_not_exists {
+            format!("if_not_exists({attr_name}, {left})")
+        } else {
+            left
+        };
+
         let op = format!("{}", self.operation);
         let op_expression = match self.operand {
-            Operand::_Attr(a) => {
-                let operand_attr = a.into_attr_name();
-                let operand_attr_name = format!("#{operand_attr}");
-                let val = format!("{op} {operand_attr_name}");
-                names.insert(operand_attr_name, operand_attr);
-                val
+            Operand::Attr(a) => {
+                let operand_attr_name = path_name(&a, &mut names);
+                format!("{op} {operand_attr_name}")
             }
-            Operand::Value(placeholder, value) => {
-                let val = format!("{op} {placeholder}");
-                values.insert(placeholder, value);
-                val
+            Operand::Value(value) => {
+                let placeholder = placeholders.bind(value, &mut values);
+                format!("{op} {placeholder}")
             }
         };
 
-        match self.value {
-            SetValue::Attr(a) => {
-                let set_attr = a.into_attr_name();
-                let set_attr_name = format!("#{set_attr}");
-                let expression = format!("{attr_name} = {set_attr_name} {op_expression}");
-                names.insert(set_attr_name, set_attr);
-                SetOrRemove::Set(expression, names, values)
-            }
-            SetValue::Value(placeholder, value) => {
-                let expression = format!("{attr_name} = {placeholder} {op_expression}");
-                values.insert(placeholder, value);
-                SetOrRemove::Set(expression, names, values)
-            }
-        }
+        let expression = format!("{attr_name} = {left} {op_expression}");
+        SetOrRemove::Set(expression, names, values)
+    }
+}
+
+impl<T: super::IntoAttrName> UpdateSetExpressionBuilder for SetListAppend<T> {
+    fn build_with(self, placeholders: &mut super::Placeholders) -> SetOrRemove {
+        let mut names: super::AttributeNames = std::collections::HashMap::new();
+        let attr_name = path_name(&self.target, &mut names);
+        let mut values: super::AttributeValues = std::collections::HashMap::new();
+        let placeholder = placeholders.bind(self.values, &mut values);
+
+        let list = if self.if_not_exists {
+            let empty = placeholders.bind(empty_list(), &mut values);
+            format!("if_not_exists({attr_name}, {empty})")
+        } else {
+            attr_name.clone()
+        };
+
+        let expression = if self.front {
+            format!("{attr_name} = list_append({placeholder}, {list})")
+        } else {
+            format!("{attr_name} = list_append({list}, {placeholder})")
+        };
+        SetOrRemove::Set(expression, names, values)
+    }
+}
+
+#[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
+fn empty_list() -> super::AttributeValue {
+    super::AttributeValue {
+        l: Some(vec![]),
+        ..super::AttributeValue::default()
     }
 }
 
+#[cfg(feature = "aws-sdk")]
+fn empty_list() -> super::AttributeValue {
+    super::AttributeValue::L(vec![])
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -221,7 +294,6 @@ mod tests {
 
     #[test]
     fn test_set_value_expression() {
-        crate::value_id::reset_value_id();
         if let SetOrRemove::Set(expression, names, values) =
             Set::new(UserAttrNames::Name).value("updated!!").build()
         {
@@ -239,7 +311,6 @@ mod tests {
 
     #[test]
     fn test_set_if_not_exists() {
-        crate::value_id::reset_value_id();
         if let SetOrRemove::Set(expression, names, values) = Set::new(UserAttrNames::Name)
             .value("updated!!")
             .if_not_exists()
@@ -262,7 +333,6 @@ mod tests {
 
     #[test]
     fn test_set_attr_expression_with_add_value() {
-        crate::value_id::reset_value_id();
         if let SetOrRemove::Set(expression, names, values) = Set::new(UserAttrNames::Age)
             .attr(UserAttrNames::Age)
             .add_value(10)
@@ -279,4 +349,109 @@ mod tests {
         }
         panic!("should not pass");
     }
+
+    #[test]
+    fn test_set_attr_expression_with_sub_attr() {
+        if let SetOrRemove::Set(expression, names, values) = Set::new(UserAttrNames::Age)
+            .attr(UserAttrNames::Name)
+            .sub_attr(UserAttrNames::Age)
+            .build()
+        {
+            let mut expected_names = std::collections::HashMap::new();
+            expected_names.insert("#age".to_owned(), "age".to_owned());
+            expected_names.insert("#name".to_owned(), "name".to_owned());
+            assert_eq!(expression, "#age = #name - #age".to_owned());
+            assert_eq!(names, expected_names);
+            assert!(values.is_empty());
+            return;
+        }
+        panic!("should not pass");
+    }
+
+    #[test]
+    fn test_set_if_not_exists_with_add_value() {
+        if let SetOrRemove::Set(expression, names, values) = Set::new(UserAttrNames::Age)
+            .value(0)
+            .if_not_exists()
+            .add_value(1)
+            .build()
+        {
+            let mut expected_names = std::collections::HashMap::new();
+            let mut expected_values = std::collections::HashMap::new();
+            expected_names.insert("#age".to_owned(), "age".to_owned());
+            expected_values.insert(":value0".to_owned(), 0.into_attr());
+            expected_values.insert(":value1".to_owned(), 1.into_attr());
+            assert_eq!(
+                expression,
+                "#age = if_not_exists(#age, :value0) + :value1".to_owned()
+            );
+            assert_eq!(names, expected_names);
+            assert_eq!(values, expected_values);
+            return;
+        }
+        panic!("should not pass");
+    }
+
+    #[test]
+    fn test_set_list_append() {
+        if let SetOrRemove::Set(expression, _, values) = Set::new(UserAttrNames::Name)
+            .list_append(vec!["a".to_owned()])
+            .if_not_exists()
+            .build()
+        {
+            assert_eq!(
+                expression,
+                "#name = list_append(if_not_exists(#name, :value1), :value0)".to_owned()
+            );
+            assert_eq!(values.get(":value1"), Some(&empty_list()));
+        } else {
+            panic!("should not pass");
+        }
+
+        if let SetOrRemove::Set(expression, _, values) = Set::new(UserAttrNames::Name)
+            .list_prepend(vec!["a".to_owned()])
+            .build()
+        {
+            assert_eq!(expression, "#name = list_append(:value0, #name)".to_owned());
+            assert_eq!(values.len(), 1);
+        } else {
+            panic!("should not pass");
+        }
+    }
+
+    #[test]
+    fn test_set_nested_path() {
+        if let SetOrRemove::Set(expression, names, values) =
+            Set::<UserAttrNames>::path(UserAttrNames::Name.field("first-name"))
+                .value("bokuweb")
+                .build()
+        {
+            let mut expected_names = std::collections::HashMap::new();
+            let mut expected_values = std::collections::HashMap::new();
+            expected_names.insert("#name".to_owned(), "name".to_owned());
+            expected_names.insert("#_66697273742d6e616d65".to_owned(), "first-name".to_owned());
+            expected_values.insert(":value0".to_owned(), "bokuweb".into_attr());
+            assert_eq!(
+                expression,
+                "#name.#_66697273742d6e616d65 = :value0".to_owned()
+            );
+            assert_eq!(names, expected_names);
+            assert_eq!(values, expected_values);
+            return;
+        }
+        panic!("should not pass");
+    }
+
+    #[test]
+    fn test_set_index() {
+        if let SetOrRemove::Set(expression, _, _) = Set::new(UserAttrNames::Name)
+            .index(2)
+            .attr(UserAttrNames::Name.index(0))
+            .build()
+        {
+            assert_eq!(expression, "#name[2] = #name[0]".to_owned());
+            return;
+        }
+        panic!("should not pass");
+    }
 }
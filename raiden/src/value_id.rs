@@ -1,16 +1,16 @@
-use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
 static VALUE_ID: AtomicUsize = AtomicUsize::new(0);
 
+#[deprecated(note = "placeholders are allocated per request by `Placeholders`")]
 pub fn generate_value_id() -> usize {
-    use std::sync::atomic::Ordering;
-
-    let id = VALUE_ID.load(Ordering::Relaxed);
-    VALUE_ID.store(id.wrapping_add(1), Ordering::Relaxed);
-    id
+    VALUE_ID.fetch_add(1, Ordering::Relaxed)
 }
 
+/// Placeholders no longer depend on a global counter, so it only resets the one of
+/// [`generate_value_id`].
+#[deprecated(
+    note = "placeholders are allocated per request by `Placeholders`, so there is nothing to reset"
+)]
 pub fn reset_value_id() {
-    use std::sync::atomic::Ordering;
-
     VALUE_ID.store(0, Ordering::Relaxed);
 }
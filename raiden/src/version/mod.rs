@@ -0,0 +1,240 @@
+//! Helpers for optimistic locking with `#[raiden(version)]`.
+
+use crate::{
+    AttributeNames, AttributeValue, AttributeValues, ConversionError, IntoAttribute, Placeholders,
+    RaidenError,
+};
+
+/// The condition of a versioned request, which ensures the stored version equals `expected`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VersionCondition {
+    attr_name: String,
+    expected: Option<AttributeValue>,
+    condition: (String, AttributeNames, AttributeValues),
+}
+
+impl VersionCondition {
+    // Whether `item`, or the lack of one, holds another version than the expected one.
+    fn conflicts_with(&self, item: Option<&AttributeValues>) -> bool {
+        item.and_then(|item| item.get(&self.attr_name)) != self.expected.as_ref()
+    }
+}
+
+/// Builds the condition which ensures the stored version equals `expected`.
+///
+/// `None` means that the item must not exist yet.
+pub fn version_condition(
+    attr_name: &str,
+    expected: Option<AttributeValue>,
+    placeholders: &mut Placeholders,
+) -> VersionCondition {
+    let name_placeholder = crate::name_placeholder(attr_name);
+    let names = AttributeNames::from([(name_placeholder.clone(), attr_name.to_owned())]);
+
+    let condition = match &expected {
+        Some(expected) => {
+            let mut values = AttributeValues::new();
+            let value_placeholder = placeholders.bind(expected.clone(), &mut values);
+            let expression = format!("{name_placeholder} = {value_placeholder}");
+            (expression, names, values)
+        }
+        None => (
+            format!("attribute_not_exists({name_placeholder})"),
+            names,
+            AttributeValues::new(),
+        ),
+    };
+    VersionCondition {
+        attr_name: attr_name.to_owned(),
+        expected,
+        condition,
+    }
+}
+
+/// Builds the `ADD` expression which increments the version by one.
+pub fn increment_version(
+    attr_name: &str,
+    placeholders: &mut Placeholders,
+) -> (String, AttributeNames, AttributeValues) {
+    let name_placeholder = crate::name_placeholder(attr_name);
+    let mut values = AttributeValues::new();
+    let value_placeholder = placeholders.bind(1.into_attr(), &mut values);
+    (
+        format!("{name_placeholder} {value_placeholder}"),
+        AttributeNames::from([(name_placeholder, attr_name.to_owned())]),
+        values,
+    )
+}
+
+/// The error of a put whose version is already the maximum value of its type.
+pub fn version_overflow(attr_name: &str) -> RaidenError {
+    RaidenError::AttributeConvertError {
+        attr_name: attr_name.to_owned(),
+        source: ConversionError::message("the version can not be incremented any further"),
+    }
+}
+
+/// Combines the version condition with the condition already set on a request.
+pub fn apply_version_condition(
+    condition_expression: Option<String>,
+    attr_names: Option<AttributeNames>,
+    attr_values: Option<AttributeValues>,
+    version_condition: &VersionCondition,
+) -> (String, Option<AttributeNames>, Option<AttributeValues>) {
+    crate::merge_condition(
+        condition_expression,
+        attr_names,
+        attr_values,
+        version_condition.condition.clone(),
+    )
+}
+
+/// Reports a failed condition of a versioned request as a version conflict if the stored version
+/// differs from the expected one, since the other conditions of the request may have failed.
+///
+/// The request should return the stored item with `ReturnValuesOnConditionCheckFailure=ALL_OLD`,
+/// and `with_item` tells whether its caller asked for it too; it is dropped from the error otherwise.
+#[cfg(feature = "aws-sdk")]
+pub fn into_version_conflict(
+    error: RaidenError,
+    version_condition: &VersionCondition,
+    with_item: bool,
+) -> RaidenError {
+    match error {
+        RaidenError::ConditionalCheckFailedWithItem { message, item } => {
+            if version_condition.conflicts_with(Some(&item)) {
+                RaidenError::VersionConflict(message)
+            } else if with_item {
+                RaidenError::ConditionalCheckFailedWithItem { message, item }
+            } else {
+                RaidenError::ConditionalCheckFailed(message)
+            }
+        }
+        // No item is returned when there is none.
+        RaidenError::ConditionalCheckFailed(message) if version_condition.conflicts_with(None) => {
+            RaidenError::VersionConflict(message)
+        }
+        error => error,
+    }
+}
+
+/// Reports a failed condition of a versioned request as a version conflict if the stored version
+/// differs from the expected one, since the other conditions of the request may have failed.
+///
+/// Since rusoto can not return the stored item with the failed condition, its version is read
+/// with a consistent read of `key`. The error is kept as is if the read fails.
+#[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
+pub async fn into_version_conflict(
+    error: RaidenError,
+    version_condition: &VersionCondition,
+    client: &crate::SharedBackend,
+    table_name: String,
+    key: AttributeValues,
+) -> RaidenError {
+    let RaidenError::ConditionalCheckFailed(message) = error else {
+        return error;
+    };
+    let name_placeholder = crate::name_placeholder(&version_condition.attr_name);
+    let request = crate::GetItemRequest {
+        table_name,
+        key,
+        consistent_read: Some(true),
+        projection_expression: Some(name_placeholder.clone()),
+        expression_attribute_names: Some(AttributeNames::from([(
+            name_placeholder,
+            version_condition.attr_name.clone(),
+        )])),
+        ..Default::default()
+    };
+    match client.get_item(request).await {
+        Ok(output) if version_condition.conflicts_with(output.item.as_ref()) => {
+            RaidenError::VersionConflict(message)
+        }
+        _ => RaidenError::ConditionalCheckFailed(message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_condition_for_new_item() {
+        let VersionCondition {
+            condition: (expression, names, values),
+            ..
+        } = version_condition("version", None, &mut Placeholders::new());
+
+        assert_eq!(expression, "attribute_not_exists(#version)");
+        assert_eq!(names.get("#version"), Some(&"version".to_owned()));
+        assert!(values.is_empty());
+    }
+
+    #[test]
+    fn test_version_placeholders_of_non_identifier_name() {
+        let mut placeholders = Placeholders::new();
+        let VersionCondition {
+            condition: (expression, names, _),
+            ..
+        } = version_condition("row-version", Some(2.into_attr()), &mut placeholders);
+        let (increment, increment_names, _) = increment_version("row-version", &mut placeholders);
+
+        assert_eq!(expression, "#_726f772d76657273696f6e = :value0");
+        assert_eq!(increment, "#_726f772d76657273696f6e :value1");
+        assert_eq!(names, increment_names);
+        assert_eq!(
+            names.get("#_726f772d76657273696f6e"),
+            Some(&"row-version".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_apply_version_condition_keeps_existing_condition() {
+        let condition = version_condition("version", Some(2.into_attr()), &mut Placeholders::new());
+        let placeholder = condition.condition.2.keys().next().cloned().unwrap();
+        let (expression, names, values) = apply_version_condition(
+            Some("attribute_exists(#id)".to_owned()),
+            Some(AttributeNames::from([("#id".to_owned(), "id".to_owned())])),
+            None,
+            &condition,
+        );
+
+        assert_eq!(
+            expression,
+            format!("(attribute_exists(#id)) AND (#version = {placeholder})")
+        );
+        assert_eq!(names.unwrap().len(), 2);
+        assert_eq!(values.unwrap().len(), 1);
+    }
+
+    #[cfg(feature = "aws-sdk")]
+    #[test]
+    fn test_into_version_conflict_compares_stored_version() {
+        let condition = version_condition("version", Some(2.into_attr()), &mut Placeholders::new());
+        let failed = |version: u64| RaidenError::ConditionalCheckFailedWithItem {
+            message: "failed".to_owned(),
+            item: AttributeValues::from([("version".to_owned(), version.into_attr())]),
+        };
+
+        assert!(matches!(
+            into_version_conflict(failed(3), &condition, false),
+            RaidenError::VersionConflict(_)
+        ));
+        assert!(matches!(
+            into_version_conflict(failed(2), &condition, false),
+            RaidenError::ConditionalCheckFailed(_)
+        ));
+        assert!(matches!(
+            into_version_conflict(failed(2), &condition, true),
+            RaidenError::ConditionalCheckFailedWithItem { .. }
+        ));
+        assert!(matches!(
+            into_version_conflict(
+                RaidenError::ConditionalCheckFailed("failed".to_owned()),
+                &condition,
+                false
+            ),
+            RaidenError::VersionConflict(_)
+        ));
+    }
+}
@@ -0,0 +1,402 @@
+#[cfg(test)]
+mod tests {
+    #[cfg(test)]
+    use pretty_assertions::assert_eq;
+    use raiden::memory::MemoryDb;
+    use raiden::*;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Raiden, Debug, Clone, PartialEq)]
+    pub struct BackendUser {
+        #[raiden(partition_key)]
+        id: String,
+        name: String,
+    }
+
+    // Records the operations it receives and forwards them to the in-memory DynamoDB.
+    struct RecordingBackend {
+        inner: SharedBackend,
+        calls: Arc<Mutex<Vec<&'static str>>>,
+    }
+
+    macro_rules! recording_backend {
+        ($($operation: ident => $request: ty, $response: ty;)*) => {
+            impl DynamoBackend for RecordingBackend {
+                $(
+                    fn $operation(&self, request: $request) -> BackendFuture<'_, $response> {
+                        self.calls.lock().unwrap().push(stringify!($operation));
+                        self.inner.$operation(request)
+                    }
+                )*
+            }
+        };
+    }
+
+    recording_backend! {
+        get_item => GetItemRequest, GetItemResponse;
+        put_item => PutItemRequest, PutItemResponse;
+        update_item => UpdateItemRequest, UpdateItemResponse;
+        delete_item => DeleteItemRequest, DeleteItemResponse;
+        query => QueryRequest, QueryResponse;
+        scan => ScanRequest, ScanResponse;
+        batch_get_item => BatchGetItemRequest, BatchGetItemResponse;
+        batch_write_item => BatchWriteItemRequest, BatchWriteItemResponse;
+        transact_get_items => TransactGetItemsRequest, TransactGetItemsResponse;
+        transact_write_items => TransactWriteItemsRequest, TransactWriteItemsResponse;
+        create_table => CreateTableRequest, CreateTableResponse;
+        delete_table => DeleteTableRequest, DeleteTableResponse;
+        describe_table => DescribeTableRequest, DescribeTableResponse;
+        update_time_to_live => UpdateTimeToLiveRequest, UpdateTimeToLiveResponse;
+    }
+
+    #[tokio::test]
+    async fn test_client_with_backend() {
+        let db = MemoryDb::new();
+        let calls = Arc::new(Mutex::new(vec![]));
+        let client = BackendUser::client_with_backend(RecordingBackend {
+            inner: Arc::new(db.backend()),
+            calls: calls.clone(),
+        });
+        client.create_table().run().await.unwrap();
+
+        let input = BackendUser::put_item_builder()
+            .id("id0".to_owned())
+            .name("bokuweb".to_owned())
+            .build();
+        client.put(input).run().await.unwrap();
+        let res = client.get("id0").run().await.unwrap();
+        assert_eq!(
+            res.item,
+            BackendUser {
+                id: "id0".to_owned(),
+                name: "bokuweb".to_owned(),
+            }
+        );
+
+        WriteTx::new_with_backend(RecordingBackend {
+            inner: Arc::new(db.backend()),
+            calls: calls.clone(),
+        })
+        .delete(BackendUser::delete("id0"))
+        .run()
+        .await
+        .unwrap();
+        assert!(client.get("id0").run().await.is_err());
+
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![
+                "create_table",
+                "put_item",
+                "get_item",
+                "transact_write_items",
+                "get_item"
+            ]
+        );
+    }
+
+    // Answers every read with the same item. It is built from raiden's own types only, so it
+    // works with both SDKs.
+    struct StubBackend {
+        requests: Arc<Mutex<Vec<GetItemRequest>>>,
+    }
+
+    macro_rules! stub_backend {
+        ($($operation: ident => $request: ty, $response: ty;)*) => {
+            impl DynamoBackend for StubBackend {
+                fn get_item(&self, request: GetItemRequest) -> BackendFuture<'_, GetItemResponse> {
+                    self.requests.lock().unwrap().push(request);
+                    let item = AttributeValues::from([
+                        ("id".to_owned(), "stub".to_owned().into_attr()),
+                        ("name".to_owned(), "bokuweb".to_owned().into_attr()),
+                    ]);
+                    Box::pin(async move {
+                        Ok(GetItemResponse {
+                            item: Some(item),
+                            ..Default::default()
+                        })
+                    })
+                }
+
+                $(
+                    fn $operation(&self, _: $request) -> BackendFuture<'_, $response> {
+                        unimplemented!()
+                    }
+                )*
+            }
+        };
+    }
+
+    stub_backend! {
+        put_item => PutItemRequest, PutItemResponse;
+        update_item => UpdateItemRequest, UpdateItemResponse;
+        delete_item => DeleteItemRequest, DeleteItemResponse;
+        query => QueryRequest, QueryResponse;
+        scan => ScanRequest, ScanResponse;
+        batch_get_item => BatchGetItemRequest, BatchGetItemResponse;
+        batch_write_item => BatchWriteItemRequest, BatchWriteItemResponse;
+        transact_get_items => TransactGetItemsRequest, TransactGetItemsResponse;
+        transact_write_items => TransactWriteItemsRequest, TransactWriteItemsResponse;
+        create_table => CreateTableRequest, CreateTableResponse;
+        delete_table => DeleteTableRequest, DeleteTableResponse;
+        describe_table => DescribeTableRequest, DescribeTableResponse;
+        update_time_to_live => UpdateTimeToLiveRequest, UpdateTimeToLiveResponse;
+    }
+
+    #[tokio::test]
+    async fn test_client_with_stub_backend() {
+        let requests = Arc::new(Mutex::new(vec![]));
+        let client = BackendUser::client_with_backend(StubBackend {
+            requests: requests.clone(),
+        });
+        let res = client.get("id0").consistent().run().await.unwrap();
+        assert_eq!(
+            res.item,
+            BackendUser {
+                id: "stub".to_owned(),
+                name: "bokuweb".to_owned(),
+            }
+        );
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].table_name, "BackendUser");
+        assert_eq!(
+            requests[0].key,
+            AttributeValues::from([("id".to_owned(), "id0".to_owned().into_attr())])
+        );
+        assert_eq!(requests[0].consistent_read, Some(true));
+    }
+
+    // Loses the response of the first transaction after it was applied, like a dropped connection.
+    struct FlakyBackend {
+        inner: SharedBackend,
+        tokens: Arc<Mutex<Vec<Option<String>>>>,
+    }
+
+    macro_rules! flaky_backend {
+        ($($operation: ident => $request: ty, $response: ty;)*) => {
+            impl DynamoBackend for FlakyBackend {
+                $(
+                    fn $operation(&self, request: $request) -> BackendFuture<'_, $response> {
+                        self.inner.$operation(request)
+                    }
+                )*
+
+                fn transact_write_items(
+                    &self,
+                    request: TransactWriteItemsRequest,
+                ) -> BackendFuture<'_, TransactWriteItemsResponse> {
+                    let attempt = {
+                        let mut tokens = self.tokens.lock().unwrap();
+                        tokens.push(request.client_request_token.clone());
+                        tokens.len()
+                    };
+                    Box::pin(async move {
+                        let res = self.inner.transact_write_items(request).await;
+                        if attempt == 1 {
+                            return Err(RaidenError::InternalServerError("connection closed".to_owned()));
+                        }
+                        res
+                    })
+                }
+            }
+        };
+    }
+
+    flaky_backend! {
+        get_item => GetItemRequest, GetItemResponse;
+        put_item => PutItemRequest, PutItemResponse;
+        update_item => UpdateItemRequest, UpdateItemResponse;
+        delete_item => DeleteItemRequest, DeleteItemResponse;
+        query => QueryRequest, QueryResponse;
+        scan => ScanRequest, ScanResponse;
+        batch_get_item => BatchGetItemRequest, BatchGetItemResponse;
+        batch_write_item => BatchWriteItemRequest, BatchWriteItemResponse;
+        transact_get_items => TransactGetItemsRequest, TransactGetItemsResponse;
+        create_table => CreateTableRequest, CreateTableResponse;
+        delete_table => DeleteTableRequest, DeleteTableResponse;
+        describe_table => DescribeTableRequest, DescribeTableResponse;
+        update_time_to_live => UpdateTimeToLiveRequest, UpdateTimeToLiveResponse;
+    }
+
+    struct RetryOnce;
+
+    impl RetryStrategy for RetryOnce {
+        fn should_retry(&self, error: &RaidenError) -> bool {
+            matches!(error, RaidenError::InternalServerError(_))
+        }
+
+        fn policy(&self) -> Policy {
+            Policy::Limit(1)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transact_write_retry_is_idempotent() {
+        let db = MemoryDb::new();
+        let client = BackendUser::client_with_backend(db.backend());
+        client.create_table().run().await.unwrap();
+
+        let tokens = Arc::new(Mutex::new(vec![]));
+        let input = BackendUser::put_item_builder()
+            .id("id0".to_owned())
+            .name("bokuweb".to_owned())
+            .build();
+        let cond = BackendUser::condition().attr_not_exists(BackendUser::id());
+        WriteTx::new_with_backend(FlakyBackend {
+            inner: Arc::new(db.backend()),
+            tokens: tokens.clone(),
+        })
+        .with_retries(Box::new(RetryOnce))
+        .put(BackendUser::put(input).condition(cond))
+        .run()
+        .await
+        .unwrap();
+
+        let tokens = tokens.lock().unwrap();
+        assert_eq!(tokens.len(), 2);
+        assert!(tokens[0].is_some());
+        assert_eq!(tokens[0], tokens[1]);
+    }
+
+    #[tokio::test]
+    async fn test_transact_write_idempotency_token() {
+        let db = MemoryDb::new();
+        let client = BackendUser::client_with_backend(db.backend());
+        client.create_table().run().await.unwrap();
+
+        let tokens = Arc::new(Mutex::new(vec![]));
+        WriteTx::new_with_backend(FlakyBackend {
+            inner: Arc::new(db.backend()),
+            tokens: tokens.clone(),
+        })
+        .with_retries(Box::new(RetryOnce))
+        .idempotency_token("request-0")
+        .delete(BackendUser::delete("id0"))
+        .run()
+        .await
+        .unwrap();
+
+        assert_eq!(
+            *tokens.lock().unwrap(),
+            vec![Some("request-0".to_owned()), Some("request-0".to_owned())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_transact_write_tokens_differ_between_transactions() {
+        let db = MemoryDb::new();
+        let client = BackendUser::client_with_backend(db.backend());
+        client.create_table().run().await.unwrap();
+
+        let tokens = Arc::new(Mutex::new(vec![]));
+        for id in ["id0", "id1"] {
+            WriteTx::new_with_backend(FlakyBackend {
+                inner: Arc::new(db.backend()),
+                tokens: tokens.clone(),
+            })
+            .with_retries(Box::new(RetryOnce))
+            .delete(BackendUser::delete(id))
+            .run()
+            .await
+            .unwrap();
+        }
+
+        let tokens = tokens.lock().unwrap();
+        assert_eq!(tokens.len(), 3);
+        assert_eq!(tokens[0], tokens[1]);
+        assert_ne!(tokens[1], tokens[2]);
+    }
+
+    #[derive(Default)]
+    struct RecordingObserver {
+        requests: Mutex<Vec<ObservedRequest>>,
+    }
+
+    impl Observer for RecordingObserver {
+        fn observe(&self, request: &ObservedRequest) {
+            self.requests.lock().unwrap().push(request.clone());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_client_with_observer() {
+        let db = MemoryDb::new();
+        let observer = Arc::new(RecordingObserver::default());
+        let client = BackendUser::client_with_backend(db.backend()).with_observer(observer.clone());
+        client.create_table().run().await.unwrap();
+
+        let input = BackendUser::put_item_builder()
+            .id("id0".to_owned())
+            .name("bokuweb".to_owned())
+            .build();
+        client
+            .put(input.clone())
+            .return_consumed_capacity(ReturnConsumedCapacity::Total)
+            .run()
+            .await
+            .unwrap();
+        let cond = BackendUser::condition().attr_not_exists(BackendUser::id());
+        assert!(client.put(input).condition(cond).run().await.is_err());
+
+        let requests = observer.requests.lock().unwrap();
+        let apis: Vec<_> = requests.iter().map(|r| r.api).collect();
+        assert_eq!(apis, vec!["create_table", "put_item", "put_item"]);
+        assert!(requests
+            .iter()
+            .all(|r| r.table_name.as_deref() == Some("BackendUser") && r.attempt == 1));
+        assert_eq!(requests[1].consumed_capacity.len(), 1);
+        assert_eq!(requests[1].consumed_capacity[0].capacity_units, Some(1.0));
+        assert_eq!(requests[1].error, None);
+        assert!(requests[2].consumed_capacity.is_empty());
+        assert_eq!(requests[2].error, Some(ErrorClass::ConditionalCheckFailed));
+        assert!(!requests[2].will_retry);
+    }
+
+    #[tokio::test]
+    async fn test_transact_write_observer_reports_attempts() {
+        let db = MemoryDb::new();
+        let client = BackendUser::client_with_backend(db.backend());
+        client.create_table().run().await.unwrap();
+
+        let observer = Arc::new(RecordingObserver::default());
+        WriteTx::new_with_backend(FlakyBackend {
+            inner: Arc::new(db.backend()),
+            tokens: Arc::new(Mutex::new(vec![])),
+        })
+        .with_retries(Box::new(RetryOnce))
+        .with_observer(observer.clone())
+        .delete(BackendUser::delete("id0"))
+        .run()
+        .await
+        .unwrap();
+
+        let requests = observer.requests.lock().unwrap();
+        let attempts: Vec<_> = requests
+            .iter()
+            .map(|r| {
+                (
+                    r.api,
+                    r.table_name.clone(),
+                    r.attempt,
+                    r.will_retry,
+                    r.error,
+                )
+            })
+            .collect();
+        assert_eq!(
+            attempts,
+            vec![
+                (
+                    "transact_write_items",
+                    None,
+                    1,
+                    true,
+                    Some(ErrorClass::Server)
+                ),
+                ("transact_write_items", None, 2, false, None),
+            ]
+        );
+    }
+}
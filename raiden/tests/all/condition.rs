@@ -1,4 +1,5 @@
 #[cfg(test)]
+#[allow(deprecated)]
 mod tests {
 
     use std::collections::HashMap;
@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use std::collections::{BTreeMap, HashMap};
+    use std::collections::{BTreeMap, BTreeSet, HashMap};
 
     use pretty_assertions::assert_eq;
     use raiden::*;
@@ -38,6 +38,14 @@ mod tests {
         SendResponse { id: String },
     }
 
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, RaidenDocument)]
+    pub struct Attachment {
+        #[serde(with = "raiden::serde_attr::bytes")]
+        body: Vec<u8>,
+        labels: StringSet<BTreeSet<String>>,
+        sizes: NumberSet<BTreeSet<u64>>,
+    }
+
     #[derive(Raiden)]
     #[raiden(table_name = "user")]
     #[derive(Debug, Clone, PartialEq)]
@@ -264,6 +272,31 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "aws-sdk")]
+    #[test]
+    fn test_document_with_binary_and_sets_round_trip() {
+        let attachment = Attachment {
+            body: vec![0, 159, 255],
+            labels: StringSet(BTreeSet::from(["a".to_owned(), "b".to_owned()])),
+            sizes: NumberSet(BTreeSet::from([1, 2])),
+        };
+        let attr = attachment.clone().into_attr();
+
+        let AttributeValue::M(values) = &attr else {
+            panic!("should be a map");
+        };
+        assert_eq!(values["body"], AttributeValue::B(vec![0, 159, 255].into()));
+        assert_eq!(
+            values["labels"],
+            AttributeValue::Ss(vec!["a".to_owned(), "b".to_owned()])
+        );
+        assert_eq!(
+            values["sizes"],
+            AttributeValue::Ns(vec!["1".to_owned(), "2".to_owned()])
+        );
+        assert_eq!(Attachment::from_attr(Some(attr)).unwrap(), attachment);
+    }
+
     #[test]
     fn test_raiden_document_enum_attribute_names_are_unprojected() {
         assert_eq!(Message::attribute_names(), None);
@@ -356,6 +389,27 @@ mod tests {
         assert!(Message::from_item(item).is_err());
     }
 
+    #[test]
+    fn test_from_item_reports_nested_document_path() {
+        let mut profile = HashMap::new();
+        profile.insert("display_name".to_owned(), "bokuweb".to_owned());
+        profile.insert("level".to_owned(), "high".to_owned());
+
+        let mut item = HashMap::new();
+        item.insert("id".to_owned(), "id0".to_owned().into_attr());
+        item.insert("profile".to_owned(), profile.into_attr());
+        item.insert(
+            "profiles".to_owned(),
+            HashMap::<String, DerivedProfile>::new().into_attr(),
+        );
+
+        let err = UserWithDerivedDocument::from_item(item).unwrap_err();
+        assert_eq!(Some("profile.level".to_owned()), err.attribute_path());
+        assert!(err
+            .to_string()
+            .starts_with("attribute \"profile.level\" convert error"));
+    }
+
     #[tokio::test]
     async fn test_put_input_with_document_and_maps() {
         let client = crate::all::create_client_from_struct!(UserWithDocument);
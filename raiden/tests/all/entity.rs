@@ -0,0 +1,239 @@
+#[cfg(test)]
+mod tests {
+    #[cfg(test)]
+    use pretty_assertions::assert_eq;
+    use raiden::memory::MemoryDb;
+    use raiden::*;
+
+    #[derive(Raiden, Debug, Clone, PartialEq)]
+    #[raiden(table_name = "SingleTable")]
+    pub struct EntityUser {
+        #[raiden(partition_key)]
+        #[raiden(rename = "PK")]
+        pk: String,
+        #[raiden(sort_key)]
+        #[raiden(rename = "SK")]
+        sk: String,
+        #[raiden(rename = "type")]
+        kind: String,
+        name: String,
+    }
+
+    #[derive(Raiden, Debug, Clone, PartialEq)]
+    #[raiden(table_name = "SingleTable")]
+    pub struct EntityOrder {
+        #[raiden(partition_key)]
+        #[raiden(rename = "PK")]
+        pk: String,
+        #[raiden(sort_key)]
+        #[raiden(rename = "SK")]
+        sk: String,
+        #[raiden(rename = "type")]
+        kind: String,
+        total: usize,
+    }
+
+    #[derive(Raiden, Debug, Clone, PartialEq)]
+    #[raiden(table_name = "SingleTable")]
+    #[raiden(tag = "type", partition_key = "PK", sort_key = "SK")]
+    pub enum Entity {
+        #[raiden(rename = "USER")]
+        User(EntityUser),
+        #[raiden(rename = "ORDER")]
+        Order(EntityOrder),
+    }
+
+    #[derive(Raiden, Debug, Clone, PartialEq)]
+    #[raiden(table_name = "KeywordKeyTable")]
+    pub struct KeywordKeyUser {
+        #[raiden(partition_key)]
+        #[raiden(rename = "type")]
+        kind: String,
+        #[raiden(sort_key)]
+        #[raiden(rename = "1sk")]
+        sk: String,
+        tag: String,
+    }
+
+    // Key names are not Rust identifiers, so the enum has to keep them as attribute names only.
+    #[derive(Raiden, Debug, Clone, PartialEq)]
+    #[raiden(table_name = "KeywordKeyTable")]
+    #[raiden(tag = "tag", partition_key = "type", sort_key = "1sk")]
+    pub enum KeywordKeyEntity {
+        #[raiden(rename = "USER")]
+        User(KeywordKeyUser),
+    }
+
+    #[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
+    fn create_clients(db: &MemoryDb) -> (EntityClient, EntityUserClient, EntityOrderClient) {
+        (
+            Entity::client_with(db.client(), Region::ApNortheast1),
+            EntityUser::client_with(db.client(), Region::ApNortheast1),
+            EntityOrder::client_with(db.client(), Region::ApNortheast1),
+        )
+    }
+
+    #[cfg(feature = "aws-sdk")]
+    fn create_clients(db: &MemoryDb) -> (EntityClient, EntityUserClient, EntityOrderClient) {
+        (
+            Entity::client_with(db.client()),
+            EntityUser::client_with(db.client()),
+            EntityOrder::client_with(db.client()),
+        )
+    }
+
+    fn user() -> EntityUser {
+        EntityUser {
+            pk: "USER#1".to_owned(),
+            sk: "PROFILE".to_owned(),
+            kind: "USER".to_owned(),
+            name: "bokuweb".to_owned(),
+        }
+    }
+
+    fn order(id: usize) -> EntityOrder {
+        EntityOrder {
+            pk: "USER#1".to_owned(),
+            sk: format!("ORDER#{id}"),
+            kind: "ORDER".to_owned(),
+            total: id * 100,
+        }
+    }
+
+    async fn setup() -> (MemoryDb, EntityClient) {
+        let db = MemoryDb::new();
+        let (client, users, orders) = create_clients(&db);
+        client.create_table().run().await.unwrap();
+
+        let EntityUser { pk, sk, kind, name } = user();
+        let input = EntityUser::put_item_builder()
+            .pk(pk)
+            .sk(sk)
+            .kind(kind)
+            .name(name)
+            .build();
+        users.put(input).run().await.unwrap();
+
+        for id in 1..=2 {
+            let EntityOrder {
+                pk,
+                sk,
+                kind,
+                total,
+            } = order(id);
+            let input = EntityOrder::put_item_builder()
+                .pk(pk)
+                .sk(sk)
+                .kind(kind)
+                .total(total)
+                .build();
+            orders.put(input).run().await.unwrap();
+        }
+        (db, client)
+    }
+
+    #[tokio::test]
+    async fn test_entity_query_dispatches_items_by_tag() {
+        let (_db, client) = setup().await;
+
+        let cond = Entity::partition_key_condition().eq("USER#1");
+        let res = client.query().key_condition(cond).run().await.unwrap();
+        assert_eq!(
+            res.items,
+            vec![
+                Entity::Order(order(1)),
+                Entity::Order(order(2)),
+                Entity::User(user()),
+            ]
+        );
+        assert_eq!(res.items[2].tag(), "USER");
+
+        let cond = Entity::partition_key_condition()
+            .eq("USER#1")
+            .and(Entity::sort_key_condition().begins_with("ORDER#"));
+        let res = client.query().key_condition(cond).run().await.unwrap();
+        assert_eq!(
+            res.items,
+            vec![Entity::Order(order(1)), Entity::Order(order(2))]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_entity_get_scan_and_delete() {
+        let (_db, client) = setup().await;
+
+        let res = client.get("USER#1", "PROFILE").run().await.unwrap();
+        assert_eq!(res.item, Entity::User(user()));
+
+        let res = client.scan().run().await.unwrap();
+        assert_eq!(res.items.len(), 3);
+
+        client.delete("USER#1", "ORDER#1").run().await.unwrap();
+        let res = client.scan().run().await.unwrap();
+        assert_eq!(res.items.len(), 2);
+    }
+
+    #[test]
+    fn test_entity_from_item_rejects_unknown_or_missing_tag() {
+        let mut item = std::collections::HashMap::new();
+        item.insert("PK".to_owned(), "USER#1".to_owned().into_attr());
+        item.insert("SK".to_owned(), "PROFILE".to_owned().into_attr());
+
+        let err = Entity::from_item(item.clone()).unwrap_err();
+        assert_eq!(err.attribute_path(), Some("type".to_owned()));
+
+        item.insert("type".to_owned(), "ADMIN".to_owned().into_attr());
+        let err = Entity::from_item(item).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "attribute \"type\" convert error: unknown Entity `ADMIN`"
+        );
+    }
+
+    #[test]
+    fn test_entity_projects_attributes_of_every_variant() {
+        let names = Entity::attribute_names().unwrap();
+        for name in ["PK", "SK", "type", "name", "total"] {
+            assert_eq!(names.get(&format!("#{name}")), Some(&name.to_owned()));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_entity_with_keyword_and_non_identifier_key_names() {
+        let db = MemoryDb::new();
+        #[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
+        let (client, users) = (
+            KeywordKeyEntity::client_with(db.client(), Region::ApNortheast1),
+            KeywordKeyUser::client_with(db.client(), Region::ApNortheast1),
+        );
+        #[cfg(feature = "aws-sdk")]
+        let (client, users) = (
+            KeywordKeyEntity::client_with(db.client()),
+            KeywordKeyUser::client_with(db.client()),
+        );
+        client.create_table().run().await.unwrap();
+
+        let input = KeywordKeyUser::put_item_builder()
+            .kind("USER#1".to_owned())
+            .sk("PROFILE".to_owned())
+            .tag("USER".to_owned())
+            .build();
+        users.put(input).run().await.unwrap();
+
+        let expected = KeywordKeyEntity::User(KeywordKeyUser {
+            kind: "USER#1".to_owned(),
+            sk: "PROFILE".to_owned(),
+            tag: "USER".to_owned(),
+        });
+        let res = client.get("USER#1", "PROFILE").run().await.unwrap();
+        assert_eq!(res.item, expected);
+
+        let cond = KeywordKeyEntity::partition_key_condition().eq("USER#1");
+        let res = client.query().key_condition(cond).run().await.unwrap();
+        assert_eq!(res.items, vec![expected]);
+        assert_eq!(
+            KeywordKeyEntityAttrNames::Type.into_attr_name(),
+            "type".to_owned()
+        );
+    }
+}
@@ -1,4 +1,5 @@
 #[cfg(test)]
+#[allow(deprecated)]
 mod tests {
 
     use std::collections::HashMap;
@@ -314,4 +315,39 @@ mod tests {
         assert_eq!(attribute_names, expected_names);
         assert_eq!(attribute_values, expected_values);
     }
+
+    #[test]
+    fn test_shared_placeholders_filter_expression() {
+        let mut placeholders = Placeholders::new();
+        let (first, _, first_values) = User::filter_expression(User::name())
+            .eq("bokuweb")
+            .build_with(&mut placeholders);
+        let (second, _, second_values) = User::filter_expression(User::name())
+            .eq("bokuweb")
+            .and(User::filter_expression(User::year()).gt(1999))
+            .build_with(&mut placeholders);
+
+        let mut expected_values: std::collections::HashMap<String, AttributeValue> =
+            std::collections::HashMap::new();
+        expected_values.insert(":value0".to_owned(), "bokuweb".into_attr());
+        assert_eq!(first, "#name = :value0".to_owned());
+        assert_eq!(first_values, expected_values);
+
+        expected_values.insert(":value1".to_owned(), 1999.into_attr());
+        assert_eq!(second, "#name = :value0 AND (#year > :value1)".to_owned());
+        assert_eq!(second_values, expected_values);
+    }
+
+    #[test]
+    fn test_placeholders_are_independent_across_threads() {
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                std::thread::spawn(move || User::filter_expression(User::num()).eq(i).build().0)
+            })
+            .collect();
+
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), "#num = :value0".to_owned());
+        }
+    }
 }
@@ -94,7 +94,7 @@ mod tests {
 
         assert!(res.is_err());
 
-        if let RaidenError::AttributeConvertError { attr_name } = res.unwrap_err() {
+        if let RaidenError::AttributeConvertError { attr_name, .. } = res.unwrap_err() {
             assert_eq!("unstored", attr_name);
         } else {
             panic!("err should be RaidenError::AttributeConvertError");
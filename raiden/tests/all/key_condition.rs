@@ -1,4 +1,5 @@
 #[cfg(test)]
+#[allow(deprecated)]
 mod tests {
 
     #[cfg(test)]
@@ -19,6 +20,18 @@ mod tests {
         rename: usize,
     }
 
+    #[allow(dead_code)]
+    #[derive(Raiden)]
+    #[raiden(table_name = "user")]
+    #[derive(Debug, Clone)]
+    pub struct UserWithSortKey {
+        #[raiden(partition_key)]
+        #[raiden(rename = "Id")]
+        id: String,
+        #[raiden(sort_key)]
+        year: usize,
+    }
+
     #[test]
     fn test_eq_key_condition() {
         reset_value_id();
@@ -95,4 +108,17 @@ mod tests {
             "#id = :value0 AND (begins_with(#year, :value1))".to_owned(),
         );
     }
+
+    #[test]
+    fn test_typed_partition_and_sort_key_condition() {
+        let cond = UserWithSortKey::partition_key_condition()
+            .eq("id3")
+            .and(UserWithSortKey::sort_key_condition().between(2000, 2010));
+        let (key_condition, attribute_names, _attribute_values) = cond.build();
+        assert_eq!(
+            key_condition,
+            "#Id = :value0 AND (#year BETWEEN :value1 AND :value2)".to_owned(),
+        );
+        assert_eq!(attribute_names.get("#Id"), Some(&"Id".to_owned()));
+    }
 }
@@ -0,0 +1,276 @@
+#[cfg(test)]
+mod tests {
+    #[cfg(test)]
+    use pretty_assertions::assert_eq;
+    use raiden::memory::MemoryDb;
+    use raiden::*;
+
+    #[derive(Raiden, Debug, Clone, PartialEq)]
+    #[raiden(table_name = "KeyTemplate")]
+    #[raiden(partition_key = "USER#{user_id}")]
+    #[raiden(sort_key = "ORDER#{date}#{order_id}")]
+    pub struct TemplateOrder {
+        user_id: String,
+        date: String,
+        order_id: u32,
+        total: usize,
+    }
+
+    // Reads the stored key attributes of `TemplateOrder`.
+    #[derive(Raiden, Debug, Clone, PartialEq)]
+    #[raiden(table_name = "KeyTemplate")]
+    pub struct StoredOrder {
+        #[raiden(partition_key)]
+        #[raiden(rename = "PK")]
+        pk: String,
+        #[raiden(sort_key)]
+        #[raiden(rename = "SK")]
+        sk: String,
+        total: usize,
+    }
+
+    #[derive(Raiden, Debug, Clone, PartialEq)]
+    #[raiden(table_name = "RenamedKeyTemplate")]
+    #[raiden(partition_key = "USER#{user_id}", partition_key_attr = "pk")]
+    #[raiden(sort_key = "ORDER#{order_id}", sort_key_attr = "sk")]
+    pub struct RenamedTemplateOrder {
+        user_id: String,
+        order_id: u32,
+    }
+
+    #[derive(Raiden, Debug, Clone, PartialEq)]
+    #[raiden(table_name = "RenamedKeyTemplate")]
+    pub struct RenamedStoredOrder {
+        #[raiden(partition_key)]
+        pk: String,
+        #[raiden(sort_key)]
+        sk: String,
+        user_id: String,
+    }
+
+    #[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
+    fn create_clients(db: &MemoryDb) -> (TemplateOrderClient, StoredOrderClient, WriteTx) {
+        (
+            TemplateOrder::client_with(db.client(), Region::ApNortheast1),
+            StoredOrder::client_with(db.client(), Region::ApNortheast1),
+            WriteTx::new_with_client(db.client(), Region::ApNortheast1),
+        )
+    }
+
+    #[cfg(feature = "aws-sdk")]
+    fn create_clients(db: &MemoryDb) -> (TemplateOrderClient, StoredOrderClient, WriteTx) {
+        (
+            TemplateOrder::client_with(db.client()),
+            StoredOrder::client_with(db.client()),
+            WriteTx::new_with_client(db.client()),
+        )
+    }
+
+    fn order(date: &str, order_id: u32) -> TemplateOrder {
+        TemplateOrder {
+            user_id: "bokuweb".to_owned(),
+            date: date.to_owned(),
+            order_id,
+            total: order_id as usize * 100,
+        }
+    }
+
+    fn input(order: TemplateOrder) -> TemplateOrderPutItemInput {
+        TemplateOrder::put_item_builder()
+            .user_id(order.user_id)
+            .date(order.date)
+            .order_id(order.order_id)
+            .total(order.total)
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_key_template_synthesizes_keys_on_put() {
+        let db = MemoryDb::new();
+        let (client, stored, tx) = create_clients(&db);
+        client.create_table().run().await.unwrap();
+
+        client
+            .put(input(order("2024-01-02", 1)))
+            .run()
+            .await
+            .unwrap();
+        client
+            .batch_put(vec![input(order("2024-01-02", 2))])
+            .run()
+            .await
+            .unwrap();
+        tx.put(TemplateOrder::put(input(order("2024-01-03", 3))))
+            .run()
+            .await
+            .unwrap();
+
+        let res = stored.scan().run().await.unwrap();
+        let mut keys: Vec<(String, String)> = res
+            .items
+            .into_iter()
+            .map(|item| (item.pk, item.sk))
+            .collect();
+        keys.sort();
+        assert_eq!(
+            keys,
+            vec![
+                ("USER#bokuweb".to_owned(), "ORDER#2024-01-02#1".to_owned()),
+                ("USER#bokuweb".to_owned(), "ORDER#2024-01-02#2".to_owned()),
+                ("USER#bokuweb".to_owned(), "ORDER#2024-01-03#3".to_owned()),
+            ]
+        );
+
+        let res = client
+            .get(
+                TemplateOrder::format_partition_key("bokuweb".to_owned()),
+                TemplateOrder::format_sort_key("2024-01-03".to_owned(), 3),
+            )
+            .run()
+            .await
+            .unwrap();
+        assert_eq!(res.item, order("2024-01-03", 3));
+    }
+
+    #[tokio::test]
+    async fn test_key_template_conditions() {
+        let db = MemoryDb::new();
+        let (client, _, _) = create_clients(&db);
+        client.create_table().run().await.unwrap();
+        client
+            .batch_put(vec![
+                input(order("2024-01-02", 1)),
+                input(order("2024-01-02", 2)),
+                input(order("2024-01-03", 3)),
+            ])
+            .run()
+            .await
+            .unwrap();
+
+        let cond = TemplateOrder::partition_key_condition()
+            .eq_template(("bokuweb".to_owned(),))
+            .and(
+                TemplateOrder::sort_key_condition()
+                    .begins_with_template(("2024-01-02".to_owned(),)),
+            );
+        let res = client.query().key_condition(cond).run().await.unwrap();
+        assert_eq!(
+            res.items,
+            vec![order("2024-01-02", 1), order("2024-01-02", 2)]
+        );
+
+        let cond = TemplateOrder::partition_key_condition()
+            .eq_template(("bokuweb".to_owned(),))
+            .and(TemplateOrder::sort_key_condition().eq_template(("2024-01-03".to_owned(), 3)));
+        let res = client.query().key_condition(cond).run().await.unwrap();
+        assert_eq!(res.items, vec![order("2024-01-03", 3)]);
+    }
+
+    #[tokio::test]
+    async fn test_key_template_rejects_values_containing_the_next_literal() {
+        let db = MemoryDb::new();
+        let (client, stored, tx) = create_clients(&db);
+        client.create_table().run().await.unwrap();
+
+        let err = client
+            .put(input(order("2024#01", 1)))
+            .run()
+            .await
+            .unwrap_err();
+        assert_eq!(err.attribute_path(), Some("SK".to_owned()));
+        let err = client
+            .batch_put(vec![
+                input(order("2024-01-02", 2)),
+                input(order("2024#01", 3)),
+            ])
+            .run()
+            .await
+            .unwrap_err();
+        assert_eq!(err.attribute_path(), Some("SK".to_owned()));
+        let err = tx
+            .put(TemplateOrder::put(input(order("2024#01", 4))))
+            .run()
+            .await
+            .unwrap_err();
+        assert_eq!(err.attribute_path(), Some("SK".to_owned()));
+
+        let res = stored.scan().run().await.unwrap();
+        assert!(res.items.is_empty());
+    }
+
+    #[test]
+    fn test_key_template_parses_missing_fields_from_keys() {
+        let mut item = std::collections::HashMap::new();
+        item.insert("PK".to_owned(), "USER#bokuweb".to_owned().into_attr());
+        item.insert("SK".to_owned(), "ORDER#2024-01-02#7".to_owned().into_attr());
+        item.insert("total".to_owned(), 700_usize.into_attr());
+        assert_eq!(
+            TemplateOrder::from_item(item.clone()).unwrap(),
+            order("2024-01-02", 7)
+        );
+
+        item.insert("SK".to_owned(), "ORDER#2024-01-02#x".to_owned().into_attr());
+        let err = TemplateOrder::from_item(item.clone()).unwrap_err();
+        assert_eq!(err.attribute_path(), Some("SK".to_owned()));
+
+        item.insert("SK".to_owned(), "PROFILE".to_owned().into_attr());
+        let err = TemplateOrder::from_item(item).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "attribute \"SK\" convert error: `PROFILE` does not match the key template `ORDER#{date}#{order_id}`"
+        );
+    }
+
+    #[test]
+    fn test_key_template_projects_key_attributes() {
+        let names = TemplateOrder::attribute_names().unwrap();
+        for name in ["PK", "SK", "user_id", "total"] {
+            assert_eq!(names.get(&format!("#{name}")), Some(&name.to_owned()));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_key_template_uses_declared_key_attribute_names() {
+        let db = MemoryDb::new();
+        #[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
+        let (client, stored) = (
+            RenamedTemplateOrder::client_with(db.client(), Region::ApNortheast1),
+            RenamedStoredOrder::client_with(db.client(), Region::ApNortheast1),
+        );
+        #[cfg(feature = "aws-sdk")]
+        let (client, stored) = (
+            RenamedTemplateOrder::client_with(db.client()),
+            RenamedStoredOrder::client_with(db.client()),
+        );
+        client.create_table().run().await.unwrap();
+
+        let input = RenamedTemplateOrder::put_item_builder()
+            .user_id("bokuweb".to_owned())
+            .order_id(1)
+            .build();
+        client.put(input).run().await.unwrap();
+
+        let res = stored.scan().run().await.unwrap();
+        assert_eq!(
+            res.items,
+            vec![RenamedStoredOrder {
+                pk: "USER#bokuweb".to_owned(),
+                sk: "ORDER#1".to_owned(),
+                user_id: "bokuweb".to_owned(),
+            }]
+        );
+
+        let mut item = std::collections::HashMap::new();
+        item.insert("pk".to_owned(), "USER#bokuweb".to_owned().into_attr());
+        item.insert("sk".to_owned(), "ORDER#2".to_owned().into_attr());
+        assert_eq!(
+            RenamedTemplateOrder::from_item(item).unwrap(),
+            RenamedTemplateOrder {
+                user_id: "bokuweb".to_owned(),
+                order_id: 2,
+            }
+        );
+        let names = RenamedTemplateOrder::attribute_names().unwrap();
+        assert!(!names.contains_key("#PK") && !names.contains_key("#SK"));
+    }
+}
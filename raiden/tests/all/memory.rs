@@ -0,0 +1,821 @@
+#[cfg(test)]
+mod tests {
+    #[cfg(test)]
+    use pretty_assertions::assert_eq;
+    use raiden::memory::MemoryDb;
+    use raiden::*;
+    use std::collections::{HashMap, HashSet};
+
+    #[derive(Raiden, Debug, Clone, PartialEq)]
+    #[raiden(gsi(name = "nameIndex", partition_key = "name", sort_key = "year"))]
+    pub struct MemoryUser {
+        #[raiden(partition_key)]
+        id: String,
+        #[raiden(sort_key)]
+        year: usize,
+        name: String,
+        tags: HashSet<String>,
+    }
+
+    // The attributes changed by `update` in `test_memory_return_values`.
+    #[derive(Raiden, Debug, Clone, PartialEq)]
+    pub struct MemoryUserName {
+        #[raiden(partition_key)]
+        name: String,
+    }
+
+    #[derive(Raiden, Debug, Clone, PartialEq)]
+    pub struct MemoryCounter {
+        #[raiden(partition_key)]
+        id: String,
+        count: usize,
+        limit: usize,
+        events: Vec<String>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, RaidenDocument)]
+    pub struct MemoryProfile {
+        level: usize,
+        nickname: String,
+    }
+
+    #[derive(Raiden, Debug, Clone, PartialEq)]
+    pub struct MemoryPlayer {
+        #[raiden(partition_key)]
+        id: String,
+        profile: Document<MemoryProfile>,
+        badges: Vec<String>,
+        stats: HashMap<String, usize>,
+    }
+
+    #[derive(Raiden, Debug, Clone, PartialEq)]
+    pub struct MemoryContact {
+        #[raiden(partition_key)]
+        id: String,
+        home: HashMap<String, String>,
+        work: HashMap<String, String>,
+    }
+
+    // Key attribute names which are not valid identifiers.
+    #[derive(Raiden, Debug, Clone, PartialEq)]
+    #[raiden(rename_all = "kebab-case")]
+    pub struct MemoryDevice {
+        #[raiden(partition_key)]
+        #[raiden(rename = "device-id")]
+        id: String,
+        #[raiden(sort_key)]
+        serial_no: String,
+        label: String,
+    }
+
+    #[derive(Raiden, Debug, Clone, PartialEq)]
+    pub struct MemoryVersioned {
+        #[raiden(partition_key)]
+        id: String,
+        name: String,
+        #[raiden(version)]
+        version: u64,
+    }
+
+    #[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
+    fn create_client(db: &MemoryDb) -> MemoryUserClient {
+        MemoryUser::client_with(db.client(), Region::ApNortheast1)
+    }
+
+    #[cfg(feature = "aws-sdk")]
+    fn create_client(db: &MemoryDb) -> MemoryUserClient {
+        MemoryUser::client_with(db.client())
+    }
+
+    #[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
+    fn create_tx_client(db: &MemoryDb) -> ::raiden::WriteTx {
+        ::raiden::WriteTx::new_with_client(db.client(), Region::ApNortheast1)
+    }
+
+    #[cfg(feature = "aws-sdk")]
+    fn create_tx_client(db: &MemoryDb) -> ::raiden::WriteTx {
+        ::raiden::WriteTx::new_with_client(db.client())
+    }
+
+    #[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
+    fn create_read_tx(db: &MemoryDb) -> ::raiden::ReadTx {
+        ::raiden::ReadTx::new_with_client(db.client(), Region::ApNortheast1)
+    }
+
+    #[cfg(feature = "aws-sdk")]
+    fn create_read_tx(db: &MemoryDb) -> ::raiden::ReadTx {
+        ::raiden::ReadTx::new_with_client(db.client())
+    }
+
+    fn user(id: &str, year: usize, name: &str) -> MemoryUser {
+        MemoryUser {
+            id: id.to_owned(),
+            year,
+            name: name.to_owned(),
+            tags: HashSet::new(),
+        }
+    }
+
+    async fn setup() -> (MemoryDb, MemoryUserClient) {
+        let db = MemoryDb::new();
+        let client = create_client(&db);
+        client.create_table().run().await.unwrap();
+
+        for year in 2000..2005 {
+            let name = if year % 2 == 0 { "even" } else { "odd" };
+            let input = MemoryUser::put_item_builder()
+                .id("user".to_owned())
+                .year(year)
+                .name(name.to_owned())
+                .tags(HashSet::new())
+                .build();
+            client.put(input).run().await.unwrap();
+        }
+        (db, client)
+    }
+
+    #[tokio::test]
+    async fn test_memory_get_update_and_delete() {
+        let (_db, client) = setup().await;
+
+        let res = client.get("user", 2001_usize).run().await.unwrap();
+        assert_eq!(res.item, user("user", 2001, "odd"));
+
+        let set_expression = MemoryUser::update_expression()
+            .set(MemoryUser::name())
+            .value("updated");
+        let add_expression = MemoryUser::update_expression()
+            .add(MemoryUser::tags())
+            .value(HashSet::from(["a".to_owned()]));
+        let cond = MemoryUser::condition()
+            .attr(MemoryUser::name())
+            .eq_value("odd");
+        let res = client
+            .update("user", 2001_usize)
+            .set(set_expression)
+            .add(add_expression)
+            .condition(cond)
+            .return_all_new()
+            .run()
+            .await
+            .unwrap();
+        assert_eq!(
+            res.item,
+            Some(MemoryUser {
+                tags: HashSet::from(["a".to_owned()]),
+                ..user("user", 2001, "updated")
+            })
+        );
+
+        let cond = MemoryUser::condition()
+            .attr(MemoryUser::name())
+            .eq_value("odd");
+        let res = client
+            .delete("user", 2001_usize)
+            .condition(cond)
+            .run()
+            .await;
+        assert!(matches!(res, Err(RaidenError::ConditionalCheckFailed(_))));
+
+        client.delete("user", 2001_usize).run().await.unwrap();
+        let res = client.get("user", 2001_usize).run().await;
+        assert!(matches!(res, Err(RaidenError::ResourceNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_memory_non_identifier_key_names() {
+        let db = MemoryDb::new();
+        #[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
+        let client = MemoryDevice::client_with(db.client(), Region::ApNortheast1);
+        #[cfg(feature = "aws-sdk")]
+        let client = MemoryDevice::client_with(db.client());
+        let res = client.create_table().run().await.unwrap();
+        let key_schema: Vec<String> = res
+            .table_description
+            .unwrap()
+            .key_schema
+            .unwrap()
+            .into_iter()
+            .map(|key| key.attribute_name)
+            .collect();
+        assert_eq!(
+            key_schema,
+            vec!["device-id".to_owned(), "serial-no".to_owned()]
+        );
+
+        let device = MemoryDevice {
+            id: "device".to_owned(),
+            serial_no: "s-1".to_owned(),
+            label: "kitchen".to_owned(),
+        };
+        let input = MemoryDevice::put_item_builder()
+            .id(device.id.clone())
+            .serial_no(device.serial_no.clone())
+            .label(device.label.clone())
+            .build();
+        client.put(input).run().await.unwrap();
+
+        let res = client.get("device", "s-1").run().await.unwrap();
+        assert_eq!(res.item, device);
+
+        let cond = MemoryDevice::partition_key_condition()
+            .eq("device")
+            .and(MemoryDevice::sort_key_condition().begins_with("s-"));
+        let res = client.query().key_condition(cond).run().await.unwrap();
+        assert_eq!(res.items, vec![device.clone()]);
+
+        let res = client
+            .delete("device", "s-1")
+            .return_old()
+            .run()
+            .await
+            .unwrap();
+        assert_eq!(res.old_item, Some(device));
+    }
+
+    #[tokio::test]
+    async fn test_memory_return_values() {
+        let (_db, client) = setup().await;
+
+        let input = MemoryUser::put_item_builder()
+            .id("user".to_owned())
+            .year(2001)
+            .name("replaced".to_owned())
+            .tags(HashSet::new())
+            .build();
+        let res = client.put(input).return_old().run().await.unwrap();
+        assert_eq!(res.old_item, Some(user("user", 2001, "odd")));
+
+        let set_expression = MemoryUser::update_expression()
+            .set(MemoryUser::name())
+            .value("updated");
+        let res = client
+            .update("user", 2001_usize)
+            .set(set_expression)
+            .return_updated_old::<MemoryUserName>()
+            .run()
+            .await
+            .unwrap();
+        assert_eq!(
+            res.item,
+            Some(MemoryUserName {
+                name: "replaced".to_owned()
+            })
+        );
+
+        let res = client
+            .delete("user", 2001_usize)
+            .return_old()
+            .run()
+            .await
+            .unwrap();
+        assert_eq!(res.old_item, Some(user("user", 2001, "updated")));
+
+        let res = client
+            .delete("user", 2001_usize)
+            .return_old()
+            .run()
+            .await
+            .unwrap();
+        assert_eq!(res.old_item, None);
+    }
+
+    #[cfg(feature = "aws-sdk")]
+    #[tokio::test]
+    async fn test_memory_return_old_on_condition_failure() {
+        let (_db, client) = setup().await;
+
+        let cond = MemoryUser::condition()
+            .attr(MemoryUser::name())
+            .eq_value("even");
+        let err = client
+            .delete("user", 2001_usize)
+            .condition(cond)
+            .return_old_on_condition_failure()
+            .run()
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            RaidenError::ConditionalCheckFailedWithItem { .. }
+        ));
+        assert_eq!(
+            err.current_item::<MemoryUser>().unwrap().unwrap(),
+            user("user", 2001, "odd")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_memory_version_conflicts() {
+        let db = MemoryDb::new();
+        #[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
+        let client = MemoryVersioned::client_with(db.client(), Region::ApNortheast1);
+        #[cfg(feature = "aws-sdk")]
+        let client = MemoryVersioned::client_with(db.client());
+        client.create_table().run().await.unwrap();
+        let input = |version: u64| {
+            MemoryVersioned::put_item_builder()
+                .id("id0".to_owned())
+                .name("bokuweb".to_owned())
+                .version(version)
+                .build()
+        };
+        client.put(input(0)).run().await.unwrap();
+
+        let other_name = || {
+            MemoryVersioned::condition()
+                .attr(MemoryVersioned::name())
+                .eq_value("other")
+        };
+        let res = client.put(input(1)).condition(other_name()).run().await;
+        assert!(matches!(res, Err(RaidenError::ConditionalCheckFailed(_))));
+        let res = client.put(input(0)).run().await;
+        assert!(matches!(res, Err(RaidenError::VersionConflict(_))));
+
+        let set_name = || {
+            MemoryVersioned::update_expression()
+                .set(MemoryVersioned::name())
+                .value("updated")
+        };
+        let res = client
+            .update("id0", 1)
+            .set(set_name())
+            .condition(other_name())
+            .run()
+            .await;
+        assert!(matches!(res, Err(RaidenError::ConditionalCheckFailed(_))));
+        let res = client.update("id0", 2).set(set_name()).run().await;
+        assert!(matches!(res, Err(RaidenError::VersionConflict(_))));
+
+        let res = client.delete("id0", 1).condition(other_name()).run().await;
+        assert!(matches!(res, Err(RaidenError::ConditionalCheckFailed(_))));
+        let res = client.delete("id1", 1).run().await;
+        assert!(matches!(res, Err(RaidenError::VersionConflict(_))));
+        client.delete("id0", 1).run().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_memory_update_arithmetic_and_list_append() {
+        let db = MemoryDb::new();
+        #[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
+        let client = MemoryCounter::client_with(db.client(), Region::ApNortheast1);
+        #[cfg(feature = "aws-sdk")]
+        let client = MemoryCounter::client_with(db.client());
+        client.create_table().run().await.unwrap();
+
+        let count = MemoryCounter::update_expression()
+            .set(MemoryCounter::count())
+            .value(0)
+            .if_not_exists()
+            .add_value(5);
+        let limit = MemoryCounter::update_expression()
+            .set(MemoryCounter::limit())
+            .value(10);
+        let events = MemoryCounter::update_expression()
+            .set(MemoryCounter::events())
+            .list_append(vec!["created".to_owned()])
+            .if_not_exists();
+        client
+            .update("counter")
+            .set(count)
+            .set(limit)
+            .set(events)
+            .run()
+            .await
+            .unwrap();
+
+        let count = MemoryCounter::update_expression()
+            .set(MemoryCounter::count())
+            .attr(MemoryCounter::count())
+            .sub_value(2);
+        let limit = MemoryCounter::update_expression()
+            .set(MemoryCounter::limit())
+            .attr(MemoryCounter::limit())
+            .add_attr(MemoryCounter::count());
+        let events = MemoryCounter::update_expression()
+            .set(MemoryCounter::events())
+            .list_prepend(vec!["first".to_owned()]);
+        let res = client
+            .update("counter")
+            .set(count)
+            .set(limit)
+            .set(events)
+            .return_all_new()
+            .run()
+            .await
+            .unwrap();
+        assert_eq!(
+            res.item,
+            Some(MemoryCounter {
+                id: "counter".to_owned(),
+                count: 3,
+                limit: 15,
+                events: vec!["first".to_owned(), "created".to_owned()],
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_memory_update_nested_paths() {
+        let db = MemoryDb::new();
+        #[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
+        let client = MemoryPlayer::client_with(db.client(), Region::ApNortheast1);
+        #[cfg(feature = "aws-sdk")]
+        let client = MemoryPlayer::client_with(db.client());
+        client.create_table().run().await.unwrap();
+
+        let input = MemoryPlayer::put_item_builder()
+            .id("player".to_owned())
+            .profile(Document::new(MemoryProfile {
+                level: 1,
+                nickname: "rookie".to_owned(),
+            }))
+            .badges(vec!["bronze".to_owned(), "silver".to_owned()])
+            .stats(HashMap::from([
+                ("wins".to_owned(), 1),
+                ("losses".to_owned(), 2),
+            ]))
+            .build();
+        client.put(input).run().await.unwrap();
+
+        let level = MemoryPlayer::update_expression()
+            .set(MemoryPlayer::profile().field(MemoryProfile::level()))
+            .attr(MemoryPlayer::profile().field(MemoryProfile::level()))
+            .add_value(1);
+        let badge = MemoryPlayer::update_expression()
+            .set(MemoryPlayer::badges())
+            .index(1)
+            .value("gold");
+        let wins = MemoryPlayer::update_expression()
+            .add(MemoryPlayer::stats().key("wins"))
+            .value(1);
+        let res = client
+            .update("player")
+            .set(level)
+            .set(badge)
+            .add(wins)
+            .remove(MemoryPlayer::stats().key("losses"))
+            .return_all_new()
+            .run()
+            .await
+            .unwrap();
+        assert_eq!(
+            res.item,
+            Some(MemoryPlayer {
+                id: "player".to_owned(),
+                profile: Document::new(MemoryProfile {
+                    level: 2,
+                    nickname: "rookie".to_owned(),
+                }),
+                badges: vec!["bronze".to_owned(), "gold".to_owned()],
+                stats: HashMap::from([("wins".to_owned(), 2)]),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_memory_update_non_identifier_nested_paths() {
+        let db = MemoryDb::new();
+        #[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
+        let client = MemoryContact::client_with(db.client(), Region::ApNortheast1);
+        #[cfg(feature = "aws-sdk")]
+        let client = MemoryContact::client_with(db.client());
+        client.create_table().run().await.unwrap();
+
+        let input = MemoryContact::put_item_builder()
+            .id("contact".to_owned())
+            .home(HashMap::new())
+            .work(HashMap::new())
+            .build();
+        client.put(input).run().await.unwrap();
+
+        // Both keys sit at the same depth, so they must not share a name placeholder.
+        let first_name = MemoryContact::update_expression()
+            .set(MemoryContact::home().key("first-name"))
+            .value("bokuweb");
+        let last_name = MemoryContact::update_expression()
+            .set(MemoryContact::work().key("last-name"))
+            .value("raiden");
+        let res = client
+            .update("contact")
+            .set(first_name)
+            .set(last_name)
+            .return_all_new()
+            .run()
+            .await
+            .unwrap();
+        assert_eq!(
+            res.item,
+            Some(MemoryContact {
+                id: "contact".to_owned(),
+                home: HashMap::from([("first-name".to_owned(), "bokuweb".to_owned())]),
+                work: HashMap::from([("last-name".to_owned(), "raiden".to_owned())]),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_memory_query_and_scan() {
+        let (_db, client) = setup().await;
+
+        let cond = MemoryUser::partition_key_condition()
+            .eq("user")
+            .and(MemoryUser::sort_key_condition().gt(2001));
+        let res = client
+            .query()
+            .key_condition(cond)
+            .desc()
+            .limit(2)
+            .run()
+            .await
+            .unwrap();
+        assert_eq!(
+            res.items,
+            vec![user("user", 2004, "even"), user("user", 2003, "odd")]
+        );
+        assert!(res.next_token.is_some());
+
+        let cond = MemoryUser::partition_key_condition()
+            .eq("user")
+            .and(MemoryUser::sort_key_condition().gt(2001));
+        let res = client
+            .query()
+            .key_condition(cond)
+            .desc()
+            .next_token(res.next_token.unwrap())
+            .run()
+            .await
+            .unwrap();
+        assert_eq!(res.items, vec![user("user", 2002, "even")]);
+
+        let cond = MemoryUser::name_index_key_condition().eq("odd");
+        let res = client
+            .query()
+            .name_index()
+            .key_condition(cond)
+            .run()
+            .await
+            .unwrap();
+        assert_eq!(
+            res.items,
+            vec![user("user", 2001, "odd"), user("user", 2003, "odd")]
+        );
+
+        let filter = MemoryUser::filter_expression(MemoryUser::name()).eq("even");
+        let res = client.scan().filter(filter).run().await.unwrap();
+        assert_eq!(res.items.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_memory_parallel_scan_resume() {
+        use raiden::scan::{ScanSegment, ScanSegmentState};
+
+        let db = MemoryDb::new();
+        #[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
+        let client = MemoryCounter::client_with(db.client(), Region::ApNortheast1);
+        #[cfg(feature = "aws-sdk")]
+        let client = MemoryCounter::client_with(db.client());
+        client.create_table().run().await.unwrap();
+        for i in 0..10 {
+            let input = MemoryCounter::put_item_builder()
+                .id(format!("counter{i}"))
+                .count(i)
+                .limit(10)
+                .events(vec![])
+                .build();
+            client.put(input).run().await.unwrap();
+        }
+
+        // The second segment has not been started when the scan was interrupted.
+        let segments = vec![
+            ScanSegment {
+                segment: 0,
+                total_segments: 2,
+                state: ScanSegmentState::Done,
+            },
+            ScanSegment {
+                segment: 1,
+                total_segments: 2,
+                state: ScanSegmentState::Pending,
+            },
+        ];
+        let res = client
+            .scan()
+            .parallel(2)
+            .resume(segments)
+            .run()
+            .await
+            .unwrap();
+        let expected = client.scan().segment(1, 2).run().await.unwrap();
+        assert!(!expected.items.is_empty());
+        assert_eq!(res.items, expected.items);
+        assert_eq!(
+            res.segments
+                .iter()
+                .map(|segment| segment.segment)
+                .collect::<Vec<_>>(),
+            vec![0, 1]
+        );
+        assert!(res.segments.iter().all(ScanSegment::is_completed));
+    }
+
+    #[tokio::test]
+    async fn test_memory_transact_write() {
+        let (db, client) = setup().await;
+
+        let input = MemoryUser::put_item_builder()
+            .id("new".to_owned())
+            .year(2000)
+            .name("new".to_owned())
+            .tags(HashSet::new())
+            .build();
+        let cond = MemoryUser::condition().attr_not_exists(MemoryUser::id());
+        let res = create_tx_client(&db)
+            .put(MemoryUser::put(input.clone()))
+            .condition_check(MemoryUser::condition_check("user", 2000_usize).condition(cond))
+            .run()
+            .await;
+        match res {
+            Err(RaidenError::TransactionCanceled { reasons, .. }) => assert_eq!(
+                reasons,
+                RaidenTransactionCancellationReasons(vec![
+                    None,
+                    Some(RaidenTransactionCancellationReason::ConditionalCheckFailed),
+                ])
+            ),
+            _ => panic!("err should be RaidenError::TransactionCanceled"),
+        }
+        assert!(client.get("new", 2000_usize).run().await.is_err());
+
+        create_tx_client(&db)
+            .put(MemoryUser::put(input))
+            .delete(MemoryUser::delete("user", 2000_usize))
+            .run()
+            .await
+            .unwrap();
+        assert!(client.get("new", 2000_usize).run().await.is_ok());
+        assert!(client.get("user", 2000_usize).run().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_memory_transact_write_cancellations() {
+        let (db, _client) = setup().await;
+
+        let cond = MemoryUser::condition()
+            .attr(MemoryUser::name())
+            .eq_value("even");
+        let err = create_tx_client(&db)
+            .delete(MemoryUser::delete("user", 2000_usize))
+            .condition_check(MemoryUser::condition_check("user", 2001_usize).condition(cond))
+            .run()
+            .await
+            .unwrap_err();
+
+        let cancellations = err.transaction_cancellations().unwrap();
+        assert_eq!(cancellations.len(), 2);
+        assert_eq!(cancellations[0].index, 0);
+        assert_eq!(cancellations[0].reason, None);
+        assert_eq!(cancellations[1].index, 1);
+        assert_eq!(
+            cancellations[1].reason,
+            Some(RaidenTransactionCancellationReason::ConditionalCheckFailed)
+        );
+        assert!(cancellations[1].current_item::<MemoryUser>().is_none());
+    }
+
+    #[cfg(feature = "aws-sdk")]
+    #[tokio::test]
+    async fn test_memory_transact_write_cancellation_item() {
+        let (db, _client) = setup().await;
+
+        let cond = MemoryUser::condition()
+            .attr(MemoryUser::name())
+            .eq_value("even");
+        let err = create_tx_client(&db)
+            .delete(MemoryUser::delete("user", 2000_usize))
+            .condition_check(
+                MemoryUser::condition_check("user", 2001_usize)
+                    .condition(cond)
+                    .return_old_on_condition_failure(),
+            )
+            .run()
+            .await
+            .unwrap_err();
+
+        let cancellations = err.transaction_cancellations().unwrap();
+        assert_eq!(
+            cancellations[1].message.as_deref(),
+            Some("The conditional request failed")
+        );
+        assert_eq!(
+            cancellations[1]
+                .current_item::<MemoryUser>()
+                .unwrap()
+                .unwrap(),
+            user("user", 2001, "odd")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_memory_read_tx_across_models() {
+        let (db, _client) = setup().await;
+        #[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
+        let counters = MemoryCounter::client_with(db.client(), Region::ApNortheast1);
+        #[cfg(feature = "aws-sdk")]
+        let counters = MemoryCounter::client_with(db.client());
+        counters.create_table().run().await.unwrap();
+        let count = MemoryCounter::update_expression()
+            .set(MemoryCounter::count())
+            .value(3);
+        let limit = MemoryCounter::update_expression()
+            .set(MemoryCounter::limit())
+            .value(10);
+        counters
+            .update("counter")
+            .set(count)
+            .set(limit)
+            .run()
+            .await
+            .unwrap();
+
+        let res = create_read_tx(&db)
+            .get(MemoryUser::get("user", 2001_usize))
+            .get(MemoryCounter::get("counter"))
+            .get(MemoryUser::get("missing", 2001_usize))
+            .run()
+            .await
+            .unwrap();
+
+        let (user_item, counter, missing) = res.items;
+        assert_eq!(user_item, Some(user("user", 2001, "odd")));
+        assert_eq!(counter.map(|counter| counter.count), Some(3));
+        assert_eq!(missing, None);
+    }
+
+    #[tokio::test]
+    async fn test_memory_consumed_capacity() {
+        let (db, client) = setup().await;
+
+        let input = MemoryUser::put_item_builder()
+            .id("capacity".to_owned())
+            .year(2000)
+            .name("even".to_owned())
+            .tags(HashSet::new())
+            .build();
+        let res = client
+            .put(input)
+            .return_consumed_capacity(ReturnConsumedCapacity::Indexes)
+            .run()
+            .await
+            .unwrap();
+        let consumed = res.consumed_capacity.unwrap();
+        assert_eq!(consumed.capacity_units, Some(1.0));
+        assert_eq!(consumed.table.unwrap().capacity_units, Some(1.0));
+
+        let res = client.get("user", 2000_usize).run().await.unwrap();
+        assert_eq!(res.consumed_capacity, None);
+
+        // Each segment is a request, and the output sums all of them.
+        let res = client
+            .scan()
+            .return_consumed_capacity(ReturnConsumedCapacity::Total)
+            .parallel(3)
+            .run()
+            .await
+            .unwrap();
+        assert_eq!(res.consumed_capacity.unwrap().capacity_units, Some(3.0));
+
+        // 30 items are written by two requests of at most 25 items.
+        let inputs = (0..30)
+            .map(|year| {
+                MemoryUser::put_item_builder()
+                    .id("batch".to_owned())
+                    .year(year)
+                    .name("batch".to_owned())
+                    .tags(HashSet::new())
+                    .build()
+            })
+            .collect();
+        let res = client
+            .batch_put(inputs)
+            .return_consumed_capacity(ReturnConsumedCapacity::Total)
+            .run()
+            .await
+            .unwrap();
+        let consumed = res.consumed_capacity.unwrap();
+        assert_eq!(consumed.len(), 1);
+        assert_eq!(consumed[0].table_name.as_deref(), Some("MemoryUser"));
+        assert_eq!(consumed[0].capacity_units, Some(2.0));
+
+        let res = create_tx_client(&db)
+            .return_consumed_capacity(ReturnConsumedCapacity::Total)
+            .delete(MemoryUser::delete("user", 2000_usize))
+            .delete(MemoryUser::delete("user", 2001_usize))
+            .run()
+            .await
+            .unwrap();
+        let consumed = res.consumed_capacity.unwrap();
+        assert_eq!(consumed.len(), 1);
+        assert_eq!(consumed[0].capacity_units, Some(1.0));
+    }
+}
@@ -1,20 +1,29 @@
+mod backend;
 mod batch_delete;
 mod batch_get;
 mod batch_put;
 mod condition;
 mod delete;
 mod document;
+mod entity;
 mod filter_expression;
 mod get;
 mod key_condition;
+mod key_template;
+mod memory;
 mod put;
 mod query;
 mod rename;
 mod rename_all;
+mod scalar;
 mod scan;
+mod table;
+mod timestamp;
 mod transact_get;
 mod transact_write;
+mod ttl;
 mod update;
+mod version;
 
 #[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
 macro_rules! create_client {
@@ -1,4 +1,5 @@
 #[cfg(test)]
+#[allow(deprecated)]
 mod tests {
 
     use std::collections::HashMap;
@@ -12,6 +13,7 @@ mod tests {
         #[raiden(partition_key)]
         id: String,
         name: String,
+        #[raiden(sort_key)]
         year: usize,
         num: usize,
         option: Option<String>,
@@ -37,7 +39,7 @@ mod tests {
     #[tokio::test]
     async fn test_query() {
         let client = crate::all::create_client_from_struct!(QueryTestData0);
-        let cond = QueryTestData0::key_condition(QueryTestData0::id()).eq("id0");
+        let cond = QueryTestData0::partition_key_condition().eq("id0");
         let res = client.query().key_condition(cond).run().await;
 
         assert_eq!(
@@ -70,9 +72,9 @@ mod tests {
     #[tokio::test]
     async fn test_query_with_and_key_condition() {
         let client = crate::all::create_client_from_struct!(QueryTestData0);
-        let cond = QueryTestData0::key_condition(QueryTestData0::id())
+        let cond = QueryTestData0::partition_key_condition()
             .eq("id0")
-            .and(QueryTestData0::key_condition(QueryTestData0::year()).eq(1999));
+            .and(QueryTestData0::sort_key_condition().eq(1999));
         let res = client.query().key_condition(cond).run().await;
 
         assert_eq!(
@@ -96,7 +98,7 @@ mod tests {
     #[tokio::test]
     async fn test_query_with_simple_filter() {
         let client = crate::all::create_client_from_struct!(QueryTestData0);
-        let cond = QueryTestData0::key_condition(QueryTestData0::id()).eq("id3");
+        let cond = QueryTestData0::partition_key_condition().eq("id3");
         let filter = QueryTestData0::filter_expression(QueryTestData0::num()).eq(4000);
         let res = client
             .query()
@@ -112,7 +114,7 @@ mod tests {
     async fn test_query_builder_keeps_map_path_attribute_names() {
         reset_value_id();
         let client = crate::all::create_client_from_struct!(QueryMapPathTest);
-        let cond = QueryMapPathTest::key_condition(QueryMapPathTest::id()).eq("id0");
+        let cond = QueryMapPathTest::partition_key_condition().eq("id0");
         let filter =
             QueryMapPathTest::filter_expression(QueryMapPathTest::metadata().key("score")).eq(42);
 
@@ -155,7 +157,7 @@ mod tests {
     async fn test_query_builder_keeps_document_path_attribute_names() {
         reset_value_id();
         let client = crate::all::create_client_from_struct!(QueryMapPathTest);
-        let cond = QueryMapPathTest::key_condition(QueryMapPathTest::id()).eq("id0");
+        let cond = QueryMapPathTest::partition_key_condition().eq("id0");
         let filter = QueryMapPathTest::filter_expression(
             QueryMapPathTest::profile().field(Profile::level()),
         )
@@ -199,7 +201,7 @@ mod tests {
     #[tokio::test]
     async fn test_query_with_size_filter() {
         let client = crate::all::create_client_from_struct!(QueryTestData0);
-        let cond = QueryTestData0::key_condition(QueryTestData0::id()).eq("id5");
+        let cond = QueryTestData0::partition_key_condition().eq("id5");
         let filter = QueryTestData0::filter_expression(QueryTestData0::name())
             .size()
             .ge(4);
@@ -216,7 +218,7 @@ mod tests {
     #[tokio::test]
     async fn test_query_with_or_filter() {
         let client = crate::all::create_client_from_struct!(QueryTestData0);
-        let cond = QueryTestData0::key_condition(QueryTestData0::id()).eq("id3");
+        let cond = QueryTestData0::partition_key_condition().eq("id3");
         let filter = QueryTestData0::filter_expression(QueryTestData0::name())
             .eq("bar0")
             .or(QueryTestData0::filter_expression(QueryTestData0::name()).eq("bar1"));
@@ -233,7 +235,7 @@ mod tests {
     #[tokio::test]
     async fn test_query_with_attribute_exists_filter() {
         let client = crate::all::create_client_from_struct!(QueryTestData0);
-        let cond = QueryTestData0::key_condition(QueryTestData0::id()).eq("id4");
+        let cond = QueryTestData0::partition_key_condition().eq("id4");
         let filter = QueryTestData0::filter_expression(QueryTestData0::option()).attribute_exists();
         let res = client
             .query()
@@ -248,7 +250,7 @@ mod tests {
     #[tokio::test]
     async fn test_query_with_attribute_not_exists_filter() {
         let client = crate::all::create_client_from_struct!(QueryTestData0);
-        let cond = QueryTestData0::key_condition(QueryTestData0::id()).eq("id4");
+        let cond = QueryTestData0::partition_key_condition().eq("id4");
         let filter =
             QueryTestData0::filter_expression(QueryTestData0::option()).attribute_not_exists();
         let res = client
@@ -264,7 +266,7 @@ mod tests {
     #[tokio::test]
     async fn test_query_with_attribute_type_filter() {
         let client = crate::all::create_client_from_struct!(QueryTestData0);
-        let cond = QueryTestData0::key_condition(QueryTestData0::id()).eq("id4");
+        let cond = QueryTestData0::partition_key_condition().eq("id4");
         let filter = QueryTestData0::filter_expression(QueryTestData0::option())
             .attribute_type(raiden::AttributeType::S);
         let res = client
@@ -280,7 +282,7 @@ mod tests {
     #[tokio::test]
     async fn test_query_with_contains_filter() {
         let client = crate::all::create_client_from_struct!(QueryTestData0);
-        let cond = QueryTestData0::key_condition(QueryTestData0::id()).eq("id4");
+        let cond = QueryTestData0::partition_key_condition().eq("id4");
         let filter = QueryTestData0::filter_expression(QueryTestData0::name()).contains("bar");
         let res = client
             .query()
@@ -295,7 +297,7 @@ mod tests {
     #[tokio::test]
     async fn test_query_in_filter() {
         let client = crate::all::create_client_from_struct!(QueryTestData0);
-        let cond = QueryTestData0::key_condition(QueryTestData0::id()).eq("id4");
+        let cond = QueryTestData0::partition_key_condition().eq("id4");
         let filter =
             QueryTestData0::filter_expression(QueryTestData0::name()).r#in(vec!["bar0", "bar1"]);
         let res = client
@@ -968,6 +970,55 @@ mod tests {
         assert_eq!(res.items.len(), 1);
     }
 
+    #[tokio::test]
+    async fn test_query_pages() {
+        use raiden::futures::TryStreamExt;
+
+        let client = crate::all::create_client_from_struct!(Test);
+        let cond = Test::key_condition(Test::ref_id()).eq("id0");
+        let pages: Vec<_> = client
+            .query()
+            .test_gsi()
+            .key_condition(cond)
+            .pages()
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert!(!pages.is_empty());
+        assert_eq!(pages.last().unwrap().next_token, None);
+        assert_eq!(pages.iter().map(|page| page.items.len()).sum::<usize>(), 10);
+    }
+
+    #[tokio::test]
+    async fn test_query_into_stream_with_limit_and_next_token() {
+        use raiden::futures::TryStreamExt;
+
+        let client = crate::all::create_client_from_struct!(Test);
+        let cond = Test::key_condition(Test::ref_id()).eq("id0");
+        let res = client
+            .query()
+            .test_gsi()
+            .limit(4)
+            .key_condition(cond)
+            .run()
+            .await
+            .unwrap();
+        assert_eq!(res.items.len(), 4);
+
+        let cond = Test::key_condition(Test::ref_id()).eq("id0");
+        let items: Vec<Test> = client
+            .query()
+            .test_gsi()
+            .next_token(res.next_token.unwrap())
+            .key_condition(cond)
+            .into_stream()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(items.len(), 6);
+    }
+
     #[derive(Raiden)]
     #[raiden(table_name = "Project")]
     #[raiden(rename_all = "camelCase")]
@@ -1006,7 +1057,7 @@ mod tests {
     #[tokio::test]
     async fn test_query_for_projection_expression() {
         let client = crate::all::create_client_from_struct!(QueryTestData0a);
-        let cond = QueryTestData0a::key_condition(QueryTestData0a::id()).eq("id0");
+        let cond = QueryTestData0a::partition_key_condition().eq("id0");
         let res = client.query().key_condition(cond).run().await;
 
         assert_eq!(
@@ -1044,9 +1095,9 @@ mod tests {
     #[tokio::test]
     async fn test_query_with_begins_with_key_condition() {
         let client = crate::all::create_client_from_struct!(QueryTestData1);
-        let cond = QueryTestData1::key_condition(QueryTestData1::id())
+        let cond = QueryTestData1::partition_key_condition()
             .eq("id0")
-            .and(QueryTestData1::key_condition(QueryTestData1::name()).begins_with("j"));
+            .and(QueryTestData1::sort_key_condition().begins_with("j"));
         let res = client.query().key_condition(cond).run().await;
 
         assert_eq!(
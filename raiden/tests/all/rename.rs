@@ -71,7 +71,7 @@ mod tests {
     #[tokio::test]
     async fn test_rename_query() {
         let client = crate::all::create_client_from_struct!(RenameTest);
-        let cond = RenameTest::key_condition(RenameTest::id()).eq("id0");
+        let cond = RenameTest::partition_key_condition().eq("id0");
         let res = client.query().key_condition(cond).run().await;
 
         assert_eq!(
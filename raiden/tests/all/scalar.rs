@@ -0,0 +1,166 @@
+#[cfg(test)]
+mod tests {
+    #[cfg(test)]
+    use pretty_assertions::assert_eq;
+    use raiden::memory::MemoryDb;
+    use raiden::*;
+    use std::collections::{BTreeSet, HashSet};
+
+    #[derive(RaidenAttribute, Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct UserId(String);
+
+    #[derive(RaidenAttribute, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    pub struct Score(u32);
+
+    #[derive(RaidenAttribute, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[raiden(rename_all = "snake_case")]
+    pub enum Status {
+        Active,
+        InProgress,
+        #[raiden(rename = "BANNED")]
+        Banned,
+    }
+
+    #[derive(RaidenAttribute, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[raiden(as_number)]
+    pub enum Priority {
+        Low = 1,
+        High = 10,
+    }
+
+    #[derive(Raiden, Debug, Clone, PartialEq)]
+    #[raiden(table_name = "ScalarUser")]
+    pub struct ScalarUser {
+        #[raiden(partition_key)]
+        id: UserId,
+        score: Score,
+        status: Status,
+        previous_status: Option<Status>,
+        priority: Priority,
+        statuses: HashSet<Status>,
+        priorities: HashSet<Priority>,
+        friends: HashSet<UserId>,
+        scores: BTreeSet<Score>,
+    }
+
+    #[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
+    fn create_client(db: &MemoryDb) -> ScalarUserClient {
+        ScalarUser::client_with(db.client(), Region::ApNortheast1)
+    }
+
+    #[cfg(feature = "aws-sdk")]
+    fn create_client(db: &MemoryDb) -> ScalarUserClient {
+        ScalarUser::client_with(db.client())
+    }
+
+    #[test]
+    fn test_scalar_attribute_values() {
+        assert_eq!(
+            UserId("a".to_owned()).into_attr(),
+            "a".to_owned().into_attr()
+        );
+        assert_eq!(Score(3).into_attr(), 3_u32.into_attr());
+        assert_eq!(Status::Active.into_attr(), "active".to_owned().into_attr());
+        assert_eq!(
+            Status::InProgress.into_attr(),
+            "in_progress".to_owned().into_attr()
+        );
+        assert_eq!(Status::Banned.into_attr(), "BANNED".to_owned().into_attr());
+        assert_eq!(Priority::High.into_attr(), 10_i64.into_attr());
+
+        assert_eq!(
+            Status::from_attr(Some("in_progress".to_owned().into_attr())).unwrap(),
+            Status::InProgress
+        );
+        assert_eq!(
+            Priority::from_attr(Some(1_i64.into_attr())).unwrap(),
+            Priority::Low
+        );
+        assert_eq!(
+            UserId::from_attr(Some("a".to_owned().into_attr())).unwrap(),
+            UserId("a".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_scalar_attribute_rejects_unknown_values() {
+        let err = Status::from_attr(Some("deleted".to_owned().into_attr())).unwrap_err();
+        assert_eq!(err.to_string(), "unknown Status `deleted`");
+
+        let err = Priority::from_attr(Some(5_i64.into_attr())).unwrap_err();
+        assert_eq!(err.to_string(), "unknown Priority `5`");
+
+        let err = Status::from_attr(Some(1_i64.into_attr())).unwrap_err();
+        assert!(matches!(err, ConversionError::UnexpectedType { .. }));
+    }
+
+    #[test]
+    fn test_scalar_sets_of_numbers() {
+        let priorities = HashSet::from([Priority::Low, Priority::High]);
+        let value = priorities.clone().into_attr();
+        assert_eq!(AttributeType::of(&value), Some(AttributeType::NS));
+        assert_eq!(
+            HashSet::<Priority>::from_attr(Some(value)).unwrap(),
+            priorities
+        );
+
+        let scores = BTreeSet::from([Score(1), Score(42)]);
+        let value = scores.clone().into_attr();
+        assert_eq!(value, BTreeSet::from([1_u32, 42]).into_attr());
+        assert_eq!(BTreeSet::<Score>::from_attr(Some(value)).unwrap(), scores);
+
+        let statuses = HashSet::from([Status::Active]);
+        let value = statuses.into_attr();
+        assert_eq!(AttributeType::of(&value), Some(AttributeType::SS));
+        assert!(HashSet::<Priority>::from_attr(Some(value)).is_err());
+    }
+
+    #[test]
+    fn test_scalar_key_attribute_types() {
+        assert_eq!(<UserId as KeyAttribute>::KEY_TYPE, AttributeType::S);
+        assert_eq!(<Score as KeyAttribute>::KEY_TYPE, AttributeType::N);
+        assert_eq!(<Status as KeyAttribute>::KEY_TYPE, AttributeType::S);
+        assert_eq!(<Priority as KeyAttribute>::KEY_TYPE, AttributeType::N);
+    }
+
+    #[tokio::test]
+    async fn test_scalar_attribute_round_trip() {
+        let db = MemoryDb::new();
+        let client = create_client(&db);
+        client.create_table().run().await.unwrap();
+
+        let user = ScalarUser {
+            id: UserId("bokuweb".to_owned()),
+            score: Score(42),
+            status: Status::InProgress,
+            previous_status: None,
+            priority: Priority::High,
+            statuses: HashSet::from([Status::Active, Status::Banned]),
+            priorities: HashSet::from([Priority::Low, Priority::High]),
+            friends: HashSet::from([UserId("raiden".to_owned())]),
+            scores: BTreeSet::from([Score(1), Score(42)]),
+        };
+        let input = ScalarUser::put_item_builder()
+            .id(user.id.clone())
+            .score(user.score)
+            .status(user.status)
+            .priority(user.priority)
+            .statuses(user.statuses.clone())
+            .priorities(user.priorities.clone())
+            .friends(user.friends.clone())
+            .scores(user.scores.clone())
+            .build();
+        client.put(input).run().await.unwrap();
+
+        let res = client
+            .get(UserId("bokuweb".to_owned()))
+            .run()
+            .await
+            .unwrap();
+        assert_eq!(res.item, user);
+
+        let filter = ScalarUser::filter_expression(ScalarUser::status()).eq(Status::InProgress);
+        let res = client.scan().filter(filter).run().await.unwrap();
+        assert_eq!(res.items, vec![user]);
+    }
+}
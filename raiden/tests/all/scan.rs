@@ -516,6 +516,128 @@ mod tests {
         assert_eq!(res.unwrap().items.len(), 100);
     }
 
+    #[tokio::test]
+    async fn test_scan_pages_follow_last_evaluated_key() {
+        use raiden::futures::TryStreamExt;
+
+        let client = crate::all::create_client_from_struct!(ScanLargeDataTest);
+        let pages: Vec<_> = client.scan().pages().try_collect().await.unwrap();
+
+        assert!(pages.len() > 1);
+        assert!(pages[..pages.len() - 1]
+            .iter()
+            .all(|page| page.last_evaluated_key.is_some()));
+        assert_eq!(pages.last().unwrap().last_evaluated_key, None);
+        assert_eq!(
+            pages.iter().map(|page| page.items.len()).sum::<usize>(),
+            100
+        );
+    }
+
+    #[tokio::test]
+    async fn test_scan_into_stream() {
+        use raiden::futures::TryStreamExt;
+
+        let client = crate::all::create_client_from_struct!(ScanLargeDataTest);
+        let items: Vec<ScanLargeDataTest> =
+            client.scan().into_stream().try_collect().await.unwrap();
+
+        assert_eq!(items.len(), 100);
+    }
+
+    #[tokio::test]
+    async fn test_parallel_scan() {
+        let client = crate::all::create_client_from_struct!(ScanLargeDataTest);
+        let res = client.scan().parallel(4).run().await.unwrap();
+
+        assert_eq!(res.items.len(), 100);
+        assert_eq!(res.count, Some(100));
+        assert_eq!(
+            res.segments
+                .iter()
+                .map(|segment| segment.segment)
+                .collect::<Vec<_>>(),
+            vec![0, 1, 2, 3]
+        );
+        assert!(res.segments.iter().all(|segment| segment.is_completed()));
+    }
+
+    #[tokio::test]
+    async fn test_parallel_scan_without_segments() {
+        use raiden::futures::TryStreamExt;
+
+        let client = crate::all::create_client_from_struct!(ScanLargeDataTest);
+        let res = client.scan().parallel(0).run().await;
+        assert!(matches!(
+            res,
+            Err(raiden::scan::ParallelScanError {
+                source: RaidenError::Validation(_),
+                ..
+            })
+        ));
+
+        let res: Result<Vec<ScanLargeDataTest>, _> =
+            client.scan().parallel(0).into_stream().try_collect().await;
+        assert!(matches!(res, Err(RaidenError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_parallel_scan_resume() {
+        let client = crate::all::create_client_from_struct!(ScanLargeDataTest);
+        let mut res = client.scan().parallel(2).limit(10).run().await.unwrap();
+        let mut ids: Vec<String> = res.items.into_iter().map(|item| item.id).collect();
+
+        assert!(ids.len() <= 20);
+        while res.segments.iter().any(|segment| !segment.is_completed()) {
+            res = client
+                .scan()
+                .parallel(2)
+                .limit(10)
+                .resume(res.segments)
+                .run()
+                .await
+                .unwrap();
+            ids.extend(res.items.into_iter().map(|item| item.id));
+        }
+
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), 100);
+    }
+
+    #[tokio::test]
+    async fn test_parallel_scan_into_stream() {
+        use raiden::futures::TryStreamExt;
+
+        let client = crate::all::create_client_from_struct!(ScanLargeDataTest);
+        let items: Vec<ScanLargeDataTest> = client
+            .scan()
+            .parallel(4)
+            .concurrency(2)
+            .into_stream()
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(items.len(), 100);
+    }
+
+    #[tokio::test]
+    async fn test_scan_into_stream_with_limit() {
+        use raiden::futures::TryStreamExt;
+
+        let client = crate::all::create_client_from_struct!(Test);
+        let items: Vec<Test> = client
+            .scan()
+            .limit(5)
+            .into_stream()
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(items.len(), 5);
+    }
+
     #[derive(Raiden, Debug)]
     #[raiden(table_name = "ScanWithFilterTestData0")]
     #[allow(dead_code)]
@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod tests {
+
+    #[cfg(test)]
+    use pretty_assertions::assert_eq;
+    use raiden::*;
+
+    #[allow(dead_code)]
+    #[derive(Raiden, Debug, Clone)]
+    #[raiden(gsi(name = "nameIndex", partition_key = "name", sort_key = "age"))]
+    pub struct CreateTableTest0 {
+        #[raiden(partition_key)]
+        id: String,
+        #[raiden(sort_key)]
+        year: usize,
+        name: String,
+        age: u32,
+        #[raiden(omit_gsi = "nameIndex")]
+        description: String,
+    }
+
+    #[tokio::test]
+    async fn test_create_describe_and_delete_table() {
+        let client = crate::all::create_client_from_struct!(CreateTableTest0);
+        let res = client.create_table().run().await;
+        assert_eq!(res.is_ok(), true);
+
+        let table = client.describe_table().run().await.unwrap();
+        let description = table.table_description.unwrap();
+        assert_eq!(description.table_name, Some("CreateTableTest0".to_owned()));
+
+        let key_schema: Vec<String> = description
+            .key_schema
+            .unwrap()
+            .into_iter()
+            .map(|key| key.attribute_name)
+            .collect();
+        assert_eq!(key_schema, vec!["id".to_owned(), "year".to_owned()]);
+
+        let mut attribute_names: Vec<String> = description
+            .attribute_definitions
+            .unwrap()
+            .into_iter()
+            .map(|definition| definition.attribute_name)
+            .collect();
+        attribute_names.sort();
+        assert_eq!(
+            attribute_names,
+            vec![
+                "age".to_owned(),
+                "id".to_owned(),
+                "name".to_owned(),
+                "year".to_owned()
+            ]
+        );
+
+        let indexes = description.global_secondary_indexes.unwrap();
+        assert_eq!(indexes.len(), 1);
+        assert_eq!(indexes[0].index_name, Some("nameIndex".to_owned()));
+
+        let res = client.delete_table().run().await;
+        assert_eq!(res.is_ok(), true);
+
+        let res = client.describe_table().run().await;
+        assert!(matches!(res, Err(RaidenError::ResourceNotFound(_))));
+    }
+
+    #[allow(dead_code)]
+    #[derive(Raiden, Debug, Clone)]
+    pub struct CreateTableTest1 {
+        #[raiden(partition_key)]
+        id: String,
+        expired_at: usize,
+    }
+
+    #[tokio::test]
+    async fn test_create_table_with_prefix_and_provisioned_throughput() {
+        let client = crate::all::create_client_from_struct!(CreateTableTest1)
+            .table_prefix("test-")
+            .table_suffix("-staging");
+        let res = client
+            .create_table()
+            .provisioned_throughput(5, 5)
+            .time_to_live("expired_at")
+            .run()
+            .await;
+        assert_eq!(res.is_ok(), true);
+
+        let description = res.unwrap().table_description.unwrap();
+        assert_eq!(
+            description.table_name,
+            Some("test-CreateTableTest1-staging".to_owned())
+        );
+
+        let res = client.create_table().run().await;
+        assert!(matches!(res, Err(RaidenError::ResourceInUse(_))));
+
+        let res = client.delete_table().run().await;
+        assert_eq!(res.is_ok(), true);
+    }
+
+    #[allow(dead_code)]
+    #[derive(Raiden, Debug, Clone)]
+    pub struct CreateTableTest2 {
+        #[raiden(partition_key)]
+        id: String,
+        #[raiden(ttl)]
+        expires_at: std::time::SystemTime,
+    }
+
+    #[tokio::test]
+    async fn test_create_table_with_ttl_field() {
+        let client = crate::all::create_client_from_struct!(CreateTableTest2);
+        let res = client.create_table().run().await;
+        assert_eq!(res.is_ok(), true);
+
+        let res = client.delete_table().run().await;
+        assert_eq!(res.is_ok(), true);
+    }
+}
@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod tests {
+    #[cfg(test)]
+    use pretty_assertions::assert_eq;
+    use raiden::*;
+
+    const FIXED_NOW: &str = "1600000000";
+
+    #[derive(Raiden, Debug, Clone, PartialEq)]
+    #[raiden(table_name = "TimestampTestData0")]
+    #[raiden(rename_all = "camelCase")]
+    pub struct Timestamped {
+        #[raiden(partition_key)]
+        id: String,
+        name: String,
+        #[raiden(created_at)]
+        created_at: String,
+        #[raiden(updated_at)]
+        updated_at: i64,
+    }
+
+    #[tokio::test]
+    async fn test_put_with_timestamps() {
+        std::env::set_var("RAIDEN_CLOCK_FIXED_BY", FIXED_NOW);
+
+        let client = crate::all::create_client_from_struct!(Timestamped);
+        let input = Timestamped::put_item_builder()
+            .id("newId0".to_owned())
+            .name("bokuweb".to_owned())
+            .build();
+        let res = client.put(input).run().await.unwrap();
+        assert_eq!(res.item.created_at, "2020-09-13T12:26:40.000Z");
+        assert_eq!(res.item.updated_at, 1_600_000_000);
+
+        let res = client.get("newId0").run().await.unwrap();
+        assert_eq!(
+            res.item,
+            Timestamped {
+                id: "newId0".to_owned(),
+                name: "bokuweb".to_owned(),
+                created_at: "2020-09-13T12:26:40.000Z".to_owned(),
+                updated_at: 1_600_000_000,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_refreshes_updated_at() {
+        std::env::set_var("RAIDEN_CLOCK_FIXED_BY", FIXED_NOW);
+
+        let client = crate::all::create_client_from_struct!(Timestamped);
+        let set_expression = Timestamped::update_expression()
+            .set(Timestamped::name())
+            .value("updated");
+        let res = client
+            .update("id0")
+            .set(set_expression)
+            .return_all_new()
+            .run()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            res.item,
+            Some(Timestamped {
+                id: "id0".to_owned(),
+                name: "updated".to_owned(),
+                created_at: "2019-01-01T00:00:00.000Z".to_owned(),
+                updated_at: 1_600_000_000,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_sets_created_at_for_new_item() {
+        std::env::set_var("RAIDEN_CLOCK_FIXED_BY", FIXED_NOW);
+
+        let client = crate::all::create_client_from_struct!(Timestamped);
+        let set_expression = Timestamped::update_expression()
+            .set(Timestamped::name())
+            .value("bokuweb");
+        let res = client
+            .update("newId1")
+            .set(set_expression)
+            .return_all_new()
+            .run()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            res.item,
+            Some(Timestamped {
+                id: "newId1".to_owned(),
+                name: "bokuweb".to_owned(),
+                created_at: "2020-09-13T12:26:40.000Z".to_owned(),
+                updated_at: 1_600_000_000,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_keeps_updated_at_set_by_caller() {
+        std::env::set_var("RAIDEN_CLOCK_FIXED_BY", FIXED_NOW);
+
+        let client = crate::all::create_client_from_struct!(Timestamped);
+        let set_expression = Timestamped::update_expression()
+            .set(Timestamped::updated_at())
+            .value(1);
+        let res = client
+            .update("id1")
+            .set(set_expression)
+            .return_all_new()
+            .run()
+            .await
+            .unwrap();
+
+        assert_eq!(res.item.unwrap().updated_at, 1);
+    }
+}
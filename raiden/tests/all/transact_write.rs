@@ -253,4 +253,76 @@ mod tests {
             panic!("err should be RaidenError::TransactionCanceled");
         }
     }
+
+    #[derive(Raiden, Debug, Clone, PartialEq)]
+    pub struct TxSortKeyTestData0 {
+        #[raiden(partition_key)]
+        id: String,
+        #[raiden(sort_key)]
+        year: usize,
+        name: String,
+    }
+
+    #[tokio::test]
+    async fn test_transact_write_with_sort_key() {
+        let tx = create_client().await;
+        let set_expression = TxSortKeyTestData0::update_expression()
+            .set(TxSortKeyTestData0::name())
+            .value("updated!!");
+        let cond = TxSortKeyTestData0::condition().attr_exists(TxSortKeyTestData0::id());
+
+        assert_eq!(
+            tx.update(TxSortKeyTestData0::update("id0", 1999_usize).set(set_expression))
+                .delete(TxSortKeyTestData0::delete("id0", 2000_usize))
+                .condition_check(
+                    TxSortKeyTestData0::condition_check("id1", 2003_usize).condition(cond)
+                )
+                .run()
+                .await
+                .is_ok(),
+            true,
+        );
+
+        let client = crate::all::create_client_from_struct!(TxSortKeyTestData0);
+        let res = client.get("id0", 1999_usize).run().await;
+        assert_eq!(
+            res.unwrap().item,
+            TxSortKeyTestData0 {
+                id: "id0".to_owned(),
+                year: 1999,
+                name: "updated!!".to_owned()
+            }
+        );
+
+        let res = client.get("id0", 2000_usize).run().await;
+        assert!(res.is_err());
+
+        if let RaidenError::ResourceNotFound(msg) = res.unwrap_err() {
+            assert_eq!("resource not found", msg);
+        } else {
+            panic!("err should be RaidenError::ResourceNotFound");
+        }
+    }
+
+    #[tokio::test]
+    async fn should_fail_when_condition_check_with_sort_key_ng() {
+        let tx = create_client().await;
+        let cond = TxSortKeyTestData0::condition().attr_exists(TxSortKeyTestData0::id());
+
+        let res = tx
+            .condition_check(TxSortKeyTestData0::condition_check("id1", 2004_usize).condition(cond))
+            .run()
+            .await;
+
+        if let RaidenError::TransactionCanceled { reasons, .. } = res.unwrap_err() {
+            assert_eq!(
+                RaidenTransactionCancellationReasons(vec![Some(
+                    RaidenTransactionCancellationReason::ConditionalCheckFailed
+                )]),
+                reasons
+            );
+        } else {
+            panic!("err should be RaidenError::TransactionCanceled");
+        }
+    }
 }
@@ -0,0 +1,162 @@
+#[cfg(test)]
+mod tests {
+    #[cfg(test)]
+    use pretty_assertions::assert_eq;
+    use raiden::*;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    #[derive(Raiden, Debug, Clone, PartialEq)]
+    #[raiden(table_name = "TtlTestData0")]
+    #[raiden(rename_all = "camelCase")]
+    pub struct TtlTestData0 {
+        #[raiden(partition_key)]
+        id: String,
+        name: String,
+        #[raiden(ttl)]
+        expires_at: Option<SystemTime>,
+    }
+
+    // A TTL attribute whose name is not a valid placeholder as is.
+    #[derive(Raiden, Debug, Clone, PartialEq)]
+    pub struct RenamedTtl {
+        #[raiden(partition_key)]
+        id: String,
+        #[raiden(ttl)]
+        #[raiden(rename = "expires-at")]
+        expires_at: Option<SystemTime>,
+    }
+
+    fn sorted_ids(items: Vec<TtlTestData0>) -> Vec<String> {
+        let mut ids: Vec<String> = items.into_iter().map(|item| item.id).collect();
+        ids.sort();
+        ids
+    }
+
+    #[tokio::test]
+    async fn test_get_expired_item() {
+        let client = crate::all::create_client_from_struct!(TtlTestData0);
+        let res = client.get("expired").run().await.unwrap();
+        assert_eq!(
+            res.item.expires_at,
+            Some(UNIX_EPOCH + Duration::from_secs(1000))
+        );
+
+        let res = client.get("expired").filter_expired().run().await;
+        assert!(matches!(res, Err(RaidenError::ResourceNotFound(_))));
+
+        let res = client.get("alive").filter_expired().run().await;
+        assert!(res.is_ok());
+        let res = client.get("noExpiry").filter_expired().run().await;
+        assert!(res.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_scan_filter_expired() {
+        let client = crate::all::create_client_from_struct!(TtlTestData0);
+        let res = client.scan().filter_expired().run().await.unwrap();
+        assert_eq!(
+            sorted_ids(res.items),
+            vec!["alive".to_owned(), "noExpiry".to_owned()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_scan_filter_expired_with_filter() {
+        let client = crate::all::create_client_from_struct!(TtlTestData0);
+        let filter = TtlTestData0::filter_expression(TtlTestData0::name()).eq("john");
+        let res = client
+            .scan()
+            .filter_expired()
+            .filter(filter)
+            .run()
+            .await
+            .unwrap();
+        assert_eq!(res.items, vec![]);
+
+        let filter = TtlTestData0::filter_expression(TtlTestData0::name()).eq("bob");
+        let res = client
+            .scan()
+            .filter(filter)
+            .filter_expired()
+            .run()
+            .await
+            .unwrap();
+        assert_eq!(sorted_ids(res.items), vec!["alive".to_owned()]);
+    }
+
+    #[tokio::test]
+    async fn test_query_filter_expired() {
+        let client = crate::all::create_client_from_struct!(TtlTestData0);
+        let cond = TtlTestData0::partition_key_condition().eq("expired");
+        let res = client
+            .query()
+            .key_condition(cond)
+            .filter_expired()
+            .run()
+            .await
+            .unwrap();
+        assert_eq!(res.items, vec![]);
+
+        let cond = TtlTestData0::partition_key_condition().eq("alive");
+        let res = client
+            .query()
+            .key_condition(cond)
+            .filter_expired()
+            .run()
+            .await
+            .unwrap();
+        assert_eq!(sorted_ids(res.items), vec!["alive".to_owned()]);
+    }
+
+    #[tokio::test]
+    async fn test_put_with_expires_in() {
+        let client = crate::all::create_client_from_struct!(TtlTestData0);
+        let expires_at = ttl::expires_in(Duration::from_secs(60 * 60));
+        let input = TtlTestData0::put_item_builder()
+            .id("newId0".to_owned())
+            .name("bokuweb".to_owned())
+            .expires_at(expires_at)
+            .build();
+        client.put(input).run().await.unwrap();
+
+        let res = client.get("newId0").filter_expired().run().await.unwrap();
+        let stored = res.item.expires_at.unwrap();
+        assert_eq!(
+            stored.duration_since(UNIX_EPOCH).unwrap().as_secs(),
+            expires_at.duration_since(UNIX_EPOCH).unwrap().as_secs()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_filter_expired_with_renamed_ttl() {
+        let db = raiden::memory::MemoryDb::new();
+        #[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
+        let client = RenamedTtl::client_with(db.client(), Region::ApNortheast1);
+        #[cfg(feature = "aws-sdk")]
+        let client = RenamedTtl::client_with(db.client());
+        client.create_table().run().await.unwrap();
+
+        for (id, expires_at) in [
+            ("expired", UNIX_EPOCH + Duration::from_secs(1000)),
+            ("alive", ttl::expires_in(Duration::from_secs(60 * 60))),
+        ] {
+            let input = RenamedTtl::put_item_builder()
+                .id(id.to_owned())
+                .expires_at(expires_at)
+                .build();
+            client.put(input).run().await.unwrap();
+        }
+        let input = RenamedTtl::put_item_builder()
+            .id("noExpiry".to_owned())
+            .build();
+        client.put(input).run().await.unwrap();
+
+        let res = client.scan().filter_expired().run().await.unwrap();
+        let mut ids: Vec<String> = res.items.into_iter().map(|item| item.id).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["alive".to_owned(), "noExpiry".to_owned()]);
+
+        let res = client.get("expired").filter_expired().run().await;
+        assert!(matches!(res, Err(RaidenError::ResourceNotFound(_))));
+    }
+}
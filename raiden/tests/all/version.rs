@@ -0,0 +1,181 @@
+#[cfg(test)]
+mod tests {
+    #[cfg(any(feature = "rusoto", feature = "rusoto_rustls"))]
+    async fn create_tx_client() -> ::raiden::WriteTx {
+        ::raiden::WriteTx::new(Region::Custom {
+            endpoint: "http://127.0.0.1:8000".into(),
+            name: "ap-northeast-1".into(),
+        })
+    }
+
+    #[cfg(feature = "aws-sdk")]
+    async fn create_tx_client() -> ::raiden::WriteTx {
+        let sdk_config = ::raiden::aws_sdk::aws_config::defaults(
+            ::raiden::aws_sdk::config::BehaviorVersion::latest(),
+        )
+        .endpoint_url("http://127.0.0.1:8000")
+        .region(::raiden::config::Region::from_static("ap-northeast-1"))
+        .load()
+        .await;
+        let sdk_client = ::raiden::Client::new(&sdk_config);
+
+        ::raiden::WriteTx::new_with_client(sdk_client)
+    }
+
+    #[cfg(test)]
+    use pretty_assertions::assert_eq;
+    use raiden::*;
+
+    #[derive(Raiden, Debug, Clone, PartialEq)]
+    #[raiden(table_name = "VersionTestData0")]
+    pub struct Versioned {
+        #[raiden(partition_key)]
+        id: String,
+        name: String,
+        #[raiden(version)]
+        version: u64,
+    }
+
+    #[derive(Raiden, Debug, Clone, PartialEq)]
+    #[raiden(table_name = "VersionTestData0")]
+    pub struct SmallVersioned {
+        #[raiden(partition_key)]
+        id: String,
+        #[raiden(version)]
+        version: u8,
+    }
+
+    #[tokio::test]
+    async fn test_put_new_item_with_version() {
+        let client = crate::all::create_client_from_struct!(Versioned);
+        let input = Versioned::put_item_builder()
+            .id("newId0".to_owned())
+            .name("bokuweb".to_owned())
+            .version(0)
+            .build();
+        let res = client.put(input.clone()).run().await.unwrap();
+        assert_eq!(res.item.version, 1);
+
+        let res = client.put(input).run().await;
+        assert!(matches!(res, Err(RaidenError::VersionConflict(_))));
+    }
+
+    #[tokio::test]
+    async fn test_put_existing_item_with_version() {
+        let client = crate::all::create_client_from_struct!(Versioned);
+        let input = Versioned::put_item_builder()
+            .id("id3".to_owned())
+            .name("updated".to_owned())
+            .version(1)
+            .build();
+        let res = client.put(input).run().await;
+        assert!(matches!(res, Err(RaidenError::VersionConflict(_))));
+
+        let input = Versioned::put_item_builder()
+            .id("id3".to_owned())
+            .name("updated".to_owned())
+            .version(2)
+            .build();
+        let res = client.put(input).run().await.unwrap();
+        assert_eq!(res.item.version, 3);
+
+        let res = client.get("id3").run().await.unwrap();
+        assert_eq!(res.item.version, 3);
+    }
+
+    #[tokio::test]
+    async fn test_put_rejects_version_overflow() {
+        let client = crate::all::create_client_from_struct!(SmallVersioned);
+        let input = SmallVersioned::put_item_builder()
+            .id("overflowId0".to_owned())
+            .version(u8::MAX)
+            .build();
+        let err = client.put(input.clone()).run().await.unwrap_err();
+        assert_eq!(err.attribute_path(), Some("version".to_owned()));
+
+        let tx = create_tx_client().await;
+        let err = tx.put(SmallVersioned::put(input)).run().await.unwrap_err();
+        assert_eq!(err.attribute_path(), Some("version".to_owned()));
+
+        let res = client.get("overflowId0").run().await;
+        assert!(matches!(res, Err(RaidenError::ResourceNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_update_with_expected_version() {
+        let client = crate::all::create_client_from_struct!(Versioned);
+        let set_expression = Versioned::update_expression()
+            .set(Versioned::name())
+            .value("updated");
+        let res = client
+            .update("id0", 1)
+            .set(set_expression)
+            .return_all_new()
+            .run()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            res.item,
+            Some(Versioned {
+                id: "id0".to_owned(),
+                name: "updated".to_owned(),
+                version: 2,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_with_stale_version() {
+        let client = crate::all::create_client_from_struct!(Versioned);
+        let set_expression = Versioned::update_expression()
+            .set(Versioned::name())
+            .value("updated");
+        let res = client.update("id1", 2).set(set_expression).run().await;
+
+        assert!(matches!(res, Err(RaidenError::VersionConflict(_))));
+    }
+
+    #[tokio::test]
+    async fn test_update_with_failed_condition_keeps_condition_error() {
+        let client = crate::all::create_client_from_struct!(Versioned);
+        let cond = Versioned::condition().attr_not_exists(Versioned::id());
+        let res = client.update("id1", 3).condition(cond).run().await;
+
+        assert!(matches!(res, Err(RaidenError::ConditionalCheckFailed(_))));
+    }
+
+    #[tokio::test]
+    async fn test_delete_with_expected_version() {
+        let client = crate::all::create_client_from_struct!(Versioned);
+        let res = client.delete("id2", 2).run().await;
+        assert!(matches!(res, Err(RaidenError::VersionConflict(_))));
+
+        let res = client.delete("id2", 1).run().await;
+        assert!(res.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_transact_write_with_version() {
+        let tx = create_tx_client().await;
+        let input = Versioned::put_item_builder()
+            .id("newId1".to_owned())
+            .name("bokuweb".to_owned())
+            .version(0)
+            .build();
+        let res = tx
+            .put(Versioned::put(input))
+            .update(Versioned::update("id4", 1))
+            .run()
+            .await;
+        assert!(res.is_ok());
+
+        let client = crate::all::create_client_from_struct!(Versioned);
+        assert_eq!(client.get("newId1").run().await.unwrap().item.version, 1);
+        assert_eq!(client.get("id4").run().await.unwrap().item.version, 2);
+
+        let tx = create_tx_client().await;
+        let res = tx.update(Versioned::update("id4", 1)).run().await;
+        assert!(res.is_err());
+    }
+}
@@ -0,0 +1,16 @@
+use raiden::*;
+
+#[derive(Raiden)]
+#[raiden(table_name = "User")]
+struct User {
+    #[raiden(partition_key)]
+    id: String,
+    #[raiden(partition_key)]
+    name: String,
+    #[raiden(sort_key)]
+    year: usize,
+    #[raiden(sort_key)]
+    month: usize,
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+use raiden::*;
+
+#[derive(Raiden)]
+#[raiden(table_name = "User")]
+struct User {
+    #[raiden(partition_key)]
+    id: String,
+    #[raiden(version)]
+    version: Option<u64>,
+    #[raiden(created_at)]
+    created_at: u64,
+    #[raiden(created_at)]
+    registered_at: u64,
+    #[raiden(ttl)]
+    expires_at: i64,
+    #[raiden(ttl)]
+    deleted_at: i64,
+}
+
+fn main() {}
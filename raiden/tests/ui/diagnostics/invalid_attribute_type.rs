@@ -0,0 +1,25 @@
+use raiden::*;
+
+#[derive(RaidenAttribute)]
+#[raiden(as_number)]
+struct Score(u32);
+
+#[derive(RaidenAttribute)]
+struct Point {
+    x: u32,
+}
+
+#[derive(RaidenAttribute)]
+enum Status {
+    Active,
+    Banned { reason: String },
+}
+
+#[derive(RaidenAttribute)]
+#[raiden(rename_all = "snake_case")]
+enum Priority {
+    #[raiden(renmae = "low")]
+    Low,
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+use raiden::*;
+
+#[derive(Raiden)]
+#[raiden(table_name = "Entity")]
+#[raiden(partition_key = "PK")]
+pub struct User {
+    #[raiden(rename = "PK")]
+    id: String,
+}
+
+#[derive(Raiden)]
+#[raiden(table_name = "Entity", partition_key = "PK")]
+enum WithoutTag {
+    User(User),
+}
+
+#[derive(Raiden)]
+#[raiden(table_name = "Entity", tag = "type", partition_key = "PK")]
+enum Entity {
+    User(User),
+    Unknown,
+}
+
+fn main() {}
@@ -0,0 +1,29 @@
+use raiden::*;
+
+#[derive(Raiden)]
+#[raiden(table_name = "User")]
+#[raiden(gsi = "byEmail")]
+pub struct User {
+    #[raiden(partition_key)]
+    id: String,
+    email: String,
+}
+
+#[derive(RaidenIndex)]
+struct UserByEmail {
+    email: String,
+}
+
+#[derive(RaidenIndex)]
+#[raiden(source = "&User", gsi = "byEmail")]
+struct UserEmail {
+    email: String,
+}
+
+#[derive(RaidenIndex)]
+#[raiden(source = "User", gsi = "byEmail", partition_key = "email")]
+struct UserEmailKey {
+    email: String,
+}
+
+fn main() {}
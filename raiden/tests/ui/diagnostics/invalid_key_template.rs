@@ -0,0 +1,29 @@
+use raiden::*;
+
+#[derive(Raiden)]
+#[raiden(table_name = "Order")]
+#[raiden(partition_key = "USER#{user}")]
+#[raiden(sort_key = "ORDER#{date}{order_id}")]
+struct Order {
+    user_id: String,
+    date: String,
+    order_id: u32,
+}
+
+#[derive(Raiden)]
+#[raiden(table_name = "Order")]
+#[raiden(partition_key = "USER#{user_id}")]
+struct Profile {
+    #[raiden(partition_key)]
+    user_id: String,
+}
+
+#[derive(Raiden)]
+#[raiden(table_name = "Order")]
+#[raiden(partition_key = "USER#{user_id}", partition_key_attr = "user_id")]
+#[raiden(sort_key_attr = "SK")]
+struct Invoice {
+    user_id: String,
+}
+
+fn main() {}
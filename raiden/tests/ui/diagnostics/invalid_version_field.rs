@@ -0,0 +1,21 @@
+use raiden::*;
+
+#[derive(Raiden)]
+#[raiden(table_name = "User")]
+struct User {
+    #[raiden(partition_key)]
+    id: String,
+    #[raiden(version)]
+    version: String,
+}
+
+#[derive(Raiden)]
+#[raiden(table_name = "Order")]
+struct Order {
+    #[raiden(partition_key)]
+    id: String,
+    #[raiden(version)]
+    version: f64,
+}
+
+fn main() {}
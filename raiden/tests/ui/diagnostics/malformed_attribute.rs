@@ -0,0 +1,12 @@
+use raiden::*;
+
+#[derive(Raiden)]
+#[raiden(table_name)]
+struct User {
+    #[raiden(partition_key = "id")]
+    id: String,
+    #[raiden(rename = 1)]
+    name: String,
+}
+
+fn main() {}
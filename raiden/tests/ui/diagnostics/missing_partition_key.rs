@@ -0,0 +1,9 @@
+use raiden::*;
+
+#[derive(Raiden)]
+#[raiden(table_name = "User")]
+struct User {
+    id: String,
+}
+
+fn main() {}
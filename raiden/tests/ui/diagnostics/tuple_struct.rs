@@ -0,0 +1,6 @@
+use raiden::*;
+
+#[derive(Raiden)]
+struct User(String);
+
+fn main() {}
@@ -0,0 +1,12 @@
+use raiden::*;
+
+#[derive(Raiden)]
+#[raiden(table_name = "User", tabel_name = "Typo")]
+struct User {
+    #[raiden(partition_key)]
+    id: String,
+    #[raiden(renmae = "Name")]
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use raiden::*;
+
+#[derive(Raiden)]
+#[raiden(table_name = "User")]
+#[raiden(gsi(name = "byEmail", partition_key = "emial"))]
+struct User {
+    #[raiden(partition_key)]
+    id: String,
+    email: String,
+    #[raiden(omit_gsi = "byName")]
+    name: String,
+}
+
+#[derive(Raiden)]
+#[raiden(table_name = "Account")]
+#[raiden(gsi(partition_key = "name"))]
+struct Account {
+    #[raiden(partition_key)]
+    id: String,
+    name: String,
+}
+
+fn main() {}
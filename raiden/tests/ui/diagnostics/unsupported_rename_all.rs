@@ -0,0 +1,10 @@
+use raiden::*;
+
+#[derive(Raiden)]
+#[raiden(table_name = "User", rename_all = "Train-Case")]
+struct User {
+    #[raiden(partition_key)]
+    id: String,
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use raiden::*;
+
+#[derive(Raiden)]
+#[raiden(table_name = "QueryTestData0")]
+struct User {
+    #[raiden(partition_key)]
+    id: String,
+    #[raiden(sort_key)]
+    year: usize,
+    name: String,
+}
+
+fn create_client() -> UserClient {
+    unimplemented!()
+}
+
+fn main() {
+    let cond = User::partition_key_condition()
+        .eq("id0")
+        .and(User::sort_key_condition().gt(1999));
+
+    let _ = cond.and(User::sort_key_condition().lt(2010));
+}
@@ -0,0 +1,19 @@
+use raiden::*;
+
+#[derive(Raiden)]
+#[raiden(table_name = "QueryTestData0")]
+struct User {
+    #[raiden(partition_key)]
+    id: String,
+    #[raiden(sort_key)]
+    year: usize,
+    name: String,
+}
+
+fn create_client() -> UserClient {
+    unimplemented!()
+}
+
+fn main() {
+    let _ = User::partition_key_condition().begins_with("id");
+}
@@ -0,0 +1,22 @@
+use raiden::*;
+
+#[derive(Raiden)]
+#[raiden(table_name = "QueryTestData0")]
+struct User {
+    #[raiden(partition_key)]
+    id: String,
+    #[raiden(sort_key)]
+    year: usize,
+    name: String,
+}
+
+fn create_client() -> UserClient {
+    unimplemented!()
+}
+
+fn main() {
+    let client = create_client();
+
+    let cond = User::sort_key_condition().eq(1999);
+    let _ = client.query().key_condition(cond);
+}
@@ -0,0 +1,17 @@
+use raiden::*;
+
+#[derive(Raiden)]
+#[raiden(table_name = "app")]
+#[raiden(partition_key = "USER#{user_id}")]
+#[raiden(sort_key = "ORDER#{date}#{order_id}")]
+struct Order {
+    user_id: String,
+    date: String,
+    order_id: u32,
+}
+
+fn main() {
+    let _ = Order::partition_key_condition()
+        .eq_template(("bokuweb".to_owned(), 1))
+        .and(Order::sort_key_condition().begins_with_template(("2024-01-02".to_owned(), "1", 2)));
+}
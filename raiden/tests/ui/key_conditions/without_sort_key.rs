@@ -0,0 +1,15 @@
+use raiden::*;
+
+#[derive(Raiden)]
+#[raiden(table_name = "QueryTestData0a")]
+struct User {
+    #[raiden(partition_key)]
+    id: String,
+    name: String,
+}
+
+fn main() {
+    let _ = User::partition_key_condition()
+        .eq("id0")
+        .and(User::key_condition(User::name()).eq("bokuweb"));
+}
@@ -0,0 +1,5 @@
+#[test]
+fn derive_errors_are_reported_as_diagnostics() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/diagnostics/*.rs");
+}
@@ -0,0 +1,5 @@
+#[test]
+fn key_conditions_are_type_safe() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/key_conditions/*.rs");
+}
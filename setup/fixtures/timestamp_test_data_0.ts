@@ -0,0 +1,24 @@
+import type { CreateAndPut } from "../dynamo_util.ts";
+
+export const timestampTestData0: CreateAndPut = {
+  table: {
+    TableName: "TimestampTestData0",
+    KeySchema: [{ AttributeName: "id", KeyType: "HASH" }],
+    AttributeDefinitions: [{ AttributeName: "id", AttributeType: "S" }],
+    ProvisionedThroughput: { ReadCapacityUnits: 5, WriteCapacityUnits: 5 },
+  },
+  items: [
+    {
+      id: { S: "id0" },
+      name: { S: "john" },
+      createdAt: { S: "2019-01-01T00:00:00.000Z" },
+      updatedAt: { N: "1546300800" },
+    },
+    {
+      id: { S: "id1" },
+      name: { S: "bob" },
+      createdAt: { S: "2019-01-01T00:00:00.000Z" },
+      updatedAt: { N: "1546300800" },
+    },
+  ],
+};
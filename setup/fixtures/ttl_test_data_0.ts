@@ -0,0 +1,15 @@
+import type { CreateAndPut } from "../dynamo_util.ts";
+
+export const ttlTestData0: CreateAndPut = {
+  table: {
+    TableName: "TtlTestData0",
+    KeySchema: [{ AttributeName: "id", KeyType: "HASH" }],
+    AttributeDefinitions: [{ AttributeName: "id", AttributeType: "S" }],
+    ProvisionedThroughput: { ReadCapacityUnits: 5, WriteCapacityUnits: 5 },
+  },
+  items: [
+    { id: { S: "expired" }, name: { S: "john" }, expiresAt: { N: "1000" } },
+    { id: { S: "alive" }, name: { S: "bob" }, expiresAt: { N: "4102444800" } },
+    { id: { S: "noExpiry" }, name: { S: "alice" } },
+  ],
+};
@@ -0,0 +1,21 @@
+import type { CreateAndPut } from "../dynamo_util.ts";
+
+export const txSortKeyTestData0: CreateAndPut = {
+  table: {
+    TableName: "TxSortKeyTestData0",
+    KeySchema: [
+      { AttributeName: "id", KeyType: "HASH" },
+      { AttributeName: "year", KeyType: "RANGE" },
+    ],
+    AttributeDefinitions: [
+      { AttributeName: "id", AttributeType: "S" },
+      { AttributeName: "year", AttributeType: "N" },
+    ],
+    ProvisionedThroughput: { ReadCapacityUnits: 5, WriteCapacityUnits: 5 },
+  },
+  items: [
+    { id: { S: "id0" }, year: { N: "1999" }, name: { S: "john" } },
+    { id: { S: "id0" }, year: { N: "2000" }, name: { S: "john" } },
+    { id: { S: "id1" }, year: { N: "2003" }, name: { S: "bob" } },
+  ],
+};
@@ -0,0 +1,17 @@
+import type { CreateAndPut } from "../dynamo_util.ts";
+
+export const versionTestData0: CreateAndPut = {
+  table: {
+    TableName: "VersionTestData0",
+    KeySchema: [{ AttributeName: "id", KeyType: "HASH" }],
+    AttributeDefinitions: [{ AttributeName: "id", AttributeType: "S" }],
+    ProvisionedThroughput: { ReadCapacityUnits: 5, WriteCapacityUnits: 5 },
+  },
+  items: [
+    { id: { S: "id0" }, name: { S: "john" }, version: { N: "1" } },
+    { id: { S: "id1" }, name: { S: "bob" }, version: { N: "3" } },
+    { id: { S: "id2" }, name: { S: "alice" }, version: { N: "1" } },
+    { id: { S: "id3" }, name: { S: "carol" }, version: { N: "2" } },
+    { id: { S: "id4" }, name: { S: "dave" }, version: { N: "1" } },
+  ],
+};
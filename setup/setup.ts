@@ -28,9 +28,12 @@ import { scanLargeDataTest } from "./fixtures/scan_large_data_test.ts";
 import { scanTestData0 } from "./fixtures/scan_test_data_0.ts";
 import { scanWithFilterTestData0 } from "./fixtures/scan_with_filter_test_data_0.ts";
 import { testUserStaging } from "./fixtures/test_user_staging.ts";
+import { timestampTestData0 } from "./fixtures/timestamp_test_data_0.ts";
+import { ttlTestData0 } from "./fixtures/ttl_test_data_0.ts";
 import { txConditionalCheckTestData0 } from "./fixtures/tx_conditional_check_test_data_0.ts";
 import { txConditionalCheckTestData1 } from "./fixtures/tx_conditional_check_test_data_1.ts";
 import { txDeleteTestData0 } from "./fixtures/tx_delete_test_data_0.ts";
+import { txSortKeyTestData0 } from "./fixtures/tx_sort_key_test_data_0.ts";
 import { updateAddTestData0 } from "./fixtures/update_add_test_data_0.ts";
 import { updateDeleteTestData0 } from "./fixtures/update_delete_test_data_0.ts";
 import { updateRemoveTestData0 } from "./fixtures/update_remove_test_data_0.ts";
@@ -40,6 +43,7 @@ import { updateWithContainsInSetCondition } from "./fixtures/update_with_contain
 import { useDefaultForNull } from "./fixtures/use_default_for_null_data.ts";
 import { useDefaultTestData0 } from "./fixtures/use_default_test_data_0.ts";
 import { user } from "./fixtures/user.ts";
+import { versionTestData0 } from "./fixtures/version_test_data_0.ts";
 
 const client = new DynamoDBClient({
   region: "ap-northeast-1",
@@ -76,9 +80,12 @@ const data = [
   scanTestData0,
   scanWithFilterTestData0,
   testUserStaging,
+  timestampTestData0,
+  ttlTestData0,
   txConditionalCheckTestData0,
   txConditionalCheckTestData1,
   txDeleteTestData0,
+  txSortKeyTestData0,
   updateAddTestData0,
   updateDeleteTestData0,
   updateRemoveTestData0,
@@ -88,6 +95,7 @@ const data = [
   useDefaultForNull,
   useDefaultTestData0,
   user,
+  versionTestData0,
 ];
 
 // NOTE: Running these operations concurrently with `Promise.all` would lead to running out of write buffer.